@@ -0,0 +1,351 @@
+// Headless command-line interface
+// Runs queries, downloads and updates without Tauri, for dedicated servers and cron jobs
+//
+// Usage: rimworld-workshop-downloader --cli [options] <command>
+//   query <mods-path>       List installed mods with Workshop updates
+//   download <id>...        Download Workshop items into SteamCMD's content folder
+//   update <mods-path>      Download and install every outdated mod
+// Options:
+//   --json                  Print machine-readable JSON on stdout
+//   --steamcmd <path>       SteamCMD executable or installation folder
+//   --instances <n>         Maximum number of parallel SteamCMD instances
+//   --ignore <id>           Skip a mod when querying or updating (repeatable)
+//   --backup-dir <path>     Back up mods before updating them
+//
+// Logs are written to stderr, so stdout only holds the results.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+use serde::Serialize;
+use crate::core::mod_manager::{InstallOptions, ModUpdater};
+use crate::core::mod_scanner::{query_mods_for_updates, BaseMod};
+use crate::core::steamcmd_client::{DownloadedMod, Downloader};
+use crate::services::{find_all_mod_folders_with_id, validate_mods_path, write_last_updated_file};
+
+const USAGE: &str = "Usage: rimworld-workshop-downloader --cli [--json] [--steamcmd <path>] [--instances <n>] <command>
+
+Commands:
+  query <mods-path> [--ignore <id>]...
+  download <id>...
+  update <mods-path> [--ignore <id>]... [--backup-dir <path>]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    Query {
+        mods_path: String,
+        ignored_mods: Vec<String>,
+    },
+    Download {
+        mod_ids: Vec<String>,
+    },
+    Update {
+        mods_path: String,
+        ignored_mods: Vec<String>,
+        backup_directory: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    pub json: bool,
+    pub steamcmd: Option<PathBuf>,
+    pub max_instances: Option<usize>,
+    pub command: CliCommand,
+}
+
+/// Outcome of a single mod, keyed by mod ID in the output
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModResult {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ModResult {
+    fn ok(path: &std::path::Path) -> Self {
+        Self { success: true, path: Some(path.to_string_lossy().to_string()), error: None }
+    }
+
+    fn failed(error: impl Into<String>) -> Self {
+        Self { success: false, path: None, error: Some(error.into()) }
+    }
+}
+
+/// Parse the arguments following `--cli`
+pub fn parse_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut json = false;
+    let mut steamcmd = None;
+    let mut max_instances = None;
+    let mut ignored_mods = Vec::new();
+    let mut backup_directory = None;
+    let mut positional = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().cloned().ok_or_else(|| format!("Missing value for {}", name));
+        match arg.as_str() {
+            "--json" => json = true,
+            "--steamcmd" => steamcmd = Some(PathBuf::from(value(arg)?)),
+            "--instances" => {
+                let instances = value(arg)?;
+                max_instances = Some(instances.parse::<usize>()
+                    .map_err(|_| format!("Invalid number of instances: {}", instances))?);
+            }
+            "--ignore" => ignored_mods.push(value(arg)?),
+            "--backup-dir" => backup_directory = Some(PathBuf::from(value(arg)?)),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            other if other.starts_with("--") => return Err(format!("Unknown option: {}\n\n{}", other, USAGE)),
+            other => positional.push(other.to_string()),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = match positional.next().as_deref() {
+        Some("query") => CliCommand::Query {
+            mods_path: positional.next().ok_or_else(|| format!("Missing mods path\n\n{}", USAGE))?,
+            ignored_mods,
+        },
+        Some("download") => {
+            let mod_ids: Vec<String> = positional.by_ref().collect();
+            if mod_ids.is_empty() {
+                return Err(format!("Missing mod IDs\n\n{}", USAGE));
+            }
+            if let Some(invalid) = mod_ids.iter().find(|id| id.is_empty() || !id.chars().all(|c| c.is_ascii_digit())) {
+                return Err(format!("Invalid mod ID: {}", invalid));
+            }
+            CliCommand::Download { mod_ids }
+        }
+        Some("update") => CliCommand::Update {
+            mods_path: positional.next().ok_or_else(|| format!("Missing mods path\n\n{}", USAGE))?,
+            ignored_mods,
+            backup_directory,
+        },
+        Some(other) => return Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+        None => return Err(USAGE.to_string()),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("Unexpected argument: {}", extra));
+    }
+
+    Ok(CliArgs { json, steamcmd, max_instances, command })
+}
+
+/// Run the CLI with the arguments following `--cli`, returning the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let args = match parse_args(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 1;
+        }
+    };
+
+    match runtime.block_on(run_command(&args)) {
+        Ok(success) => if success { 0 } else { 1 },
+        Err(e) => {
+            if args.json {
+                println!("{}", serde_json::json!({ "error": e }));
+            } else {
+                eprintln!("Error: {}", e);
+            }
+            1
+        }
+    }
+}
+
+/// Run a parsed command, returns false if any mod failed
+async fn run_command(args: &CliArgs) -> Result<bool, String> {
+    match &args.command {
+        CliCommand::Query { mods_path, ignored_mods } => {
+            let path = validate_mods_path(mods_path).map_err(|e| e.to_string())?;
+            let mods = query_mods_for_updates(&path, ignored_mods)
+                .await
+                .map_err(|e| format!("Failed to query mods: {}", e))?;
+            print_mods(&mods, args.json);
+            Ok(true)
+        }
+        CliCommand::Download { mod_ids } => {
+            let mut downloader = create_downloader(args).await?;
+            let (downloaded, mut results) = download(&mut downloader, mod_ids, args.max_instances).await?;
+            for downloaded_mod in downloaded {
+                results.insert(downloaded_mod.mod_id, ModResult::ok(&downloaded_mod.mod_path));
+            }
+            Ok(print_results(&results, args.json))
+        }
+        CliCommand::Update { mods_path, ignored_mods, backup_directory } => {
+            let path = validate_mods_path(mods_path).map_err(|e| e.to_string())?;
+            let mods: Vec<BaseMod> = query_mods_for_updates(&path, ignored_mods)
+                .await
+                .map_err(|e| format!("Failed to query mods: {}", e))?
+                .into_iter()
+                // Removed or banned mods can't be downloaded again
+                .filter(|m| !m.non_steam_mod && m.status.is_none())
+                .collect();
+            if mods.is_empty() {
+                eprintln!("[CLI] All mods are up to date");
+                return Ok(print_results(&BTreeMap::new(), args.json));
+            }
+
+            let mut downloader = create_downloader(args).await?;
+            let mod_ids: Vec<String> = mods.iter().map(|m| m.mod_id.clone()).collect();
+            let (downloaded, mut results) = download(&mut downloader, &mod_ids, args.max_instances).await?;
+            let install_options = InstallOptions {
+                backup_directory: backup_directory.clone(),
+                // Nobody can answer the corrupted mod prompt, so keep a corrupted folder and install under a new name
+                force_overwrite_corrupted: Some(false),
+                ..InstallOptions::new(downloader.download_path().clone(), path.clone())
+            };
+
+            let updater = ModUpdater;
+            for downloaded_mod in downloaded {
+                let Some(original_mod) = mods.iter().find(|m| m.mod_id == downloaded_mod.mod_id) else {
+                    continue;
+                };
+                let result = updater.update_mod(
+                    &downloaded_mod.mod_id,
+                    &downloaded_mod.mod_path,
+                    original_mod.folder.as_deref(),
+                    original_mod.details.as_ref().map(|d| d.title.as_str()),
+                    original_mod.details.as_ref().map(|d| d.time_updated),
+                    &install_options,
+                ).await;
+
+                let mod_result = match result {
+                    Ok(updated_path) => {
+                        if let Err(e) = updater.remove_orphaned_mod_folders(&path, &downloaded_mod.mod_id, &updated_path, backup_directory.as_deref()).await {
+                            eprintln!("[CLI] Failed to reconcile folders for mod {}: {}", downloaded_mod.mod_id, e);
+                        }
+                        if let Some(details) = &original_mod.details {
+                            let folders = find_all_mod_folders_with_id(&path, &downloaded_mod.mod_id)
+                                .await
+                                .unwrap_or_default();
+                            futures::future::join_all(
+                                folders.into_iter().map(|folder| write_last_updated_file(folder, details.time_updated))
+                            ).await;
+                        }
+                        ModResult::ok(&updated_path)
+                    }
+                    Err(e) => ModResult::failed(e.to_string()),
+                };
+                results.insert(downloaded_mod.mod_id, mod_result);
+            }
+            Ok(print_results(&results, args.json))
+        }
+    }
+}
+
+async fn create_downloader(args: &CliArgs) -> Result<Downloader, String> {
+    let mut downloader = Downloader::new(None);
+    if let Some(steamcmd) = &args.steamcmd {
+        let executable = Downloader::resolve_steamcmd_executable(steamcmd)?;
+        Downloader::check_steamcmd_runs(&executable).await?;
+        downloader.set_custom_executable(Some(executable));
+    }
+    Ok(downloader)
+}
+
+/// Download mods, returning the downloaded ones and a failure for every other requested mod
+async fn download(
+    downloader: &mut Downloader,
+    mod_ids: &[String],
+    max_instances: Option<usize>,
+) -> Result<(Vec<DownloadedMod>, BTreeMap<String, ModResult>), String> {
+    let mut receiver = downloader.download_mods(mod_ids, None, max_instances)
+        .await
+        .map_err(|e| format!("Failed to download mods: {}", e))?;
+
+    let mut downloaded = Vec::new();
+    let mut errors = HashMap::new();
+    while let Some(result) = receiver.recv().await {
+        match result {
+            Ok(downloaded_mod) => downloaded.push(downloaded_mod),
+            // A mod can fail several attempts, the last failure is the one that counts
+            Err(e) => {
+                errors.insert(e.mod_id().to_string(), e.to_string());
+            }
+        }
+    }
+
+    let seen: HashSet<&str> = downloaded.iter().map(|m| m.mod_id.as_str()).collect();
+    let failures = mod_ids.iter()
+        .filter(|id| !seen.contains(id.as_str()))
+        .map(|id| {
+            let error = errors.remove(id)
+                .unwrap_or_else(|| "Download failed - SteamCMD reported failure".to_string());
+            (id.clone(), ModResult::failed(error))
+        })
+        .collect();
+    Ok((downloaded, failures))
+}
+
+fn print_mods(mods: &[BaseMod], json: bool) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(mods).unwrap_or_default());
+        return;
+    }
+    for m in mods {
+        let title = m.details.as_ref().map(|d| d.title.as_str()).unwrap_or("");
+        let status = match m.status {
+            Some(status) => format!(" [{:?}]", status).to_lowercase(),
+            None => String::new(),
+        };
+        println!("{}\t{}\t{}{}", m.mod_id, m.folder.as_deref().unwrap_or(""), title, status);
+    }
+}
+
+/// Print per-mod results, returns true if every mod succeeded
+fn print_results(results: &BTreeMap<String, ModResult>, json: bool) -> bool {
+    if json {
+        println!("{}", serde_json::to_string_pretty(results).unwrap_or_default());
+    } else {
+        for (mod_id, result) in results {
+            match (&result.path, &result.error) {
+                (Some(path), _) => println!("{}\tok\t{}", mod_id, path),
+                (_, Some(error)) => println!("{}\tfailed\t{}", mod_id, error),
+                _ => println!("{}\tok", mod_id),
+            }
+        }
+    }
+    results.values().all(|r| r.success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = parse_args(&args(&["--json", "update", "/mods", "--ignore", "123", "--backup-dir", "/backups"])).unwrap();
+        assert!(parsed.json);
+        assert_eq!(parsed.command, CliCommand::Update {
+            mods_path: "/mods".to_string(),
+            ignored_mods: vec!["123".to_string()],
+            backup_directory: Some(PathBuf::from("/backups")),
+        });
+
+        let parsed = parse_args(&args(&["download", "1", "2", "--instances", "3"])).unwrap();
+        assert_eq!(parsed.max_instances, Some(3));
+        assert_eq!(parsed.command, CliCommand::Download { mod_ids: args(&["1", "2"]) });
+
+        assert!(parse_args(&args(&["download"])).is_err());
+        assert!(parse_args(&args(&["download", "abc"])).is_err());
+        assert!(parse_args(&args(&["query"])).is_err());
+        assert!(parse_args(&args(&["query", "/mods", "--bogus"])).is_err());
+        assert!(parse_args(&args(&["sync"])).is_err());
+    }
+}
@@ -2,24 +2,78 @@
 
 use std::path::PathBuf;
 use serde_json;
-use tauri::{command, AppHandle};
-use crate::services::{extract_folder_name, get_mods_path_from_mod_path};
+use futures::StreamExt;
+use tauri::{command, AppHandle, Emitter};
+use crate::services::{extract_folder_name, get_mods_path_from_mod_path, find_all_mod_folders_with_id, save_backup_mode, save_backup_format, canonicalize_path_or_fallback, get_mod_watcher, ignore_path_in_watcher, load_configured_mods_path, WatcherIgnoreGuard};
 use crate::core::access_check::ensure_directory_access;
+use crate::core::backup_archive::{extract_zip_backup, find_backup, is_zip_backup};
+use crate::core::backup_retention::{list_backups as list_backups_query, prune_backups as prune_backups_query, BackupInfo};
+use crate::core::incremental_backup::{is_incremental_backup, remove_latest_version, restore_incremental_backup};
+use crate::core::mod_lock::lock_mod_folder;
+use crate::core::mod_manager::{extended_length_path, move_dir, BackupFormat, BackupMode, ModUpdater};
+use crate::core::mod_scanner::{clear_last_updated, create_base_mod_from_path, list_installed_mods_fast, query_mod_id, query_mod_info, BaseMod, DISABLED_MODS_FOLDER};
+use crate::error::AppError;
+
+/// Validate a mod folder path before modifying it
+/// Refuses paths that are not a folder directly inside the configured mods directory and checks write access to it
+/// Returns the mods directory the mod lives in
+fn validate_mod_folder_path(app: &AppHandle, mod_path: &std::path::Path) -> Result<PathBuf, String> {
+    if mod_path.as_os_str().is_empty() {
+        return Err("Mod path cannot be empty".to_string());
+    }
+    
+    // Only the parent is resolved: the mod folder may not exist when restoring a deleted mod,
+    // and a linked mod resolves to its download outside the mods directory
+    let mods_path = get_mods_path_from_mod_path(mod_path)?;
+    let configured_mods_path = load_configured_mods_path(app)
+        .ok_or_else(|| format!("Refusing to modify {:?}: no mods directory is configured", mod_path))?;
+    if mods_path.as_os_str().is_empty() ||
+       canonicalize_path_or_fallback(&mods_path) != canonicalize_path_or_fallback(&configured_mods_path) {
+        return Err(format!("Refusing to modify {:?}: it is not inside the mods directory {:?}", mod_path, configured_mods_path));
+    }
+    let mods_path_str = mods_path.to_string_lossy().to_string();
+    ensure_directory_access(app, &mods_path, &mods_path_str)?;
+    
+    // Reject paths like "Mods/.." whose last component is not a folder name
+    extract_folder_name(mod_path)?;
+    
+    Ok(mods_path)
+}
+
+/// Choose how mods are backed up before an update
+/// Incremental backups keep versions that only store changed files; None goes back to full copies
+#[command]
+pub async fn set_backup_mode(app: AppHandle, mode: Option<BackupMode>) -> Result<BackupMode, AppError> {
+    let mode = mode.unwrap_or_default();
+    save_backup_mode(&app, mode).map_err(AppError::failed)?;
+    Ok(mode)
+}
+
+/// Choose how full backups are stored
+/// Zip compresses each backup into a single `.zip`, None goes back to plain folders
+#[command]
+pub async fn set_backup_format(app: AppHandle, format: Option<BackupFormat>) -> Result<BackupFormat, AppError> {
+    let format = format.unwrap_or_default();
+    save_backup_format(&app, format).map_err(AppError::failed)?;
+    Ok(format)
+}
 
 /// Check if backup exists for a mod (optimized with spawn_blocking)
 #[command]
 pub async fn check_backup(
     mod_path: String,
     backup_directory: Option<String>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     if let Some(backup_dir) = backup_directory {
         let mod_path_buf = PathBuf::from(&mod_path);
-        let folder_name = extract_folder_name(&mod_path_buf)?;
+        let folder_name = extract_folder_name(&mod_path_buf).map_err(AppError::failed)?;
         
         let backup_path = PathBuf::from(&backup_dir).join(&folder_name);
         
         // Use spawn_blocking for I/O operations to avoid blocking the async runtime
         let result = tokio::task::spawn_blocking(move || {
+            // The backup may be a folder or a compressed `.zip`
+            let backup_path = find_backup(&backup_path).unwrap_or(backup_path);
             if backup_path.exists() {
                 match std::fs::metadata(&backup_path) {
                     Ok(metadata) => {
@@ -32,7 +86,8 @@ pub async fn check_backup(
                             "backupPath": backup_path.to_string_lossy(),
                             "backupDate": backup_date.duration_since(std::time::UNIX_EPOCH)
                                 .unwrap()
-                                .as_secs()
+                                .as_secs(),
+                            "compressed": is_zip_backup(&backup_path)
                         }))
                     }
                     Err(e) => Err(format!("Failed to get backup metadata: {}", e))
@@ -44,7 +99,7 @@ pub async fn check_backup(
                 }))
             }
         }).await
-        .map_err(|e| format!("Task panicked: {:?}", e))??;
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
         
         Ok(result)
     } else {
@@ -60,7 +115,7 @@ pub async fn check_backup(
 pub async fn check_backups(
     mod_paths: Vec<String>,
     backup_directory: Option<String>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     if mod_paths.is_empty() {
         return Ok(serde_json::json!({}));
     }
@@ -83,6 +138,7 @@ pub async fn check_backups(
             
             // Spawn blocking task for each backup check
             let future = tokio::task::spawn_blocking(move || {
+                let backup_path = find_backup(&backup_path).unwrap_or(backup_path);
                 if backup_path.exists() {
                     match std::fs::metadata(&backup_path) {
                         Ok(metadata) => {
@@ -95,7 +151,8 @@ pub async fn check_backups(
                                 "backupPath": backup_path.to_string_lossy(),
                                 "backupDate": backup_date.duration_since(std::time::UNIX_EPOCH)
                                     .unwrap()
-                                    .as_secs()
+                                    .as_secs(),
+                                "compressed": is_zip_backup(&backup_path)
                             }))
                         }
                         Err(_) => Some(serde_json::json!({
@@ -150,56 +207,55 @@ pub async fn restore_backup(
     app: AppHandle,
     mod_path: String,
     backup_directory: String,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     
     let normalized_mod_path = PathBuf::from(&mod_path);
     let normalized_backup_directory = PathBuf::from(&backup_directory);
     
-    // Check directory access to parent mods directory (write access is required for restore)
-    // Use parent directory because the mod folder may not exist when restoring a deleted mod
-    let mods_path = get_mods_path_from_mod_path(&normalized_mod_path)?;
-    let mods_path_str = mods_path.to_string_lossy().to_string();
-    ensure_directory_access(&app, &mods_path, &mods_path_str)?;
+    // Write access to the parent mods directory is required for restore
+    validate_mod_folder_path(&app, &normalized_mod_path).map_err(AppError::failed)?;
     
     // Safety check: ensure backupDirectory is not inside modPath (or vice versa)
     if normalized_mod_path.starts_with(&normalized_backup_directory) ||
        normalized_backup_directory.starts_with(&normalized_mod_path) {
-        return Err("Backup directory cannot be inside mods path or vice versa. They must be separate directories.".to_string());
+        return Err(AppError::invalid_input("Backup directory cannot be inside mods path or vice versa. They must be separate directories."));
     }
     
     // Extract folder name from modPath
-    let folder_name = extract_folder_name(&normalized_mod_path)?;
+    let folder_name = extract_folder_name(&normalized_mod_path).map_err(AppError::failed)?;
     
     let backup_path = normalized_backup_directory.join(folder_name);
     
     // Additional safety check
     if !backup_path.starts_with(&normalized_backup_directory) {
-        return Err("Invalid backup path detected".to_string());
+        return Err(AppError::invalid_input("Invalid backup path detected"));
     }
     
     // Critical safety check: ensure backupPath and modPath are not the same
     if backup_path == normalized_mod_path {
-        return Err("Backup path and mod path cannot be the same. Please ensure backup directory is different from mods directory.".to_string());
+        return Err(AppError::invalid_input("Backup path and mod path cannot be the same. Please ensure backup directory is different from mods directory."));
     }
     
-    // Check if backup exists (async)
-    let backup_exists = tokio::task::spawn_blocking({
+    // Check if backup exists (async), as a folder or a compressed `.zip`
+    let found_backup = tokio::task::spawn_blocking({
         let backup_path = backup_path.clone();
-        move || backup_path.exists()
+        move || find_backup(&backup_path)
     }).await
-    .map_err(|e| format!("Task panicked: {:?}", e))?;
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?;
     
-    if !backup_exists {
-        return Err("Backup not found".to_string());
-    }
+    let Some(found_backup) = found_backup else {
+        return Err(AppError::BackupNotFound { path: backup_path });
+    };
+    
+    // Held until the restore is done, so another instance of the app can't change the folder meanwhile
+    let _folder_lock = lock_mod_folder(&normalized_mod_path)?;
     
     // Ignore this path in mod watcher during restore operation
-    use crate::services::{ignore_path_in_watcher, WatcherIgnoreGuard};
     ignore_path_in_watcher(normalized_mod_path.clone()).await;
     let _guard = WatcherIgnoreGuard::new(normalized_mod_path.clone()).await;
     
-    // Remove current mod folder (async)
-    let mod_path_clone = normalized_mod_path.clone();
+    // Remove current mod folder (async), long paths included
+    let mod_path_clone = extended_length_path(&normalized_mod_path);
     tokio::task::spawn_blocking(move || {
         if mod_path_clone.exists() {
             std::fs::remove_dir_all(&mod_path_clone)
@@ -207,22 +263,45 @@ pub async fn restore_backup(
         }
         Ok::<(), String>(())
     }).await
-    .map_err(|e| format!("Task panicked: {:?}", e))??;
-    
-    // Copy backup to mods folder (async)
-    use crate::core::mod_manager::copy_dir_all_async;
-    let backup_path_clone = backup_path.clone();
-    let mod_path_clone2 = normalized_mod_path.clone();
-    copy_dir_all_async(&backup_path_clone, &mod_path_clone2).await
-        .map_err(|e| format!("Failed to copy backup: {}", e))?;
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
     
-    // Delete the backup (async) - only after successful copy
-    let backup_path_clone2 = backup_path.clone();
-    tokio::task::spawn_blocking(move || {
-        std::fs::remove_dir_all(&backup_path_clone2)
-            .map_err(|e| format!("Failed to delete backup: {}", e))
-    }).await
-    .map_err(|e| format!("Task panicked: {:?}", e))??;
+    let incremental = is_incremental_backup(&backup_path);
+    if incremental {
+        // Rebuild the newest version from the version folders, then drop it so older versions remain restorable
+        let backup_path_clone = backup_path.clone();
+        let mod_path_clone2 = normalized_mod_path.clone();
+        tokio::task::spawn_blocking(move || {
+            restore_incremental_backup(&backup_path_clone, &mod_path_clone2)
+                .map_err(|e| format!("Failed to copy backup: {}", e))?;
+            remove_latest_version(&backup_path_clone)
+        }).await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    } else if is_zip_backup(&found_backup) {
+        // Extract the archive into the mods folder, then delete it like a restored backup folder
+        let mod_path_clone2 = normalized_mod_path.clone();
+        tokio::task::spawn_blocking(move || {
+            extract_zip_backup(&found_backup, &mod_path_clone2)
+                .map_err(|e| format!("Failed to extract backup: {}", e))?;
+            std::fs::remove_file(&found_backup)
+                .map_err(|e| format!("Failed to delete backup: {}", e))
+        }).await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    } else {
+        // Copy backup to mods folder (async)
+        use crate::core::mod_manager::copy_dir_all_async;
+        let backup_path_clone = backup_path.clone();
+        let mod_path_clone2 = normalized_mod_path.clone();
+        copy_dir_all_async(&backup_path_clone, &mod_path_clone2).await
+            .map_err(|e| AppError::failed(format!("Failed to copy backup: {}", e)))?;
+        
+        // Delete the backup (async) - only after successful copy
+        let backup_path_clone2 = extended_length_path(&backup_path);
+        tokio::task::spawn_blocking(move || {
+            std::fs::remove_dir_all(&backup_path_clone2)
+                .map_err(|e| format!("Failed to delete backup: {}", e))
+        }).await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    }
     
     // Manually unignore the path (this consumes the guard and prevents Drop from running)
     // If we reach here, the operation was successful
@@ -234,19 +313,304 @@ pub async fn restore_backup(
     }))
 }
 
+/// Delete a mod folder, optionally moving it to the recycle bin so it can be recovered
+#[command]
+pub async fn delete_mod(
+    app: AppHandle,
+    mod_path: String,
+    to_recycle_bin: bool,
+) -> Result<serde_json::Value, AppError> {
+    let normalized_mod_path = PathBuf::from(&mod_path);
+    let mods_path = validate_mod_folder_path(&app, &normalized_mod_path).map_err(AppError::failed)?;
+    
+    if !normalized_mod_path.is_dir() {
+        return Err(AppError::path_not_found(&normalized_mod_path));
+    }
+    
+    // Read the mod ID before the folder is gone, a folder without About is not a mod and is never deleted
+    let mod_info = query_mod_info(&normalized_mod_path)
+        .map_err(|e| AppError::failed(format!("Failed to read mod info: {}", e)))?
+        .ok_or_else(|| AppError::failed(format!("Not a mod folder: {:?}", normalized_mod_path)))?;
+    let mod_id = mod_info.mod_id;
+    
+    // Held until the folder is gone, so another instance of the app can't change it meanwhile
+    let _folder_lock = lock_mod_folder(&normalized_mod_path)?;
+    
+    // Ignore this path in mod watcher, the removal is reported below
+    let canonical_mod_path = canonicalize_path_or_fallback(&normalized_mod_path);
+    ignore_path_in_watcher(normalized_mod_path.clone()).await;
+    let _guard = WatcherIgnoreGuard::new(normalized_mod_path.clone()).await;
+    
+    let mod_path_clone = normalized_mod_path.clone();
+    tokio::task::spawn_blocking(move || {
+        if to_recycle_bin {
+            trash::delete(&mod_path_clone)
+                .map_err(|e| format!("Failed to move mod folder to recycle bin: {}", e))
+        } else {
+            std::fs::remove_dir_all(&mod_path_clone)
+                .map_err(|e| format!("Failed to remove mod folder: {}", e))
+        }
+    }).await
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    eprintln!("[DeleteMod] Removed {:?} (recycle bin: {})", normalized_mod_path, to_recycle_bin);
+    get_mod_watcher().lock().await.forget_known_mod(&canonical_mod_path).await;
+    _guard.unignore().await;
+    
+    // Other copies of the same mod lose their update timestamp so they are re-checked
+    let remaining = find_all_mod_folders_with_id(&mods_path, &mod_id).await.unwrap_or_default();
+    for folder in remaining {
+        if let Err(e) = clear_last_updated(&folder) {
+            eprintln!("[DeleteMod] {}", e);
+        }
+    }
+    
+    let _ = app.emit("mod-removed", serde_json::json!({
+        "modId": mod_id,
+    }));
+    
+    Ok(serde_json::json!({
+        "modId": mod_id,
+        "modPath": normalized_mod_path.to_string_lossy(),
+        "recycled": to_recycle_bin,
+    }))
+}
+
+/// Rename a mod folder, appending `_` to the sanitized name if another folder already has it
+/// The watcher keeps tracking the mod under its new folder; mod-removed and mod-added update the UI
+#[command]
+pub async fn rename_mod_folder(
+    app: AppHandle,
+    mod_path: String,
+    new_name: String,
+) -> Result<serde_json::Value, AppError> {
+    let normalized_mod_path = PathBuf::from(&mod_path);
+    validate_mod_folder_path(&app, &normalized_mod_path).map_err(AppError::failed)?;
+    
+    if !normalized_mod_path.is_dir() {
+        return Err(AppError::path_not_found(&normalized_mod_path));
+    }
+    let old_mod_info = query_mod_info(&normalized_mod_path)
+        .map_err(|e| AppError::failed(format!("Failed to read mod info: {}", e)))?
+        .ok_or_else(|| AppError::failed(format!("Not a mod folder: {:?}", normalized_mod_path)))?;
+    
+    let target_path = ModUpdater::rename_target(&normalized_mod_path, &new_name).map_err(AppError::failed)?;
+    if target_path == normalized_mod_path {
+        return Ok(serde_json::json!({
+            "modId": old_mod_info.mod_id,
+            "modPath": normalized_mod_path.to_string_lossy(),
+        }));
+    }
+    
+    // Held until the rename is done, so another instance of the app can't change either folder meanwhile
+    let _folder_lock = lock_mod_folder(&normalized_mod_path)?;
+    let _target_lock = lock_mod_folder(&target_path)?;
+    
+    // Ignore both folders in mod watcher, the rename is reported below
+    let canonical_mod_path = canonicalize_path_or_fallback(&normalized_mod_path);
+    ignore_path_in_watcher(normalized_mod_path.clone()).await;
+    ignore_path_in_watcher(target_path.clone()).await;
+    let _guard = WatcherIgnoreGuard::new(normalized_mod_path.clone()).await;
+    let _target_guard = WatcherIgnoreGuard::new(target_path.clone()).await;
+    
+    // A rename keeps the About folder and .lastupdated in place
+    let mod_path_clone = normalized_mod_path.clone();
+    let target_path_clone = target_path.clone();
+    tokio::task::spawn_blocking(move || {
+        std::fs::rename(&mod_path_clone, &target_path_clone)
+            .map_err(|e| format!("Failed to rename mod folder: {}", e))
+    }).await
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    eprintln!("[RenameMod] Renamed {:?} to {:?}", normalized_mod_path, target_path);
+    
+    // Non-Steam mods are identified by their folder name, so their ID changes too
+    let mod_info = query_mod_info(&target_path).ok().flatten().unwrap_or(old_mod_info.clone());
+    let base_mod = create_base_mod_from_path(mod_info.mod_id.clone(), &target_path, None, mod_info.is_non_steam);
+    
+    get_mod_watcher().lock().await
+        .rename_known_mod(&canonical_mod_path, &target_path, mod_info.mod_id.clone()).await;
+    
+    let _ = app.emit("mod-removed", serde_json::json!({
+        "modId": old_mod_info.mod_id,
+    }));
+    let _ = app.emit("mod-added", serde_json::json!({
+        "modId": mod_info.mod_id,
+        "mod": base_mod,
+    }));
+    
+    Ok(serde_json::json!({
+        "modId": mod_info.mod_id,
+        "modPath": target_path.to_string_lossy(),
+    }))
+}
+
+/// Disable a mod by moving its folder out of the mods directory, or enable it by moving it back
+/// `mod_path` is the folder's path in the mods directory, also while the mod is disabled
+/// Disabled mods go to `disabled_dir` (default `Mods/.disabled`), keeping About and `.lastupdated`
+#[command]
+pub async fn set_mod_enabled(
+    app: AppHandle,
+    mod_path: String,
+    enabled: bool,
+    disabled_dir: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let normalized_mod_path = PathBuf::from(&mod_path);
+    let mods_path = validate_mod_folder_path(&app, &normalized_mod_path).map_err(AppError::failed)?;
+    let folder_name = extract_folder_name(&normalized_mod_path).map_err(AppError::failed)?;
+    
+    let disabled_dir = disabled_dir
+        .filter(|dir| !dir.trim().is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| mods_path.join(DISABLED_MODS_FOLDER));
+    if canonicalize_path_or_fallback(&disabled_dir) == canonicalize_path_or_fallback(&mods_path) {
+        return Err(AppError::invalid_input("Disabled mods folder cannot be the mods directory"));
+    }
+    let disabled_path = disabled_dir.join(&folder_name);
+    
+    let (source_path, target_path) = if enabled {
+        (disabled_path.clone(), normalized_mod_path.clone())
+    } else {
+        (normalized_mod_path.clone(), disabled_path.clone())
+    };
+    if !source_path.is_dir() {
+        if target_path.is_dir() {
+            // Already in the requested state
+            return Ok(serde_json::json!({
+                "modId": query_mod_id(&target_path).ok().flatten(),
+                "modPath": target_path.to_string_lossy(),
+                "enabled": enabled,
+            }));
+        }
+        return Err(AppError::path_not_found(&source_path));
+    }
+    if target_path.exists() {
+        return Err(AppError::invalid_input(format!("Cannot move mod folder, {:?} already exists", target_path)));
+    }
+    let mod_info = query_mod_info(&source_path)
+        .map_err(|e| AppError::failed(format!("Failed to read mod info: {}", e)))?
+        .ok_or_else(|| AppError::failed(format!("Not a mod folder: {:?}", source_path)))?;
+    
+    // Held until the move is done, so another instance of the app can't change the folder meanwhile
+    let _folder_lock = lock_mod_folder(&normalized_mod_path)?;
+    
+    // Ignore both folders in mod watcher, the move is reported below
+    let canonical_mod_path = canonicalize_path_or_fallback(&normalized_mod_path);
+    ignore_path_in_watcher(normalized_mod_path.clone()).await;
+    ignore_path_in_watcher(disabled_path.clone()).await;
+    let _guard = WatcherIgnoreGuard::new(normalized_mod_path.clone()).await;
+    let _disabled_guard = WatcherIgnoreGuard::new(disabled_path.clone()).await;
+    
+    let source_path_clone = source_path.clone();
+    let target_path_clone = target_path.clone();
+    tokio::task::spawn_blocking(move || move_dir(&source_path_clone, &target_path_clone))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    eprintln!("[SetModEnabled] Moved {:?} to {:?}", source_path, target_path);
+    
+    let watcher = get_mod_watcher();
+    let watcher_guard = watcher.lock().await;
+    if enabled {
+        watcher_guard.rename_known_mod(&disabled_path, &normalized_mod_path, mod_info.mod_id.clone()).await;
+        let base_mod = create_base_mod_from_path(mod_info.mod_id.clone(), &normalized_mod_path, None, mod_info.is_non_steam);
+        let _ = app.emit("mod-added", serde_json::json!({
+            "modId": mod_info.mod_id,
+            "mod": base_mod,
+        }));
+    } else {
+        watcher_guard.forget_known_mod(&canonical_mod_path).await;
+        let _ = app.emit("mod-removed", serde_json::json!({
+            "modId": mod_info.mod_id,
+        }));
+    }
+    
+    Ok(serde_json::json!({
+        "modId": mod_info.mod_id,
+        "modPath": target_path.to_string_lossy(),
+        "enabled": enabled,
+    }))
+}
+
+/// List the mods in a folder of disabled mods, empty if nothing was disabled yet
+#[command]
+pub async fn list_disabled_mods(disabled_dir: String) -> Result<Vec<BaseMod>, AppError> {
+    if disabled_dir.trim().is_empty() {
+        return Err(AppError::invalid_input("Disabled mods folder cannot be empty"));
+    }
+    let path = PathBuf::from(&disabled_dir);
+    if !path.is_dir() {
+        return Ok(vec![]);
+    }
+    
+    list_installed_mods_fast(&path)
+        .await
+        .map_err(|e| AppError::failed(format!("Failed to list disabled mods: {}", e)))
+}
+
+/// List the backups in the backup directory with their size and modification time, newest first
+#[command]
+pub async fn list_backups(backup_directory: String) -> Result<Vec<BackupInfo>, AppError> {
+    if backup_directory.trim().is_empty() {
+        return Err(AppError::invalid_input("Backup directory cannot be empty"));
+    }
+    let backup_dir = PathBuf::from(&backup_directory);
+    
+    tokio::task::spawn_blocking(move || list_backups_query(&backup_dir))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Delete all but the `keep_newest` most recent backups, other folders and files in the backup directory are kept
+/// Refuses to run when the backup directory and the mods directory overlap, so mods are never deleted
+/// Returns the deleted backups
+#[command]
+pub async fn prune_backups(
+    app: AppHandle,
+    backup_directory: String,
+    mods_path: String,
+    keep_newest: usize,
+) -> Result<Vec<BackupInfo>, AppError> {
+    if backup_directory.trim().is_empty() {
+        return Err(AppError::invalid_input("Backup directory cannot be empty"));
+    }
+    let backup_dir = PathBuf::from(&backup_directory);
+    let mods_dir = PathBuf::from(&mods_path);
+    
+    // Safety check: same rule as restore_backup, the directories must be separate
+    let backup_canonical = canonicalize_path_or_fallback(&backup_dir);
+    let mods_canonical = canonicalize_path_or_fallback(&mods_dir);
+    if mods_path.trim().is_empty() ||
+       backup_canonical.starts_with(&mods_canonical) ||
+       mods_canonical.starts_with(&backup_canonical) {
+        return Err(AppError::invalid_input("Backup directory cannot be inside mods path or vice versa. They must be separate directories."));
+    }
+    
+    ensure_directory_access(&app, &backup_dir, &backup_directory).map_err(AppError::failed)?;
+    
+    let removed = tokio::task::spawn_blocking(move || prune_backups_query(&backup_dir, keep_newest))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    eprintln!("[Backups] Pruned {} backup(s), keeping the {} newest", removed.len(), keep_newest);
+    Ok(removed)
+}
+
 /// Restore backups for multiple mods (optimized batch version)
+/// Emits `restore-started` when a mod begins restoring and `restore-progress` as each one completes
 #[command]
 pub async fn restore_backups(
     app: AppHandle,
     mod_paths: Vec<String>,
     backup_directory: String,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     if mod_paths.is_empty() {
         return Ok(serde_json::json!({}));
     }
     
     // Restore all backups in parallel
-    let mut restore_futures = Vec::new();
+    let total = mod_paths.len();
+    let mut restore_futures = futures::stream::FuturesUnordered::new();
     
     for mod_path in mod_paths {
         let mod_path_clone = mod_path.clone();
@@ -255,6 +619,11 @@ pub async fn restore_backups(
         
         // Spawn restore task for each mod
         let future = async move {
+            // Copying the backup is the slow part, announce it before it begins
+            let _ = app_clone.emit("restore-started", serde_json::json!({
+                "modPath": mod_path_clone,
+                "total": total,
+            }));
             let result = restore_backup(app_clone, mod_path_clone.clone(), backup_dir_clone).await;
             (mod_path_clone, result)
         };
@@ -262,12 +631,16 @@ pub async fn restore_backups(
         restore_futures.push(future);
     }
     
-    // Wait for all restores to complete
-    let results = futures::future::join_all(restore_futures).await;
-    
-    // Build result map
+    // Report each restore as it completes and build result map
     let mut result_map = serde_json::Map::new();
-    for (mod_path, result) in results {
+    while let Some((mod_path, result)) = restore_futures.next().await {
+        let _ = app.emit("restore-progress", serde_json::json!({
+            "modPath": mod_path,
+            "success": result.is_ok(),
+            "error": result.as_ref().err(),
+            "completed": result_map.len() + 1,
+            "total": total,
+        }));
         match result {
             Ok(success_data) => {
                 result_map.insert(mod_path, serde_json::json!({
@@ -3,32 +3,82 @@
 use std::path::PathBuf;
 use serde_json;
 use tauri::{command, AppHandle, Emitter};
-use crate::core::mod_manager::ModUpdater;
-use crate::core::mod_scanner::query_mod_batch;
+use crate::core::download_queue::DownloadPriority;
+use crate::core::mod_lifecycle::{emit_mod_lifecycle, ModPhase};
+use crate::core::mod_manager::{scan_corrupted_mods, InstallError, InstallFilter, InstallOptions, LinkMode, ModUpdater};
+use crate::core::mods_config::activate_mod;
+use crate::core::mod_scanner::{find_missing_dependencies, get_mod_last_updated_time, parse_workshop_id, query_mod_batch};
+use crate::core::content_fingerprint::is_identical_content;
 use crate::core::access_check::ensure_directory_access;
-use crate::services::{get_downloader, get_steam_api, write_last_updated_file};
+use crate::services::{get_downloader, get_steam_api, find_all_mod_folders_with_id, validate_mods_path, write_last_updated_file, load_failure_history, load_install_filter, load_install_options, save_install_filter};
+use crate::error::AppError;
+
+/// Cancel in-flight downloads of the given mods
+/// Kills the SteamCMD instances downloading them and removes their partial downloads
+#[command]
+pub async fn cancel_download(app: AppHandle, mod_ids: Vec<String>) -> Result<(), AppError> {
+    if mod_ids.is_empty() {
+        return Err(AppError::invalid_input("mod_ids array is required"));
+    }
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.cancel_mods(&mod_ids);
+    
+    for mod_id in &mod_ids {
+        dl.mark_downloaded(mod_id);
+        emit_mod_lifecycle(&app, mod_id, ModPhase::Cancelled, serde_json::Value::Null);
+    }
+    
+    Ok(())
+}
 
 /// Download mod(s) from Steam Workshop
+/// Pass the path of RimWorld's ModsConfig.xml as `activate_in_mods_config` to also activate the mod
+/// An installed copy newer than the Workshop version is only replaced with `allow_downgrade`
+/// With `stage_only` the mod is left in SteamCMD's workshop content folder and that path is returned,
+/// for users who install mods with another tool
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn download_mod(
     app: AppHandle,
     mod_id: String,
     _title: Option<String>,
     mods_path: String,
     max_steamcmd_instances: Option<usize>,
-) -> Result<serde_json::Value, String> {
+    link_mode: Option<LinkMode>,
+    activate_in_mods_config: Option<String>,
+    allow_downgrade: Option<bool>,
+    stage_only: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
+    // Users paste Workshop links as often as bare IDs
+    let mod_id = parse_workshop_id(&mod_id).ok_or_else(|| AppError::invalid_mod_id(mod_id))?;
+    let link_mode = link_mode.unwrap_or_default();
+    let allow_downgrade = allow_downgrade.unwrap_or(false);
+    // When set, the installed mod is added to the active mod list of this ModsConfig.xml
+    let activate_in = activate_in_mods_config.filter(|p| !p.trim().is_empty()).map(PathBuf::from);
+    let stage_only = stage_only.unwrap_or(false);
+    if stage_only && activate_in.is_some() {
+        return Err(AppError::invalid_input("A staged mod is not installed and can't be activated"));
+    }
+    
     // Check if mod is already downloading
     {
         let downloader = get_downloader();
         let dl = downloader.lock().await;
         if dl.is_downloading(&mod_id) {
-            return Err("Mod is already being downloaded".to_string());
+            return Err(AppError::invalid_input("Mod is already being downloaded"));
         }
+        // Report a missing SteamCMD as such instead of as a failed download
+        dl.find_steamcmd_executable().await
+            .map_err(|message| AppError::SteamCmdMissing { message })?;
     }
     
-    // Check directory access before proceeding
+    // Check directory access before proceeding, staging doesn't touch the mods folder
     let mods_path_buf = PathBuf::from(&mods_path);
-    ensure_directory_access(&app, &mods_path_buf, &mods_path)?;
+    if !stage_only {
+        ensure_directory_access(&app, &mods_path_buf, &mods_path).map_err(AppError::failed)?;
+    }
     
     // Mark as downloading
     {
@@ -37,11 +87,19 @@ pub async fn download_mod(
         dl.mark_downloading(mod_id.clone());
     }
     
+    // The size Steam reports lets an incomplete download be detected, as in batch downloads
+    let mod_sizes: Option<std::collections::HashMap<String, u64>> = get_steam_api().lock().await
+        .cached_file_details(&mod_id)
+        .map(|details| std::collections::HashMap::from([(mod_id.clone(), details.file_size)]));
+    
     // Download mod
     let mod_id_for_download = mod_id.clone();
     let downloader_for_download = get_downloader();
     let mut dl_guard = downloader_for_download.lock().await;
-    let mod_receiver_result = dl_guard.download_mods(&[mod_id_for_download], Some(&app), max_steamcmd_instances).await;
+    // The user is waiting for this mod, it takes the next free SteamCMD instance even while an update runs
+    let mod_receiver_result = dl_guard
+        .download_mods_with_priority(&[mod_id_for_download], mod_sizes.as_ref(), Some(&app), max_steamcmd_instances, DownloadPriority::High)
+        .await;
     drop(dl_guard); // Release lock before await
     
     let mut mod_receiver = match mod_receiver_result {
@@ -52,7 +110,7 @@ pub async fn download_mod(
             let mut dl_cleanup = downloader_cleanup.lock().await;
             dl_cleanup.mark_downloaded(&mod_id);
             drop(dl_cleanup);
-            return Err(format!("Failed to download mod: {}", e));
+            return Err(AppError::DownloadFailed { mod_ids: vec![mod_id], message: e });
         }
     };
     
@@ -64,22 +122,39 @@ pub async fn download_mod(
             let mut dl = downloader.lock().await;
             dl.mark_downloaded(&mod_id);
             drop(dl);
-            return Err(format!("Mod download failed: {}", e));
+            return Err(e.into());
         }
         None => {
             let downloader = get_downloader();
             let mut dl = downloader.lock().await;
             dl.mark_downloaded(&mod_id);
             drop(dl);
-            return Err("Mod download completed but no mod folder was created".to_string());
+            return Err(AppError::DownloadFailed {
+                mod_ids: vec![mod_id],
+                message: "Mod download completed but no mod folder was created".to_string(),
+            });
         }
     };
     
+    if stage_only {
+        let downloader = get_downloader();
+        let mut dl = downloader.lock().await;
+        dl.mark_downloaded(&mod_id);
+        drop(dl);
+        emit_mod_lifecycle(&app, &mod_id, ModPhase::Downloaded, serde_json::json!({
+            "staged": true,
+            "modPath": downloaded_mod.mod_path.to_string_lossy(),
+        }));
+        return Ok(serde_json::json!({
+            "modId": downloaded_mod.mod_id,
+            "modPath": downloaded_mod.mod_path.to_string_lossy(),
+            "folder": downloaded_mod.folder,
+            "staged": true,
+        }));
+    }
+    
     // Emit installing event before copying
-    let _ = app.emit("mod-state", serde_json::json!({
-        "modId": mod_id,
-        "state": "installing"
-    }));
+    emit_mod_lifecycle(&app, &mod_id, ModPhase::Installing, serde_json::Value::Null);
     
     // Copy mod to mods folder
     let updater = ModUpdater;
@@ -126,48 +201,60 @@ pub async fn download_mod(
         }
     };
     
+    // The user is asked if a corrupted mod is found
+    let install_options = InstallOptions {
+        allow_downgrade,
+        ..load_install_options(&app, download_path, mods_path_buf.clone(), link_mode)
+    };
     let mod_path_result = updater.update_mod(
         &downloaded_mod.mod_id,
         &downloaded_mod.mod_path,
-        &download_path,
-        &mods_path_buf,
-        None,
-        false,
         None,
         mod_title.as_deref(),
-        None, // force_overwrite_corrupted - None means ask user if corrupted mod found
+        Some(time_updated),
+        &install_options,
     ).await;
     
     let mod_id_for_cleanup = mod_id.clone();
     let mod_path = match mod_path_result {
         Ok(path) => path,
-        Err(e) => {
-            // Check if this is a corrupted mod conflict error
-            if e.starts_with("CORRUPTED_MOD_CONFLICT:") {
-                // Parse the error to extract folder name and mod ID
-                let parts: Vec<&str> = e.split(':').collect();
-                if parts.len() >= 3 {
-                    let folder_name = parts[1].to_string();
-                    // Return special error format for frontend to handle
-                    let downloader_cleanup = get_downloader();
-                    let mut dl_cleanup = downloader_cleanup.lock().await;
-                    dl_cleanup.mark_downloaded(&mod_id_for_cleanup);
-                    drop(dl_cleanup);
-                    return Err(format!("CORRUPTED_MOD_CONFLICT:{}:{}:{}", folder_name, mod_id, mod_title.as_deref().unwrap_or(&mod_id)));
-                }
-            }
+        Err(error) => {
             // Cleanup on error
             let downloader_cleanup = get_downloader();
             let mut dl_cleanup = downloader_cleanup.lock().await;
             dl_cleanup.mark_downloaded(&mod_id_for_cleanup);
             drop(dl_cleanup);
-            return Err(format!("Failed to update mod: {}", e));
+            if matches!(error, InstallError::Downgrade { .. }) {
+                let _ = app.emit("downgrade-warning", &error);
+            }
+            // A corrupted folder isn't a failure yet, the UI asks the user and calls resolve_corrupted_conflict
+            if let InstallError::Failed { message } = &error {
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Failed, serde_json::json!({
+                    "error": message,
+                }));
+            }
+            return Err(error.into());
         }
     };
     
     // Create .lastupdated file
     write_last_updated_file(mod_path.clone(), time_updated).await;
     
+    // Workshop dependencies that aren't installed, so the UI can offer to download them too
+    let missing_dependencies = find_missing_dependencies(&mod_path, &mod_id, &mods_path_buf).await;
+    
+    // Add the mod to the game's active mod list (a failure here doesn't undo the install)
+    let mut activated = false;
+    if let Some(config_path) = activate_in {
+        match ModUpdater::get_package_id(&mod_path) {
+            Some(package_id) => match activate_mod(&config_path, &package_id) {
+                Ok(changed) => activated = changed,
+                Err(e) => eprintln!("[DownloadMod] Failed to activate mod {}: {}", mod_id, e),
+            },
+            None => eprintln!("[DownloadMod] Mod {} has no packageId, not activating it", mod_id),
+        }
+    }
+    
     // Mark as downloaded
     {
         let downloader_final = get_downloader();
@@ -177,10 +264,7 @@ pub async fn download_mod(
     }
     
     // Emit mod-state: completed event
-    let _ = app.emit("mod-state", serde_json::json!({
-        "modId": mod_id,
-        "state": "completed"
-    }));
+    emit_mod_lifecycle(&app, &mod_id, ModPhase::Installed, serde_json::Value::Null);
     
     // Emit mod-updated event to notify frontend that mod was successfully downloaded and installed
     let _ = app.emit("mod-updated", serde_json::json!({
@@ -192,20 +276,27 @@ pub async fn download_mod(
         "modId": downloaded_mod.mod_id,
         "modPath": mod_path.to_string_lossy(),
         "folder": downloaded_mod.folder,
+        "activated": activated,
+        "missingDependencies": missing_dependencies,
     }))
 }
 
-/// Continue download with user decision for corrupted mod conflict
+/// Resolve a `CorruptedModConflict` returned by `download_mod` or `update_mods`
+/// Installs the copy already downloaded to SteamCMD's content folder, overwriting the corrupted folder
+/// when `overwrite` is true or installing next to it under a new name otherwise
 #[command]
-pub async fn continue_download_with_decision(
+pub async fn resolve_corrupted_conflict(
     app: AppHandle,
     mod_id: String,
     mods_path: String,
     overwrite: bool,
-) -> Result<serde_json::Value, String> {
+    link_mode: Option<LinkMode>,
+) -> Result<serde_json::Value, AppError> {
+    let link_mode = link_mode.unwrap_or_default();
+    
     // Check directory access before proceeding
     let mods_path_buf = PathBuf::from(&mods_path);
-    ensure_directory_access(&app, &mods_path_buf, &mods_path)?;
+    ensure_directory_access(&app, &mods_path_buf, &mods_path).map_err(AppError::failed)?;
     
     // Get download path
     let downloader = get_downloader();
@@ -214,62 +305,51 @@ pub async fn continue_download_with_decision(
         dl.download_path().clone()
     };
     
-    // Find the downloaded mod in download folder
+    // Reuse the mod downloaded before the conflict instead of downloading it again
     let download_mod_path = download_path.join(&mod_id);
     if !download_mod_path.exists() || !download_mod_path.is_dir() {
-        return Err(format!("Downloaded mod not found at {:?}", download_mod_path));
+        return Err(AppError::path_not_found(&download_mod_path));
     }
     
-    // Get mod details to retrieve title
-    let mod_title = match query_mod_batch(&[mod_id.clone()], 0).await {
-        Ok(mut details) => {
-            if let Some(detail) = details.pop() {
-                Some(detail.title.clone())
-            } else {
-                None
-            }
-        }
-        Err(_) => None,
+    // Get mod details to retrieve title and time_updated
+    let (mod_title, time_updated) = match query_mod_batch(&[mod_id.clone()], 0).await {
+        Ok(mut details) => match details.pop() {
+            Some(detail) => (Some(detail.title.clone()), detail.time_updated),
+            None => (None, std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64),
+        },
+        Err(_) => (None, std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64),
     };
     
+    emit_mod_lifecycle(&app, &mod_id, ModPhase::Installing, serde_json::Value::Null);
+    
     // Copy mod to mods folder with user decision
     let updater = ModUpdater;
+    let install_options = InstallOptions {
+        force_overwrite_corrupted: Some(overwrite), // User decision
+        ..load_install_options(&app, download_path, mods_path_buf.clone(), link_mode)
+    };
     let mod_path_result = updater.update_mod(
         &mod_id,
         &download_mod_path,
-        &download_path,
-        &mods_path_buf,
-        None,
-        false,
         None,
         mod_title.as_deref(),
-        Some(overwrite), // force_overwrite_corrupted - user decision
+        None, // The user already chose to install this version
+        &install_options,
     ).await;
     
     let mod_path = match mod_path_result {
         Ok(path) => path,
         Err(e) => {
-            return Err(format!("Failed to update mod: {}", e));
-        }
-    };
-    
-    // Get time_updated for .lastupdated file
-    let time_updated = match query_mod_batch(&[mod_id.clone()], 0).await {
-        Ok(mut details) => {
-            if let Some(detail) = details.pop() {
-                detail.time_updated
-            } else {
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() as i64
-            }
-        }
-        Err(_) => {
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64
+            emit_mod_lifecycle(&app, &mod_id, ModPhase::Failed, serde_json::json!({
+                "error": e.to_string(),
+            }));
+            return Err(e.into());
         }
     };
     
@@ -285,10 +365,7 @@ pub async fn continue_download_with_decision(
     }
     
     // Emit mod-state: completed event
-    let _ = app.emit("mod-state", serde_json::json!({
-        "modId": mod_id,
-        "state": "completed"
-    }));
+    emit_mod_lifecycle(&app, &mod_id, ModPhase::Installed, serde_json::Value::Null);
     
     // Emit mod-updated event
     let _ = app.emit("mod-updated", serde_json::json!({
@@ -302,10 +379,331 @@ pub async fn continue_download_with_decision(
         .map(|s| s.to_string())
         .unwrap_or_else(|| mod_id.clone());
     
+    let missing_dependencies = find_missing_dependencies(&mod_path, &mod_id, &mods_path_buf).await;
+    
     Ok(serde_json::json!({
         "modId": mod_id,
         "modPath": mod_path.to_string_lossy(),
         "folder": folder,
+        "missingDependencies": missing_dependencies,
     }))
 }
 
+/// Re-verify installed mods with SteamCMD and reinstall the ones whose files were repaired
+/// SteamCMD checks the copies in its Workshop content folder against Steam and re-downloads missing or damaged files;
+/// a mod is only reinstalled if the verified copy differs from the installed folder
+/// Returns a map of mod ID to `{ success, changed, modPath }` or `{ success: false, error }`
+#[command]
+pub async fn revalidate_mods(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    mods_path: String,
+    max_steamcmd_instances: Option<usize>,
+    link_mode: Option<LinkMode>,
+) -> Result<serde_json::Value, AppError> {
+    if mod_ids.is_empty() {
+        return Err(AppError::invalid_input("mod_ids array is required"));
+    }
+    let link_mode = link_mode.unwrap_or_default();
+    
+    let mods_path_buf = validate_mods_path(&mods_path)?;
+    ensure_directory_access(&app, &mods_path_buf, &mods_path).map_err(AppError::failed)?;
+    
+    let downloader = get_downloader();
+    let (mut mod_receiver, download_path) = {
+        let mut dl = downloader.lock().await;
+        let receiver = dl.revalidate_mods(&mod_ids, Some(&app), max_steamcmd_instances)
+            .await
+            .map_err(|e| AppError::DownloadFailed { mod_ids: mod_ids.clone(), message: e })?;
+        (receiver, dl.download_path().clone())
+    };
+    
+    let updater = ModUpdater;
+    let install_options = InstallOptions {
+        force_overwrite_corrupted: Some(true), // Repairing a damaged install is the point, overwrite a corrupted folder
+        ..load_install_options(&app, download_path, mods_path_buf.clone(), link_mode)
+    };
+    let mut result_map = serde_json::Map::new();
+    let mut download_errors = std::collections::HashMap::new();
+    while let Some(result) = mod_receiver.recv().await {
+        let downloaded_mod = match result {
+            Ok(downloaded_mod) => downloaded_mod,
+            Err(e) => {
+                eprintln!("[RevalidateMods] {}", e);
+                download_errors.insert(e.mod_id().to_string(), e);
+                continue;
+            }
+        };
+        let mod_id = downloaded_mod.mod_id.clone();
+        
+        let installed_path = find_all_mod_folders_with_id(&mods_path_buf, &mod_id)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .next();
+        
+        // Nothing to reinstall if the verified copy matches the installed one
+        if let Some(installed_path) = &installed_path {
+            let downloaded = downloaded_mod.mod_path.clone();
+            let installed = installed_path.clone();
+            let identical = tokio::task::spawn_blocking(move || is_identical_content(&downloaded, &installed))
+                .await
+                .map_err(|e| format!("Task panicked: {:?}", e))
+                .and_then(|r| r)
+                .unwrap_or_else(|e| {
+                    eprintln!("[RevalidateMods] Failed to compare content of mod {}: {}", mod_id, e);
+                    false
+                });
+            if identical {
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Installed, serde_json::json!({
+                    "skipped": "identical-content",
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": true,
+                    "changed": false,
+                    "modPath": installed_path.to_string_lossy(),
+                }));
+                continue;
+            }
+        }
+        
+        emit_mod_lifecycle(&app, &mod_id, ModPhase::Installing, serde_json::Value::Null);
+        
+        // Revalidating doesn't make a mod newer, keep the update time it had
+        let time_updated = installed_path.as_deref()
+            .and_then(|path| get_mod_last_updated_time(path).ok())
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64);
+        let existing_folder_name = installed_path.as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string());
+        
+        let install_result = updater.update_mod(
+            &mod_id,
+            &downloaded_mod.mod_path,
+            existing_folder_name.as_deref(),
+            None,
+            None,
+            &install_options,
+        ).await;
+        
+        match install_result {
+            Ok(mod_path) => {
+                if let Some(time_updated) = time_updated {
+                    write_last_updated_file(mod_path.clone(), time_updated).await;
+                }
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Installed, serde_json::Value::Null);
+                let _ = app.emit("mod-updated", serde_json::json!({
+                    "modId": mod_id,
+                    "success": true,
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": true,
+                    "changed": true,
+                    "modPath": mod_path.to_string_lossy(),
+                }));
+            }
+            Err(e) => {
+                let e = e.to_string();
+                eprintln!("[RevalidateMods] Failed to reinstall mod {}: {}", mod_id, e);
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Failed, serde_json::json!({
+                    "error": e,
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": false,
+                    "error": e,
+                }));
+            }
+        }
+    }
+    
+    // Mods SteamCMD couldn't validate
+    for mod_id in &mod_ids {
+        if !result_map.contains_key(mod_id) {
+            let result = match download_errors.remove(mod_id) {
+                Some(error) => serde_json::json!({
+                    "success": false,
+                    "error": error.to_string(),
+                    "downloadError": error,
+                }),
+                None => serde_json::json!({
+                    "success": false,
+                    "error": "Validation failed - SteamCMD reported failure",
+                }),
+            };
+            result_map.insert(mod_id.clone(), result);
+        }
+    }
+    
+    Ok(serde_json::Value::Object(result_map))
+}
+
+/// Re-download mods reported by `scan_corrupted_mods` and install them over their corrupted folders
+/// Returns a map of mod ID to `{ success, modPath }` or `{ success: false, error }`
+#[command]
+pub async fn repair_mods(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    mods_path: String,
+    max_steamcmd_instances: Option<usize>,
+    link_mode: Option<LinkMode>,
+) -> Result<serde_json::Value, AppError> {
+    if mod_ids.is_empty() {
+        return Err(AppError::invalid_input("mod_ids array is required"));
+    }
+    let link_mode = link_mode.unwrap_or_default();
+    
+    let mods_path_buf = validate_mods_path(&mods_path)?;
+    ensure_directory_access(&app, &mods_path_buf, &mods_path).map_err(AppError::failed)?;
+    
+    // Corrupted folders are replaced in place, so repaired mods keep their folder names
+    let scan_path = mods_path_buf.clone();
+    let corrupted_folders: std::collections::HashMap<String, String> = tokio::task::spawn_blocking(move || scan_corrupted_mods(&scan_path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?
+        .into_iter()
+        .filter_map(|corrupted| Some((corrupted.mod_id?, corrupted.folder)))
+        .collect();
+    
+    // Title for new folders and update time for .lastupdated
+    let details: std::collections::HashMap<String, (String, i64)> = query_mod_batch(&mod_ids, 0)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|detail| (detail.publishedfileid.clone(), (detail.title, detail.time_updated)))
+        .collect();
+    
+    let downloader = get_downloader();
+    let (mut mod_receiver, download_path) = {
+        let mut dl = downloader.lock().await;
+        let receiver = dl.download_mods(&mod_ids, Some(&app), max_steamcmd_instances)
+            .await
+            .map_err(|e| AppError::DownloadFailed { mod_ids: mod_ids.clone(), message: e })?;
+        (receiver, dl.download_path().clone())
+    };
+    
+    let updater = ModUpdater;
+    let install_options = InstallOptions {
+        force_overwrite_corrupted: Some(true), // The corrupted folder is what gets repaired
+        ..load_install_options(&app, download_path, mods_path_buf.clone(), link_mode)
+    };
+    let mut result_map = serde_json::Map::new();
+    let mut download_errors = std::collections::HashMap::new();
+    while let Some(result) = mod_receiver.recv().await {
+        let downloaded_mod = match result {
+            Ok(downloaded_mod) => downloaded_mod,
+            Err(e) => {
+                eprintln!("[RepairMods] {}", e);
+                download_errors.insert(e.mod_id().to_string(), e);
+                continue;
+            }
+        };
+        let mod_id = downloaded_mod.mod_id.clone();
+        let (mod_title, time_updated) = match details.get(&mod_id) {
+            Some((title, time_updated)) => (Some(title.as_str()), Some(*time_updated)),
+            None => (None, None),
+        };
+        
+        emit_mod_lifecycle(&app, &mod_id, ModPhase::Installing, serde_json::Value::Null);
+        
+        let install_result = updater.update_mod(
+            &mod_id,
+            &downloaded_mod.mod_path,
+            corrupted_folders.get(&mod_id).map(String::as_str),
+            mod_title,
+            None,
+            &install_options,
+        ).await;
+        
+        match install_result {
+            Ok(mod_path) => {
+                if let Some(time_updated) = time_updated {
+                    write_last_updated_file(mod_path.clone(), time_updated).await;
+                }
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Installed, serde_json::Value::Null);
+                let _ = app.emit("mod-updated", serde_json::json!({
+                    "modId": mod_id,
+                    "success": true,
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": true,
+                    "modPath": mod_path.to_string_lossy(),
+                }));
+            }
+            Err(e) => {
+                let e = e.to_string();
+                eprintln!("[RepairMods] Failed to reinstall mod {}: {}", mod_id, e);
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Failed, serde_json::json!({
+                    "error": e,
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": false,
+                    "error": e,
+                }));
+            }
+        }
+    }
+    
+    // Mods SteamCMD couldn't download
+    for mod_id in &mod_ids {
+        if !result_map.contains_key(mod_id) {
+            let result = match download_errors.remove(mod_id) {
+                Some(error) => serde_json::json!({
+                    "success": false,
+                    "error": error.to_string(),
+                    "downloadError": error,
+                }),
+                None => serde_json::json!({
+                    "success": false,
+                    "error": "Download failed - SteamCMD reported failure",
+                }),
+            };
+            result_map.insert(mod_id.clone(), result);
+        }
+    }
+    
+    Ok(serde_json::Value::Object(result_map))
+}
+
+/// Get mods whose downloads kept failing across sessions, with suggested manual steps
+#[command]
+pub async fn get_problem_mods(
+    app: AppHandle,
+    min_failures: Option<u32>,
+) -> Result<serde_json::Value, AppError> {
+    const DEFAULT_MIN_FAILURES: u32 = 3;
+    
+    let history = load_failure_history(&app).map_err(AppError::failed)?;
+    let problem_mods = history.problem_mods(min_failures.unwrap_or(DEFAULT_MIN_FAILURES));
+    
+    serde_json::to_value(problem_mods)
+        .map_err(|e| AppError::failed(format!("Failed to serialize problem mods: {}", e)))
+}
+
+/// Get the current status of each SteamCMD instance of the running download
+#[command]
+pub async fn get_instance_statuses() -> Result<serde_json::Value, AppError> {
+    let downloader = get_downloader();
+    let dl = downloader.lock().await;
+    
+    serde_json::to_value(dl.instance_statuses())
+        .map_err(|e| AppError::failed(format!("Failed to serialize instance statuses: {}", e)))
+}
+
+/// Choose which files are skipped when installing a mod and which are kept from the installed copy
+/// None goes back to installing every file and keeping nothing
+#[command]
+pub async fn set_install_filter(app: AppHandle, filter: Option<InstallFilter>) -> Result<InstallFilter, AppError> {
+    let filter = filter.unwrap_or_default();
+    // Validated first, so invalid patterns are never persisted
+    filter.exclude_set().map_err(AppError::invalid_input)?;
+    filter.preserved_paths().map_err(AppError::invalid_input)?;
+    save_install_filter(&app, &filter).map_err(AppError::failed)?;
+    Ok(filter)
+}
+
+/// Get which files are skipped when installing a mod and which are kept from the installed copy
+#[command]
+pub async fn get_install_filter(app: AppHandle) -> Result<InstallFilter, AppError> {
+    Ok(load_install_filter(&app))
+}
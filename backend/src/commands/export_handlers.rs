@@ -1,10 +1,15 @@
-// Export commands
+// Export and import commands
 
-use crate::core::mod_scanner::{BaseMod, list_installed_mods as list_installed_mods_query, update_mod_details as update_mod_details_query};
-use crate::core::access_check::check_directory_access_with_warning;
-use crate::services::validate_mods_path;
-use tauri::{command, AppHandle};
+use std::collections::HashMap;
+use crate::core::mod_scanner::{BaseMod, list_installed_mods as list_installed_mods_query, list_installed_mods_fast, query_mod_batch, update_mod_details as update_mod_details_query};
+use crate::core::mod_list::{build_mod_list, missing_mod_ids, parse_mod_list};
+use crate::core::mod_lifecycle::{emit_mod_lifecycle, ModPhase};
+use crate::core::mod_manager::{InstallOptions, LinkMode, ModUpdater};
+use crate::core::access_check::{check_directory_access_with_warning, ensure_directory_access};
+use crate::services::{get_downloader, load_install_options, validate_mods_path, write_last_updated_file};
+use tauri::{command, AppHandle, Emitter};
 use tauri_plugin_clipboard_manager::ClipboardExt;
+use crate::error::AppError;
 
 /// Export mod list to clipboard
 /// Copies formatted string with one mod per line to clipboard
@@ -16,7 +21,7 @@ pub async fn export_mods_to_clipboard(
     app: AppHandle,
     mods: Option<Vec<BaseMod>>,
     mods_path: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mods = if let Some(provided_mods) = mods {
         // Use mods provided by frontend (already loaded and have Steam details)
         provided_mods
@@ -25,21 +30,21 @@ pub async fn export_mods_to_clipboard(
         let path = validate_mods_path(&mods_path_str)?;
         
         // Check directory access (read access is required)
-        check_directory_access_with_warning(&app, &path, &mods_path_str)?;
+        check_directory_access_with_warning(&app, &path, &mods_path_str).map_err(AppError::failed)?;
         
         // List installed mods
         let mut fetched_mods = list_installed_mods_query(&path)
             .await
-            .map_err(|e| format!("Failed to list installed mods: {}", e))?;
+            .map_err(|e| AppError::failed(format!("Failed to list installed mods: {}", e)))?;
         
         // Update mod details to get Steam titles
         fetched_mods = update_mod_details_query(fetched_mods)
             .await
-            .map_err(|e| format!("Failed to update mod details: {}", e))?;
+            .map_err(|e| AppError::failed(format!("Failed to update mod details: {}", e)))?;
         
         fetched_mods
     } else {
-        return Err("Either mods or mods_path must be provided".to_string());
+        return Err(AppError::invalid_input("Either mods or mods_path must be provided"));
     };
         
     let header = format!(
@@ -102,7 +107,178 @@ pub async fn export_mods_to_clipboard(
     // Copy to clipboard using Tauri plugin
     app.clipboard()
         .write_text(formatted_text)
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+        .map_err(|e| AppError::failed(format!("Failed to copy to clipboard: {}", e)))?;
     
     Ok(())
 }
+
+/// Export the installed Workshop mods as a shareable JSON mod list
+/// Each entry holds the Workshop ID, folder name and packageId of a mod
+#[command]
+pub async fn export_mod_list(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<String, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    let installed = list_installed_mods_fast(&path)
+        .await
+        .map_err(|e| AppError::failed(format!("Failed to list installed mods: {}", e)))?;
+    
+    let mod_list = tokio::task::spawn_blocking(move || build_mod_list(&installed))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?;
+    
+    serde_json::to_string_pretty(&mod_list)
+        .map_err(|e| AppError::failed(format!("Failed to serialize mod list: {}", e)))
+}
+
+/// Download and install the mods of a shared mod list that aren't installed yet
+/// Accepts an exported JSON mod list or a plain list of Workshop IDs/URLs, one per line
+/// Returns a map of mod ID to `{ success, data | error }` for every downloaded mod
+#[command]
+pub async fn import_mod_list(
+    app: AppHandle,
+    list: String,
+    mods_path: String,
+    max_steamcmd_instances: Option<usize>,
+    link_mode: Option<LinkMode>,
+) -> Result<serde_json::Value, AppError> {
+    let link_mode = link_mode.unwrap_or_default();
+    let mod_ids = parse_mod_list(&list);
+    if mod_ids.is_empty() {
+        return Err(AppError::invalid_input("Mod list does not contain any mod IDs"));
+    }
+    
+    let path = validate_mods_path(&mods_path)?;
+    ensure_directory_access(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    // Only fetch what isn't installed yet
+    let installed = list_installed_mods_fast(&path)
+        .await
+        .map_err(|e| AppError::failed(format!("Failed to list installed mods: {}", e)))?;
+    let missing = missing_mod_ids(&mod_ids, &installed);
+    eprintln!("[ImportModList] {} of {} mod(s) in the list are not installed", missing.len(), mod_ids.len());
+    if missing.is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    
+    // Fetch titles, update times and sizes in batches of 50
+    let mut details_map = HashMap::new();
+    for batch in missing.chunks(50) {
+        match query_mod_batch(batch, 0).await {
+            Ok(details) => {
+                for detail in details {
+                    details_map.insert(detail.publishedfileid.clone(), detail);
+                }
+            }
+            Err(e) => eprintln!("[ImportModList] Failed to fetch details for mod list batch: {}", e),
+        }
+    }
+    let mod_sizes: HashMap<String, u64> = details_map.iter()
+        .map(|(id, d)| (id.clone(), d.file_size))
+        .collect();
+    
+    let downloader = get_downloader();
+    let (mut mod_receiver, download_path) = {
+        let mut dl = downloader.lock().await;
+        let receiver = dl.download_mods_with_sizes(&missing, Some(&mod_sizes), Some(&app), max_steamcmd_instances)
+            .await
+            .map_err(|e| AppError::DownloadFailed { mod_ids: missing.clone(), message: e })?;
+        (receiver, dl.download_path().clone())
+    };
+    
+    let updater = ModUpdater;
+    let install_options = InstallOptions {
+        force_overwrite_corrupted: Some(false), // Never overwrite a corrupted folder during a bulk import, install next to it
+        ..load_install_options(&app, download_path, path, link_mode)
+    };
+    let mut result_map = serde_json::Map::new();
+    let mut download_errors = std::collections::HashMap::new();
+    while let Some(result) = mod_receiver.recv().await {
+        let downloaded_mod = match result {
+            Ok(downloaded_mod) => downloaded_mod,
+            Err(e) => {
+                eprintln!("[ImportModList] {}", e);
+                download_errors.insert(e.mod_id().to_string(), e);
+                continue;
+            }
+        };
+        
+        let mod_id = downloaded_mod.mod_id.clone();
+        emit_mod_lifecycle(&app, &mod_id, ModPhase::Installing, serde_json::Value::Null);
+        
+        let details = details_map.get(&mod_id);
+        let install_result = updater.update_mod(
+            &mod_id,
+            &downloaded_mod.mod_path,
+            None,
+            details.map(|d| d.title.as_str()),
+            details.map(|d| d.time_updated),
+            &install_options,
+        ).await;
+        
+        match install_result {
+            Ok(installed_path) => {
+                let time_updated = details.map(|d| d.time_updated).unwrap_or_else(|| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs() as i64
+                });
+                write_last_updated_file(installed_path.clone(), time_updated).await;
+                
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Installed, serde_json::Value::Null);
+                let _ = app.emit("mod-updated", serde_json::json!({
+                    "modId": mod_id,
+                    "success": true,
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": true,
+                    "data": {
+                        "modPath": installed_path.to_string_lossy(),
+                        "folder": downloaded_mod.folder,
+                    }
+                }));
+            }
+            Err(e) => {
+                let e = e.to_string();
+                eprintln!("[ImportModList] Failed to install mod {}: {}", mod_id, e);
+                emit_mod_lifecycle(&app, &mod_id, ModPhase::Failed, serde_json::json!({
+                    "error": e,
+                }));
+                let _ = app.emit("mod-updated", serde_json::json!({
+                    "modId": mod_id,
+                    "success": false,
+                    "error": e,
+                }));
+                result_map.insert(mod_id, serde_json::json!({
+                    "success": false,
+                    "error": e
+                }));
+            }
+        }
+    }
+    
+    // Mods that never arrived from SteamCMD
+    for mod_id in &missing {
+        if !result_map.contains_key(mod_id) {
+            let result = match download_errors.remove(mod_id) {
+                Some(error) => serde_json::json!({
+                    "success": false,
+                    "error": error.to_string(),
+                    "downloadError": error,
+                }),
+                None => serde_json::json!({
+                    "success": false,
+                    "error": "Download failed - SteamCMD reported failure"
+                }),
+            };
+            result_map.insert(mod_id.clone(), result);
+        }
+    }
+    
+    Ok(serde_json::Value::Object(result_map))
+}
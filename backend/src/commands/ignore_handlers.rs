@@ -2,15 +2,16 @@
 
 use std::path::PathBuf;
 use serde_json;
-use tauri::command;
-use crate::core::mod_scanner::BaseMod;
-use crate::services::{find_all_mod_folders_with_id, fetch_mod_times_updated, write_ignore_update_file, get_mods_path_from_mod_path};
+use tauri::{command, AppHandle};
+use crate::core::mod_scanner::{find_about_dir, parse_workshop_id, BaseMod};
+use crate::services::{find_all_mod_folders_with_id, fetch_mod_times_updated, write_ignore_update_file, get_mods_path_from_mod_path, load_ignored_mods, save_mod_ignored};
+use crate::error::AppError;
 
 /// Ignore this update - create .ignoredupdate file with current remote timestamp
 #[command]
 pub async fn ignore_update(
     mods: Vec<BaseMod>,
-) -> Result<Vec<serde_json::Value>, String> {
+) -> Result<Vec<serde_json::Value>, AppError> {
     if mods.is_empty() {
         return Ok(vec![]);
     }
@@ -117,7 +118,7 @@ pub async fn ignore_update(
 #[command]
 pub async fn check_ignored_updates(
     mod_paths: Vec<String>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     if mod_paths.is_empty() {
         return Ok(serde_json::json!({}));
     }
@@ -127,7 +128,7 @@ pub async fn check_ignored_updates(
     
     for mod_path in mod_paths {
         let mod_path_buf = PathBuf::from(&mod_path);
-        let about_path = mod_path_buf.join("About");
+        let about_path = find_about_dir(&mod_path_buf);
         let ignore_update_path = about_path.join(".ignoredupdate");
         let mod_path_clone = mod_path.clone();
         
@@ -163,7 +164,7 @@ pub async fn check_ignored_updates(
 #[command]
 pub async fn undo_ignore_update(
     mods: Vec<BaseMod>,
-) -> Result<Vec<serde_json::Value>, String> {
+) -> Result<Vec<serde_json::Value>, AppError> {
     if mods.is_empty() {
         return Ok(vec![]);
     }
@@ -174,7 +175,7 @@ pub async fn undo_ignore_update(
     for mod_ref in mods {
         let mod_id = mod_ref.mod_id.clone();
         let mod_path = PathBuf::from(&mod_ref.mod_path);
-        let mods_path = get_mods_path_from_mod_path(&mod_path)?;
+        let mods_path = get_mods_path_from_mod_path(&mod_path).map_err(AppError::failed)?;
         
         let mod_id_clone = mod_id.clone();
         let mods_path_clone = mods_path.clone();
@@ -188,7 +189,7 @@ pub async fn undo_ignore_update(
             // Remove .ignoredupdate files in parallel
             let mut file_futures = Vec::new();
             for folder_path in all_mod_folders {
-                let about_path = folder_path.join("About");
+                let about_path = find_about_dir(&folder_path);
                 let ignore_update_path = about_path.join(".ignoredupdate");
                 
                 let future = tokio::task::spawn_blocking(move || {
@@ -226,3 +227,24 @@ pub async fn undo_ignore_update(
     Ok(undone_mods)
 }
 
+/// List the mods whose updates are always ignored (settings `ignoredMods`), sorted by mod ID
+#[command]
+pub async fn list_ignored(app: AppHandle) -> Result<Vec<String>, AppError> {
+    Ok(load_ignored_mods(&app).into_iter().collect())
+}
+
+/// Always ignore updates of a mod, also after its folder is deleted and the mod downloaded again
+/// The mod is added to the settings `ignoredMods` list under `title` (its ID if omitted), returns the updated list
+#[command]
+pub async fn ignore(app: AppHandle, mod_id: String, title: Option<String>) -> Result<Vec<String>, AppError> {
+    let mod_id = parse_workshop_id(&mod_id).ok_or_else(|| AppError::invalid_mod_id(mod_id))?;
+    Ok(save_mod_ignored(&app, &mod_id, title.as_deref(), true).map_err(AppError::failed)?.into_iter().collect())
+}
+
+/// Stop ignoring updates of a mod added with `ignore` or from the settings, returns the updated list
+/// `.ignoredupdate` files of its folders are left alone, `undo_ignore_update` removes them
+#[command]
+pub async fn unignore(app: AppHandle, mod_id: String) -> Result<Vec<String>, AppError> {
+    let mod_id = parse_workshop_id(&mod_id).ok_or_else(|| AppError::invalid_mod_id(mod_id))?;
+    Ok(save_mod_ignored(&app, &mod_id, None, false).map_err(AppError::failed)?.into_iter().collect())
+}
@@ -8,6 +8,11 @@ pub mod workshop_handlers;
 pub mod download_handlers;
 pub mod watcher_handlers;
 pub mod export_handlers;
+pub mod settings_handlers;
+pub mod schedule_handlers;
+pub mod steamcmd_handlers;
+pub mod mods_config_handlers;
+pub mod open_handlers;
 pub mod types;
 
 // Re-export all handlers for easy access
@@ -18,4 +23,9 @@ pub use ignore_handlers::*;
 pub use workshop_handlers::*;
 pub use download_handlers::*;
 pub use watcher_handlers::*;
-pub use export_handlers::*;
\ No newline at end of file
+pub use export_handlers::*;
+pub use settings_handlers::*;
+pub use schedule_handlers::*;
+pub use steamcmd_handlers::*;
+pub use mods_config_handlers::*;
+pub use open_handlers::*;
\ No newline at end of file
@@ -0,0 +1,30 @@
+// RimWorld ModsConfig.xml (active mod list) commands
+
+use std::path::PathBuf;
+use tauri::command;
+use crate::core::mods_config::{read_mods_config, write_active_mods, ModsConfig};
+use crate::error::AppError;
+
+/// Get the active mods and game version from RimWorld's Config/ModsConfig.xml
+/// A missing file is reported as only the base game being active
+#[command]
+pub async fn get_active_mods(config_path: String) -> Result<ModsConfig, AppError> {
+    let config_path = PathBuf::from(config_path);
+    tokio::task::spawn_blocking(move || read_mods_config(&config_path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Replace the active mod list (packageIds in load order) in RimWorld's Config/ModsConfig.xml
+/// The rest of the file is kept as is; the file is created if it doesn't exist yet
+#[command]
+pub async fn set_active_mods(config_path: String, package_ids: Vec<String>) -> Result<ModsConfig, AppError> {
+    let package_ids: Vec<String> = package_ids.into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect();
+    let config_path = PathBuf::from(config_path);
+    tokio::task::spawn_blocking(move || write_active_mods(&config_path, &package_ids))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
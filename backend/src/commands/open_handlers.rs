@@ -0,0 +1,35 @@
+// Commands opening mods in the system's default apps
+
+use std::path::PathBuf;
+use tauri::{command, AppHandle};
+use tauri_plugin_opener::OpenerExt;
+use crate::error::AppError;
+
+/// Reveal a mod folder in the system file manager
+/// The folder is checked first, so a stale path fails here instead of showing an OS error dialog
+#[command]
+pub async fn open_mod_folder(app: AppHandle, mod_path: String) -> Result<(), AppError> {
+    let path = PathBuf::from(&mod_path);
+    if !path.exists() {
+        return Err(AppError::path_not_found(&path));
+    }
+    if !path.is_dir() {
+        return Err(AppError::not_a_directory(&path));
+    }
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| AppError::failed(format!("Failed to open mod folder: {}", e)))
+}
+
+/// Open the Steam Workshop page of a mod in the default browser
+#[command]
+pub async fn open_workshop_page(app: AppHandle, mod_id: String) -> Result<(), AppError> {
+    let mod_id = mod_id.trim();
+    if mod_id.is_empty() || !mod_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppError::invalid_input(format!("Invalid mod id: {}", mod_id)));
+    }
+    let url = format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", mod_id);
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| AppError::failed(format!("Failed to open Workshop page: {}", e)))
+}
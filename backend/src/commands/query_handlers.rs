@@ -1,25 +1,62 @@
 // Mod query commands
 
-use crate::core::mod_scanner::{query_mods_for_updates, BaseMod, update_mod_details as update_mod_details_query, list_installed_mods as list_installed_mods_query};
-use crate::core::access_check::check_directory_access_with_warning;
-use crate::services::validate_mods_path;
-use tauri::{command, AppHandle};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use crate::core::mod_scanner::{query_mods_for_updates, BaseMod, update_mod_details as update_mod_details_query, list_installed_mods as list_installed_mods_query, normalize_about_folder_case as normalize_about_folder_case_query, normalize_published_file_ids as normalize_published_file_ids_query, backfill_published_file_ids as backfill_published_file_ids_query, PublishedFileIdBackfill, query_mods_for_updates_with_timing, set_scan_concurrency as set_scan_concurrency_query, mod_size, mod_update_status, query_mod_batch, query_mod_id, dependency_report, DependencyReport, ModSize, ModUpdateStatus, TimedScanResult};
+use crate::core::duplicate_mods::{find_duplicate_mods as find_duplicate_mods_query, find_packageid_collisions as find_packageid_collisions_query, DuplicateGroup};
+use crate::core::content_fingerprint::{verify_mod_checksums as verify_mod_checksums_query, VerifyReport};
+use crate::core::load_order::{detect_load_conflicts as detect_load_conflicts_query, LoadConflict};
+use crate::core::mod_manager::{scan_corrupted_mods as scan_corrupted_mods_query, CorruptedMod};
+use crate::core::about_xml::{validate_about_metadata as validate_about_metadata_query, check_version_compatibility as check_version_compatibility_query, AboutValidationReport, VersionCompatibilityReport};
+use crate::core::access_check::{check_directory_access_with_warning, ensure_directory_access};
+use crate::core::thumbnail_cache::{get_thumbnail, DEFAULT_THUMBNAIL_DIM, THUMBNAIL_CACHE_FOLDER};
+use crate::services::{get_steam_api, load_ignored_mods, save_scan_concurrency, validate_mods_path};
+use base64::Engine;
+use tauri::{command, AppHandle, Manager};
+use crate::error::AppError;
+
+/// Mods ignored by the UI plus the ones in the settings `ignoredMods` list
+fn with_settings_ignored_mods(app: &AppHandle, mut ignored_mods: Vec<String>) -> Vec<String> {
+    ignored_mods.extend(load_ignored_mods(app));
+    ignored_mods
+}
 
 /// Query mods folder for outdated mods
+/// Mods in `ignored_mods` or the settings `ignoredMods` list are never reported as outdated
 #[command]
 pub async fn query_mods(
     app: AppHandle,
     mods_path: String,
     ignored_mods: Vec<String>,
-) -> Result<Vec<BaseMod>, String> {
+) -> Result<Vec<BaseMod>, AppError> {
     let path = validate_mods_path(&mods_path)?;
     
     // Check directory access (read access is required, write access is checked but not required for querying)
-    check_directory_access_with_warning(&app, &path, &mods_path)?;
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    let ignored_mods = with_settings_ignored_mods(&app, ignored_mods);
     
     query_mods_for_updates(&path, &ignored_mods)
         .await
-        .map_err(|e| format!("Failed to query mods: {}", e))
+        .map_err(|e| AppError::failed(format!("Failed to query mods: {}", e)))
+}
+
+/// Query mods folder for outdated mods, recording how long each mod folder took to scan
+/// Returns the result of the scan together with the `limit` slowest mods (10 by default)
+#[command]
+pub async fn query_mods_with_timing(
+    app: AppHandle,
+    mods_path: String,
+    ignored_mods: Vec<String>,
+    limit: Option<usize>,
+) -> Result<TimedScanResult, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    let ignored_mods = with_settings_ignored_mods(&app, ignored_mods);
+    
+    query_mods_for_updates_with_timing(&path, &ignored_mods, limit.unwrap_or(10))
+        .await
+        .map_err(|e| AppError::failed(format!("Failed to query mods: {}", e)))
 }
 
 /// List all installed mods in mods folder (fast version - returns immediately with local data only)
@@ -27,15 +64,27 @@ pub async fn query_mods(
 pub async fn list_installed_mods(
     app: AppHandle,
     mods_path: String,
-) -> Result<Vec<BaseMod>, String> {
+) -> Result<Vec<BaseMod>, AppError> {
     let path = validate_mods_path(&mods_path)?;
     
     // Check directory access (read access is required, write access is checked but not required for listing)
-    check_directory_access_with_warning(&app, &path, &mods_path)?;
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
     
     list_installed_mods_query(&path)
         .await
-        .map_err(|e| format!("Failed to list installed mods: {}", e))
+        .map_err(|e| AppError::failed(format!("Failed to list installed mods: {}", e)))
+}
+
+/// Set how many mod folders are read in parallel when listing installed mods
+/// Pass None to go back to one per CPU
+#[command]
+pub async fn set_scan_concurrency(app: AppHandle, concurrency: Option<usize>) -> Result<(), AppError> {
+    if concurrency == Some(0) {
+        return Err(AppError::invalid_input("Scan concurrency must be at least 1"));
+    }
+    save_scan_concurrency(&app, concurrency).map_err(AppError::failed)?;
+    set_scan_concurrency_query(concurrency);
+    Ok(())
 }
 
 /// Update mod details from Steam API in background
@@ -43,9 +92,264 @@ pub async fn list_installed_mods(
 #[command]
 pub async fn update_mod_details(
     mods: Vec<BaseMod>,
-) -> Result<Vec<BaseMod>, String> {
+) -> Result<Vec<BaseMod>, AppError> {
     update_mod_details_query(mods)
         .await
-        .map_err(|e| format!("Failed to update mod details: {}", e))
+        .map_err(|e| AppError::failed(format!("Failed to update mod details: {}", e)))
 }
 
+
+/// Report installed mods whose About.xml lacks a name, packageId or supportedVersions
+#[command]
+pub async fn validate_about_metadata(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<Vec<AboutValidationReport>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    validate_about_metadata_query(&path)
+        .await
+        .map_err(|e| AppError::failed(format!("Failed to validate About.xml metadata: {}", e)))
+}
+
+/// Report installed mods whose supportedVersions don't include the running game version
+/// Versions are compared as major.minor, mods without supportedVersions are reported as unknown
+#[command]
+pub async fn check_version_compatibility(
+    app: AppHandle,
+    mods_path: String,
+    game_version: String,
+) -> Result<Vec<VersionCompatibilityReport>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    check_version_compatibility_query(&path, game_version.trim())
+        .await
+        .map_err(|e| AppError::failed(format!("Failed to check version compatibility: {}", e)))
+}
+
+/// Find mods installed more than once, grouped by publishedFileId or packageId
+/// Each group reports the folders' sizes and which one was updated most recently
+#[command]
+pub async fn find_duplicate_mods(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<Vec<DuplicateGroup>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    tokio::task::spawn_blocking(move || find_duplicate_mods_query(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Find packageIds shared by several installed mods, e.g. a Workshop and a local copy of the same mod
+/// RimWorld refuses to load when both are active, so the UI can warn before the game is launched
+#[command]
+pub async fn find_packageid_collisions(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<Vec<(String, Vec<PathBuf>)>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    tokio::task::spawn_blocking(move || find_packageid_collisions_query(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Find mod folders missing their About folder or About.xml
+/// Mods with a recovered Workshop ID can be fixed with `repair_mods`, unrecoverable ones only by deleting them
+#[command]
+pub async fn scan_corrupted_mods(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<Vec<CorruptedMod>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    tokio::task::spawn_blocking(move || scan_corrupted_mods_query(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Resolve the dependencies a mod declares in its About.xml against the mods installed in `mods_path`
+/// Each dependency is reported as installed (matched by packageId, whatever the folder), missing with a
+/// Workshop ID to download, or missing without a link
+#[command]
+pub async fn get_dependency_report(
+    app: AppHandle,
+    mod_path: String,
+    mods_path: String,
+) -> Result<DependencyReport, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    dependency_report(&PathBuf::from(mod_path), &path).await.map_err(AppError::failed)
+}
+
+/// Report installed mods that are incompatible with each other
+/// With the path of RimWorld's Config/ModsConfig.xml, active mods loaded against their loadAfter/loadBefore are reported too
+#[command]
+pub async fn detect_load_conflicts(
+    app: AppHandle,
+    mods_path: String,
+    config_path: Option<String>,
+) -> Result<Vec<LoadConflict>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    let config_path = config_path.map(PathBuf::from);
+    tokio::task::spawn_blocking(move || detect_load_conflicts_query(&path, config_path.as_deref()))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Rename mod About folders with mismatched case (e.g. `about`) to `About`
+/// Returns paths of the mod folders that were fixed
+#[command]
+pub async fn normalize_about_folder_case(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<Vec<String>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    // Renaming requires write access
+    ensure_directory_access(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    let fixed = tokio::task::spawn_blocking(move || normalize_about_folder_case_query(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    Ok(fixed.into_iter().map(|p| p.to_string_lossy().to_string()).collect())
+}
+
+/// Rewrite PublishedFileId.txt files containing a BOM, whitespace or extra newlines to a clean `<id>\n`
+/// Returns paths of the mod folders that were fixed
+#[command]
+pub async fn normalize_published_file_ids(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<Vec<String>, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    ensure_directory_access(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    let fixed = tokio::task::spawn_blocking(move || normalize_published_file_ids_query(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    Ok(fixed.into_iter().map(|p| p.to_string_lossy().to_string()).collect())
+}
+
+/// Write PublishedFileId.txt for mods installed by other tools without one, recovering the ID from
+/// About.xml or the folder name; mods whose ID can't be found are reported as unresolved
+#[command]
+pub async fn backfill_published_file_ids(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<PublishedFileIdBackfill, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    ensure_directory_access(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    tokio::task::spawn_blocking(move || backfill_published_file_ids_query(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Check whether a single installed mod has an update, without scanning the whole mods folder
+/// Workshop details cached by earlier lookups are used when available
+#[command]
+pub async fn check_mod_update(mod_path: String) -> Result<ModUpdateStatus, AppError> {
+    let path = PathBuf::from(&mod_path);
+    if !path.is_dir() {
+        return Err(AppError::path_not_found(&path));
+    }
+    let mod_id = query_mod_id(&path)
+        .map_err(|e| AppError::failed(format!("Failed to read mod ID: {}", e)))?
+        .ok_or_else(|| AppError::invalid_input(format!("Not a Workshop mod: {}", mod_path)))?;
+    
+    let cached = get_steam_api().lock().await.cached_file_details(&mod_id);
+    let details = match cached {
+        Some(details) => details,
+        None => query_mod_batch(std::slice::from_ref(&mod_id), 0)
+            .await
+            .map_err(|e| AppError::failed(format!("Failed to query mod details: {}", e)))?
+            .into_iter()
+            .find(|d| d.publishedfileid == mod_id)
+            .ok_or_else(|| AppError::failed(format!("No Workshop details found for mod {}", mod_id)))?,
+    };
+    
+    let remote_time = details.time_updated;
+    tokio::task::spawn_blocking(move || mod_update_status(&path, remote_time))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Get the on-disk size of a mod folder in bytes, linked mods report the size of their target
+#[command]
+pub async fn get_mod_size(mod_path: String) -> Result<ModSize, AppError> {
+    tokio::task::spawn_blocking(move || mod_size(&PathBuf::from(mod_path)))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Get a downscaled preview image of a mod as a PNG data URL, at most `max_dim` pixels wide and high
+/// Thumbnails are cached on disk until the preview changes; null if the mod has no preview
+#[command]
+pub async fn get_mod_thumbnail(app: AppHandle, mod_path: String, max_dim: Option<u32>) -> Result<Option<String>, AppError> {
+    let cache_dir = app.path()
+        .app_cache_dir()
+        .map_err(|e| AppError::failed(format!("Failed to get app cache directory: {}", e)))?
+        .join(THUMBNAIL_CACHE_FOLDER);
+    let max_dim = max_dim.unwrap_or(DEFAULT_THUMBNAIL_DIM);
+    
+    let thumbnail = tokio::task::spawn_blocking(move || get_thumbnail(&PathBuf::from(mod_path), max_dim, &cache_dir))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    Ok(thumbnail.map(|png| format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png))))
+}
+
+/// Check an installed mod's files against the checksum manifest written when it was installed
+/// Reports files that are missing, changed or not part of the install; fails if the mod has no manifest
+#[command]
+pub async fn verify_mod_checksums(mod_path: String) -> Result<VerifyReport, AppError> {
+    tokio::task::spawn_blocking(move || verify_mod_checksums_query(&PathBuf::from(mod_path)))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)
+}
+
+/// Get the on-disk sizes of multiple mod folders, keyed by mod path
+/// Folders are measured in parallel; paths that can't be read are left out
+#[command]
+pub async fn get_mod_sizes(mod_paths: Vec<String>) -> Result<HashMap<String, ModSize>, AppError> {
+    let size_futures: Vec<_> = mod_paths.into_iter()
+        .map(|mod_path| {
+            let mod_path_buf = PathBuf::from(&mod_path);
+            (mod_path, tokio::task::spawn_blocking(move || mod_size(&mod_path_buf)))
+        })
+        .collect();
+
+    let mut sizes = HashMap::new();
+    for (mod_path, future) in size_futures {
+        match future.await {
+            Ok(Ok(size)) => {
+                sizes.insert(mod_path, size);
+            }
+            Ok(Err(e)) => eprintln!("[ModSize] Skipping {}: {}", mod_path, e),
+            Err(_) => {
+                // Task panicked, skip
+            }
+        }
+    }
+    Ok(sizes)
+}
@@ -0,0 +1,251 @@
+// Scheduled bulk download commands
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Emitter};
+use crate::core::mod_list::parse_mod_list;
+use crate::core::mod_lifecycle::{emit_mod_lifecycle, ModPhase};
+use crate::core::mod_manager::{InstallOptions, LinkMode, ModUpdater};
+use crate::core::mod_scanner::query_mod_batch;
+use crate::core::scheduler::{JobStatus, ScheduledJob};
+use crate::core::access_check::ensure_directory_access;
+use crate::services::{get_downloader, get_job_scheduler, load_install_options, record_job_history, validate_mods_path, write_last_updated_file};
+use crate::error::AppError;
+
+/// Schedule a manifest-driven bulk download to start at `start_time` (unix seconds)
+/// When `rate_limit_kbps` is set, downloads run on a single throttled SteamCMD instance
+#[command]
+pub async fn schedule_bulk_download(
+    app: AppHandle,
+    manifest_path: String,
+    mods_path: String,
+    start_time: i64,
+    rate_limit_kbps: Option<u32>,
+    max_steamcmd_instances: Option<usize>,
+) -> Result<ScheduledJob, AppError> {
+    let manifest = PathBuf::from(&manifest_path);
+    if !manifest.is_file() {
+        return Err(AppError::path_not_found(&manifest));
+    }
+
+    let mods_path_buf = validate_mods_path(&mods_path)?;
+    ensure_directory_access(&app, &mods_path_buf, &mods_path).map_err(AppError::failed)?;
+
+    let scheduler = get_job_scheduler();
+    let mut scheduler_guard = scheduler.lock().await;
+    let job_id = scheduler_guard.add_job(manifest_path.clone(), mods_path.clone(), start_time, rate_limit_kbps);
+
+    let app_clone = app.clone();
+    let handle = tokio::spawn(async move {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        if start_time > now {
+            tokio::time::sleep(tokio::time::Duration::from_secs((start_time - now) as u64)).await;
+        }
+
+        update_job(&app_clone, job_id, |job| job.status = JobStatus::Running).await;
+        eprintln!("[Scheduler] Starting scheduled bulk download {} from {}", job_id, manifest_path);
+
+        let result = run_bulk_download(&app_clone, job_id, manifest, mods_path_buf, rate_limit_kbps, max_steamcmd_instances).await;
+
+        update_job(&app_clone, job_id, |job| match result {
+            Ok(()) => job.status = JobStatus::Completed,
+            Err(e) => {
+                eprintln!("[Scheduler] Scheduled bulk download {} failed: {}", job_id, e);
+                job.status = JobStatus::Failed;
+                job.error = Some(e);
+            }
+        }).await;
+
+        let finished_job = {
+            let scheduler = get_job_scheduler();
+            let guard = scheduler.lock().await;
+            guard.get(job_id).cloned()
+        };
+        if let Some(job) = finished_job {
+            record_job_history(&app_clone, &job);
+        }
+    });
+
+    scheduler_guard.set_handle(job_id, handle);
+    scheduler_guard.get(job_id)
+        .cloned()
+        .ok_or_else(|| AppError::failed(format!("Scheduled job not found: {}", job_id)))
+}
+
+/// List scheduled bulk download jobs
+#[command]
+pub async fn list_scheduled_jobs() -> Result<Vec<ScheduledJob>, AppError> {
+    let scheduler = get_job_scheduler();
+    let guard = scheduler.lock().await;
+    Ok(guard.list())
+}
+
+/// Cancel a scheduled bulk download job
+/// Cancelling a running job also stops its SteamCMD processes
+#[command]
+pub async fn cancel_scheduled_job(app: AppHandle, job_id: u64) -> Result<(), AppError> {
+    let (previous_status, job) = {
+        let scheduler = get_job_scheduler();
+        let mut guard = scheduler.lock().await;
+        let previous_status = guard.cancel(job_id).map_err(AppError::failed)?;
+        (previous_status, guard.get(job_id).cloned())
+    };
+
+    if let Some(job) = job {
+        // Downloads started by the user meanwhile keep running
+        if previous_status == JobStatus::Running && !job.mod_ids.is_empty() {
+            get_downloader().lock().await.cancel_mods(&job.mod_ids);
+        }
+        let _ = app.emit("scheduled-job-updated", &job);
+        record_job_history(&app, &job);
+    }
+
+    Ok(())
+}
+
+/// Update a job and notify the frontend
+async fn update_job<F: FnOnce(&mut ScheduledJob)>(app: &AppHandle, job_id: u64, f: F) {
+    let job = {
+        let scheduler = get_job_scheduler();
+        let mut guard = scheduler.lock().await;
+        guard.update(job_id, f);
+        guard.get(job_id).cloned()
+    };
+
+    if let Some(job) = job {
+        let _ = app.emit("scheduled-job-updated", &job);
+    }
+}
+
+/// Download and install every mod listed in the manifest
+async fn run_bulk_download(
+    app: &AppHandle,
+    job_id: u64,
+    manifest_path: PathBuf,
+    mods_path: PathBuf,
+    rate_limit_kbps: Option<u32>,
+    max_steamcmd_instances: Option<usize>,
+) -> Result<(), String> {
+    let content = tokio::task::spawn_blocking(move || std::fs::read_to_string(&manifest_path))
+        .await
+        .map_err(|e| format!("Task panicked: {:?}", e))?
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+
+    let mod_ids = parse_mod_list(&content);
+    if mod_ids.is_empty() {
+        return Err("Manifest does not contain any mod IDs".to_string());
+    }
+
+    let total = mod_ids.len();
+    update_job(app, job_id, |job| {
+        job.total = total;
+        job.mod_ids = mod_ids.clone();
+    }).await;
+
+    // Fetch titles, update times and sizes in batches of 50
+    let mut details_map = HashMap::new();
+    for batch in mod_ids.chunks(50) {
+        match query_mod_batch(batch, 0).await {
+            Ok(details) => {
+                for detail in details {
+                    details_map.insert(detail.publishedfileid.clone(), detail);
+                }
+            }
+            Err(e) => {
+                eprintln!("[Scheduler] Failed to fetch details for manifest batch: {}", e);
+            }
+        }
+    }
+    let mod_sizes: HashMap<String, u64> = details_map.iter()
+        .map(|(id, d)| (id.clone(), d.file_size))
+        .collect();
+
+    // The throttle is per instance, so a single instance keeps the total rate predictable
+    let max_instances = if rate_limit_kbps.is_some() {
+        Some(1)
+    } else {
+        max_steamcmd_instances
+    };
+
+    let downloader = get_downloader();
+    let (mut mod_receiver, download_path) = {
+        let mut dl = downloader.lock().await;
+        let previous_throttle = dl.download_throttle();
+        // Without a rate limit of its own the job uses the throttle configured for all downloads
+        dl.set_download_throttle(rate_limit_kbps.or(previous_throttle));
+        let receiver_result = dl.download_mods_with_sizes(&mod_ids, Some(&mod_sizes), Some(app), max_instances).await;
+        // The throttle is captured when the download starts, restore the previous value right away
+        dl.set_download_throttle(previous_throttle);
+        (receiver_result?, dl.download_path().clone())
+    };
+
+    let updater = ModUpdater;
+    let install_options = InstallOptions {
+        force_overwrite_corrupted: Some(false), // Unattended - never overwrite a corrupted folder, install next to it
+        ..load_install_options(app, download_path, mods_path, LinkMode::Copy)
+    };
+    let mut completed = 0;
+    while let Some(result) = mod_receiver.recv().await {
+        let downloaded_mod = match result {
+            Ok(downloaded_mod) => downloaded_mod,
+            Err(e) => {
+                eprintln!("[Scheduler] Job {}: {}", job_id, e);
+                continue;
+            }
+        };
+
+        let mod_id = downloaded_mod.mod_id.clone();
+        emit_mod_lifecycle(app, &mod_id, ModPhase::Installing, serde_json::Value::Null);
+
+        let details = details_map.get(&mod_id);
+        let install_result = updater.update_mod(
+            &mod_id,
+            &downloaded_mod.mod_path,
+            None,
+            details.map(|d| d.title.as_str()),
+            details.map(|d| d.time_updated),
+            &install_options,
+        ).await;
+
+        match install_result {
+            Ok(installed_path) => {
+                let time_updated = details.map(|d| d.time_updated).unwrap_or_else(|| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs() as i64
+                });
+                write_last_updated_file(installed_path, time_updated).await;
+                completed += 1;
+
+                emit_mod_lifecycle(app, &mod_id, ModPhase::Installed, serde_json::Value::Null);
+                let _ = app.emit("mod-updated", serde_json::json!({
+                    "modId": mod_id,
+                    "success": true,
+                }));
+            }
+            Err(e) => {
+                let e = e.to_string();
+                eprintln!("[Scheduler] Job {}: failed to install mod {}: {}", job_id, mod_id, e);
+                emit_mod_lifecycle(app, &mod_id, ModPhase::Failed, serde_json::json!({
+                    "error": e,
+                }));
+                let _ = app.emit("mod-updated", serde_json::json!({
+                    "modId": mod_id,
+                    "success": false,
+                    "error": e,
+                }));
+            }
+        }
+
+        update_job(app, job_id, |job| job.completed = completed).await;
+    }
+
+    update_job(app, job_id, |job| job.failed = total - completed).await;
+    eprintln!("[Scheduler] Job {} finished: {} of {} mod(s) installed", job_id, completed, total);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+// Settings store commands
+
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use crate::core::settings_store::{validate_store_content, default_store, StoreValidation, SETTINGS_STORE_NAME};
+use crate::core::mods_path_check::{check_mods_path, ModsPathStatus};
+use crate::error::AppError;
+
+/// Get the path of the settings store file
+fn settings_store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(SETTINGS_STORE_NAME))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Check that the settings store parses and contains expected keys with valid types
+#[command]
+pub async fn validate_store(app: AppHandle) -> Result<StoreValidation, AppError> {
+    let store_path = settings_store_path(&app).map_err(AppError::failed)?;
+    
+    let content = tokio::task::spawn_blocking(move || {
+        if store_path.exists() {
+            std::fs::read_to_string(&store_path)
+                .map(Some)
+                .map_err(|e| format!("Failed to read settings store: {}", e))
+        } else {
+            Ok(None)
+        }
+    }).await
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    Ok(validate_store_content(content.as_deref()))
+}
+
+/// Back up the current settings store and replace it with default settings
+/// Returns the path of the backup, if there was a store file to back up
+#[command]
+pub async fn reset_store_to_defaults(app: AppHandle) -> Result<Option<String>, AppError> {
+    let store_path = settings_store_path(&app).map_err(AppError::failed)?;
+    
+    let backup_path = tokio::task::spawn_blocking(move || {
+        if let Some(parent) = store_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+        }
+        
+        let backup_path = if store_path.exists() {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            let backup_path = store_path.with_file_name(format!("{}.{}.bak", SETTINGS_STORE_NAME, timestamp));
+            std::fs::copy(&store_path, &backup_path)
+                .map_err(|e| format!("Failed to back up settings store: {}", e))?;
+            Some(backup_path)
+        } else {
+            None
+        };
+        
+        let content = serde_json::to_string_pretty(&default_store())
+            .map_err(|e| format!("Failed to serialize default settings: {}", e))?;
+        std::fs::write(&store_path, content)
+            .map_err(|e| format!("Failed to write settings store: {}", e))?;
+        
+        Ok::<Option<PathBuf>, String>(backup_path)
+    }).await
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    // Make the store plugin pick up the fresh file instead of its in-memory copy
+    if let Ok(store) = app.store(SETTINGS_STORE_NAME) {
+        if let Err(e) = store.reload() {
+            eprintln!("[Settings] Failed to reload settings store: {}", e);
+        }
+    }
+    
+    if let Some(ref path) = backup_path {
+        eprintln!("[Settings] Settings store reset to defaults, backup saved to {:?}", path);
+    }
+    
+    Ok(backup_path.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Check whether a folder picked as the mods folder looks like one, suggesting the right folder when it doesn't
+/// Meant for onboarding, a wrong folder otherwise just shows no mods
+#[command]
+pub async fn validate_mods_path(mods_path: String) -> Result<ModsPathStatus, AppError> {
+    let path = PathBuf::from(mods_path);
+    tokio::task::spawn_blocking(move || check_mods_path(&path))
+        .await
+        .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))
+}
@@ -0,0 +1,323 @@
+// SteamCMD maintenance commands
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::core::steamcmd_client::{Downloader, DownloadRetryPolicy, SteamCmdCleanupReport, SteamCmdHealth, SteamCredentials, DEFAULT_APP_ID, DEFAULT_THROTTLED_INSTANCES};
+use crate::core::access_check::check_directory_access;
+use crate::core::mod_scanner::set_workshop_app_id;
+use crate::core::steamcmd_log::SteamCmdLogLine;
+use crate::services::{get_downloader, save_download_dir, save_download_throttle, save_max_memory_usage, save_steamcmd_path, save_steamcmd_verbose_log};
+use tauri::{command, AppHandle, Manager};
+use crate::error::AppError;
+
+/// Terminate stuck SteamCMD processes spawned by the app and remove stale lock files
+/// Use when downloads won't start anymore after a crash or a killed download
+#[command]
+pub async fn cleanup_stuck_steamcmd() -> Result<SteamCmdCleanupReport, AppError> {
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    Ok(dl.cleanup_stuck_processes().await)
+}
+
+/// Use a custom SteamCMD executable (or installation directory) instead of searching for it
+/// Pass None or an empty path to go back to the automatic search
+/// Returns the resolved executable path
+#[command]
+pub async fn set_steamcmd_path(app: AppHandle, path: Option<String>) -> Result<Option<String>, AppError> {
+    let executable = apply_steamcmd_path(&app, path.as_deref()).await.map_err(AppError::failed)?;
+    Ok(executable.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Validate, persist and apply a custom SteamCMD executable, an empty path restores the automatic search
+async fn apply_steamcmd_path(app: &AppHandle, path: Option<&str>) -> Result<Option<PathBuf>, String> {
+    let path = path.map(|p| p.trim()).filter(|p| !p.is_empty());
+    
+    let executable = match path {
+        Some(path) => {
+            let executable = Downloader::resolve_steamcmd_executable(&PathBuf::from(path))?;
+            Downloader::check_steamcmd_runs(&executable).await?;
+            Some(executable)
+        }
+        None => None,
+    };
+    
+    save_steamcmd_path(app, executable.as_deref())?;
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_custom_executable(executable.clone());
+    
+    Ok(executable)
+}
+
+/// Run SteamCMD once so it finishes its self-update, and report whether it works
+/// A broken installation (e.g. missing 32-bit libraries or linux32 files) fails with what to do about it
+#[command]
+pub async fn check_steamcmd_health() -> Result<SteamCmdHealth, AppError> {
+    let executable = {
+        let downloader = get_downloader();
+        let dl = downloader.lock().await;
+        if dl.has_active_downloads() {
+            return Err(AppError::invalid_input("SteamCMD cannot be checked while mods are downloading"));
+        }
+        dl.find_steamcmd_executable().await
+            .map_err(|message| AppError::SteamCmdMissing { message })?
+    };
+    
+    Downloader::check_steamcmd_health(&executable).await
+        .map_err(|message| AppError::SteamCmdMissing { message })
+}
+
+/// Download a fresh SteamCMD into the app data folder and use it from now on
+/// Returns the path of the new executable
+#[command]
+pub async fn reinstall_steamcmd(app: AppHandle) -> Result<String, AppError> {
+    {
+        let downloader = get_downloader();
+        let dl = downloader.lock().await;
+        if dl.has_active_downloads() {
+            return Err(AppError::invalid_input("SteamCMD cannot be reinstalled while mods are downloading"));
+        }
+    }
+    let install_dir = app.path()
+        .app_data_dir()
+        .map_err(|e| AppError::failed(format!("Failed to get app data directory: {}", e)))?
+        .join("steamcmd");
+    
+    let executable = tokio::task::spawn_blocking(move || {
+        // Start from an empty folder, leftovers of a broken self-update are what is being fixed
+        // Downloaded Workshop items stay, symlinked and hardlinked mods point into them
+        if install_dir.exists() {
+            remove_steamcmd_installation(&install_dir)
+                .map_err(|e| format!("Failed to remove old SteamCMD installation: {}", e))?;
+        }
+        steamcmd_downloader::install_steamcmd(&install_dir)
+            .map_err(|e| format!("Failed to install SteamCMD: {}", e))
+    }).await
+    .map_err(|e| AppError::failed(format!("Task panicked: {:?}", e)))?.map_err(AppError::failed)?;
+    
+    eprintln!("[SteamCMD] Reinstalled SteamCMD at {:?}", executable);
+    
+    let executable = apply_steamcmd_path(&app, Some(&executable.to_string_lossy())).await.map_err(AppError::failed)?
+        .unwrap_or(executable);
+    Ok(executable.to_string_lossy().to_string())
+}
+
+/// Remove everything in a SteamCMD folder except `steamapps/workshop`
+fn remove_steamcmd_installation(install_dir: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(install_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name() == "steamapps" && entry.file_type()?.is_dir() {
+            for entry in std::fs::read_dir(&path)? {
+                let entry = entry?;
+                if entry.file_name() == "workshop" {
+                    continue;
+                }
+                remove_entry(&entry)?;
+            }
+        } else {
+            remove_entry(&entry)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_entry(entry: &std::fs::DirEntry) -> std::io::Result<()> {
+    if entry.file_type()?.is_dir() {
+        std::fs::remove_dir_all(entry.path())
+    } else {
+        std::fs::remove_file(entry.path())
+    }
+}
+
+/// Get the custom SteamCMD executable, if one is configured
+#[command]
+pub async fn get_steamcmd_path() -> Result<Option<String>, AppError> {
+    let downloader = get_downloader();
+    let dl = downloader.lock().await;
+    Ok(dl.custom_executable().map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Download Workshop items into another directory, e.g. a fast SSD while mods live on a slower drive
+/// SteamCMD puts them into `steamapps/workshop/content/<app id>` below it; None or an empty path goes back
+/// to the SteamCMD folder. Returns the directory mods are now downloaded to
+#[command]
+pub async fn set_download_dir(app: AppHandle, path: Option<String>) -> Result<String, AppError> {
+    let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).map(PathBuf::from);
+    
+    if let Some(path) = &path {
+        if !path.is_absolute() {
+            return Err(AppError::invalid_input(format!("Download directory must be an absolute path: {}", path.display())));
+        }
+        std::fs::create_dir_all(path)
+            .map_err(|e| AppError::failed(format!("Failed to create download directory: {}", e)))?;
+        check_directory_access(path)
+            .map_err(|e| AppError::invalid_input(e.reason))?;
+    }
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_install_dir(path.clone()).map_err(AppError::failed)?;
+    save_download_dir(&app, path.as_deref()).map_err(AppError::failed)?;
+    Ok(dl.download_path().to_string_lossy().to_string())
+}
+
+/// Get the configured download directory, None if mods are downloaded into the SteamCMD folder
+#[command]
+pub async fn get_download_dir() -> Result<Option<String>, AppError> {
+    let downloader = get_downloader();
+    let dl = downloader.lock().await;
+    Ok(dl.install_dir().map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Configure how long to wait for mod downloads
+/// Without a fixed timeout it scales with each mod's size; the poll interval trades detection speed for CPU usage
+#[command]
+pub async fn set_download_timeouts(timeout_secs: Option<u64>, poll_interval_ms: Option<u64>) -> Result<(), AppError> {
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_download_timeout(timeout_secs.map(Duration::from_secs));
+    if let Some(ms) = poll_interval_ms {
+        dl.set_poll_interval(Duration::from_millis(ms));
+    }
+    Ok(())
+}
+
+/// Configure how often failed downloads are retried, each retry waits about twice as long as the previous one
+/// `max_retries_per_mod` stops retrying a single mod early while the rest of the batch keeps its retries;
+/// None keeps the current setting and a per-mod budget of 0 removes it
+#[command]
+pub async fn set_download_retries(max_retries: Option<u32>, max_retries_per_mod: Option<u32>) -> Result<(), AppError> {
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    let current = dl.retry_policy();
+    dl.set_retry_policy(DownloadRetryPolicy {
+        max_retries: max_retries.unwrap_or(current.max_retries),
+        max_retries_per_mod: match max_retries_per_mod {
+            Some(0) => None,
+            Some(n) => Some(n),
+            None => current.max_retries_per_mod,
+        },
+    });
+    Ok(())
+}
+
+/// Limit the download bandwidth so downloads don't saturate the connection
+/// SteamCMD throttles each instance separately, so while `throttle_kbps` is set at most
+/// `throttled_max_instances` instances run in parallel (1 by default, capping the total rate at the throttle)
+/// Pass None or 0 as `throttle_kbps` to remove the limit; the settings are kept across restarts
+/// `steamcmd_override` points at a SteamCMD executable or installation folder used instead of the bundled one;
+/// None keeps the current setting and an empty string restores the automatic search
+#[command]
+pub async fn configure_downloader(
+    app: AppHandle,
+    throttle_kbps: Option<u32>,
+    throttled_max_instances: Option<usize>,
+    steamcmd_override: Option<String>,
+) -> Result<serde_json::Value, AppError> {
+    let throttle_kbps = throttle_kbps.filter(|kbps| *kbps > 0);
+    if throttled_max_instances == Some(0) {
+        return Err(AppError::invalid_input("throttled_max_instances must be at least 1"));
+    }
+    let throttled_max_instances = throttled_max_instances.unwrap_or(DEFAULT_THROTTLED_INSTANCES);
+    
+    // Validated first, so an invalid path leaves every setting unchanged
+    if let Some(path) = &steamcmd_override {
+        apply_steamcmd_path(&app, Some(path)).await.map_err(AppError::failed)?;
+    }
+    
+    save_download_throttle(&app, throttle_kbps, throttled_max_instances).map_err(AppError::failed)?;
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_download_throttle(throttle_kbps);
+    dl.set_throttled_max_instances(throttled_max_instances);
+    
+    Ok(serde_json::json!({
+        "throttleKbps": dl.download_throttle(),
+        "throttledMaxInstances": dl.throttled_max_instances(),
+        "steamcmdOverride": dl.custom_executable().map(|p| p.to_string_lossy().to_string()),
+    }))
+}
+
+/// Switch the Steam game Workshop items are downloaded for
+/// Pass None to go back to RimWorld; returns the app id now in use
+#[command]
+pub async fn set_app_id(app_id: Option<u32>) -> Result<u32, AppError> {
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_app_id(app_id.unwrap_or(DEFAULT_APP_ID)).map_err(AppError::failed)?;
+    // Mods of that game are the ones checked for updates
+    set_workshop_app_id(dl.app_id());
+    Ok(dl.app_id())
+}
+
+/// Cap the memory used by parallel downloads, in bytes
+/// Fewer SteamCMD instances are started when the cap (or available system memory) doesn't fit them all
+/// Pass None or 0 to remove the cap
+#[command]
+pub async fn set_max_memory_usage(app: AppHandle, bytes: Option<u64>) -> Result<(), AppError> {
+    let bytes = bytes.filter(|b| *b > 0);
+    save_max_memory_usage(&app, bytes).map_err(AppError::failed)?;
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_max_memory_usage(bytes);
+    
+    Ok(())
+}
+
+/// Log SteamCMD in with a Steam account instead of anonymously
+/// Needed for Workshop items that can only be downloaded by accounts owning RimWorld
+/// Credentials are kept in memory only; pass None to go back to anonymous login
+#[command]
+pub async fn set_steam_credentials(credentials: Option<SteamCredentials>) -> Result<(), AppError> {
+    if let Some(credentials) = &credentials {
+        if credentials.username.trim().is_empty() || credentials.password.is_empty() {
+            return Err(AppError::invalid_input("Username and password are required"));
+        }
+        let invalid = |value: &str| value.contains(['"', '\n', '\r']) || value.trim() != value;
+        if credentials.username.contains(char::is_whitespace) || invalid(&credentials.password) {
+            return Err(AppError::invalid_input("Username or password contains characters SteamCMD can't handle"));
+        }
+    }
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_credentials(credentials);
+    Ok(())
+}
+
+/// Raw SteamCMD output of the most recently started download, at most the last 2000 lines
+/// The Steam password is redacted
+#[command]
+pub async fn get_last_download_log() -> Result<Vec<SteamCmdLogLine>, AppError> {
+    let downloader = get_downloader();
+    let dl = downloader.lock().await;
+    Ok(dl.last_download_log())
+}
+
+/// Emit SteamCMD output live as `steamcmd-log` events while downloading, kept across restarts
+/// Applies to downloads started afterwards
+#[command]
+pub async fn set_steamcmd_verbose_log(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    save_steamcmd_verbose_log(&app, enabled).map_err(AppError::failed)?;
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    dl.set_verbose_log(enabled);
+    Ok(())
+}
+
+/// Send the Steam Guard code requested by a `steam-guard-required` event to SteamCMD
+#[command]
+pub async fn submit_steam_guard_code(code: String) -> Result<(), AppError> {
+    if code.trim().is_empty() {
+        return Err(AppError::invalid_input("Steam Guard code is required"));
+    }
+    
+    let downloader = get_downloader();
+    let dl = downloader.lock().await;
+    dl.submit_steam_guard_code(&code).map_err(AppError::failed)
+}
@@ -1,16 +1,20 @@
 // Mod update commands
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
 use crate::core::mod_scanner::BaseMod;
-use crate::core::mod_manager::ModUpdater;
+use crate::core::mod_lifecycle::{emit_mod_lifecycle, ModPhase};
+use crate::core::mod_manager::{detect_local_changes, install_concurrency, set_install_concurrency as set_install_concurrency_query, FolderReservations, InstallError, InstallLimiter, InstallOptions, LinkMode, ModUpdater};
 use crate::core::access_check::ensure_directory_access;
-use crate::services::{get_downloader, get_mods_path_from_mod_path, find_all_mod_folders_with_id, write_last_updated_file, reset_update_cancel_flag, is_update_cancelled, cancel_update};
+use crate::core::content_fingerprint::is_identical_content;
+use crate::services::{canonicalize_path_or_fallback, get_downloader, get_mods_path_from_mod_path, validate_mods_path, find_all_mod_folders_with_id, load_install_options, save_install_concurrency, write_last_updated_files, reset_update_cancel_flag, is_update_cancelled, cancel_update, mark_update_in_progress};
+use crate::error::AppError;
 
 /// Cancel ongoing mod updates
 #[tauri::command]
-pub async fn cancel_update_mods(app: AppHandle) -> Result<(), String> {
+pub async fn cancel_update_mods(app: AppHandle) -> Result<(), AppError> {
     cancel_update();
     
     // Emit cancellation event for all active mods
@@ -21,32 +25,66 @@ pub async fn cancel_update_mods(app: AppHandle) -> Result<(), String> {
 
 /// Check if update is cancelled
 #[tauri::command]
-pub async fn check_update_cancelled() -> Result<bool, String> {
+pub async fn check_update_cancelled() -> Result<bool, AppError> {
     Ok(is_update_cancelled())
 }
 
 /// Reset the cancellation flag
 #[tauri::command]
-pub async fn reset_update_cancel_flag_command() -> Result<(), String> {
+pub async fn reset_update_cancel_flag_command() -> Result<(), AppError> {
     reset_update_cancel_flag();
     Ok(())
 }
 
+/// Set how many mods `update_mods` installs at once, copying more at a time mostly thrashes the disk
+/// Pass None to go back to the default
+#[tauri::command]
+pub async fn set_install_concurrency(app: AppHandle, concurrency: Option<usize>) -> Result<(), AppError> {
+    if concurrency == Some(0) {
+        return Err(AppError::invalid_input("Install concurrency must be at least 1"));
+    }
+    save_install_concurrency(&app, concurrency).map_err(AppError::failed)?;
+    set_install_concurrency_query(concurrency);
+    Ok(())
+}
+
 /// Update mods
+/// `mods_path` is the mods folder all mods must be in; without it, the folder of the first mod is used
+/// `app_id` is the game Workshop items are downloaded for in this update only (the downloader's game by default)
+/// With `check_local_modifications`, mods with files edited since their install are not updated;
+/// they are returned with `localModifications` so the UI can ask before overwriting them
+/// Installed copies newer than the Workshop version are kept and reported with `downgrade-warning`,
+/// unless `allow_downgrade` is set
+/// With `stage_only` nothing is installed, the returned mods have `stagedPath` set to their download
+/// in SteamCMD's workshop content folder
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn update_mods(
     app: AppHandle,
     mods: Vec<BaseMod>,
+    mods_path: Option<String>,
     backup_mods: bool,
     backup_directory: Option<String>,
     max_steamcmd_instances: Option<usize>,
-) -> Result<Vec<BaseMod>, String> {
+    link_mode: Option<LinkMode>,
+    app_id: Option<u32>,
+    check_local_modifications: Option<bool>,
+    allow_downgrade: Option<bool>,
+    stage_only: Option<bool>,
+) -> Result<Vec<BaseMod>, AppError> {
     if mods.is_empty() {
-        return Err("mods array is required".to_string());
+        return Err(AppError::invalid_input("mods array is required"));
     }
+    let link_mode = link_mode.unwrap_or_default();
+    let allow_downgrade = allow_downgrade.unwrap_or(false);
+    let stage_only = stage_only.unwrap_or(false);
+    // Downloads are bounded by the SteamCMD instances, installs by this
+    let install_limiter = InstallLimiter::new(install_concurrency());
     
     // Reset cancellation flag at the start of update
     reset_update_cancel_flag();
+    // Closing the app waits for the update while this is held
+    let _in_progress = mark_update_in_progress();
     
     // Filter out non-Steam mods - they can't be updated from Workshop
     let steam_mods: Vec<BaseMod> = mods.into_iter()
@@ -54,16 +92,42 @@ pub async fn update_mods(
         .collect();
     
     if steam_mods.is_empty() {
-        return Err("No Steam Workshop mods to update. Non-Steam mods cannot be updated.".to_string());
+        return Err(AppError::invalid_input("No Steam Workshop mods to update. Non-Steam mods cannot be updated."));
     }
     
-    // Extract modsPath from first mod
-    let first_mod = &steam_mods[0];
-    let mods_path = get_mods_path_from_mod_path(&PathBuf::from(&first_mod.mod_path))?;
+    let mods_path = match mods_path {
+        Some(mods_path) => {
+            let mods_path = validate_mods_path(&mods_path)?;
+            ensure_mods_in_folder(&steam_mods, &mods_path).map_err(AppError::failed)?;
+            mods_path
+        }
+        // Extract modsPath from first mod
+        None => get_mods_path_from_mod_path(&PathBuf::from(&steam_mods[0].mod_path)).map_err(AppError::failed)?,
+    };
     let mods_path_str = mods_path.to_string_lossy().to_string();
     
     // Check directory access before proceeding
-    ensure_directory_access(&app, &mods_path, &mods_path_str)?;
+    ensure_directory_access(&app, &mods_path, &mods_path_str).map_err(AppError::failed)?;
+    
+    // Hold back mods with local changes instead of overwriting them, staging leaves them untouched anyway
+    let (steam_mods, held_back_mods) = if check_local_modifications.unwrap_or(false) && !stage_only {
+        let (modified, unmodified) = find_locally_modified_mods(steam_mods).await;
+        for held_back in &modified {
+            eprintln!("[UPDATE_MODS] Mod {} has {} local modification(s), not updating", held_back.mod_id, held_back.local_modifications.len());
+            let _ = app.emit("mod-updated", serde_json::json!({
+                "modId": held_back.mod_id,
+                "success": false,
+                "skipped": "local-modifications",
+                "localModifications": held_back.local_modifications,
+            }));
+        }
+        if unmodified.is_empty() {
+            return Ok(modified);
+        }
+        (unmodified, modified)
+    } else {
+        (steam_mods, Vec::new())
+    };
     
     // Prepare mods for download
     let mod_ids: Vec<String> = steam_mods.iter().map(|m| m.mod_id.clone()).collect();
@@ -81,30 +145,47 @@ pub async fn update_mods(
     let downloader = get_downloader();
     let (mut mod_receiver, download_path) = {
         let mut dl = downloader.lock().await;
-        let download_path = dl.download_path().clone();
-        let mod_receiver_result = if mod_sizes.is_empty() {
-            // No size information available, use simple download
-            dl.download_mods(&mod_ids, Some(&app), max_steamcmd_instances).await
-        } else {
-            // Use size-based load balancing
-            dl.download_mods_with_sizes(&mod_ids, Some(&mod_sizes), Some(&app), max_steamcmd_instances).await
-        };
+        // Report a missing SteamCMD as such instead of as a failed download
+        dl.find_steamcmd_executable().await
+            .map_err(|message| AppError::SteamCmdMissing { message })?;
+        // The app id only applies to this update, later downloads keep the downloader's game
+        let app_id = app_id.unwrap_or_else(|| dl.app_id());
+        let download_path = dl.download_path_for_app(app_id);
+        // Without size information the mods are not load balanced
+        let mod_sizes = (!mod_sizes.is_empty()).then_some(&mod_sizes);
+        let mod_receiver_result = dl.download_mods_for_app(&mod_ids, mod_sizes, app_id, Some(&app), max_steamcmd_instances).await;
         
         match mod_receiver_result {
             Ok(mod_receiver) => (mod_receiver, download_path),
             Err(e) => {
                 // If download completely failed, return error
-                return Err(format!("Failed to download mods: {}", e));
+                if is_update_cancelled() {
+                    return Err(AppError::Cancelled);
+                }
+                return Err(AppError::DownloadFailed { mod_ids, message: e });
             }
         }
     };
     
+    let install_options = Arc::new(InstallOptions {
+        backup_directory: backup_directory.filter(|_| backup_mods).map(PathBuf::from),
+        allow_downgrade,
+        ..load_install_options(&app, download_path, mods_path.clone(), link_mode)
+    });
+    
     // Create HashMap for O(1) lookup instead of O(n) find()
     // Clone the mods data so we can move it into spawned tasks
     let mods_map: HashMap<String, BaseMod> = steam_mods.iter()
         .map(|m| (m.mod_id.clone(), m.clone()))
         .collect();
     
+    // Destination folders are resolved one mod at a time, starting from the folders the mods already use,
+    // so two mods installed concurrently never pick the same new folder name
+    let folder_reservations = FolderReservations::default();
+    folder_reservations.reserve(
+        steam_mods.iter().filter_map(|m| m.folder.as_deref().map(|folder| (folder, m.mod_id.as_str())))
+    ).await;
+    
     // Track which mods we've seen (for detecting failures)
     let mut seen_mod_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
     let mut update_handles = Vec::new();
@@ -139,10 +220,7 @@ pub async fn update_mods(
             // Emit cancellation event for remaining mods
             for mod_id in &mod_ids {
                 if !seen_mod_ids.contains(mod_id) {
-                    let _ = app.emit("mod-state", serde_json::json!({
-                        "modId": mod_id,
-                        "state": "cancelled"
-                    }));
+                    emit_mod_lifecycle(&app, mod_id, ModPhase::Cancelled, serde_json::Value::Null);
                 }
             }
             
@@ -186,6 +264,7 @@ pub async fn update_mods(
                 }
             }
             
+            cancelled_mods.extend(held_back_mods);
             return Ok(cancelled_mods);
         }
         
@@ -200,16 +279,30 @@ pub async fn update_mods(
                 // Mark this mod as seen
                 seen_mod_ids.insert(downloaded_mod.mod_id.clone());
                 
+                // Staged mods are done once downloaded, there is nothing to install
+                if stage_only {
+                    let mod_id = downloaded_mod.mod_id.clone();
+                    let staged_path = downloaded_mod.mod_path.clone();
+                    emit_mod_lifecycle(&app, &mod_id, ModPhase::Downloaded, serde_json::json!({
+                        "staged": true,
+                        "modPath": staged_path.to_string_lossy(),
+                    }));
+                    let _ = app.emit("mod-updated", serde_json::json!({
+                        "modId": mod_id,
+                        "success": true,
+                        "staged": true,
+                    }));
+                    update_handles.push(tokio::spawn(async move { (mod_id, Ok(staged_path)) }));
+                    continue;
+                }
+                
                 // Emit installing state immediately
-                let _ = app.emit("mod-state", serde_json::json!({
-                    "modId": downloaded_mod.mod_id,
-                    "state": "installing"
-                }));
+                emit_mod_lifecycle(&app, &downloaded_mod.mod_id, ModPhase::Installing, serde_json::Value::Null);
                 
                 let mod_id = downloaded_mod.mod_id.clone();
                 let mod_path = downloaded_mod.mod_path.clone();
                 let original_mod = mods_map.get(&mod_id)
-                    .ok_or_else(|| format!("Original mod not found for {}", mod_id))?;
+                    .ok_or_else(|| AppError::failed(format!("Original mod not found for {}", mod_id)))?;
                 
                 let existing_folder_name = original_mod.folder.clone();
                 let mod_title = original_mod.details.as_ref().map(|d| d.title.clone());
@@ -222,80 +315,152 @@ pub async fn update_mods(
                             .as_secs() as i64
                     });
                 
-                let download_path_clone = download_path.clone();
                 let mods_path_clone = mods_path.clone();
-                let backup_dir_clone = backup_directory.as_ref().map(|s| PathBuf::from(s));
                 let app_clone = app.clone();
+                let folder_reservations = folder_reservations.clone();
+                let install_options = install_options.clone();
+                let install_limiter = install_limiter.clone();
                 
                 // Spawn independent task for each mod installation
                 // This ensures events are emitted immediately when each mod completes
                 let handle = tokio::spawn(async move {
+                    let _install_permit = install_limiter.acquire().await;
+                    
                     // Check if cancelled before processing
                     if is_update_cancelled() {
                         eprintln!("[UPDATE_MODS] Update cancelled, skipping mod {}", mod_id);
-                        let _ = app_clone.emit("mod-state", serde_json::json!({
-                            "modId": mod_id,
-                            "state": "cancelled"
-                        }));
+                        emit_mod_lifecycle(&app_clone, &mod_id, ModPhase::Cancelled, serde_json::Value::Null);
                         return (mod_id, Err("Update cancelled".to_string()));
                     }
                     
                     eprintln!("[UPDATE_MODS] Processing downloaded mod: {} at {:?}", mod_id, mod_path);
+                    
+                    // Authors sometimes re-publish without changes, only bumping time_updated
+                    // If the downloaded content matches the installed one, skip the install
+                    let installed_path = existing_folder_name.as_ref()
+                        .map(|name| mods_path_clone.join(name))
+                        .filter(|path| path.is_dir());
+                    if let Some(installed_path) = installed_path {
+                        let downloaded = mod_path.clone();
+                        let installed = installed_path.clone();
+                        let identical = tokio::task::spawn_blocking(move || is_identical_content(&downloaded, &installed))
+                            .await
+                            .map_err(|e| format!("Task panicked: {:?}", e))
+                            .and_then(|r| r)
+                            .unwrap_or_else(|e| {
+                                eprintln!("[UPDATE_MODS] Failed to compare content of mod {}: {}", mod_id, e);
+                                false
+                            });
+                        
+                        if identical {
+                            eprintln!("[UPDATE_MODS] Mod {} content is identical to the installed version, skipping install", mod_id);
+                            
+                            let all_mod_folders = find_all_mod_folders_with_id(&mods_path_clone, &mod_id)
+                                .await
+                                .unwrap_or_default();
+                            write_last_updated_files(all_mod_folders, remote_update_time).await;
+                            
+                            emit_mod_lifecycle(&app_clone, &mod_id, ModPhase::Installed, serde_json::json!({
+                                "skipped": "identical-content",
+                            }));
+                            let _ = app_clone.emit("mod-updated", serde_json::json!({
+                                "modId": mod_id,
+                                "success": true,
+                                "skipped": "identical-content",
+                            }));
+                            
+                            return (mod_id, Ok(installed_path));
+                        }
+                    }
                                 
+                    if install_options.backup_directory.is_some() {
+                        emit_mod_lifecycle(&app_clone, &mod_id, ModPhase::BackingUp, serde_json::Value::Null);
+                    }
+                    
                     let updater = ModUpdater;
-                    let mod_path_result = updater.update_mod(
+                    // A corrupted folder with the mod's name is reported as a conflict for the user to resolve
+                    let folder_name_result = folder_reservations.resolve(
                         &mod_id,
                         &mod_path,
-                        &download_path_clone,
+                        &install_options.download_path,
                         &mods_path_clone,
                         existing_folder_name.as_deref(),
-                        backup_mods,
-                        backup_dir_clone.as_deref(),
                         mod_title.as_deref(),
-                        None, // force_overwrite_corrupted - None means ask user if corrupted mod found
                     ).await;
+                    let mod_path_result = match folder_name_result {
+                        Ok(folder_name) => updater.update_mod(
+                            &mod_id,
+                            &mod_path,
+                            Some(&folder_name),
+                            mod_title.as_deref(),
+                            // Refuse to replace an installed copy that is newer than this release, unless allow_downgrade is set
+                            Some(remote_update_time),
+                            &install_options,
+                        ).await,
+                        Err(e) => Err(e),
+                    };
             
             match mod_path_result {
                 Ok(updated_path) => {
                     eprintln!("[UPDATE_MODS] Successfully updated mod {} to {:?}", mod_id, updated_path);
-                    
+
+                    // Remove folders with the same mod ID left behind when the folder name changed
+                    let removed_folders: Vec<String> = updater
+                        .remove_orphaned_mod_folders(&mods_path_clone, &mod_id, &updated_path, install_options.backup_directory.as_deref())
+                        .await
+                        .unwrap_or_else(|e| {
+                            eprintln!("[UPDATE_MODS] Failed to reconcile folders for mod {}: {}", mod_id, e);
+                            Vec::new()
+                        })
+                        .iter()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .collect();
+
                     // Find all folders with the same mod ID and update .lastupdated
                     let all_mod_folders = find_all_mod_folders_with_id(&mods_path_clone, &mod_id)
                         .await
                         .unwrap_or_default();
                     
-                    // Update .lastupdated files in parallel
-                    let mut update_file_futures = Vec::new();
-                    for folder_path in all_mod_folders {
-                        update_file_futures.push(write_last_updated_file(folder_path, remote_update_time));
-                    }
-                    
-                    // Wait for all .lastupdated files to be written
-                    futures::future::join_all(update_file_futures).await;
+                    // Update .lastupdated files, a few at a time
+                    write_last_updated_files(all_mod_folders, remote_update_time).await;
                     
                     // Emit "completed" state event IMMEDIATELY
                     // This marks the mod as completed in the UI
-                    let _ = app_clone.emit("mod-state", serde_json::json!({
-                        "modId": mod_id,
-                        "state": "completed",
-                    }));
+                    emit_mod_lifecycle(&app_clone, &mod_id, ModPhase::Installed, serde_json::Value::Null);
                     
                     // Emit mod-updated event for backward compatibility and final status
                     let _ = app_clone.emit("mod-updated", serde_json::json!({
                         "modId": mod_id,
                         "success": true,
+                        "removedFolders": removed_folders,
                     }));
                     
                     (mod_id, Ok(updated_path))
                 }
-                Err(e) => {
-                    eprintln!("[UPDATE_MODS] Error updating mod {}: {}", mod_id, e);
+                Err(install_error) => {
+                    eprintln!("[UPDATE_MODS] Error updating mod {}: {}", mod_id, install_error);
+                    let e = install_error.to_string();
+                    
+                    // The UI can ask the user and retry this mod with allow_downgrade
+                    if matches!(install_error, InstallError::Downgrade { .. }) {
+                        let _ = app_clone.emit("downgrade-warning", &install_error);
+                    }
+                    
+                    if install_error == InstallError::Cancelled {
+                        emit_mod_lifecycle(&app_clone, &mod_id, ModPhase::Cancelled, serde_json::Value::Null);
+                    } else {
+                        emit_mod_lifecycle(&app_clone, &mod_id, ModPhase::Failed, serde_json::json!({
+                            "error": e,
+                        }));
+                    }
                     
                     // Emit event for failed mod update IMMEDIATELY
+                    // installError lets the UI offer resolve_corrupted_conflict for corrupted folders
                     let _ = app_clone.emit("mod-updated", serde_json::json!({
                         "modId": mod_id,
                         "success": false,
-                        "error": e.to_string(),
+                        "error": e,
+                        "installError": install_error,
                     }));
                     
                     (mod_id, Err(e))
@@ -305,16 +470,9 @@ pub async fn update_mods(
         
         update_handles.push(handle);
             }
-            Err(error_msg) => {
-                // Check if error is due to cancellation
-                if error_msg.contains("cancelled") || error_msg.contains("Update cancelled by user") {
-                    eprintln!("[UPDATE_MODS] Download cancelled (not a failure): {}", error_msg);
-                    // Don't treat cancellation as failure - it will be handled by cancellation check above
-                    continue;
-                }
-                
-                // Handle download failure - extract mod ID from error message if possible
-                eprintln!("[UPDATE_MODS] Download channel reported error: {}", error_msg);
+            Err(error) => {
+                // Cancellation closes the channel instead of sending an error, so this is a real failure
+                eprintln!("[UPDATE_MODS] Download channel reported error: {}", error);
                 // Don't emit mod-updated here - let the retry system handle state transitions
                 // The retry system will emit "retry-queued" or "failed" as appropriate
                 // We just log the error here
@@ -345,10 +503,7 @@ pub async fn update_mods(
         // If cancelled, mark remaining mods as cancelled, not failed
         for failed_mod_id in &failed_download_mod_ids {
             eprintln!("[UPDATE_MODS] Mod {} download cancelled (not failed)", failed_mod_id);
-            let _ = app.emit("mod-state", serde_json::json!({
-                "modId": failed_mod_id,
-                "state": "cancelled"
-            }));
+            emit_mod_lifecycle(&app, failed_mod_id, ModPhase::Cancelled, serde_json::Value::Null);
         }
     }
     
@@ -405,6 +560,7 @@ pub async fn update_mods(
             }
         }
         
+        cancelled_mods.extend(held_back_mods);
         return Ok(cancelled_mods);
     }
     
@@ -430,11 +586,14 @@ pub async fn update_mods(
         }
 
         match result {
-            Ok(_path) => {
+            Ok(path) => {
                 // Find the original mod to return
                 if let Some(original_mod) = mods_map.get(&mod_id) {
                     let mut updated_mod = original_mod.clone();
                     updated_mod.updated = Some(true);
+                    if stage_only {
+                        updated_mod.staged_path = Some(path.to_string_lossy().to_string());
+                    }
                     updated_mods.push(updated_mod);
                 }
             }
@@ -459,6 +618,45 @@ pub async fn update_mods(
         }
     }
     
+    updated_mods.extend(held_back_mods);
     Ok(updated_mods)
 }
 
+/// Check that every mod lives directly in the mods folder, updates are installed there
+fn ensure_mods_in_folder(mods: &[BaseMod], mods_path: &Path) -> Result<(), String> {
+    let mods_path = canonicalize_path_or_fallback(mods_path);
+    let outside: Vec<&str> = mods.iter()
+        .filter(|m| {
+            Path::new(&m.mod_path).parent()
+                .map(|parent| canonicalize_path_or_fallback(parent) != mods_path)
+                .unwrap_or(true)
+        })
+        .map(|m| m.mod_path.as_str())
+        .collect();
+    if outside.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Mods are not in the mods folder {}: {}", mods_path.display(), outside.join(", ")))
+    }
+}
+
+/// Split mods into those with files changed since their install (with `local_modifications` set) and the rest
+async fn find_locally_modified_mods(mods: Vec<BaseMod>) -> (Vec<BaseMod>, Vec<BaseMod>) {
+    let checks = mods.into_iter().map(|mut base_mod| async move {
+        let mod_path = PathBuf::from(&base_mod.mod_path);
+        let changes = tokio::task::spawn_blocking(move || detect_local_changes(&mod_path))
+            .await
+            .unwrap_or_default();
+        base_mod.local_modifications = changes.iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        if !base_mod.local_modifications.is_empty() {
+            base_mod.updated = Some(false);
+        }
+        base_mod
+    });
+    futures::future::join_all(checks).await
+        .into_iter()
+        .partition(|base_mod| !base_mod.local_modifications.is_empty())
+}
+
@@ -1,32 +1,40 @@
 // Mod watcher commands
 
 use tauri::{command, AppHandle};
-use crate::services::{get_mod_watcher, validate_mods_path};
+use std::path::PathBuf;
+use std::time::Duration;
+use crate::services::{get_backup_watcher, get_mod_watcher, save_watcher_debounce, validate_mods_path};
 use crate::core::access_check::check_directory_access_with_warning;
+use crate::core::mod_watcher::{set_watcher_debounce as set_watcher_debounce_query, ReconcileResult};
+use crate::error::AppError;
+
+/// Longest accepted debounce, beyond this the mod list would visibly lag behind the folder
+const MAX_WATCHER_DEBOUNCE_MS: u64 = 10_000;
 
 /// Start watching the mods folder for changes
 #[command]
 pub async fn start_mod_watcher(
     app: AppHandle,
     mods_path: String,
-) -> Result<(), String> {
+    ignore_patterns: Option<Vec<String>>,
+) -> Result<(), AppError> {
     let path = validate_mods_path(&mods_path)?;
     
     // Check directory access (read access is required for watching)
-    check_directory_access_with_warning(&app, &path, &mods_path)?;
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
     
     let watcher = get_mod_watcher();
     let mut watcher_guard = watcher.lock().await;
     
-    watcher_guard.start_watching(path, app).await
-        .map_err(|e| format!("Failed to start mod watcher: {}", e))?;
+    watcher_guard.start_watching(path, app, ignore_patterns).await
+        .map_err(|e| AppError::failed(format!("Failed to start mod watcher: {}", e)))?;
     
     Ok(())
 }
 
 /// Stop watching the mods folder
 #[command]
-pub async fn stop_mod_watcher() -> Result<(), String> {
+pub async fn stop_mod_watcher() -> Result<(), AppError> {
     let watcher = get_mod_watcher();
     let mut watcher_guard = watcher.lock().await;
     
@@ -35,3 +43,61 @@ pub async fn stop_mod_watcher() -> Result<(), String> {
     Ok(())
 }
 
+/// Set how long a mod folder must go without file system events before the watcher processes it
+/// Pass None to go back to the default (300 ms)
+#[command]
+pub async fn set_watcher_debounce(app: AppHandle, debounce_ms: Option<u64>) -> Result<(), AppError> {
+    if debounce_ms.is_some_and(|ms| ms > MAX_WATCHER_DEBOUNCE_MS) {
+        return Err(AppError::invalid_input(format!("Watcher debounce cannot exceed {} ms", MAX_WATCHER_DEBOUNCE_MS)));
+    }
+    save_watcher_debounce(&app, debounce_ms).map_err(AppError::failed)?;
+    set_watcher_debounce_query(debounce_ms.map(Duration::from_millis));
+    Ok(())
+}
+
+/// Apply changes made to the mods folder outside the app that the watcher missed
+/// Returns only the net changes instead of re-querying every mod
+#[command]
+pub async fn reconcile_mods(
+    app: AppHandle,
+    mods_path: String,
+) -> Result<ReconcileResult, AppError> {
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    let watcher = get_mod_watcher();
+    let watcher_guard = watcher.lock().await;
+    
+    watcher_guard.reconcile(&path).await
+        .map_err(|e| AppError::failed(format!("Failed to reconcile mods: {}", e)))
+}
+
+/// Start watching the backup directory, emitting backup-added/backup-removed keyed by folder name
+#[command]
+pub async fn start_backup_watcher(
+    app: AppHandle,
+    backup_directory: String,
+) -> Result<(), AppError> {
+    if backup_directory.trim().is_empty() {
+        return Err(AppError::invalid_input("Backup directory cannot be empty"));
+    }
+    let path = PathBuf::from(&backup_directory);
+    
+    let watcher = get_backup_watcher();
+    let mut watcher_guard = watcher.lock().await;
+    
+    watcher_guard.start_watching(path, app).await
+        .map_err(|e| AppError::failed(format!("Failed to start backup watcher: {}", e)))
+}
+
+/// Stop watching the backup directory
+#[command]
+pub async fn stop_backup_watcher() -> Result<(), AppError> {
+    let watcher = get_backup_watcher();
+    let mut watcher_guard = watcher.lock().await;
+    
+    watcher_guard.stop_watching().await;
+    
+    Ok(())
+}
@@ -1,20 +1,27 @@
 // Steam API-related commands
 
 use serde_json;
-use tauri::command;
-use crate::services::get_steam_api;
-use crate::core::mod_scanner::query_mod_batch;
+use futures::StreamExt;
+use tauri::{command, AppHandle};
+use crate::services::{validate_mods_path, get_steam_api, save_api_cache, save_api_cache_ttl, save_network_config, save_scrape_concurrency};
+use crate::core::workshop_client::{scrape_concurrency, ChangelogEntry, CollectionInfo, network_config, set_disk_cache_ttl, set_network_config as set_network_config_query, set_scrape_concurrency as set_scrape_concurrency_query, NetworkConfig, SteamApi, SteamStatus, DEFAULT_COLLECTION_DEPTH, DEFAULT_DISK_CACHE_TTL};
+use crate::core::mod_scanner::{list_installed_mods_fast, parse_workshop_id, query_mod_batch};
+use crate::core::collection_diff::{diff_collection as diff_collection_query, CollectionDiff};
+use crate::core::access_check::check_directory_access_with_warning;
+use crate::core::api_rate_limiter::RateLimitBucket;
+use crate::error::AppError;
 
 /// Get file details from Steam Workshop (optimized - uses batch query internally)
 #[command]
-pub async fn get_file_details(mod_id: String) -> Result<serde_json::Value, String> {
+pub async fn get_file_details(mod_id: String) -> Result<serde_json::Value, AppError> {
+    let mod_id = parse_workshop_id(&mod_id).ok_or_else(|| AppError::invalid_mod_id(mod_id))?;
     // Use batch query for efficiency (even for single mod)
     match query_mod_batch(&[mod_id.clone()], 0).await {
         Ok(mut details) => {
             if let Some(detail) = details.pop() {
                 Ok(serde_json::to_value(detail).unwrap())
             } else {
-                Err("No file details found".to_string())
+                Err(AppError::failed("No file details found"))
             }
         }
         Err(e) => {
@@ -24,7 +31,7 @@ pub async fn get_file_details(mod_id: String) -> Result<serde_json::Value, Strin
                 let mut api = steam_api.lock().await;
                 api.get_file_details(&mod_id).await
             }
-            .map_err(|_| format!("Failed to fetch file details: {}", e))?;
+            .map_err(|_| AppError::failed(format!("Failed to fetch file details: {}", e)))?;
             
             Ok(serde_json::to_value(details).unwrap())
         }
@@ -35,7 +42,7 @@ pub async fn get_file_details(mod_id: String) -> Result<serde_json::Value, Strin
 #[command]
 pub async fn get_file_details_batch(
     mod_ids: Vec<String>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     if mod_ids.is_empty() {
         return Ok(serde_json::json!({}));
     }
@@ -55,6 +62,8 @@ pub async fn get_file_details_batch(
         let batch_end = std::cmp::min(i + BATCH_SIZE, unique_ids.len());
         let batch = &unique_ids[i..batch_end];
         
+        // Batches share the API rate limit with individual detail queries
+        get_steam_api().lock().await.wait_rate_limit(RateLimitBucket::Api).await;
         match query_mod_batch(batch, 0).await {
             Ok(mut details) => {
                 all_details.append(&mut details);
@@ -68,13 +77,9 @@ pub async fn get_file_details_batch(
                         all_details.push(detail);
                     }
                 }
+                save_api_cache().await;
             }
         }
-        
-        // Small delay between batches to avoid rate limiting
-        if i + BATCH_SIZE < unique_ids.len() {
-            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
-        }
     }
     
     // Build result map
@@ -96,7 +101,8 @@ pub async fn get_file_details_batch(
 
 /// Check if a file is a collection (optimized - uses batch query internally)
 #[command]
-pub async fn is_collection(mod_id: String) -> Result<serde_json::Value, String> {
+pub async fn is_collection(mod_id: String) -> Result<serde_json::Value, AppError> {
+    let mod_id = parse_workshop_id(&mod_id).ok_or_else(|| AppError::invalid_mod_id(mod_id))?;
     // Use batch query for efficiency (even for single mod)
     match query_mod_batch(&[mod_id.clone()], 0).await {
         Ok(mut details) => {
@@ -132,7 +138,7 @@ pub async fn is_collection(mod_id: String) -> Result<serde_json::Value, String>
                     let mut api = steam_api.lock().await;
                     api.is_collection(&mod_id).await
                 }
-                .map_err(|e| format!("Failed to check if collection: {}", e))?;
+                .map_err(|e| AppError::failed(format!("Failed to check if collection: {}", e)))?;
                 
                 Ok(serde_json::json!({
                     "isCollection": is_collection
@@ -146,7 +152,7 @@ pub async fn is_collection(mod_id: String) -> Result<serde_json::Value, String>
                 let mut api = steam_api.lock().await;
                 api.is_collection(&mod_id).await
             }
-            .map_err(|e| format!("Failed to check if collection: {}", e))?;
+            .map_err(|e| AppError::failed(format!("Failed to check if collection: {}", e)))?;
             
             Ok(serde_json::json!({
                 "isCollection": is_collection
@@ -159,7 +165,7 @@ pub async fn is_collection(mod_id: String) -> Result<serde_json::Value, String>
 #[command]
 pub async fn is_collection_batch(
     mod_ids: Vec<String>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, AppError> {
     if mod_ids.is_empty() {
         return Ok(serde_json::json!({}));
     }
@@ -171,7 +177,7 @@ pub async fn is_collection_batch(
         .cloned()
         .collect();
     
-    // Query details in batches of 50 in parallel
+    // Query details in batches of 50, a few batches at a time
     const BATCH_SIZE: usize = 50;
     let mut batch_futures = Vec::new();
     
@@ -182,10 +188,8 @@ pub async fn is_collection_batch(
         let steam_api = get_steam_api();
         
         let future = async move {
-            // Small delay to stagger requests
-            if batch_idx > 0 {
-                tokio::time::sleep(tokio::time::Duration::from_millis(100 * batch_idx as u64)).await;
-            }
+            // Batches share the API rate limit with individual detail queries
+            steam_api.lock().await.wait_rate_limit(RateLimitBucket::Api).await;
             
             match query_mod_batch(&batch, 0).await {
                 Ok(details) => Ok(details),
@@ -205,9 +209,12 @@ pub async fn is_collection_batch(
         batch_futures.push(future);
     }
     
-    // Wait for all batches in parallel
+    // Wait for all batches, bounded like scraping as the fallback queries mods one by one
     let batch_results: Vec<Result<Vec<crate::core::mod_scanner::WorkshopFileDetails>, Box<dyn std::error::Error + Send + Sync>>> = 
-        futures::future::join_all(batch_futures).await;
+        futures::stream::iter(batch_futures)
+            .buffer_unordered(scrape_concurrency())
+            .collect()
+            .await;
     let mut all_details = Vec::new();
     for result in batch_results {
         if let Ok(mut details) = result {
@@ -249,7 +256,7 @@ pub async fn is_collection_batch(
         }
     }
     
-    // Scrape mods that need it (in parallel)
+    // Scrape mods that need it, a few at a time and within the scraping rate limit
     if !mods_to_scrape.is_empty() {
         let mut scrape_futures = Vec::new();
         
@@ -266,7 +273,10 @@ pub async fn is_collection_batch(
             scrape_futures.push(future);
         }
         
-        let scrape_results = futures::future::join_all(scrape_futures).await;
+        let scrape_results: Vec<(String, bool)> = futures::stream::iter(scrape_futures)
+            .buffer_unordered(scrape_concurrency())
+            .collect()
+            .await;
         for (mod_id, is_collection) in scrape_results {
             result_map.insert(mod_id, serde_json::json!({
                 "isCollection": is_collection
@@ -286,29 +296,78 @@ pub async fn is_collection_batch(
     Ok(serde_json::Value::Object(result_map))
 }
 
-/// Get collection details (list of mods in collection)
+/// Get collection details (list of mods in collection, nested collections are resolved)
+/// Each mod carries the `parentCollectionId` of the collection it was found in
 #[command]
-pub async fn get_collection_details(collection_id: String) -> Result<Vec<serde_json::Value>, String> {
+pub async fn get_collection_details(collection_id: String, max_depth: Option<usize>) -> Result<Vec<serde_json::Value>, AppError> {
+    let collection_id = parse_workshop_id(&collection_id).ok_or_else(|| AppError::invalid_mod_id(collection_id))?;
+    let max_depth = max_depth.unwrap_or(DEFAULT_COLLECTION_DEPTH);
     let steam_api = get_steam_api();
     let details = {
         let mut api = steam_api.lock().await;
-        api.get_collection_details(&collection_id).await
+        api.get_collection_details(&collection_id, max_depth).await
     }
-    .map_err(|e| format!("Failed to fetch collection details: {}", e))?;
+    .map_err(|e| AppError::failed(format!("Failed to fetch collection details: {}", e)))?;
     
     Ok(details.into_iter()
         .map(|d| serde_json::to_value(d).unwrap())
         .collect())
 }
 
+/// Get a collection's own title, description, author and item count, e.g. to name it while importing
+#[command]
+pub async fn get_collection_info(collection_id: String) -> Result<CollectionInfo, AppError> {
+    let collection_id = parse_workshop_id(&collection_id).ok_or_else(|| AppError::invalid_mod_id(collection_id))?;
+    let steam_api = get_steam_api();
+    let info = {
+        let mut api = steam_api.lock().await;
+        api.get_collection_info(&collection_id).await
+    }
+    .map_err(|e| AppError::failed(format!("Failed to fetch collection info: {}", e)))?;
+    save_api_cache().await;
+    
+    Ok(info)
+}
+
+/// Compare a collection (nested collections included) with the installed mods
+/// `toAdd` lists the missing mods, ready for a batch download, `extraInstalled` the installed Workshop mods it doesn't contain
+#[command]
+pub async fn diff_collection(
+    app: AppHandle,
+    collection_id: String,
+    mods_path: String,
+    max_depth: Option<usize>,
+) -> Result<CollectionDiff, AppError> {
+    let collection_id = parse_workshop_id(&collection_id).ok_or_else(|| AppError::invalid_mod_id(collection_id))?;
+    let path = validate_mods_path(&mods_path)?;
+    
+    check_directory_access_with_warning(&app, &path, &mods_path).map_err(AppError::failed)?;
+    
+    let max_depth = max_depth.unwrap_or(DEFAULT_COLLECTION_DEPTH);
+    let steam_api = get_steam_api();
+    let collection = {
+        let mut api = steam_api.lock().await;
+        api.get_collection_details(&collection_id, max_depth).await
+    }
+    .map_err(|e| AppError::failed(format!("Failed to fetch collection details: {}", e)))?;
+    save_api_cache().await;
+    
+    let installed = list_installed_mods_fast(&path).await
+        .map_err(|e| AppError::failed(format!("Failed to list installed mods: {}", e)))?;
+    
+    Ok(diff_collection_query(&collection, &installed))
+}
+
 /// Get collection details for multiple collections (optimized batch version)
 #[command]
 pub async fn get_collection_details_batch(
     collection_ids: Vec<String>,
-) -> Result<serde_json::Value, String> {
+    max_depth: Option<usize>,
+) -> Result<serde_json::Value, AppError> {
     if collection_ids.is_empty() {
         return Ok(serde_json::json!({}));
     }
+    let max_depth = max_depth.unwrap_or(DEFAULT_COLLECTION_DEPTH);
     
     // Remove duplicates
     let unique_ids: Vec<String> = collection_ids.iter()
@@ -324,7 +383,7 @@ pub async fn get_collection_details_batch(
         let future = async move {
             let steam_api = get_steam_api();
             let mut api = steam_api.lock().await;
-            match api.get_collection_details(&collection_id_clone).await {
+            match api.get_collection_details(&collection_id_clone, max_depth).await {
                 Ok(details) => (collection_id_clone, details),
                 Err(_) => (collection_id_clone, vec![]),
             }
@@ -333,6 +392,7 @@ pub async fn get_collection_details_batch(
     }
     
     let collection_results = futures::future::join_all(collection_futures).await;
+    save_api_cache().await;
     
     // Build result map: collection_id -> array of mod details
     let mut result_map = serde_json::Map::new();
@@ -347,3 +407,86 @@ pub async fn get_collection_details_batch(
     Ok(serde_json::Value::Object(result_map))
 }
 
+
+/// Get the change notes of a Workshop item, newest first, so users can decide whether to apply an update
+#[command]
+pub async fn get_changelog(mod_id: String) -> Result<Vec<ChangelogEntry>, AppError> {
+    if mod_id.is_empty() || !mod_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppError::invalid_input(format!("Invalid mod ID: {}", mod_id)));
+    }
+    let steam_api = get_steam_api();
+    let mut api = steam_api.lock().await;
+    api.get_changelog(&mod_id).await
+        .map_err(|e| AppError::failed(format!("Failed to fetch changelog: {}", e)))
+}
+
+/// Set the language used for Workshop titles and descriptions (Steam language name, e.g. "german")
+#[command]
+pub async fn set_display_language(lang: String) -> Result<(), AppError> {
+    let steam_api = get_steam_api();
+    let mut api = steam_api.lock().await;
+    api.set_language(&lang).map_err(AppError::failed)
+}
+
+/// Get the language currently used for Workshop titles and descriptions
+#[command]
+pub async fn get_display_language() -> Result<String, AppError> {
+    let steam_api = get_steam_api();
+    let api = steam_api.lock().await;
+    Ok(api.language().to_string())
+}
+
+/// Check whether Steam itself is up, to tell Steam outages apart from local problems
+#[command]
+pub async fn get_steam_status() -> Result<SteamStatus, AppError> {
+    Ok(SteamApi::get_status().await)
+}
+
+/// Drop every cached Steam API response, including the cache persisted on disk
+#[command]
+pub async fn clear_cache() -> Result<(), AppError> {
+    let steam_api = get_steam_api();
+    let mut api = steam_api.lock().await;
+    api.clear_cache().map_err(AppError::failed)
+}
+
+/// Set how long cached Steam API responses are reused across restarts
+/// Pass None to go back to the default of 6 hours
+#[command]
+pub async fn set_api_cache_ttl(app: AppHandle, ttl_secs: Option<u64>) -> Result<(), AppError> {
+    save_api_cache_ttl(&app, ttl_secs).map_err(AppError::failed)?;
+    set_disk_cache_ttl(ttl_secs.map(std::time::Duration::from_secs).unwrap_or(DEFAULT_DISK_CACHE_TTL));
+    Ok(())
+}
+
+/// Set how many Workshop pages (or detail batches) batch commands fetch in parallel
+/// Pass None to go back to the default of 3; higher values risk a temporary IP ban by Steam
+#[command]
+pub async fn set_scrape_concurrency(app: AppHandle, concurrency: Option<usize>) -> Result<(), AppError> {
+    if concurrency == Some(0) {
+        return Err(AppError::invalid_input("Scrape concurrency must be at least 1"));
+    }
+    save_scrape_concurrency(&app, concurrency).map_err(AppError::failed)?;
+    set_scrape_concurrency_query(concurrency);
+    Ok(())
+}
+
+/// Configure how Steam is reached on restricted networks, kept across restarts
+/// `proxy` is an HTTP(S) proxy URL, the system proxy (`HTTPS_PROXY`) is used when it is empty
+/// `apiBaseUrl` replaces `http://api.steampowered.com`; `apiKey` is an optional Steam Web API key
+/// that raises the rate limits of Web API requests
+/// Returns the settings now in use
+#[command]
+pub async fn set_network_config(app: AppHandle, config: NetworkConfig) -> Result<NetworkConfig, AppError> {
+    // Validated first, so invalid URLs are never persisted
+    set_network_config_query(config).map_err(AppError::invalid_input)?;
+    let config = network_config();
+    save_network_config(&app, &config).map_err(AppError::failed)?;
+    Ok(config)
+}
+
+/// Get the proxy, API base URL and API key used for requests to Steam
+#[command]
+pub async fn get_network_config() -> Result<NetworkConfig, AppError> {
+    Ok(network_config())
+}
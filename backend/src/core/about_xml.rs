@@ -0,0 +1,423 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use serde::Serialize;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use crate::core::mod_scanner::{find_about_dir, query_mod_info};
+
+/// Metadata read from a mod's About/About.xml
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AboutMetadata {
+    pub name: Option<String>,
+    pub package_id: Option<String>,
+    pub author: Option<String>,
+    pub supported_versions: Vec<String>,
+    /// packageIds of mods this mod must load after
+    pub load_after: Vec<String>,
+    /// packageIds of mods this mod must load before
+    pub load_before: Vec<String>,
+    /// packageIds of mods that break this mod when both are active
+    pub incompatible_with: Vec<String>,
+    /// Workshop ID some authors put in About.xml, RimWorld itself reads PublishedFileId.txt
+    pub published_file_id: Option<String>,
+}
+
+/// Parse the content of an About.xml file
+/// Only fields directly under ModMetaData are read
+pub fn parse_about_xml(content: &str) -> Result<AboutMetadata, String> {
+    let mut reader = Reader::from_str(content);
+    reader.trim_text(true);
+
+    let mut metadata = AboutMetadata::default();
+    // Element names from ModMetaData down to the current element
+    let mut stack: Vec<String> = Vec::new();
+    let mut in_mod_metadata = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if !in_mod_metadata && name == "ModMetaData" {
+                    in_mod_metadata = true;
+                    stack.clear();
+                } else if in_mod_metadata {
+                    stack.push(name);
+                }
+            }
+            Ok(Event::Text(e)) => {
+                if !in_mod_metadata {
+                    continue;
+                }
+                let text = e.unescape().unwrap_or_default().trim().to_string();
+                if text.is_empty() {
+                    continue;
+                }
+                match stack.iter().map(|s| s.as_str()).collect::<Vec<&str>>().as_slice() {
+                    ["name"] => metadata.name = Some(text),
+                    ["packageId"] => metadata.package_id = Some(text),
+                    ["author"] => metadata.author = Some(text),
+                    ["publishedFileId"] => metadata.published_file_id = Some(text),
+                    ["supportedVersions", "li"] => metadata.supported_versions.push(text),
+                    ["loadAfter", "li"] => metadata.load_after.push(text),
+                    ["loadBefore", "li"] => metadata.load_before.push(text),
+                    ["incompatibleWith", "li"] => metadata.incompatible_with.push(text),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(e)) if in_mod_metadata => {
+                if stack.is_empty() && e.name().as_ref() == b"ModMetaData" {
+                    in_mod_metadata = false;
+                } else {
+                    stack.pop();
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Failed to parse About.xml: {:?}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(metadata)
+}
+
+/// Read an XML file of a mod, tolerating what older mods ship
+/// A UTF-8 byte order mark is stripped and content that isn't UTF-8 (usually Latin-1) is decoded lossily,
+/// the markup itself is ASCII so only odd characters in text are lost
+pub fn read_mod_xml(path: &Path) -> std::io::Result<String> {
+    let bytes = fs::read(path)?;
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Read About.xml metadata from a mod folder
+/// Returns None if the mod has no About.xml
+pub fn read_about_metadata(mod_path: &Path) -> Result<Option<AboutMetadata>, String> {
+    let about_xml_path = find_about_dir(mod_path).join("About.xml");
+    if !about_xml_path.exists() {
+        return Ok(None);
+    }
+
+    let content = read_mod_xml(&about_xml_path)
+        .map_err(|e| format!("Failed to read About.xml: {}", e))?;
+
+    parse_about_xml(&content).map(Some)
+}
+
+/// Problem found in a mod's About.xml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "detail")]
+pub enum AboutIssue {
+    MissingName,
+    MissingPackageId,
+    MissingSupportedVersions,
+    InvalidXml(String),
+}
+
+/// About.xml issues of a single installed mod
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AboutValidationReport {
+    pub mod_id: String,
+    pub mod_path: String,
+    pub folder: Option<String>,
+    pub name: Option<String>,
+    pub issues: Vec<AboutIssue>,
+}
+
+/// Check About.xml metadata for fields RimWorld needs to load a mod correctly
+pub fn check_about_metadata(metadata: &AboutMetadata) -> Vec<AboutIssue> {
+    let mut issues = Vec::new();
+    if metadata.name.is_none() {
+        issues.push(AboutIssue::MissingName);
+    }
+    if metadata.package_id.is_none() {
+        issues.push(AboutIssue::MissingPackageId);
+    }
+    if metadata.supported_versions.is_empty() {
+        issues.push(AboutIssue::MissingSupportedVersions);
+    }
+    issues
+}
+
+/// Validate About.xml of a single mod folder
+/// Returns None for folders that are not mods, have no About.xml (those are reported as corrupted elsewhere)
+/// or have no issues
+fn validate_mod_folder(folder_path: &Path) -> Option<AboutValidationReport> {
+    let info = query_mod_info(folder_path).ok().flatten()?;
+
+    let (name, issues) = match read_about_metadata(folder_path) {
+        Ok(Some(metadata)) => (metadata.name.clone(), check_about_metadata(&metadata)),
+        Ok(None) => return None,
+        Err(e) => (None, vec![AboutIssue::InvalidXml(e)]),
+    };
+
+    if issues.is_empty() {
+        return None;
+    }
+
+    Some(AboutValidationReport {
+        mod_id: info.mod_id,
+        mod_path: folder_path.to_string_lossy().to_string(),
+        folder: folder_path.file_name().and_then(|n| n.to_str()).map(|s| s.to_string()),
+        name,
+        issues,
+    })
+}
+
+/// Whether a mod declares support for the running game version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VersionCompatibility {
+    Compatible,
+    Incompatible,
+    /// The mod has no `<supportedVersions>`, so it may still work
+    Unknown,
+}
+
+/// Compatibility of a single installed mod with the running game version
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionCompatibilityReport {
+    pub mod_id: String,
+    pub mod_path: String,
+    pub folder: Option<String>,
+    pub name: Option<String>,
+    pub supported_versions: Vec<String>,
+    pub compatibility: VersionCompatibility,
+}
+
+/// Reduce a version like `1.5.4243 rev1234` to its major.minor part
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split_whitespace().next()?.split('.');
+    let major = parts.next()?.trim().parse().ok()?;
+    let minor = parts.next()?.trim().parse().ok()?;
+    Some((major, minor))
+}
+
+/// Check supported versions against the game version, comparing major.minor only
+pub fn check_version_compatibility_of(supported_versions: &[String], game_version: &str) -> VersionCompatibility {
+    if supported_versions.is_empty() {
+        return VersionCompatibility::Unknown;
+    }
+    let game = major_minor(game_version);
+    if game.is_some() && supported_versions.iter().any(|v| major_minor(v) == game) {
+        VersionCompatibility::Compatible
+    } else {
+        VersionCompatibility::Incompatible
+    }
+}
+
+fn check_mod_folder_compatibility(folder_path: &Path, game_version: &str) -> Option<VersionCompatibilityReport> {
+    let info = query_mod_info(folder_path).ok().flatten()?;
+    // Unreadable About.xml is reported by validate_about_metadata, treat it as undeclared here
+    let metadata = read_about_metadata(folder_path).ok().flatten().unwrap_or_default();
+    let compatibility = check_version_compatibility_of(&metadata.supported_versions, game_version);
+    if compatibility == VersionCompatibility::Compatible {
+        return None;
+    }
+
+    Some(VersionCompatibilityReport {
+        mod_id: info.mod_id,
+        mod_path: folder_path.to_string_lossy().to_string(),
+        folder: folder_path.file_name().and_then(|n| n.to_str()).map(|s| s.to_string()),
+        name: metadata.name,
+        supported_versions: metadata.supported_versions,
+        compatibility,
+    })
+}
+
+/// Report installed mods that don't list the running game version
+/// Mods without `<supportedVersions>` are reported as unknown rather than incompatible
+pub async fn check_version_compatibility(mods_path: &Path, game_version: &str) -> Result<Vec<VersionCompatibilityReport>, String> {
+    if major_minor(game_version).is_none() {
+        return Err(format!("Invalid game version: {}", game_version));
+    }
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    let folders: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+
+    let futures: Vec<_> = folders.into_iter()
+        .map(|folder| {
+            let game_version = game_version.to_string();
+            tokio::task::spawn_blocking(move || check_mod_folder_compatibility(&folder, &game_version))
+        })
+        .collect();
+
+    let mut reports: Vec<VersionCompatibilityReport> = futures::future::join_all(futures).await
+        .into_iter()
+        .filter_map(|r| r.ok().flatten())
+        .collect();
+    reports.sort_by(|a, b| a.folder.cmp(&b.folder));
+
+    Ok(reports)
+}
+
+/// Report installed mods whose About.xml lacks a name, packageId or supportedVersions
+pub async fn validate_about_metadata(mods_path: &Path) -> Result<Vec<AboutValidationReport>, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    let folders: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+
+    let futures: Vec<_> = folders.into_iter()
+        .map(|folder| tokio::task::spawn_blocking(move || validate_mod_folder(&folder)))
+        .collect();
+
+    let mut reports: Vec<AboutValidationReport> = futures::future::join_all(futures).await
+        .into_iter()
+        .filter_map(|r| r.ok().flatten())
+        .collect();
+    reports.sort_by(|a, b| a.folder.cmp(&b.folder));
+
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FULL_ABOUT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<ModMetaData>
+    <name>Test Mod</name>
+    <author>Someone</author>
+    <packageId>someone.testmod</packageId>
+    <supportedVersions>
+        <li>1.4</li>
+        <li>1.5</li>
+    </supportedVersions>
+    <modDependencies>
+        <li>
+            <packageId>brrainz.harmony</packageId>
+        </li>
+    </modDependencies>
+    <loadAfter>
+        <li>brrainz.harmony</li>
+    </loadAfter>
+    <loadBefore>
+        <li>someone.laterpatch</li>
+    </loadBefore>
+    <incompatibleWith>
+        <li>someone.oldtestmod</li>
+    </incompatibleWith>
+</ModMetaData>"#;
+
+    #[test]
+    fn test_parse_about_xml_full() {
+        let metadata = parse_about_xml(FULL_ABOUT).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Test Mod"));
+        assert_eq!(metadata.author.as_deref(), Some("Someone"));
+        // Nested packageId inside modDependencies must not override the mod's own
+        assert_eq!(metadata.package_id.as_deref(), Some("someone.testmod"));
+        assert_eq!(metadata.supported_versions, vec!["1.4", "1.5"]);
+        assert_eq!(metadata.load_after, vec!["brrainz.harmony"]);
+        assert_eq!(metadata.load_before, vec!["someone.laterpatch"]);
+        assert_eq!(metadata.incompatible_with, vec!["someone.oldtestmod"]);
+        assert!(check_about_metadata(&metadata).is_empty());
+    }
+
+    #[test]
+    fn test_check_about_metadata_missing_fields() {
+        let metadata = parse_about_xml("<ModMetaData><author>x</author></ModMetaData>").unwrap();
+        assert_eq!(
+            check_about_metadata(&metadata),
+            vec![AboutIssue::MissingName, AboutIssue::MissingPackageId, AboutIssue::MissingSupportedVersions]
+        );
+    }
+
+    #[test]
+    fn test_parse_about_xml_invalid() {
+        assert!(parse_about_xml("<ModMetaData><name>x</author></ModMetaData>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_about_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+
+        // Valid mod - not reported
+        let good = mods_path.join("Good");
+        fs::create_dir_all(good.join("About")).unwrap();
+        fs::write(good.join("About").join("About.xml"), FULL_ABOUT).unwrap();
+
+        // Mod without packageId and supportedVersions
+        let partial = mods_path.join("Partial");
+        fs::create_dir_all(partial.join("About")).unwrap();
+        fs::write(partial.join("About").join("About.xml"), "<ModMetaData><name>Partial</name></ModMetaData>").unwrap();
+        fs::write(partial.join("About").join("PublishedFileId.txt"), "123").unwrap();
+
+        // Mod without About.xml - corrupted, not reported here
+        fs::create_dir_all(mods_path.join("NoXml").join("About")).unwrap();
+
+        let reports = validate_about_metadata(mods_path).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].mod_id, "123");
+        assert_eq!(reports[0].issues, vec![AboutIssue::MissingPackageId, AboutIssue::MissingSupportedVersions]);
+    }
+
+    #[test]
+    fn test_version_compatibility() {
+        let versions = vec!["1.4".to_string(), "1.5".to_string()];
+        assert_eq!(check_version_compatibility_of(&versions, "1.5.4243 rev1234"), VersionCompatibility::Compatible);
+        assert_eq!(check_version_compatibility_of(&versions, "1.3"), VersionCompatibility::Incompatible);
+        // 1.50 is not 1.5
+        assert_eq!(check_version_compatibility_of(&versions, "1.50"), VersionCompatibility::Incompatible);
+        assert_eq!(check_version_compatibility_of(&[], "1.5"), VersionCompatibility::Unknown);
+    }
+
+    #[test]
+    fn test_read_mod_xml_with_bom_and_latin1() {
+        use crate::core::mod_manager::ModUpdater;
+
+        let temp_dir = TempDir::new().unwrap();
+        let write_about = |folder: &str, content: &[u8]| {
+            let about_dir = temp_dir.path().join(folder).join("About");
+            fs::create_dir_all(&about_dir).unwrap();
+            fs::write(about_dir.join("About.xml"), content).unwrap();
+            temp_dir.path().join(folder)
+        };
+
+        let mut bom = b"\xEF\xBB\xBF".to_vec();
+        bom.extend_from_slice(FULL_ABOUT.as_bytes());
+        let mod_path = write_about("Bom", &bom);
+        assert!(read_mod_xml(&mod_path.join("About").join("About.xml")).unwrap().starts_with("<?xml"));
+        assert_eq!(ModUpdater::get_package_id(&mod_path).as_deref(), Some("someone.testmod"));
+        assert_eq!(read_about_metadata(&mod_path).unwrap().unwrap().package_id.as_deref(), Some("someone.testmod"));
+
+        // "Café" in Latin-1
+        let mod_path = write_about("Latin1", b"<ModMetaData><name>Caf\xE9</name><packageId>old.cafe</packageId></ModMetaData>");
+        assert_eq!(ModUpdater::get_package_id(&mod_path).as_deref(), Some("old.cafe"));
+        let metadata = read_about_metadata(&mod_path).unwrap().unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Caf\u{FFFD}"));
+    }
+
+    #[tokio::test]
+    async fn test_check_version_compatibility() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        let create_mod = |folder: &str, about: &str| {
+            let about_dir = mods_path.join(folder).join("About");
+            fs::create_dir_all(&about_dir).unwrap();
+            fs::write(about_dir.join("About.xml"), about).unwrap();
+        };
+        create_mod("Current", FULL_ABOUT);
+        create_mod("Old", "<ModMetaData><name>Old</name><supportedVersions><li>1.0</li></supportedVersions></ModMetaData>");
+        create_mod("Undeclared", "<ModMetaData><name>Undeclared</name></ModMetaData>");
+
+        let reports = check_version_compatibility(mods_path, "1.5.4243").await.unwrap();
+        let statuses: Vec<(Option<&str>, VersionCompatibility)> = reports.iter()
+            .map(|r| (r.folder.as_deref(), r.compatibility))
+            .collect();
+        assert_eq!(statuses, vec![
+            (Some("Old"), VersionCompatibility::Incompatible),
+            (Some("Undeclared"), VersionCompatibility::Unknown),
+        ]);
+        assert!(check_version_compatibility(mods_path, "unknown").await.is_err());
+    }
+}
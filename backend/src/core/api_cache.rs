@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use serde::Serialize;
 
 /// Simple in-memory cache with TTL (Time To Live)
 pub struct Cache<T> {
@@ -10,6 +13,8 @@ pub struct Cache<T> {
 struct CacheEntry<T> {
     data: T,
     expires_at: Instant,
+    /// Wall clock time the data was fetched, kept so entries can be persisted
+    stored_at: SystemTime,
 }
 
 impl<T> Cache<T> {
@@ -24,7 +29,26 @@ impl<T> Cache<T> {
     /// Set a value in the cache with optional custom TTL
     pub fn set(&mut self, key: String, data: T, ttl: Option<Duration>) {
         let expires_at = Instant::now() + ttl.unwrap_or(self.default_ttl);
-        self.cache.insert(key, CacheEntry { data, expires_at });
+        self.cache.insert(key, CacheEntry { data, expires_at, stored_at: SystemTime::now() });
+    }
+
+    /// Insert a value fetched earlier (e.g. loaded from disk), expiring `max_age` after `stored_at`
+    /// Returns false and skips the value if it is already stale
+    pub fn restore(&mut self, key: String, data: T, stored_at: SystemTime, max_age: Duration) -> bool {
+        let age = SystemTime::now().duration_since(stored_at).unwrap_or_default();
+        let Some(remaining) = max_age.checked_sub(age).filter(|r| !r.is_zero()) else {
+            return false;
+        };
+        self.cache.insert(key, CacheEntry { data, expires_at: Instant::now() + remaining, stored_at });
+        true
+    }
+
+    /// Non-expired entries with the time they were fetched at
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &T, SystemTime)> {
+        let now = Instant::now();
+        self.cache.iter()
+            .filter(move |(_, entry)| entry.expires_at > now)
+            .map(|(key, entry)| (key, &entry.data, entry.stored_at))
     }
 
     /// Get a value from the cache, returning None if expired or not found
@@ -58,6 +82,36 @@ impl<T> Cache<T> {
     }
 }
 
+/// Write a value as JSON to a temp file next to `path`, then rename it over `path`
+/// Readers never see a half-written file and concurrent writers never interleave
+pub fn write_json_atomic<V: Serialize>(path: &Path, value: &V) -> Result<(), String> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+    
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+    }
+    
+    let json = serde_json::to_vec(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", path.display(), e))?;
+    
+    // Unique temp name per write, so two writers never share a temp file
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("cache");
+    let temp_path = path.with_file_name(format!(
+        "{}.{}-{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+    ));
+    
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    std::fs::rename(&temp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,5 +158,32 @@ mod tests {
         assert_eq!(cache.get("key1"), None);
         assert_eq!(cache.get("key2"), None);
     }
-}
 
+    #[test]
+    fn test_cache_restore_respects_age() {
+        let mut cache = Cache::new(Duration::from_secs(60));
+        let hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        
+        assert!(!cache.restore("stale".to_string(), "value", hour_ago, Duration::from_secs(1800)));
+        assert!(cache.restore("fresh".to_string(), "value", hour_ago, Duration::from_secs(7200)));
+        
+        assert_eq!(cache.get("stale"), None);
+        assert_eq!(cache.get("fresh"), Some(&"value"));
+        let (_, _, stored_at) = cache.entries().next().unwrap();
+        assert_eq!(stored_at, hour_ago);
+    }
+
+    #[test]
+    fn test_write_json_atomic() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested").join("cache.json");
+        
+        write_json_atomic(&path, &serde_json::json!({ "a": 1 })).unwrap();
+        write_json_atomic(&path, &serde_json::json!({ "a": 2 })).unwrap();
+        
+        let content: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(content["a"], 2);
+        // No temp files are left behind
+        assert_eq!(std::fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
@@ -40,6 +41,62 @@ impl RateLimiter {
     }
 }
 
+/// Groups of Steam endpoints that are throttled independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitBucket {
+    /// Official Web API (GetPublishedFileDetails and friends)
+    Api,
+    /// Workshop HTML pages, which get the client temporarily IP-blocked when hit too often
+    Scraping,
+}
+
+/// Minimum delay between requests for each bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    pub api: Duration,
+    pub scraping: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            api: Duration::from_millis(250),
+            scraping: Duration::from_millis(2000), // 2 seconds
+        }
+    }
+}
+
+/// Rate limiter keeping a separate delay per bucket
+pub struct BucketRateLimiter {
+    limiters: HashMap<RateLimitBucket, RateLimiter>,
+}
+
+impl BucketRateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        let mut limiters = HashMap::new();
+        limiters.insert(RateLimitBucket::Api, RateLimiter::new(config.api));
+        limiters.insert(RateLimitBucket::Scraping, RateLimiter::new(config.scraping));
+        Self { limiters }
+    }
+
+    /// Wait for the rate limit delay of a bucket
+    pub async fn wait(&mut self, bucket: RateLimitBucket) {
+        if let Some(limiter) = self.limiters.get_mut(&bucket) {
+            limiter.wait().await;
+        }
+    }
+
+    /// Execute a function with the rate limiting of a bucket
+    pub async fn execute<F, Fut, T>(&mut self, bucket: RateLimitBucket, f: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = T>,
+    {
+        self.wait(bucket).await;
+        f().await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,5 +126,24 @@ mod tests {
         
         assert_eq!(result, 42);
     }
-}
 
+    #[tokio::test]
+    async fn test_buckets_are_independent() {
+        let mut limiter = BucketRateLimiter::new(RateLimitConfig {
+            api: Duration::from_millis(10),
+            scraping: Duration::from_millis(500),
+        });
+
+        limiter.wait(RateLimitBucket::Scraping).await;
+
+        // Waiting on the scraping bucket doesn't delay API requests
+        let start = Instant::now();
+        limiter.wait(RateLimitBucket::Api).await;
+        limiter.wait(RateLimitBucket::Api).await;
+        assert!(start.elapsed() < Duration::from_millis(200));
+
+        let start = Instant::now();
+        limiter.wait(RateLimitBucket::Scraping).await;
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+}
@@ -0,0 +1,151 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Compressed backup of the backup folder `backup_path`, `Harmony` is backed up as `Harmony.zip`
+pub fn zip_backup_path(backup_path: &Path) -> PathBuf {
+    let mut path = OsString::from(backup_path.as_os_str());
+    path.push(".zip");
+    PathBuf::from(path)
+}
+
+/// Whether `path` is a compressed backup
+pub fn is_zip_backup(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// The backup stored at `backup_path`, either the backup folder or its compressed form, None if there is none
+pub fn find_backup(backup_path: &Path) -> Option<PathBuf> {
+    if backup_path.is_dir() {
+        return Some(backup_path.to_path_buf());
+    }
+    let zip_path = zip_backup_path(backup_path);
+    is_zip_backup(&zip_path).then_some(zip_path)
+}
+
+/// Compress a mod folder into `zip_path`, replacing an existing archive
+/// The archive is written under a hidden temporary name, so a failed backup keeps the previous one
+/// Returns the number of files stored
+pub fn create_zip_backup(mod_path: &Path, zip_path: &Path) -> Result<usize, String> {
+    let file_name = zip_path.file_name()
+        .ok_or_else(|| format!("Invalid backup path: {}", zip_path.display()))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = zip_path.with_file_name(temp_name);
+
+    let written = write_zip(mod_path, &temp_path)
+        .map_err(|e| format!("Failed to compress {}: {}", mod_path.display(), e))
+        .and_then(|files| {
+            fs::rename(&temp_path, zip_path)
+                .map_err(|e| format!("Failed to replace {}: {}", zip_path.display(), e))?;
+            Ok(files)
+        });
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    written
+}
+
+fn write_zip(mod_path: &Path, zip_path: &Path) -> zip::result::ZipResult<usize> {
+    let mut writer = ZipWriter::new(File::create(zip_path)?);
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+    let files = add_dir_to_zip(&mut writer, mod_path, "", options)?;
+    writer.finish()?;
+    Ok(files)
+}
+
+/// Add the contents of `dir` to the archive under `prefix`, empty folders included
+/// Symlinks are skipped rather than followed, a link pointing at one of its parents would never end
+fn add_dir_to_zip(writer: &mut ZipWriter<File>, dir: &Path, prefix: &str, options: FileOptions) -> zip::result::ZipResult<usize> {
+    let mut files = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            eprintln!("[Backup] Skipping symlink {}, it is not stored in compressed backups", path.display());
+        } else if file_type.is_dir() {
+            writer.add_directory(format!("{}/", name), options)?;
+            files += add_dir_to_zip(writer, &path, &format!("{}/", name), options)?;
+        } else {
+            writer.start_file(name, options)?;
+            io::copy(&mut File::open(&path)?, writer)?;
+            files += 1;
+        }
+    }
+    Ok(files)
+}
+
+/// Extract a compressed backup into `destination`
+/// Entries with paths leading outside of `destination` are rejected by the zip crate
+pub fn extract_zip_backup(zip_path: &Path, destination: &Path) -> Result<(), String> {
+    let file = File::open(zip_path)
+        .map_err(|e| format!("Failed to open {}: {}", zip_path.display(), e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read {}: {}", zip_path.display(), e))?;
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create directory {}: {}", destination.display(), e))?;
+    archive.extract(destination)
+        .map_err(|e| format!("Failed to extract {}: {}", zip_path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_zip_backup_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Mods").join("Harmony");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::create_dir_all(mod_path.join("Textures").join("Empty")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData/>").unwrap();
+        fs::write(mod_path.join("About").join("PublishedFileId.txt"), "2009463077").unwrap();
+
+        let backup_path = temp_dir.path().join("Backups").join("Harmony");
+        fs::create_dir_all(backup_path.parent().unwrap()).unwrap();
+        assert_eq!(find_backup(&backup_path), None);
+
+        let zip_path = zip_backup_path(&backup_path);
+        assert_eq!(zip_path, temp_dir.path().join("Backups").join("Harmony.zip"));
+        assert_eq!(create_zip_backup(&mod_path, &zip_path).unwrap(), 2);
+        assert_eq!(find_backup(&backup_path), Some(zip_path.clone()));
+        // No temporary archive is left behind
+        assert_eq!(fs::read_dir(backup_path.parent().unwrap()).unwrap().count(), 1);
+
+        let restored = temp_dir.path().join("Restored");
+        extract_zip_backup(&zip_path, &restored).unwrap();
+        assert_eq!(fs::read_to_string(restored.join("About").join("PublishedFileId.txt")).unwrap(), "2009463077");
+        assert!(restored.join("Textures").join("Empty").is_dir());
+
+        // A backup folder is preferred over the archive
+        fs::create_dir_all(&backup_path).unwrap();
+        assert_eq!(find_backup(&backup_path), Some(backup_path.clone()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zip_backup_skips_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Harmony");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData/>").unwrap();
+        // A link back to the mod folder would otherwise be followed forever
+        std::os::unix::fs::symlink(&mod_path, mod_path.join("About").join("Loop")).unwrap();
+
+        let zip_path = temp_dir.path().join("Harmony.zip");
+        assert_eq!(create_zip_backup(&mod_path, &zip_path).unwrap(), 1);
+        let restored = temp_dir.path().join("Restored");
+        extract_zip_backup(&zip_path, &restored).unwrap();
+        assert!(restored.join("About").join("About.xml").is_file());
+        assert!(!restored.join("About").join("Loop").exists());
+    }
+}
@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use serde::Serialize;
+use crate::core::backup_archive::is_zip_backup;
+use crate::core::incremental_backup::is_incremental_backup;
+use crate::core::mod_scanner::find_about_dir;
+
+/// A backed-up mod folder in the backup directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub name: String,
+    pub path: String,
+    /// Total size of the backup in bytes, all versions included for incremental backups
+    pub size: u64,
+    /// Last modification time in seconds since the Unix epoch
+    pub modified: u64,
+    pub incremental: bool,
+    /// Stored as a single `.zip`, `name` is the mod folder without the extension
+    pub compressed: bool,
+}
+
+/// Total size in bytes of all files in a directory
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries.flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// List the backups in a backup directory, newest first
+/// Only compressed backups, mod folders with an About folder and incremental backups are listed,
+/// anything else the user keeps in the directory is left alone
+/// A missing backup directory has no backups
+pub fn list_backups(backup_directory: &Path) -> Result<Vec<BackupInfo>, String> {
+    if !backup_directory.exists() {
+        return Ok(vec![]);
+    }
+    let entries = fs::read_dir(backup_directory)
+        .map_err(|e| format!("Failed to read backup directory: {}", e))?;
+
+    let mut backups: Vec<BackupInfo> = entries.flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let compressed = !is_dir && is_zip_backup(&path);
+            let incremental = is_dir && is_incremental_backup(&path);
+            let full = is_dir && find_about_dir(&path).is_dir();
+            if !compressed && !incremental && !full {
+                return None;
+            }
+            let metadata = entry.metadata().ok();
+            let modified = metadata.as_ref()
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let name = match compressed {
+                true => path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+                false => entry.file_name().to_string_lossy().to_string(),
+            };
+            Some(BackupInfo {
+                name,
+                path: path.to_string_lossy().to_string(),
+                size: match compressed {
+                    true => metadata.map(|m| m.len()).unwrap_or(0),
+                    false => dir_size(&path),
+                },
+                modified,
+                incremental,
+                compressed,
+            })
+        })
+        .collect();
+
+    backups.sort_by(|a, b| b.modified.cmp(&a.modified).then_with(|| a.name.cmp(&b.name)));
+    Ok(backups)
+}
+
+/// Delete all but the `keep_newest` most recently modified backups
+/// Returns the deleted backups
+pub fn prune_backups(backup_directory: &Path, keep_newest: usize) -> Result<Vec<BackupInfo>, String> {
+    let backups = list_backups(backup_directory)?;
+    let mut removed = Vec::new();
+    for backup in backups.into_iter().skip(keep_newest) {
+        let path = PathBuf::from(&backup.path);
+        let removed_backup = match backup.compressed {
+            true => fs::remove_file(&path),
+            false => fs::remove_dir_all(&path),
+        };
+        match removed_backup {
+            Ok(()) => removed.push(backup),
+            Err(e) => eprintln!("[Backups] Failed to delete backup {:?}: {}", path, e),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn create_backup(backup_directory: &Path, name: &str, age_secs: u64) {
+        let path = backup_directory.join(name);
+        fs::create_dir_all(path.join("About")).unwrap();
+        fs::write(path.join("About").join("About.xml"), "12345").unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(age_secs);
+        fs::File::open(&path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn test_list_and_prune_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let backup_directory = temp_dir.path();
+        create_backup(backup_directory, "Old", 300);
+        create_backup(backup_directory, "Newest", 0);
+        create_backup(backup_directory, "Middle", 100);
+        fs::write(backup_directory.join("notes.txt"), "not a backup").unwrap();
+        fs::create_dir_all(backup_directory.join("Screenshots")).unwrap();
+        let zip_path = backup_directory.join("Compressed.zip");
+        fs::write(&zip_path, "zipped").unwrap();
+        fs::File::options().write(true).open(&zip_path).unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(200)).unwrap();
+
+        let backups = list_backups(backup_directory).unwrap();
+        let names: Vec<&str> = backups.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["Newest", "Middle", "Compressed", "Old"]);
+        assert_eq!(backups[0].size, 5);
+        assert!(backups[2].compressed);
+        assert_eq!(backups[2].size, 6);
+
+        let removed = prune_backups(backup_directory, 1).unwrap();
+        assert_eq!(removed.len(), 3);
+        assert!(backup_directory.join("Newest").exists());
+        assert!(!backup_directory.join("Old").exists());
+        assert!(!zip_path.exists());
+        assert!(backup_directory.join("notes.txt").exists());
+        assert!(backup_directory.join("Screenshots").exists());
+    }
+
+    #[test]
+    fn test_list_backups_missing_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(list_backups(&temp_dir.path().join("missing")).unwrap().is_empty());
+    }
+}
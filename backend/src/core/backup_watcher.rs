@@ -0,0 +1,284 @@
+// File system watcher for the backup directory
+// Observes the backup directory and emits events when mod backups are added or removed
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::collections::{HashSet, HashMap};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use notify::{Watcher, RecommendedWatcher, RecursiveMode, Event, EventKind};
+use tauri::{AppHandle, Emitter};
+use crate::core::backup_archive::find_backup;
+
+/// How long a new backup folder must stay unchanged before it's reported, backups are copied file by file
+const SETTLE_DELAY: Duration = Duration::from_secs(2);
+/// Interval of the periodic verification that also catches missed events
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Name of the backup folder (a direct child of the backup directory) containing `path`
+/// A compressed backup is named after the folder it holds
+fn backup_folder_name(path: &Path, backup_path: &Path) -> Option<String> {
+    match path.strip_prefix(backup_path).ok()?.components().next()? {
+        Component::Normal(name) => name.to_str().map(|s| s.strip_suffix(".zip").unwrap_or(s).to_string()),
+        _ => None,
+    }
+}
+
+/// Names of the backups in the backup directory, folders and compressed ones, hidden entries are skipped
+fn list_backup_folders(backup_path: &Path) -> HashSet<String> {
+    let entries = match std::fs::read_dir(backup_path) {
+        Ok(entries) => entries,
+        Err(_) => return HashSet::new(),
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| backup_folder_name(&entry.path(), backup_path))
+        .filter(|name| !name.starts_with('.'))
+        .filter(|name| find_backup(&backup_path.join(name)).is_some())
+        .collect()
+}
+
+pub struct BackupWatcher {
+    watcher: Option<RecommendedWatcher>,
+    backup_path: Option<PathBuf>,
+    known_backups: Arc<Mutex<HashSet<String>>>, // Folder names of the backups reported to the UI
+    pending_folders: Arc<Mutex<HashMap<String, Instant>>>, // Folders still being written -> time of their last change
+    periodic_check_handle: Option<tokio::task::JoinHandle<()>>, // Handle for periodic check task to allow cancellation
+}
+
+impl BackupWatcher {
+    pub fn new() -> Self {
+        Self {
+            watcher: None,
+            backup_path: None,
+            known_backups: Arc::new(Mutex::new(HashSet::new())),
+            pending_folders: Arc::new(Mutex::new(HashMap::new())),
+            periodic_check_handle: None,
+        }
+    }
+
+    /// Start watching the backup directory for changes
+    /// Backups existing at this point are not reported, `check_backups` gives the initial state
+    pub async fn start_watching(&mut self, backup_path: PathBuf, app: AppHandle) -> Result<(), String> {
+        // Stop existing watcher if any
+        self.stop_watching().await;
+
+        // Backups create the directory on first use, create it now so it can be watched before that
+        std::fs::create_dir_all(&backup_path)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+        let canonical_backup_path = backup_path.canonicalize()
+            .unwrap_or_else(|_| backup_path.clone());
+        self.backup_path = Some(canonical_backup_path.clone());
+
+        let initial_backups = list_backup_folders(&canonical_backup_path);
+        eprintln!("[BackupWatcher] Initialized with {} backup(s)", initial_backups.len());
+        {
+            let mut known = self.known_backups.lock().await;
+            *known = initial_backups;
+        }
+
+        // Create channel for file system events
+        let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+        let app_clone = app.clone();
+        let backup_path_clone = canonical_backup_path.clone();
+        let known_backups_clone = self.known_backups.clone();
+        let pending_folders_clone = self.pending_folders.clone();
+
+        // Spawn task to process file system events
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                Self::process_fs_event(event, &app_clone, &backup_path_clone, &known_backups_clone, &pending_folders_clone).await;
+            }
+        });
+
+        // Spawn task to periodically report settled folders and verify the directory
+        let backup_path_clone_retry = canonical_backup_path.clone();
+        let known_backups_clone_retry = self.known_backups.clone();
+        let pending_folders_clone_retry = self.pending_folders.clone();
+        let periodic_check_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                Self::check_pending_folders(&app, &backup_path_clone_retry, &known_backups_clone_retry, &pending_folders_clone_retry).await;
+            }
+        });
+        self.periodic_check_handle = Some(periodic_check_handle);
+
+        // Create watcher
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                // Send event to channel (non-blocking)
+                let _ = tx.try_send(event);
+            }
+        })
+        .map_err(|e| format!("Failed to create file system watcher: {}", e))?;
+
+        // Recursive, so files still being copied into a backup keep it pending
+        watcher.watch(&canonical_backup_path, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch backup directory: {}", e))?;
+
+        eprintln!("[BackupWatcher] Watching canonical path: {:?}", canonical_backup_path);
+
+        self.watcher = Some(watcher);
+
+        Ok(())
+    }
+
+    /// Stop watching the backup directory
+    pub async fn stop_watching(&mut self) {
+        // Cancel periodic check task if it's running
+        if let Some(handle) = self.periodic_check_handle.take() {
+            handle.abort();
+        }
+
+        if let Some(watcher) = self.watcher.take() {
+            drop(watcher);
+            eprintln!("[BackupWatcher] Stopped watching backup directory");
+        }
+        self.backup_path = None;
+        {
+            let mut known = self.known_backups.lock().await;
+            known.clear();
+        }
+        {
+            let mut pending = self.pending_folders.lock().await;
+            pending.clear();
+        }
+    }
+
+    /// Process file system event, backup folders that changed become pending and removed ones emit backup-removed
+    async fn process_fs_event(
+        event: Event,
+        app: &AppHandle,
+        backup_path: &Path,
+        known_backups: &Arc<Mutex<HashSet<String>>>,
+        pending_folders: &Arc<Mutex<HashMap<String, Instant>>>,
+    ) {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+
+        let folders: HashSet<String> = event.paths.iter()
+            .filter_map(|p| backup_folder_name(p, backup_path))
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+
+        for folder in folders {
+            let folder_path = backup_path.join(&folder);
+            if find_backup(&folder_path).is_some() {
+                // Created, renamed into place or still being written - reported once it settles
+                let mut pending = pending_folders.lock().await;
+                pending.insert(folder, Instant::now());
+            } else {
+                let mut known = known_backups.lock().await;
+                let mut pending = pending_folders.lock().await;
+                pending.remove(&folder);
+                if known.remove(&folder) {
+                    Self::emit_backup_removed(app, &folder_path, &folder);
+                }
+            }
+        }
+    }
+
+    /// Report pending folders that stopped changing and verify the directory against the known backups
+    /// The verification catches backups added or removed while events were missed
+    async fn check_pending_folders(
+        app: &AppHandle,
+        backup_path: &Path,
+        known_backups: &Arc<Mutex<HashSet<String>>>,
+        pending_folders: &Arc<Mutex<HashMap<String, Instant>>>,
+    ) {
+        let backup_path_clone = backup_path.to_path_buf();
+        let current_folders = match tokio::task::spawn_blocking(move || list_backup_folders(&backup_path_clone)).await {
+            Ok(folders) => folders,
+            Err(e) => {
+                eprintln!("[BackupWatcher] Task error listing backups: {}", e);
+                return;
+            }
+        };
+
+        let mut known = known_backups.lock().await;
+        let mut pending = pending_folders.lock().await;
+
+        for folder in current_folders.iter().filter(|folder| !known.contains(*folder)) {
+            pending.entry(folder.clone()).or_insert_with(Instant::now);
+        }
+
+        let removed: Vec<String> = known.difference(&current_folders).cloned().collect();
+        for folder in removed {
+            known.remove(&folder);
+            pending.remove(&folder);
+            Self::emit_backup_removed(app, &backup_path.join(&folder), &folder);
+        }
+
+        let settled: Vec<String> = pending.iter()
+            .filter(|(_, last_change)| last_change.elapsed() >= SETTLE_DELAY)
+            .map(|(folder, _)| folder.clone())
+            .collect();
+        for folder in settled {
+            pending.remove(&folder);
+            if current_folders.contains(&folder) {
+                // Also re-emitted when an existing backup is replaced, so the UI picks up its new date
+                known.insert(folder.clone());
+                Self::emit_backup_added(app, &backup_path.join(&folder), &folder);
+            }
+        }
+    }
+
+    fn emit_backup_added(app: &AppHandle, backup_path: &Path, folder: &str) {
+        let backup_path = find_backup(backup_path).unwrap_or(backup_path.to_path_buf());
+        let backup_date = std::fs::metadata(&backup_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        eprintln!("[BackupWatcher] Backup added: {}", folder);
+        let _ = app.emit("backup-added", serde_json::json!({
+            "folder": folder,
+            "backupPath": backup_path.to_string_lossy(),
+            "backupDate": backup_date,
+        }));
+    }
+
+    fn emit_backup_removed(app: &AppHandle, backup_path: &Path, folder: &str) {
+        eprintln!("[BackupWatcher] Backup removed: {}", folder);
+        let _ = app.emit("backup-removed", serde_json::json!({
+            "folder": folder,
+            "backupPath": backup_path.to_string_lossy(),
+        }));
+    }
+}
+
+impl Default for BackupWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_folder_name_of_nested_paths() {
+        let backup_path = Path::new("/backups");
+        assert_eq!(backup_folder_name(&backup_path.join("Harmony"), backup_path), Some("Harmony".to_string()));
+        assert_eq!(backup_folder_name(&backup_path.join("Harmony").join("About").join("About.xml"), backup_path), Some("Harmony".to_string()));
+        assert_eq!(backup_folder_name(backup_path, backup_path), None);
+        assert_eq!(backup_folder_name(Path::new("/mods/Harmony"), backup_path), None);
+    }
+
+    #[test]
+    fn test_list_backup_folders_skips_files_and_hidden_folders() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("Harmony")).unwrap();
+        std::fs::create_dir(temp_dir.path().join(".trash")).unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+        std::fs::write(temp_dir.path().join("HugsLib.zip"), "").unwrap();
+
+        let folders = list_backup_folders(temp_dir.path());
+        assert_eq!(folders, HashSet::from(["Harmony".to_string(), "HugsLib".to_string()]));
+        assert!(list_backup_folders(&temp_dir.path().join("missing")).is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use serde::Serialize;
+use crate::core::mod_scanner::BaseMod;
+use crate::core::workshop_client::CollectionMod;
+
+/// Mod listed in a collection diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModSummary {
+    pub mod_id: String,
+    pub title: Option<String>,
+    /// Installed folder, None for mods that are not installed
+    pub mod_path: Option<String>,
+}
+
+/// What installing exactly the mods of a collection would change
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionDiff {
+    /// Mods of the collection that are not installed, in collection order
+    pub to_add: Vec<ModSummary>,
+    pub already_installed: Vec<ModSummary>,
+    /// Installed Workshop mods the collection doesn't contain, candidates for removal
+    pub extra_installed: Vec<ModSummary>,
+}
+
+/// Compare the (flattened) mods of a collection with the installed mods
+/// Local mods without a Workshop ID can't be part of a collection and are never reported as extra
+pub fn diff_collection(collection: &[CollectionMod], installed: &[BaseMod]) -> CollectionDiff {
+    let mut diff = CollectionDiff::default();
+    let collection_ids: HashSet<&str> = collection.iter().map(|m| m.details.publishedfileid.as_str()).collect();
+
+    for collection_mod in collection {
+        let details = &collection_mod.details;
+        let installed_mod = installed.iter().find(|m| !m.non_steam_mod && m.mod_id == details.publishedfileid);
+        let summary = ModSummary {
+            mod_id: details.publishedfileid.clone(),
+            title: Some(details.title.clone()).filter(|title| !title.is_empty()),
+            mod_path: installed_mod.map(|m| m.mod_path.clone()),
+        };
+        match installed_mod {
+            Some(_) => diff.already_installed.push(summary),
+            None => diff.to_add.push(summary),
+        }
+    }
+
+    for installed_mod in installed.iter().filter(|m| !m.non_steam_mod && !collection_ids.contains(m.mod_id.as_str())) {
+        diff.extra_installed.push(ModSummary {
+            mod_id: installed_mod.mod_id.clone(),
+            title: installed_mod.details.as_ref().map(|d| d.title.clone()).or_else(|| installed_mod.folder.clone()),
+            mod_path: Some(installed_mod.mod_path.clone()),
+        });
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use crate::core::mod_scanner::{create_base_mod_from_path, create_workshop_file_details};
+
+    fn collection_mod(mod_id: &str, title: &str) -> CollectionMod {
+        CollectionMod {
+            details: create_workshop_file_details(mod_id, title.to_string(), 0),
+            parent_collection_id: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_collection() {
+        let collection = vec![collection_mod("100", "Harmony"), collection_mod("200", "HugsLib")];
+        let installed = vec![
+            create_base_mod_from_path("200".to_string(), Path::new("/mods/HugsLib"), None, false),
+            create_base_mod_from_path("300".to_string(), Path::new("/mods/Old"), None, false),
+            create_base_mod_from_path("Local".to_string(), Path::new("/mods/Local"), None, true),
+        ];
+
+        let diff = diff_collection(&collection, &installed);
+        assert_eq!(diff.to_add, vec![ModSummary { mod_id: "100".to_string(), title: Some("Harmony".to_string()), mod_path: None }]);
+        assert_eq!(diff.already_installed, vec![ModSummary {
+            mod_id: "200".to_string(),
+            title: Some("HugsLib".to_string()),
+            mod_path: Some(installed[0].mod_path.clone()),
+        }]);
+        assert_eq!(diff.extra_installed.iter().map(|m| m.mod_id.as_str()).collect::<Vec<_>>(), vec!["300"]);
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::core::api_cache::write_json_atomic;
+use crate::core::mod_scanner::find_about_dir;
+
+/// Checksum manifest written into the About folder of installed mods
+pub const CHECKSUM_MANIFEST: &str = ".checksums.json";
+
+/// Files the app writes into the About folder of installed mods itself, excluded from the fingerprint
+/// so an installed copy can be compared with a fresh download
+const APP_MANAGED_FILES: &[&str] = &[
+    ".lastupdated",
+    ".ignoredupdate",
+    "PublishedFileId.txt",
+    CHECKSUM_MANIFEST,
+];
+
+/// Whether a path relative to the mod folder is a file the app manages
+/// RimWorld reads the About folder in any case, so the app writes into `about/` as well
+pub fn is_app_managed_file(relative: &str) -> bool {
+    relative.split_once('/')
+        .is_some_and(|(folder, name)| folder.eq_ignore_ascii_case("About") && APP_MANAGED_FILES.contains(&name))
+}
+
+/// Compute a SHA-256 fingerprint of a mod folder's content
+/// The fingerprint covers relative file paths and file contents, independent of the folder location
+pub fn content_fingerprint(mod_path: &Path) -> Result<String, String> {
+    let mut files = Vec::new();
+    collect_files(mod_path, mod_path, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    for (relative, path) in files {
+        if is_app_managed_file(&relative) {
+            continue;
+        }
+
+        let mut file = fs::File::open(&path)
+            .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+        let size = file.metadata()
+            .map_err(|e| format!("Failed to read metadata of {:?}: {}", path, e))?
+            .len();
+
+        // Path and size delimit each file so content can't shift between entries
+        hasher.update(relative.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(size.to_le_bytes());
+
+        loop {
+            let read = file.read(&mut buffer)
+                .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+    }
+
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Compute the SHA-256 of a single file as a hex string
+pub fn file_sha256(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Check if two mod folders have identical content (ignoring files managed by the app)
+pub fn is_identical_content(a: &Path, b: &Path) -> Result<bool, String> {
+    Ok(content_fingerprint(a)? == content_fingerprint(b)?)
+}
+
+/// SHA-256 of every file of a mod folder, keyed by relative path with '/' separators
+/// Files the app writes itself are left out, they change after the install
+pub fn compute_checksums(mod_path: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut files = Vec::new();
+    collect_files(mod_path, mod_path, &mut files)?;
+    files.into_iter()
+        .filter(|(relative, _)| !is_app_managed_file(relative))
+        .map(|(relative, path)| Ok((relative, file_sha256(&path)?)))
+        .collect()
+}
+
+/// Write the checksum manifest of an installed mod into its About folder
+pub fn write_checksum_manifest(mod_path: &Path) -> Result<(), String> {
+    let checksums = compute_checksums(mod_path)?;
+    write_json_atomic(&find_about_dir(mod_path).join(CHECKSUM_MANIFEST), &checksums)
+}
+
+/// Result of checking an installed mod against its checksum manifest, paths are relative to the mod folder
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub mod_path: String,
+    /// Files listed in the manifest that no longer exist
+    pub missing: Vec<String>,
+    /// Files whose content no longer matches the manifest
+    pub changed: Vec<String>,
+    /// Files not listed in the manifest
+    pub extra: Vec<String>,
+    /// Number of files listed in the manifest
+    pub checked: usize,
+    pub intact: bool,
+}
+
+/// Recompute the checksums of an installed mod and compare them with its manifest
+pub fn verify_mod_checksums(mod_path: &Path) -> Result<VerifyReport, String> {
+    let manifest_path = find_about_dir(mod_path).join(CHECKSUM_MANIFEST);
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read checksum manifest {:?}: {}", manifest_path, e))?;
+    let expected: BTreeMap<String, String> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse checksum manifest {:?}: {}", manifest_path, e))?;
+    let actual = compute_checksums(mod_path)?;
+
+    let mut report = VerifyReport {
+        mod_path: mod_path.to_string_lossy().to_string(),
+        checked: expected.len(),
+        ..Default::default()
+    };
+    for (relative, checksum) in &expected {
+        match actual.get(relative) {
+            None => report.missing.push(relative.clone()),
+            Some(actual_checksum) if actual_checksum != checksum => report.changed.push(relative.clone()),
+            Some(_) => {}
+        }
+    }
+    report.extra = actual.keys()
+        .filter(|relative| !expected.contains_key(*relative))
+        .cloned()
+        .collect();
+    report.intact = report.missing.is_empty() && report.changed.is_empty() && report.extra.is_empty();
+    Ok(report)
+}
+
+/// Recursively collect files as (relative path with '/' separators, absolute path)
+pub fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        let file_type = entry.file_type()
+            .map_err(|e| format!("Failed to read file type of {:?}: {}", path, e))?;
+
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            let relative = path.strip_prefix(root)
+                .map_err(|e| format!("Failed to get relative path: {}", e))?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join("/");
+            files.push((relative, path));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_mod(path: &Path, about: &str) {
+        fs::create_dir_all(path.join("About")).unwrap();
+        fs::create_dir_all(path.join("Defs")).unwrap();
+        fs::write(path.join("About").join("About.xml"), about).unwrap();
+        fs::write(path.join("Defs").join("Things.xml"), "<Defs />").unwrap();
+    }
+
+    #[test]
+    fn test_identical_content_ignores_app_managed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let downloaded = temp_dir.path().join("downloaded");
+        let installed = temp_dir.path().join("installed");
+        create_mod(&downloaded, "<ModMetaData />");
+        create_mod(&installed, "<ModMetaData />");
+        fs::write(installed.join("About").join(".lastupdated"), "1700000000").unwrap();
+        fs::write(installed.join("About").join("PublishedFileId.txt"), "123").unwrap();
+
+        assert!(is_identical_content(&downloaded, &installed).unwrap());
+    }
+
+    #[test]
+    fn test_verify_mod_checksums() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("installed");
+        create_mod(&mod_path, "<ModMetaData />");
+        assert!(verify_mod_checksums(&mod_path).is_err());
+
+        write_checksum_manifest(&mod_path).unwrap();
+        // Files written by the app after the install don't count as changes
+        fs::write(mod_path.join("About").join(".lastupdated"), "1700000000").unwrap();
+        let report = verify_mod_checksums(&mod_path).unwrap();
+        assert!(report.intact);
+        assert_eq!(report.checked, 2);
+
+        fs::write(mod_path.join("Defs").join("Things.xml"), "<Defs><ThingDef /></Defs>").unwrap();
+        fs::remove_file(mod_path.join("About").join("About.xml")).unwrap();
+        fs::write(mod_path.join("Defs").join("New.xml"), "<Defs />").unwrap();
+        let report = verify_mod_checksums(&mod_path).unwrap();
+        assert!(!report.intact);
+        assert_eq!(report.missing, vec!["About/About.xml"]);
+        assert_eq!(report.changed, vec!["Defs/Things.xml"]);
+        assert_eq!(report.extra, vec!["Defs/New.xml"]);
+    }
+
+    #[test]
+    fn test_app_managed_files_in_lowercase_about() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("installed");
+        fs::create_dir_all(mod_path.join("about")).unwrap();
+        fs::write(mod_path.join("about").join("About.xml"), "<ModMetaData />").unwrap();
+
+        write_checksum_manifest(&mod_path).unwrap();
+        assert!(mod_path.join("about").join(CHECKSUM_MANIFEST).exists());
+        fs::write(mod_path.join("about").join(".lastupdated"), "1700000000").unwrap();
+        fs::write(mod_path.join("about").join("PublishedFileId.txt"), "123").unwrap();
+        let report = verify_mod_checksums(&mod_path).unwrap();
+        assert!(report.intact);
+        assert_eq!(report.checked, 1);
+
+        // Only the About folder holds app managed files
+        assert!(!is_app_managed_file("Defs/.lastupdated"));
+        assert!(!is_app_managed_file(".lastupdated"));
+    }
+
+    #[test]
+    fn test_changed_content_differs() {
+        let temp_dir = TempDir::new().unwrap();
+        let downloaded = temp_dir.path().join("downloaded");
+        let installed = temp_dir.path().join("installed");
+        create_mod(&downloaded, "<ModMetaData><name>New</name></ModMetaData>");
+        create_mod(&installed, "<ModMetaData />");
+
+        assert!(!is_identical_content(&downloaded, &installed).unwrap());
+
+        // Extra file in the download
+        create_mod(&installed, "<ModMetaData><name>New</name></ModMetaData>");
+        fs::write(downloaded.join("Defs").join("More.xml"), "").unwrap();
+        assert!(!is_identical_content(&downloaded, &installed).unwrap());
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Priority of a download in the shared SteamCMD instance pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DownloadPriority {
+    /// Downloads the user is waiting for, like a single mod
+    High,
+    /// Bulk downloads, like updating all mods
+    #[default]
+    Normal,
+}
+
+/// Pool of SteamCMD instance slots shared by all downloads
+/// Free slots go to waiting high-priority downloads first, then to normal ones in request order
+pub struct DownloadQueue {
+    state: Mutex<QueueState>,
+}
+
+struct QueueState {
+    capacity: usize,
+    /// Indices of the slots in use, unique among running instances
+    busy: BTreeSet<usize>,
+    high: VecDeque<oneshot::Sender<InstanceSlot>>,
+    normal: VecDeque<oneshot::Sender<InstanceSlot>>,
+}
+
+impl QueueState {
+    fn next_waiter(&mut self) -> Option<oneshot::Sender<InstanceSlot>> {
+        self.high.pop_front().or_else(|| self.normal.pop_front())
+    }
+
+    fn free_index(&self) -> usize {
+        (0..).find(|index| !self.busy.contains(index)).unwrap_or_default()
+    }
+}
+
+/// Permission to run one SteamCMD instance, the slot is handed to the next waiting download when dropped
+pub struct InstanceSlot {
+    index: usize,
+    queue: Option<Arc<DownloadQueue>>,
+}
+
+impl InstanceSlot {
+    /// Index of the slot, no other running instance has the same one
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Drop for InstanceSlot {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.release(self.index);
+        }
+    }
+}
+
+impl DownloadQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                capacity: capacity.max(1),
+                busy: BTreeSet::new(),
+                high: VecDeque::new(),
+                normal: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Change how many instances may run at once
+    /// Running instances are not stopped when shrinking, their slots just aren't handed on
+    pub fn set_capacity(self: &Arc<Self>, capacity: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.capacity = capacity.max(1);
+        while state.busy.len() < state.capacity {
+            let Some(waiter) = state.next_waiter() else { break };
+            let index = state.free_index();
+            state.busy.insert(index);
+            if let Err(mut slot) = waiter.send(InstanceSlot { index, queue: Some(self.clone()) }) {
+                // The download stopped waiting
+                slot.queue = None;
+                state.busy.remove(&index);
+            }
+        }
+    }
+
+    /// Wait for a free instance slot
+    pub async fn acquire(self: &Arc<Self>, priority: DownloadPriority) -> InstanceSlot {
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            // Downloads of the same or higher priority that are already waiting go first
+            let ahead = match priority {
+                DownloadPriority::High => state.high.len(),
+                DownloadPriority::Normal => state.high.len() + state.normal.len(),
+            };
+            if ahead == 0 && state.busy.len() < state.capacity {
+                let index = state.free_index();
+                state.busy.insert(index);
+                return InstanceSlot { index, queue: Some(self.clone()) };
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            match priority {
+                DownloadPriority::High => state.high.push_back(sender),
+                DownloadPriority::Normal => state.normal.push_back(sender),
+            }
+            receiver
+        };
+        // Senders are only dropped after sending, the queue outlives its waiters
+        receiver.await.expect("Download queue dropped a waiting download")
+    }
+
+    fn release(self: &Arc<Self>, index: usize) {
+        let mut state = self.state.lock().unwrap();
+        if state.busy.len() <= state.capacity {
+            while let Some(waiter) = state.next_waiter() {
+                match waiter.send(InstanceSlot { index, queue: Some(self.clone()) }) {
+                    Ok(()) => return,
+                    Err(mut slot) => slot.queue = None,
+                }
+            }
+        }
+        state.busy.remove(&index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn try_acquire(queue: &Arc<DownloadQueue>, priority: DownloadPriority) -> Option<InstanceSlot> {
+        tokio::time::timeout(Duration::from_millis(50), queue.acquire(priority)).await.ok()
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_gets_next_free_slot() {
+        let queue = Arc::new(DownloadQueue::new(1));
+        let bulk = queue.acquire(DownloadPriority::Normal).await;
+        assert_eq!(bulk.index(), 0);
+
+        // A bulk download queued first still waits for the interactive one
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let waiter = |priority| tokio::spawn({
+            let queue = queue.clone();
+            let order = order.clone();
+            async move {
+                let slot = queue.acquire(priority).await;
+                order.lock().unwrap().push((priority, slot.index()));
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        });
+        let normal_waiter = waiter(DownloadPriority::Normal);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let high_waiter = waiter(DownloadPriority::High);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(order.lock().unwrap().is_empty());
+
+        drop(bulk);
+        high_waiter.await.unwrap();
+        normal_waiter.await.unwrap();
+        assert_eq!(*order.lock().unwrap(), vec![(DownloadPriority::High, 0), (DownloadPriority::Normal, 0)]);
+        assert!(try_acquire(&queue, DownloadPriority::Normal).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_capacity_changes() {
+        let queue = Arc::new(DownloadQueue::new(1));
+        let first = queue.acquire(DownloadPriority::Normal).await;
+        assert!(try_acquire(&queue, DownloadPriority::High).await.is_none());
+
+        // Abandoned waiters don't take a slot
+        queue.set_capacity(2);
+        let second = try_acquire(&queue, DownloadPriority::Normal).await.unwrap();
+        assert_eq!((first.index(), second.index()), (0, 1));
+
+        // Slots of running instances are not handed on while over capacity
+        queue.set_capacity(1);
+        drop(first);
+        assert!(try_acquire(&queue, DownloadPriority::High).await.is_none());
+        drop(second);
+        assert_eq!(try_acquire(&queue, DownloadPriority::Normal).await.unwrap().index(), 0);
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, EventId, Listener};
+use tokio::sync::Notify;
+use crate::core::mod_lifecycle::{ModLifecycleEvent, ModPhase};
+
+/// How often the summary is emitted while downloads are running
+const SUMMARY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Throughput for the ETA is measured over this much recent history
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(15);
+
+/// The current speed is averaged over this much recent history
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+/// Shorter spans are ignored for the speed, samples taken right after each other would give spikes
+const MIN_SPEED_SPAN: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModDownloadState {
+    Queued,
+    InProgress,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+struct ModProgress {
+    state: ModDownloadState,
+    bytes_downloaded: u64,
+    bytes_total: u64,
+}
+
+/// Overall progress of a download run, emitted as `download-summary`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSummarySnapshot {
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub in_progress: usize,
+    pub queued: usize,
+    pub bytes_total: u64,
+    pub bytes_downloaded: u64,
+    /// Rough estimate from recent throughput, None until there is enough data
+    pub eta_secs: Option<u64>,
+    /// Combined speed of all instances over the last few seconds
+    pub bytes_per_sec: u64,
+    /// Highest speed seen in this run
+    pub peak_bytes_per_sec: u64,
+    pub elapsed_secs: u64,
+}
+
+/// Counters of a download run, fed by the lifecycle events of its mods
+pub struct DownloadSummary {
+    mods: HashMap<String, ModProgress>,
+    samples: VecDeque<(Instant, u64)>,
+    /// Time of the first snapshot, the start of the run
+    started: Option<Instant>,
+    peak_bytes_per_sec: u64,
+}
+
+impl DownloadSummary {
+    /// Start a summary with every mod queued, using Workshop sizes as totals where known
+    pub fn new(mod_ids: &[String], mod_sizes: Option<&HashMap<String, u64>>) -> Self {
+        let mods = mod_ids.iter()
+            .map(|mod_id| {
+                let bytes_total = mod_sizes.and_then(|sizes| sizes.get(mod_id)).copied().unwrap_or(0);
+                (mod_id.clone(), ModProgress {
+                    state: ModDownloadState::Queued,
+                    bytes_downloaded: 0,
+                    bytes_total,
+                })
+            })
+            .collect();
+        Self {
+            mods,
+            samples: VecDeque::new(),
+            started: None,
+            peak_bytes_per_sec: 0,
+        }
+    }
+
+    /// Apply a lifecycle event of a mod
+    /// Returns true if the mod moved to another state
+    pub fn apply(&mut self, mod_id: &str, phase: ModPhase, detail: &Value) -> bool {
+        let Some(progress) = self.mods.get_mut(mod_id) else {
+            return false;
+        };
+        // A downloaded mod stays completed, whatever happens during its install
+        if progress.state == ModDownloadState::Completed {
+            return false;
+        }
+
+        let state = match phase {
+            ModPhase::Queued | ModPhase::RetryQueued => ModDownloadState::Queued,
+            ModPhase::Downloading | ModPhase::DownloadProgress => ModDownloadState::InProgress,
+            ModPhase::TimedOut | ModPhase::Failed | ModPhase::Cancelled => ModDownloadState::Failed,
+            ModPhase::Downloaded
+            | ModPhase::BackingUp
+            | ModPhase::Installing
+            | ModPhase::InstallProgress
+            | ModPhase::Installed => ModDownloadState::Completed,
+        };
+
+        if phase == ModPhase::DownloadProgress {
+            if let Some(total) = detail["bytesTotal"].as_u64().filter(|t| *t > 0) {
+                progress.bytes_total = total;
+            }
+            if let Some(downloaded) = detail["bytesDownloaded"].as_u64() {
+                progress.bytes_downloaded = downloaded;
+            }
+        }
+        if state == ModDownloadState::Completed {
+            progress.bytes_downloaded = progress.bytes_downloaded.max(progress.bytes_total);
+        }
+
+        let changed = progress.state != state;
+        progress.state = state;
+        changed
+    }
+
+    /// Current counters, recording the downloaded bytes for the throughput estimate
+    pub fn snapshot(&mut self, now: Instant) -> DownloadSummarySnapshot {
+        let count = |state| self.mods.values().filter(|m| m.state == state).count();
+        let bytes_total: u64 = self.mods.values().map(|m| m.bytes_total.max(m.bytes_downloaded)).sum();
+        let bytes_downloaded: u64 = self.mods.values().map(|m| m.bytes_downloaded).sum();
+
+        self.samples.push_back((now, bytes_downloaded));
+        while self.samples.len() > 2 && self.samples.front().is_some_and(|(t, _)| now.duration_since(*t) > THROUGHPUT_WINDOW) {
+            self.samples.pop_front();
+        }
+
+        let remaining = bytes_total.saturating_sub(bytes_downloaded);
+        let eta_secs = match (self.samples.front(), self.samples.back()) {
+            _ if remaining == 0 => Some(0),
+            (Some((first_time, first_bytes)), Some((last_time, last_bytes))) => {
+                let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+                let rate = last_bytes.saturating_sub(*first_bytes) as f64 / elapsed;
+                (elapsed > 0.0 && rate > 0.0).then(|| (remaining as f64 / rate).ceil() as u64)
+            }
+            _ => None,
+        };
+
+        let bytes_per_sec = self.recent_speed();
+        self.peak_bytes_per_sec = self.peak_bytes_per_sec.max(bytes_per_sec);
+        let started = *self.started.get_or_insert(now);
+
+        DownloadSummarySnapshot {
+            total: self.mods.len(),
+            completed: count(ModDownloadState::Completed),
+            failed: count(ModDownloadState::Failed),
+            in_progress: count(ModDownloadState::InProgress),
+            queued: count(ModDownloadState::Queued),
+            bytes_total,
+            bytes_downloaded,
+            eta_secs,
+            bytes_per_sec,
+            peak_bytes_per_sec: self.peak_bytes_per_sec,
+            elapsed_secs: now.duration_since(started).as_secs(),
+        }
+    }
+
+    /// Bytes per second between the oldest sample within SPEED_WINDOW and the newest one
+    fn recent_speed(&self) -> u64 {
+        let Some((last_time, last_bytes)) = self.samples.back() else {
+            return 0;
+        };
+        let first = self.samples.iter().find(|(time, _)| last_time.duration_since(*time) <= SPEED_WINDOW);
+        match first {
+            Some((first_time, first_bytes)) if last_time.duration_since(*first_time) >= MIN_SPEED_SPAN => {
+                let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+                (last_bytes.saturating_sub(*first_bytes) as f64 / elapsed) as u64
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Emits `download-summary` events for the mods of a download run until dropped
+/// The summary follows the `mod-lifecycle` events of those mods, so it never misses a state change
+/// that was reported to the UI; it is emitted on every transition and once per second
+pub struct DownloadSummaryReporter {
+    app: AppHandle,
+    summary: Arc<Mutex<DownloadSummary>>,
+    listener: EventId,
+    done: Arc<AtomicBool>,
+    changed: Arc<Notify>,
+}
+
+impl DownloadSummaryReporter {
+    pub fn start(app: &AppHandle, mod_ids: &[String], mod_sizes: Option<&HashMap<String, u64>>) -> Self {
+        let summary = Arc::new(Mutex::new(DownloadSummary::new(mod_ids, mod_sizes)));
+        let done = Arc::new(AtomicBool::new(false));
+        let changed = Arc::new(Notify::new());
+
+        let listener = {
+            let summary = summary.clone();
+            let changed = changed.clone();
+            app.listen("mod-lifecycle", move |event| {
+                let Ok(event) = serde_json::from_str::<ModLifecycleEvent>(event.payload()) else {
+                    return;
+                };
+                if summary.lock().unwrap().apply(&event.mod_id, event.phase, &event.detail) {
+                    changed.notify_one();
+                }
+            })
+        };
+
+        // Emitting from a separate task keeps the event listener short
+        {
+            let app = app.clone();
+            let summary = summary.clone();
+            let done = done.clone();
+            let changed = changed.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        _ = changed.notified() => {}
+                        _ = tokio::time::sleep(SUMMARY_INTERVAL) => {}
+                    }
+                    if done.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let snapshot = summary.lock().unwrap().snapshot(Instant::now());
+                    let _ = app.emit("download-summary", &snapshot);
+                }
+            });
+        }
+
+        let reporter = Self {
+            app: app.clone(),
+            summary,
+            listener,
+            done,
+            changed,
+        };
+        reporter.emit_now();
+        reporter
+    }
+
+    fn emit_now(&self) {
+        let snapshot = self.summary.lock().unwrap().snapshot(Instant::now());
+        let _ = self.app.emit("download-summary", &snapshot);
+    }
+}
+
+impl Drop for DownloadSummaryReporter {
+    fn drop(&mut self) {
+        self.app.unlisten(self.listener);
+        self.done.store(true, Ordering::Relaxed);
+        self.changed.notify_one();
+        // Final counters once the run is over
+        self.emit_now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mod_ids() -> Vec<String> {
+        vec!["1".to_string(), "2".to_string(), "3".to_string()]
+    }
+
+    #[test]
+    fn test_summary_counts_transitions() {
+        let sizes: HashMap<String, u64> = [("1".to_string(), 100), ("2".to_string(), 300)].into_iter().collect();
+        let mut summary = DownloadSummary::new(&mod_ids(), Some(&sizes));
+        let now = Instant::now();
+
+        let snapshot = summary.snapshot(now);
+        assert_eq!((snapshot.total, snapshot.queued), (3, 3));
+        assert_eq!(snapshot.bytes_total, 400);
+
+        assert!(summary.apply("1", ModPhase::Downloading, &Value::Null));
+        assert!(!summary.apply("1", ModPhase::DownloadProgress, &serde_json::json!({
+            "bytesDownloaded": 50,
+            "bytesTotal": 100,
+        })));
+        assert!(summary.apply("2", ModPhase::Failed, &Value::Null));
+        assert!(summary.apply("3", ModPhase::Downloaded, &Value::Null));
+        // Unknown mods and installs of downloaded mods don't change the counters
+        assert!(!summary.apply("4", ModPhase::Downloading, &Value::Null));
+        assert!(!summary.apply("3", ModPhase::Failed, &Value::Null));
+
+        let snapshot = summary.snapshot(now);
+        assert_eq!(snapshot.in_progress, 1);
+        assert_eq!(snapshot.failed, 1);
+        assert_eq!(snapshot.completed, 1);
+        assert_eq!(snapshot.queued, 0);
+        assert_eq!(snapshot.bytes_downloaded, 50);
+
+        // A retry moves a failed mod back to the queue
+        assert!(summary.apply("2", ModPhase::RetryQueued, &Value::Null));
+        assert_eq!(summary.snapshot(now).queued, 1);
+    }
+
+    #[test]
+    fn test_summary_eta_from_throughput() {
+        let sizes: HashMap<String, u64> = [("1".to_string(), 1000)].into_iter().collect();
+        let mut summary = DownloadSummary::new(&["1".to_string()], Some(&sizes));
+        let start = Instant::now();
+
+        assert_eq!(summary.snapshot(start).eta_secs, None);
+
+        summary.apply("1", ModPhase::DownloadProgress, &serde_json::json!({ "bytesDownloaded": 200 }));
+        let snapshot = summary.snapshot(start + Duration::from_secs(2));
+        // 200 bytes in 2 seconds, 800 left
+        assert_eq!(snapshot.eta_secs, Some(8));
+
+        summary.apply("1", ModPhase::Downloaded, &Value::Null);
+        assert_eq!(summary.snapshot(start + Duration::from_secs(3)).eta_secs, Some(0));
+    }
+
+    #[test]
+    fn test_summary_speed_metrics() {
+        let mut summary = DownloadSummary::new(&mod_ids(), None);
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let progress = |bytes: u64| serde_json::json!({ "bytesDownloaded": bytes });
+
+        assert_eq!(summary.snapshot(start).bytes_per_sec, 0);
+        summary.apply("1", ModPhase::DownloadProgress, &progress(1000));
+        // Too short a span to tell a speed
+        assert_eq!(summary.snapshot(start + Duration::from_millis(100)).bytes_per_sec, 0);
+
+        // Both instances count, 4000 bytes in 2 seconds
+        summary.apply("1", ModPhase::DownloadProgress, &progress(2000));
+        summary.apply("2", ModPhase::DownloadProgress, &progress(2000));
+        let snapshot = summary.snapshot(at(2));
+        assert_eq!((snapshot.bytes_per_sec, snapshot.peak_bytes_per_sec), (2000, 2000));
+
+        // Samples older than the window no longer count, the peak stays
+        summary.snapshot(at(7));
+        let snapshot = summary.snapshot(at(10));
+        assert_eq!((snapshot.bytes_per_sec, snapshot.peak_bytes_per_sec), (0, 2000));
+        assert_eq!(snapshot.elapsed_secs, 10);
+    }
+}
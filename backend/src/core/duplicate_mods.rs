@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use serde::Serialize;
+use crate::core::backup_retention::dir_size;
+use crate::core::mod_manager::ModUpdater;
+use crate::core::mod_scanner::{get_mod_last_updated_time, is_staging_mods_folder, query_mod_id};
+
+/// What the folders of a duplicate group have in common
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateKind {
+    PublishedFileId,
+    PackageId,
+}
+
+/// An installed copy of a duplicated mod
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateFolder {
+    pub mod_path: String,
+    pub folder: String,
+    pub size: u64,
+    /// Last update in seconds since the Unix epoch, from `.lastupdated` when present
+    pub last_updated: Option<u64>,
+}
+
+/// Mod folders that hold the same mod
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub kind: DuplicateKind,
+    /// The shared publishedFileId or (lowercase) packageId
+    pub key: String,
+    pub folders: Vec<DuplicateFolder>,
+    /// Path of the folder updated most recently
+    pub newest: Option<String>,
+}
+
+fn describe_folder(path: &Path) -> DuplicateFolder {
+    DuplicateFolder {
+        mod_path: path.to_string_lossy().to_string(),
+        folder: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        size: dir_size(path),
+        last_updated: get_mod_last_updated_time(path).ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs()),
+    }
+}
+
+fn build_group(kind: DuplicateKind, key: String, paths: &[PathBuf]) -> DuplicateGroup {
+    let folders: Vec<DuplicateFolder> = paths.iter().map(|p| describe_folder(p)).collect();
+    let newest = folders.iter()
+        .filter(|f| f.last_updated.is_some())
+        .max_by_key(|f| f.last_updated)
+        .map(|f| f.mod_path.clone());
+    DuplicateGroup { kind, key, folders, newest }
+}
+
+/// Find mods installed more than once in a mods folder, in a single pass over all folders
+/// Folders are grouped by publishedFileId and by packageId; a packageId group holding exactly
+/// the folders of a publishedFileId group is not reported twice
+pub fn find_duplicate_mods(mods_path: &Path) -> Result<Vec<DuplicateGroup>, String> {
+    let folders = mod_folders(mods_path)?;
+    let by_mod_id = group_folders(&folders, |path| query_mod_id(path).ok().flatten());
+    let by_package_id = group_folders(&folders, package_id_key);
+
+    let mut groups = Vec::new();
+    let mut reported: HashSet<Vec<PathBuf>> = HashSet::new();
+    let candidates = by_mod_id.into_iter().map(|(key, paths)| (DuplicateKind::PublishedFileId, key, paths))
+        .chain(by_package_id.into_iter().map(|(key, paths)| (DuplicateKind::PackageId, key, paths)));
+    for (kind, key, paths) in candidates {
+        if !reported.insert(paths.clone()) {
+            continue;
+        }
+        groups.push(build_group(kind, key, &paths));
+    }
+
+    Ok(groups)
+}
+
+/// Find packageIds declared by more than one installed mod folder, which RimWorld refuses to load together
+/// Unlike `find_duplicate_mods` the publishedFileId is not considered, local forks often change it but keep the packageId
+/// Returns the lowercase packageId with the sorted folders declaring it
+pub fn find_packageid_collisions(mods_path: &Path) -> Result<Vec<(String, Vec<PathBuf>)>, String> {
+    let folders = mod_folders(mods_path)?;
+    Ok(group_folders(&folders, package_id_key).into_iter().collect())
+}
+
+/// Mod folders in the mods directory, without the folders of interrupted installs
+fn mod_folders(mods_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    Ok(entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !is_staging_mods_folder(path))
+        .collect())
+}
+
+/// RimWorld compares packageIds case-insensitively
+fn package_id_key(path: &Path) -> Option<String> {
+    ModUpdater::get_package_id(path).map(|package_id| package_id.to_lowercase())
+}
+
+/// Folders sharing a key, with the folders of each group sorted
+/// Keys of a single folder are left out
+fn group_folders(folders: &[PathBuf], key_of: impl Fn(&Path) -> Option<String>) -> BTreeMap<String, Vec<PathBuf>> {
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in folders {
+        if let Some(key) = key_of(path) {
+            groups.entry(key).or_default().push(path.clone());
+        }
+    }
+    groups.retain(|_, paths| paths.len() > 1);
+    for paths in groups.values_mut() {
+        paths.sort();
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_mod(mods_path: &Path, folder: &str, mod_id: Option<&str>, package_id: &str, last_updated: Option<i64>) {
+        let about = mods_path.join(folder).join("About");
+        fs::create_dir_all(&about).unwrap();
+        fs::write(about.join("About.xml"), format!("<ModMetaData><packageId>{}</packageId></ModMetaData>", package_id)).unwrap();
+        if let Some(mod_id) = mod_id {
+            fs::write(about.join("PublishedFileId.txt"), mod_id).unwrap();
+        }
+        if let Some(timestamp) = last_updated {
+            fs::write(about.join(".lastupdated"), timestamp.to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_duplicate_mods() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        create_mod(mods_path, "Harmony", Some("2009463077"), "brrainz.harmony", Some(1_000));
+        create_mod(mods_path, "Harmony copy", Some("2009463077"), "brrainz.harmony", Some(2_000));
+        create_mod(mods_path, "Local Mod", None, "Author.Local", None);
+        create_mod(mods_path, "Local Mod (2)", None, "author.local", None);
+        create_mod(mods_path, "Unique", Some("111"), "author.unique", None);
+        // A version set aside by an interrupted install is not a mod
+        create_mod(mods_path, ".Unique.old", Some("111"), "author.unique", None);
+
+        let groups = find_duplicate_mods(mods_path).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let by_id = groups.iter().find(|g| g.kind == DuplicateKind::PublishedFileId).unwrap();
+        assert_eq!(by_id.key, "2009463077");
+        assert_eq!(by_id.folders.len(), 2);
+        assert!(by_id.newest.as_deref().unwrap().ends_with("Harmony copy"));
+
+        let by_package = groups.iter().find(|g| g.kind == DuplicateKind::PackageId).unwrap();
+        assert_eq!(by_package.key, "author.local");
+        assert_eq!(by_package.folders.len(), 2);
+    }
+
+    #[test]
+    fn test_find_packageid_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        create_mod(mods_path, "Harmony", Some("2009463077"), "brrainz.harmony", None);
+        create_mod(mods_path, "Harmony Fork", Some("999"), "Brrainz.Harmony", None);
+        create_mod(mods_path, "Unique", Some("111"), "author.unique", None);
+
+        assert_eq!(find_packageid_collisions(mods_path).unwrap(), vec![
+            ("brrainz.harmony".to_string(), vec![mods_path.join("Harmony"), mods_path.join("Harmony Fork")]),
+        ]);
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Download failure history for a single mod
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureRecord {
+    pub count: u32,
+    pub last_error: String,
+    pub last_attempt: i64,
+    #[serde(default)]
+    pub errors: HashMap<String, u32>,
+}
+
+impl FailureRecord {
+    /// Error that was reported most often for this mod
+    pub fn most_common_error(&self) -> Option<&str> {
+        self.errors
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(error, _)| error.as_str())
+    }
+}
+
+/// Mod whose download keeps failing, with suggested manual steps
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemMod {
+    pub mod_id: String,
+    pub failure_count: u32,
+    pub last_error: String,
+    pub last_attempt: i64,
+    pub most_common_error: String,
+    pub suggestions: Vec<String>,
+}
+
+/// Per-mod download failure history, persisted across sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailureHistory {
+    #[serde(flatten)]
+    records: HashMap<String, FailureRecord>,
+}
+
+impl FailureHistory {
+    /// Record a failed download attempt for a mod
+    pub fn record_failure(&mut self, mod_id: &str, error: &str, timestamp: i64) {
+        let record = self.records.entry(mod_id.to_string()).or_default();
+        record.count += 1;
+        record.last_error = error.to_string();
+        record.last_attempt = timestamp;
+        *record.errors.entry(error.to_string()).or_insert(0) += 1;
+    }
+
+    /// Forget the failure history of a mod (e.g. after a successful download)
+    /// Returns true if the mod had any recorded failures
+    pub fn clear(&mut self, mod_id: &str) -> bool {
+        self.records.remove(mod_id).is_some()
+    }
+
+    /// Get the failure record of a mod
+    pub fn get(&self, mod_id: &str) -> Option<&FailureRecord> {
+        self.records.get(mod_id)
+    }
+
+    /// Get mods that failed at least `min_failures` times, most failures first
+    pub fn problem_mods(&self, min_failures: u32) -> Vec<ProblemMod> {
+        let mut problem_mods: Vec<ProblemMod> = self.records
+            .iter()
+            .filter(|(_, record)| record.count >= min_failures)
+            .map(|(mod_id, record)| {
+                let most_common_error = record.most_common_error()
+                    .unwrap_or(&record.last_error)
+                    .to_string();
+                ProblemMod {
+                    mod_id: mod_id.clone(),
+                    failure_count: record.count,
+                    last_error: record.last_error.clone(),
+                    last_attempt: record.last_attempt,
+                    suggestions: suggestions_for_error(&most_common_error),
+                    most_common_error,
+                }
+            })
+            .collect();
+
+        problem_mods.sort_by(|a, b| b.failure_count.cmp(&a.failure_count).then_with(|| a.mod_id.cmp(&b.mod_id)));
+        problem_mods
+    }
+}
+
+/// Suggest manual steps based on the kind of error a mod keeps failing with
+pub fn suggestions_for_error(error: &str) -> Vec<String> {
+    let error_lower = error.to_lowercase();
+    let mut suggestions = Vec::new();
+
+    if error_lower.contains("timeout") {
+        suggestions.push("The mod may be very large - try downloading it on its own with a single SteamCMD instance".to_string());
+        suggestions.push("Try again later or from a different network, Steam content servers may be slow".to_string());
+    } else if error_lower.contains("incomplete") {
+        suggestions.push("Try validate mode so SteamCMD re-checks and repairs the downloaded files".to_string());
+        suggestions.push("Check that there is enough free disk space for the download".to_string());
+    } else if error_lower.contains("steamcmd") || error_lower.contains("failed for mod") {
+        suggestions.push("Check that the mod is still public on the Steam Workshop".to_string());
+        suggestions.push("Try a different content server by restarting the download later".to_string());
+    } else {
+        suggestions.push("Try updating the mod again on its own".to_string());
+    }
+
+    suggestions.push("Download the mod manually via the browser from its Steam Workshop page".to_string());
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_failure_counts_errors() {
+        let mut history = FailureHistory::default();
+        history.record_failure("123", "Download timeout for mod 123", 10);
+        history.record_failure("123", "Download timeout for mod 123", 20);
+        history.record_failure("123", "Download incomplete for mod 123", 30);
+
+        let record = history.get("123").unwrap();
+        assert_eq!(record.count, 3);
+        assert_eq!(record.last_error, "Download incomplete for mod 123");
+        assert_eq!(record.last_attempt, 30);
+        assert_eq!(record.most_common_error(), Some("Download timeout for mod 123"));
+    }
+
+    #[test]
+    fn test_problem_mods_threshold_and_order() {
+        let mut history = FailureHistory::default();
+        history.record_failure("111", "Download timeout for mod 111", 1);
+        history.record_failure("222", "Download incomplete for mod 222", 1);
+        history.record_failure("222", "Download incomplete for mod 222", 2);
+        history.record_failure("333", "Download failed for mod 333", 1);
+        history.record_failure("333", "Download failed for mod 333", 2);
+        history.record_failure("333", "Download failed for mod 333", 3);
+
+        let problem_mods = history.problem_mods(2);
+        let ids: Vec<&str> = problem_mods.iter().map(|m| m.mod_id.as_str()).collect();
+        assert_eq!(ids, vec!["333", "222"]);
+        assert!(problem_mods[1].suggestions.iter().any(|s| s.contains("validate")));
+    }
+
+    #[test]
+    fn test_clear_removes_history() {
+        let mut history = FailureHistory::default();
+        history.record_failure("123", "Download timeout for mod 123", 1);
+
+        assert!(history.clear("123"));
+        assert!(history.get("123").is_none());
+        assert!(!history.clear("123"));
+    }
+
+    #[test]
+    fn test_history_roundtrip_json() {
+        let mut history = FailureHistory::default();
+        history.record_failure("123", "Download timeout for mod 123", 1);
+
+        let value = serde_json::to_value(&history).unwrap();
+        assert_eq!(value["123"]["count"], 1);
+
+        let restored: FailureHistory = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.get("123").unwrap().count, 1);
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::core::api_cache::write_json_atomic;
+use crate::core::content_fingerprint::{collect_files, file_sha256};
+
+/// Index of an incremental backup, its presence marks a backup folder as incremental
+pub const INCREMENTAL_INDEX_FILE: &str = "incremental-backup.json";
+const MANIFEST_FILE: &str = "manifest.json";
+const FILES_DIR: &str = "files";
+
+/// Versions of an incremental backup, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupIndex {
+    versions: Vec<u32>,
+}
+
+/// A file of a backed-up mod and the version folder holding its content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFileEntry {
+    pub size: u64,
+    pub sha256: String,
+    pub stored_in: u32,
+}
+
+/// Complete state of a mod at one backup version
+/// Only files that changed since the previous version are stored in the version's own folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: u64,
+    /// Files keyed by path relative to the mod folder, with '/' separators
+    pub files: BTreeMap<String, BackupFileEntry>,
+}
+
+impl BackupManifest {
+    /// Number of files stored in this version's folder (the rest is shared with older versions)
+    pub fn stored_files(&self) -> usize {
+        self.files.values().filter(|f| f.stored_in == self.version).count()
+    }
+}
+
+/// Check if a backup folder holds an incremental backup
+pub fn is_incremental_backup(backup_path: &Path) -> bool {
+    backup_path.join(INCREMENTAL_INDEX_FILE).is_file()
+}
+
+fn version_dir(backup_path: &Path, version: u32) -> PathBuf {
+    backup_path.join(format!("v{}", version))
+}
+
+fn read_index(backup_path: &Path) -> Result<BackupIndex, String> {
+    let content = fs::read_to_string(backup_path.join(INCREMENTAL_INDEX_FILE))
+        .map_err(|e| format!("Failed to read backup index: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse backup index: {}", e))
+}
+
+fn read_manifest(backup_path: &Path, version: u32) -> Result<BackupManifest, String> {
+    let content = fs::read_to_string(version_dir(backup_path, version).join(MANIFEST_FILE))
+        .map_err(|e| format!("Failed to read manifest of backup version {}: {}", version, e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse manifest of backup version {}: {}", version, e))
+}
+
+/// Resolve a manifest path below `root`, refusing anything that could escape it
+fn resolve_relative(root: &Path, relative: &str) -> Result<PathBuf, String> {
+    let relative_path = Path::new(relative);
+    if relative.is_empty() || !relative_path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("Invalid path in backup manifest: {:?}", relative));
+    }
+    Ok(root.join(relative_path))
+}
+
+/// Get the manifest of the newest version of an incremental backup
+pub fn latest_manifest(backup_path: &Path) -> Result<Option<BackupManifest>, String> {
+    match read_index(backup_path)?.versions.last() {
+        Some(&version) => read_manifest(backup_path, version).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Add a version with the current state of a mod to its incremental backup
+/// Files whose size and SHA-256 match the previous version are not copied again
+/// A full backup at `backup_path` is replaced by a new incremental one
+pub fn create_incremental_backup(mod_path: &Path, backup_path: &Path) -> Result<BackupManifest, String> {
+    if backup_path.exists() && !is_incremental_backup(backup_path) {
+        fs::remove_dir_all(backup_path)
+            .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+    }
+    fs::create_dir_all(backup_path)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    let mut index = if is_incremental_backup(backup_path) {
+        read_index(backup_path)?
+    } else {
+        BackupIndex::default()
+    };
+    let previous = match index.versions.last() {
+        Some(&version) => Some(read_manifest(backup_path, version)?),
+        None => None,
+    };
+    let version = index.versions.last().map_or(1, |v| v + 1);
+    let version_path = version_dir(backup_path, version);
+    // Leftover of an interrupted backup
+    if version_path.exists() {
+        fs::remove_dir_all(&version_path)
+            .map_err(|e| format!("Failed to remove incomplete backup version: {}", e))?;
+    }
+    let files_path = version_path.join(FILES_DIR);
+
+    let mut files = Vec::new();
+    collect_files(mod_path, mod_path, &mut files)?;
+
+    let mut manifest = BackupManifest {
+        version,
+        created_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        files: BTreeMap::new(),
+    };
+    for (relative, path) in files {
+        let size = fs::metadata(&path)
+            .map_err(|e| format!("Failed to read metadata of {:?}: {}", path, e))?
+            .len();
+        let sha256 = file_sha256(&path)?;
+
+        let unchanged = previous.as_ref()
+            .and_then(|p| p.files.get(&relative))
+            .filter(|entry| entry.size == size && entry.sha256 == sha256);
+        let stored_in = match unchanged {
+            Some(entry) => entry.stored_in,
+            None => {
+                let destination = resolve_relative(&files_path, &relative)?;
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create directory {:?}: {}", parent, e))?;
+                }
+                fs::copy(&path, &destination)
+                    .map_err(|e| format!("Failed to back up {:?}: {}", path, e))?;
+                version
+            }
+        };
+        manifest.files.insert(relative, BackupFileEntry { size, sha256, stored_in });
+    }
+
+    // The manifest and then the index are written last, so an interrupted backup leaves the previous version intact
+    fs::create_dir_all(&version_path)
+        .map_err(|e| format!("Failed to create backup version directory: {}", e))?;
+    write_json_atomic(&version_path.join(MANIFEST_FILE), &manifest)?;
+    index.versions.push(version);
+    write_json_atomic(&backup_path.join(INCREMENTAL_INDEX_FILE), &index)?;
+
+    Ok(manifest)
+}
+
+/// Rebuild the newest version of an incremental backup at `destination`
+/// Each file is taken from the version folder that last stored it
+pub fn restore_incremental_backup(backup_path: &Path, destination: &Path) -> Result<BackupManifest, String> {
+    let manifest = latest_manifest(backup_path)?
+        .ok_or_else(|| "Backup has no versions".to_string())?;
+
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create directory {:?}: {}", destination, e))?;
+    for (relative, entry) in &manifest.files {
+        let source = resolve_relative(&version_dir(backup_path, entry.stored_in).join(FILES_DIR), relative)?;
+        let target = resolve_relative(destination, relative)?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {:?}: {}", parent, e))?;
+        }
+        fs::copy(&source, &target)
+            .map_err(|e| format!("Failed to restore {:?}: {}", relative, e))?;
+    }
+
+    Ok(manifest)
+}
+
+/// Drop the newest version of an incremental backup, the previous one becomes the newest
+/// Files of older versions are kept since they never reference newer folders
+/// Returns the number of versions left; the backup folder is removed along with the last one
+pub fn remove_latest_version(backup_path: &Path) -> Result<usize, String> {
+    let mut index = read_index(backup_path)?;
+    if let Some(version) = index.versions.pop() {
+        if index.versions.is_empty() {
+            fs::remove_dir_all(backup_path)
+                .map_err(|e| format!("Failed to delete backup: {}", e))?;
+            return Ok(0);
+        }
+        // Update the index first so a failed removal leaves an unreferenced folder, not a broken index
+        write_json_atomic(&backup_path.join(INCREMENTAL_INDEX_FILE), &index)?;
+        fs::remove_dir_all(version_dir(backup_path, version))
+            .map_err(|e| format!("Failed to delete backup version {}: {}", version, e))?;
+    }
+    Ok(index.versions.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn read_tree(root: &Path) -> BTreeMap<String, String> {
+        let mut files = Vec::new();
+        collect_files(root, root, &mut files).unwrap();
+        files.into_iter()
+            .map(|(relative, path)| (relative, fs::read_to_string(path).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_incremental_backup_stores_only_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("mod");
+        let backup_path = temp_dir.path().join("backup").join("mod");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "v1").unwrap();
+        fs::write(mod_path.join("big.dll"), "unchanged").unwrap();
+        fs::write(mod_path.join("old.txt"), "removed later").unwrap();
+
+        let first = create_incremental_backup(&mod_path, &backup_path).unwrap();
+        assert_eq!(first.version, 1);
+        assert_eq!(first.stored_files(), 3);
+        let first_state = read_tree(&mod_path);
+
+        fs::write(mod_path.join("About").join("About.xml"), "v2").unwrap();
+        fs::remove_file(mod_path.join("old.txt")).unwrap();
+        fs::write(mod_path.join("new.txt"), "added").unwrap();
+
+        let second = create_incremental_backup(&mod_path, &backup_path).unwrap();
+        assert_eq!(second.version, 2);
+        assert_eq!(second.stored_files(), 2);
+        assert_eq!(second.files["big.dll"].stored_in, 1);
+        assert!(!version_dir(&backup_path, 2).join(FILES_DIR).join("big.dll").exists());
+
+        let restored = temp_dir.path().join("restored");
+        restore_incremental_backup(&backup_path, &restored).unwrap();
+        assert_eq!(read_tree(&restored), read_tree(&mod_path));
+
+        assert_eq!(remove_latest_version(&backup_path).unwrap(), 1);
+        let restored_first = temp_dir.path().join("restored-first");
+        restore_incremental_backup(&backup_path, &restored_first).unwrap();
+        assert_eq!(read_tree(&restored_first), first_state);
+
+        assert_eq!(remove_latest_version(&backup_path).unwrap(), 0);
+        assert!(!backup_path.exists());
+    }
+
+    #[test]
+    fn test_incremental_backup_replaces_full_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("mod");
+        let backup_path = temp_dir.path().join("backup");
+        fs::create_dir_all(&mod_path).unwrap();
+        fs::write(mod_path.join("a.txt"), "a").unwrap();
+        fs::create_dir_all(&backup_path).unwrap();
+        fs::write(backup_path.join("stale.txt"), "full copy").unwrap();
+
+        create_incremental_backup(&mod_path, &backup_path).unwrap();
+        assert!(is_incremental_backup(&backup_path));
+        assert!(!backup_path.join("stale.txt").exists());
+    }
+
+    #[test]
+    fn test_resolve_relative_rejects_escapes() {
+        let root = Path::new("root");
+        assert!(resolve_relative(root, "About/About.xml").is_ok());
+        assert!(resolve_relative(root, "../outside").is_err());
+        assert!(resolve_relative(root, "").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use crate::core::about_xml::{read_about_metadata, AboutMetadata};
+use crate::core::mods_config::read_mods_config;
+
+/// A load order problem between two installed mods, packageIds are lowercase
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum LoadConflict {
+    /// Both mods are installed although one lists the other in `<incompatibleWith>`
+    Incompatible { package_id: String, incompatible_with: String },
+    /// `package_id` is active before `load_after`, but `declared_by` requires the opposite order
+    /// through `<loadAfter>` or `<loadBefore>`
+    LoadOrder { package_id: String, load_after: String, declared_by: String },
+}
+
+/// Find incompatible installed mods and, given the path of ModsConfig.xml, active mods in the wrong order
+/// Constraints referencing packageIds that are not installed are ignored
+pub fn detect_load_conflicts(mods_path: &Path, config_path: Option<&Path>) -> Result<Vec<LoadConflict>, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+
+    // RimWorld compares packageIds case-insensitively
+    let mut installed: HashMap<String, AboutMetadata> = HashMap::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if let Ok(Some(metadata)) = read_about_metadata(&path) {
+            if let Some(package_id) = metadata.package_id.as_deref() {
+                installed.insert(package_id.to_lowercase(), metadata);
+            }
+        }
+    }
+
+    let load_order: HashMap<String, usize> = match config_path {
+        Some(config_path) => read_mods_config(config_path)?
+            .active_mods
+            .iter()
+            .enumerate()
+            .map(|(index, package_id)| (package_id.to_lowercase(), index))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    Ok(find_conflicts(&installed, &load_order).into_iter().collect())
+}
+
+/// Conflicts between installed mods (keyed by lowercase packageId), given the load order index of active mods
+fn find_conflicts(installed: &HashMap<String, AboutMetadata>, load_order: &HashMap<String, usize>) -> BTreeSet<LoadConflict> {
+    let mut conflicts = BTreeSet::new();
+    for (package_id, metadata) in installed {
+        for other in &metadata.incompatible_with {
+            let other = other.to_lowercase();
+            if &other == package_id || !installed.contains_key(&other) {
+                continue;
+            }
+            // Mods often declare each other incompatible, report each pair once
+            let (first, second) = if *package_id < other { (package_id.clone(), other) } else { (other, package_id.clone()) };
+            conflicts.insert(LoadConflict::Incompatible { package_id: first, incompatible_with: second });
+        }
+
+        // (mod that must load later, mod it must load after)
+        let constraints = metadata.load_after.iter().map(|other| (package_id.clone(), other.to_lowercase()))
+            .chain(metadata.load_before.iter().map(|other| (other.to_lowercase(), package_id.clone())));
+        for (later, earlier) in constraints {
+            if !installed.contains_key(&later) || !installed.contains_key(&earlier) {
+                continue;
+            }
+            if let (Some(later_index), Some(earlier_index)) = (load_order.get(&later), load_order.get(&earlier)) {
+                if later_index < earlier_index {
+                    conflicts.insert(LoadConflict::LoadOrder {
+                        package_id: later,
+                        load_after: earlier,
+                        declared_by: package_id.clone(),
+                    });
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_mod(mods_path: &Path, folder: &str, about: &str) {
+        let about_dir = mods_path.join(folder).join("About");
+        fs::create_dir_all(&about_dir).unwrap();
+        fs::write(about_dir.join("About.xml"), about).unwrap();
+    }
+
+    #[test]
+    fn test_detect_load_conflicts() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("Mods");
+        create_mod(&mods_path, "Harmony", "<ModMetaData><packageId>brrainz.harmony</packageId></ModMetaData>");
+        create_mod(&mods_path, "Patch", r#"<ModMetaData>
+            <packageId>Someone.Patch</packageId>
+            <loadAfter><li>brrainz.harmony</li><li>not.installed</li></loadAfter>
+            <incompatibleWith><li>someone.oldpatch</li><li>not.installed</li></incompatibleWith>
+        </ModMetaData>"#);
+        create_mod(&mods_path, "OldPatch", r#"<ModMetaData>
+            <packageId>someone.oldpatch</packageId>
+            <loadBefore><li>brrainz.harmony</li></loadBefore>
+            <incompatibleWith><li>someone.patch</li></incompatibleWith>
+        </ModMetaData>"#);
+
+        // Without a load order only incompatible pairs are reported, once per pair
+        let incompatible = LoadConflict::Incompatible {
+            package_id: "someone.oldpatch".to_string(),
+            incompatible_with: "someone.patch".to_string(),
+        };
+        assert_eq!(detect_load_conflicts(&mods_path, None).unwrap(), vec![incompatible.clone()]);
+
+        let config_path = temp_dir.path().join("ModsConfig.xml");
+        fs::write(&config_path, "<ModsConfigData><activeMods><li>ludeon.rimworld</li><li>someone.patch</li><li>brrainz.harmony</li><li>someone.oldpatch</li></activeMods></ModsConfigData>").unwrap();
+        assert_eq!(detect_load_conflicts(&mods_path, Some(&config_path)).unwrap(), vec![
+            incompatible,
+            LoadConflict::LoadOrder {
+                package_id: "brrainz.harmony".to_string(),
+                load_after: "someone.oldpatch".to_string(),
+                declared_by: "someone.oldpatch".to_string(),
+            },
+            LoadConflict::LoadOrder {
+                package_id: "someone.patch".to_string(),
+                load_after: "brrainz.harmony".to_string(),
+                declared_by: "someone.patch".to_string(),
+            },
+        ]);
+    }
+}
@@ -9,6 +9,28 @@ pub mod api_rate_limiter;
 pub mod workshop_deserializers;
 pub mod mod_watcher;
 pub mod access_check;
+pub mod failure_history;
+pub mod settings_store;
+pub mod mod_list;
+pub mod scheduler;
+pub mod about_xml;
+pub mod content_fingerprint;
+pub mod mod_lifecycle;
+pub mod mods_config;
+pub mod incremental_backup;
+pub mod backup_retention;
+pub mod download_summary;
+pub mod duplicate_mods;
+pub mod backup_watcher;
+pub mod load_order;
+pub mod shutdown;
+pub mod download_queue;
+pub mod steamcmd_log;
+pub mod thumbnail_cache;
+pub mod mod_lock;
+pub mod collection_diff;
+pub mod mods_path_check;
+pub mod backup_archive;
 
 // Re-export for backward compatibility and convenience
 pub use mod_scanner::*;
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+/// Phase of a mod in the download and install pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModPhase {
+    Queued,
+    Downloading,
+    DownloadProgress,
+    RetryQueued,
+    /// Gave up waiting for the download, distinct from Failed so the UI can suggest a slower connection
+    TimedOut,
+    /// Detail has `staged` and `modPath` when the mod is left in the download folder instead of being installed
+    Downloaded,
+    BackingUp,
+    Installing,
+    InstallProgress,
+    Installed,
+    Failed,
+    Cancelled,
+}
+
+impl ModPhase {
+    /// State of the legacy `mod-state` event matching this phase
+    /// Downloaded, DownloadProgress and InstallProgress have events of their own
+    fn legacy_state(self) -> Option<&'static str> {
+        match self {
+            ModPhase::Queued => Some("queued"),
+            ModPhase::Downloading => Some("downloading"),
+            ModPhase::RetryQueued => Some("retry-queued"),
+            ModPhase::TimedOut => Some("timeout"),
+            ModPhase::Installing => Some("installing"),
+            ModPhase::Installed => Some("completed"),
+            ModPhase::Failed => Some("failed"),
+            ModPhase::Cancelled => Some("cancelled"),
+            ModPhase::Downloaded
+            | ModPhase::DownloadProgress
+            | ModPhase::BackingUp
+            | ModPhase::InstallProgress => None,
+        }
+    }
+}
+
+/// Single event describing every step of a mod's download and install, emitted as `mod-lifecycle`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModLifecycleEvent {
+    pub mod_id: String,
+    pub phase: ModPhase,
+    /// Phase specific data (error message, retry attempt, progress...), null if there is none
+    pub detail: Value,
+}
+
+impl ModLifecycleEvent {
+    pub fn new(mod_id: &str, phase: ModPhase, detail: Value) -> Self {
+        Self {
+            mod_id: mod_id.to_string(),
+            phase,
+            detail,
+        }
+    }
+
+    /// Payload of the legacy event for this phase, with the detail fields flattened into it
+    fn legacy_event(&self) -> Option<(&'static str, Value)> {
+        let mut payload = match self.phase {
+            ModPhase::Downloaded => ("mod-downloaded", serde_json::json!({ "modId": self.mod_id })),
+            ModPhase::DownloadProgress => ("mod-progress", serde_json::json!({ "modId": self.mod_id })),
+            ModPhase::InstallProgress => ("install-progress", serde_json::json!({ "modId": self.mod_id })),
+            phase => ("mod-state", serde_json::json!({
+                "modId": self.mod_id,
+                "state": phase.legacy_state()?,
+            })),
+        };
+
+        if let (Some(payload_obj), Value::Object(detail)) = (payload.1.as_object_mut(), &self.detail) {
+            for (key, value) in detail {
+                payload_obj.insert(key.clone(), value.clone());
+            }
+        }
+        Some(payload)
+    }
+}
+
+/// Emit a lifecycle event for a mod
+/// The matching `mod-state` / `mod-downloaded` / `mod-progress` / `install-progress` event is emitted as well
+pub fn emit_mod_lifecycle(app: &AppHandle, mod_id: &str, phase: ModPhase, detail: Value) {
+    let event = ModLifecycleEvent::new(mod_id, phase, detail);
+    if let Some((name, payload)) = event.legacy_event() {
+        let _ = app.emit(name, payload);
+    }
+    let _ = app.emit("mod-lifecycle", &event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_event_flattens_detail() {
+        let event = ModLifecycleEvent::new("123", ModPhase::RetryQueued, serde_json::json!({
+            "retryAttempt": 2,
+            "maxRetries": 6,
+        }));
+        let (name, payload) = event.legacy_event().unwrap();
+        assert_eq!(name, "mod-state");
+        assert_eq!(payload, serde_json::json!({
+            "modId": "123",
+            "state": "retry-queued",
+            "retryAttempt": 2,
+            "maxRetries": 6,
+        }));
+    }
+
+    #[test]
+    fn test_phases_without_legacy_event() {
+        let event = ModLifecycleEvent::new("123", ModPhase::BackingUp, Value::Null);
+        assert!(event.legacy_event().is_none());
+
+        let event = ModLifecycleEvent::new("123", ModPhase::Downloaded, Value::Null);
+        assert_eq!(event.legacy_event().unwrap().0, "mod-downloaded");
+
+        let event = ModLifecycleEvent::new("123", ModPhase::InstallProgress, serde_json::json!({ "filesCopied": 3, "totalFiles": 10 }));
+        assert_eq!(event.legacy_event().unwrap(), ("install-progress", serde_json::json!({
+            "modId": "123",
+            "filesCopied": 3,
+            "totalFiles": 10,
+        })));
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = ModLifecycleEvent::new("123", ModPhase::DownloadProgress, Value::Null);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["phase"], "download-progress");
+        assert_eq!(json["modId"], "123");
+    }
+}
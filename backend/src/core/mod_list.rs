@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::core::mod_manager::ModUpdater;
+use crate::core::mod_scanner::{parse_workshop_id, BaseMod};
+
+/// A Workshop mod of a shared mod list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModListEntry {
+    pub mod_id: String,
+    pub folder: Option<String>,
+    pub package_id: Option<String>,
+}
+
+/// A shareable list of installed Workshop mods, readable by `parse_mod_list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModListFile {
+    /// Version of the app that created the list
+    pub created_with: String,
+    pub mods: Vec<ModListEntry>,
+}
+
+/// Build a mod list from installed mods, non-Steam mods can't be shared and are left out
+pub fn build_mod_list(installed: &[BaseMod]) -> ModListFile {
+    let mut seen = HashSet::new();
+    let mods = installed.iter()
+        .filter(|m| !m.non_steam_mod && seen.insert(m.mod_id.clone()))
+        .map(|m| ModListEntry {
+            mod_id: m.mod_id.clone(),
+            folder: m.folder.clone(),
+            package_id: ModUpdater::get_package_id(Path::new(&m.mod_path)),
+        })
+        .collect();
+    ModListFile {
+        created_with: format!("Rimworld Workshop Downloader {}", env!("CARGO_PKG_VERSION")),
+        mods,
+    }
+}
+
+/// IDs of a mod list that are not installed yet, in list order
+pub fn missing_mod_ids(mod_ids: &[String], installed: &[BaseMod]) -> Vec<String> {
+    let installed_ids: HashSet<&str> = installed.iter().map(|m| m.mod_id.as_str()).collect();
+    mod_ids.iter()
+        .filter(|id| !installed_ids.contains(id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Parse a list of Workshop mod IDs from a manifest
+/// Accepts a JSON array of IDs or objects (`modId`/`id`), a JSON object with a `mods` array,
+/// or plain text with one ID or Workshop URL per line
+pub fn parse_mod_list(content: &str) -> Vec<String> {
+    let content = content.trim_start_matches('\u{FEFF}').trim();
+
+    let ids: Vec<String> = match serde_json::from_str::<Value>(content) {
+        Ok(Value::Array(items)) => items.iter().filter_map(mod_id_from_json).collect(),
+        Ok(Value::Object(obj)) => obj.get("mods")
+            .and_then(|m| m.as_array())
+            .map(|items| items.iter().filter_map(mod_id_from_json).collect())
+            .unwrap_or_default(),
+        _ => content
+            .split(['\n', ',', ';'])
+            .filter_map(mod_id_from_text)
+            .collect(),
+    };
+
+    // Remove duplicates while keeping the original order
+    let mut seen = HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(id.clone())).collect()
+}
+
+/// Extract a mod ID from a JSON manifest entry
+fn mod_id_from_json(item: &Value) -> Option<String> {
+    match item {
+        Value::String(s) => mod_id_from_text(s),
+        Value::Number(n) => n.as_u64().map(|n| n.to_string()),
+        Value::Object(obj) => obj.get("modId")
+            .or_else(|| obj.get("id"))
+            .or_else(|| obj.get("publishedfileid"))
+            .and_then(mod_id_from_json),
+        _ => None,
+    }
+}
+
+/// Extract a mod ID from a line of text (plain ID or Workshop URL)
+fn mod_id_from_text(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    parse_workshop_id(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_list() {
+        let ids = parse_mod_list("123\n456\r\n\n# comment\n123\n");
+        assert_eq!(ids, vec!["123", "456"]);
+    }
+
+    #[test]
+    fn test_parse_workshop_urls() {
+        let ids = parse_mod_list("https://steamcommunity.com/sharedfiles/filedetails/?id=818773962&searchtext=\n2009463077");
+        assert_eq!(ids, vec!["818773962", "2009463077"]);
+    }
+
+    #[test]
+    fn test_parse_json_formats() {
+        assert_eq!(parse_mod_list("[\"1\", 2]"), vec!["1", "2"]);
+        assert_eq!(parse_mod_list("{\"mods\": [{\"modId\": \"3\"}, {\"id\": 4}]}"), vec!["3", "4"]);
+    }
+
+    #[test]
+    fn test_mod_list_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let about = temp_dir.path().join("Harmony").join("About");
+        std::fs::create_dir_all(&about).unwrap();
+        std::fs::write(about.join("About.xml"), "<ModMetaData><packageId>brrainz.harmony</packageId></ModMetaData>").unwrap();
+
+        let installed_mod = |mod_id: &str, folder: &str, non_steam_mod: bool| BaseMod {
+            mod_id: mod_id.to_string(),
+            mod_path: temp_dir.path().join(folder).to_string_lossy().to_string(),
+            folder: Some(folder.to_string()),
+            details: None,
+            updated: None,
+            non_steam_mod,
+            preview_image_path: None,
+            status: None,
+            ban_reason: None,
+            supported_versions: vec![],
+            load_after: vec![],
+            load_before: vec![],
+            incompatible_with: vec![],
+            local_modifications: vec![],
+            tags: vec![],
+            author: None,
+            staged_path: None,
+        };
+        let installed = vec![
+            installed_mod("2009463077", "Harmony", false),
+            installed_mod("Local", "Local", true),
+        ];
+
+        let list = build_mod_list(&installed);
+        assert_eq!(list.mods.len(), 1);
+        assert_eq!(list.mods[0].package_id.as_deref(), Some("brrainz.harmony"));
+
+        let ids = parse_mod_list(&serde_json::to_string(&list).unwrap());
+        assert_eq!(ids, vec!["2009463077"]);
+
+        let wanted = vec!["2009463077".to_string(), "818773962".to_string()];
+        assert_eq!(missing_mod_ids(&wanted, &installed), vec!["818773962"]);
+    }
+
+    #[test]
+    fn test_parse_ignores_garbage() {
+        assert!(parse_mod_list("not a mod\nabc").is_empty());
+    }
+}
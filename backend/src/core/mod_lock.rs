@@ -0,0 +1,105 @@
+use std::fs::{self, File, TryLockError};
+use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+use crate::core::mod_manager::InstallError;
+
+/// Folder of the lock files in the system temp dir, shared by every instance of the app
+const LOCK_FOLDER: &str = "rimworld-workshop-downloader-locks";
+
+/// Advisory lock on a mod folder, released when dropped
+/// Only instances of the app honor it, the lock file is left in place for the next one
+#[derive(Debug)]
+pub struct ModFolderLock {
+    _file: File,
+}
+
+/// Lock a mod folder against changes by other instances of the app, e.g. the dev backend next to the bundled one
+/// Fails with `InstallError::Busy` instead of waiting when the lock is held
+pub fn lock_mod_folder(mod_path: &Path) -> Result<ModFolderLock, InstallError> {
+    lock_mod_folder_in(&std::env::temp_dir().join(LOCK_FOLDER), mod_path)
+}
+
+fn lock_mod_folder_in(lock_dir: &Path, mod_path: &Path) -> Result<ModFolderLock, InstallError> {
+    let file = open_lock_file(lock_dir, &format!("{}.lock", lock_key(mod_path)))?;
+    match file.try_lock() {
+        Ok(()) => Ok(ModFolderLock { _file: file }),
+        Err(TryLockError::WouldBlock) => Err(InstallError::Busy { mod_path: mod_path.display().to_string() }),
+        Err(TryLockError::Error(e)) => Err(format!("Failed to lock {}: {}", mod_path.display(), e).into()),
+    }
+}
+
+/// Lock on a SteamCMD folder, released when dropped
+/// Every instance of the app holds it shared while one of its SteamCMD processes runs there
+#[derive(Debug)]
+pub struct SteamCmdLock {
+    _file: File,
+}
+
+/// Mark a SteamCMD folder as in use by this instance of the app
+pub fn lock_steamcmd_shared(steamcmd_path: &Path) -> Result<SteamCmdLock, String> {
+    let file = open_lock_file(&std::env::temp_dir().join(LOCK_FOLDER), &steamcmd_lock_name(steamcmd_path))?;
+    file.lock_shared()
+        .map_err(|e| format!("Failed to lock {}: {}", steamcmd_path.display(), e))?;
+    Ok(SteamCmdLock { _file: file })
+}
+
+/// Lock a SteamCMD folder for removing files SteamCMD left behind
+/// None while any instance of the app is running SteamCMD there
+pub fn try_lock_steamcmd_exclusive(steamcmd_path: &Path) -> Result<Option<SteamCmdLock>, String> {
+    let file = open_lock_file(&std::env::temp_dir().join(LOCK_FOLDER), &steamcmd_lock_name(steamcmd_path))?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(SteamCmdLock { _file: file })),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(e)) => Err(format!("Failed to lock {}: {}", steamcmd_path.display(), e)),
+    }
+}
+
+fn steamcmd_lock_name(steamcmd_path: &Path) -> String {
+    format!("steamcmd-{}.lock", lock_key(steamcmd_path))
+}
+
+fn open_lock_file(lock_dir: &Path, name: &str) -> Result<File, String> {
+    fs::create_dir_all(lock_dir)
+        .map_err(|e| format!("Failed to create lock directory {}: {}", lock_dir.display(), e))?;
+    let lock_path = lock_dir.join(name);
+    File::options().create(true).truncate(false).write(true).open(&lock_path)
+        .map_err(|e| format!("Failed to open lock file {}: {}", lock_path.display(), e))
+}
+
+/// Name of a mod folder's lock file, the same for every spelling of the path
+/// The folder itself may not exist yet, so only its parent is resolved
+fn lock_key(mod_path: &Path) -> String {
+    let resolved = match (mod_path.parent().and_then(|parent| parent.canonicalize().ok()), mod_path.file_name()) {
+        (Some(parent), Some(name)) => parent.join(name),
+        _ => PathBuf::from(mod_path),
+    };
+    let mut key = resolved.to_string_lossy().into_owned();
+    // Windows paths are case-insensitive
+    if cfg!(windows) {
+        key = key.to_lowercase();
+    }
+    Sha256::digest(key.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_mod_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_dir = temp_dir.path().join("locks");
+        let mods_path = temp_dir.path().join("Mods");
+        fs::create_dir_all(&mods_path).unwrap();
+
+        let lock = lock_mod_folder_in(&lock_dir, &mods_path.join("Harmony")).unwrap();
+        // Another spelling of the same folder is the same lock
+        let error = lock_mod_folder_in(&lock_dir, &mods_path.join(".").join("Harmony")).unwrap_err();
+        assert!(matches!(error, InstallError::Busy { .. }));
+        assert!(lock_mod_folder_in(&lock_dir, &mods_path.join("Other")).is_ok());
+
+        drop(lock);
+        assert!(lock_mod_folder_in(&lock_dir, &mods_path.join("Harmony")).is_ok());
+    }
+}
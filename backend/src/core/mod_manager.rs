@@ -1,11 +1,288 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::fs;
-use crate::core::mod_scanner::query_mod_id;
-use crate::services::{ignore_path_in_watcher, WatcherIgnoreGuard, is_update_cancelled};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use crate::core::mod_scanner::{clean_published_file_id, find_about_dir, parse_workshop_id, query_mod_id, is_staging_mods_folder, write_mod_file, STAGING_MODS_FOLDER};
+use crate::core::about_xml::read_mod_xml;
+use crate::core::incremental_backup::create_incremental_backup;
+use crate::core::backup_archive::{create_zip_backup, zip_backup_path};
+use crate::core::content_fingerprint::{collect_files, is_app_managed_file, write_checksum_manifest};
+use crate::core::mod_lifecycle::{emit_mod_lifecycle, ModPhase};
+use crate::core::mod_lock::lock_mod_folder;
+use crate::core::mod_watcher::compile_ignore_patterns;
+use crate::services::{ignore_path_in_watcher, WatcherIgnoreGuard, is_update_cancelled, mark_update_in_progress};
+use globset::GlobSet;
 use quick_xml::events::Event;
 use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// How a downloaded mod is placed in the mods folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkMode {
+    /// Copy the mod files (default)
+    #[default]
+    Copy,
+    /// Link the mod folder to the downloaded folder, saving disk space
+    Symlink,
+    /// Hard link every file of the mod, falls back to copying across filesystems
+    Hardlink,
+}
+
+/// How the previous version of a mod is backed up before an update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupMode {
+    /// Replace the backup with a full copy of the mod (default)
+    #[default]
+    Full,
+    /// Keep versioned backups storing only the files that changed since the previous one
+    Incremental,
+}
+
+/// How a full backup is stored in the backup directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackupFormat {
+    /// A copy of the mod folder, fastest to create and restore (default)
+    #[default]
+    Folder,
+    /// A single compressed `.zip` next to where the folder would be, saving disk space
+    Zip,
+}
+
+/// Files and folders of mod authors' tooling that never belong in the game's mods folder
+pub const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &[
+    ".git", ".gitignore", ".gitattributes", ".vs", ".vscode", ".idea", "Thumbs.db", "desktop.ini", ".DS_Store",
+];
+
+/// Files skipped when installing a mod and files kept from the installed copy
+/// The default skips and keeps nothing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InstallFilter {
+    /// Glob patterns of files and folders not installed, matched by name and by path relative to the mod folder
+    pub exclude: Vec<String>,
+    /// Skip DEFAULT_EXCLUDE_PATTERNS as well
+    pub use_default_excludes: bool,
+    /// Paths relative to the mod folder kept from the installed copy, e.g. personal overrides or optional texture packs
+    pub preserve: Vec<String>,
+}
+
+impl InstallFilter {
+    /// Matcher of the skipped files, None when nothing is skipped
+    pub fn exclude_set(&self) -> Result<Option<GlobSet>, String> {
+        let mut patterns = self.exclude.clone();
+        if self.use_default_excludes {
+            patterns.extend(DEFAULT_EXCLUDE_PATTERNS.iter().map(|pattern| pattern.to_string()));
+        }
+        let exclude = compile_ignore_patterns(&patterns)?;
+        Ok((!exclude.is_empty()).then_some(exclude))
+    }
+
+    /// Preserved paths, rejecting absolute ones and ones leaving the mod folder
+    pub fn preserved_paths(&self) -> Result<Vec<PathBuf>, String> {
+        self.preserve.iter().map(|path| path.trim()).filter(|path| !path.is_empty()).map(|path| {
+            let relative = PathBuf::from(path);
+            if relative.components().all(|component| matches!(component, Component::Normal(_))) {
+                Ok(relative)
+            } else {
+                Err(format!("Preserved path must be inside the mod folder: {}", path))
+            }
+        }).collect()
+    }
+}
+
+/// Mods installed at once by a batch update unless configured otherwise, more mostly thrash the disk
+pub const DEFAULT_INSTALL_CONCURRENCY: usize = 4;
+
+static INSTALL_CONCURRENCY: AtomicUsize = AtomicUsize::new(DEFAULT_INSTALL_CONCURRENCY);
+
+/// Set how many mods a batch update installs at once (None resets to the default)
+pub fn set_install_concurrency(concurrency: Option<usize>) {
+    INSTALL_CONCURRENCY.store(concurrency.unwrap_or(DEFAULT_INSTALL_CONCURRENCY).max(1), Ordering::Relaxed);
+}
+
+/// Number of mods a batch update installs at once
+pub fn install_concurrency() -> usize {
+    INSTALL_CONCURRENCY.load(Ordering::Relaxed)
+}
+
+/// Bounds how many installs of a batch run at once, shared by its per-mod tasks
+/// How mods are installed, the same for every mod of one operation
+#[derive(Clone)]
+pub struct InstallOptions {
+    /// Folder the mods were downloaded to, also holds the private copies of linked mods
+    pub download_path: PathBuf,
+    /// Game mods folder the mods are installed to
+    pub mods_path: PathBuf,
+    /// Back up the installed copy here before replacing it, no backup when None
+    pub backup_directory: Option<PathBuf>,
+    pub link_mode: LinkMode,
+    pub backup_mode: BackupMode,
+    pub backup_format: BackupFormat,
+    pub install_filter: InstallFilter,
+    /// Answer to the corrupted mod prompt, None asks the user
+    pub force_overwrite_corrupted: Option<bool>,
+    /// Replace an installed copy updated later than the version being installed
+    pub allow_downgrade: bool,
+    /// Reports install progress to the UI
+    pub app: Option<AppHandle>,
+}
+
+impl InstallOptions {
+    /// Copy mods to `mods_path` without backups, filters or a UI
+    pub fn new(download_path: PathBuf, mods_path: PathBuf) -> Self {
+        Self {
+            download_path,
+            mods_path,
+            backup_directory: None,
+            link_mode: LinkMode::default(),
+            backup_mode: BackupMode::default(),
+            backup_format: BackupFormat::default(),
+            install_filter: InstallFilter::default(),
+            force_overwrite_corrupted: None,
+            allow_downgrade: false,
+            app: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InstallLimiter {
+    permits: Arc<tokio::sync::Semaphore>,
+}
+
+impl InstallLimiter {
+    pub fn new(limit: usize) -> Self {
+        Self { permits: Arc::new(tokio::sync::Semaphore::new(limit.max(1))) }
+    }
+
+    /// Wait until fewer than `limit` installs are running, the install may run while the permit is held
+    pub async fn acquire(&self) -> tokio::sync::OwnedSemaphorePermit {
+        // The semaphore is never closed
+        self.permits.clone().acquire_owned().await.expect("Install limiter closed")
+    }
+}
+
+/// Error returned when the user cancelled the update or download, converted to a cancellation instead of a failure
+pub const CANCELLED_ERROR: &str = "Update cancelled by user";
+
+/// Error returned to the UI when a mod couldn't be installed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum InstallError {
+    /// The mods folder holds a corrupted folder with the mod's name, resolved with `resolve_corrupted_conflict`
+    #[serde(rename_all = "camelCase")]
+    CorruptedModConflict {
+        folder_name: String,
+        mod_id: String,
+        mod_title: String,
+    },
+    /// The installed copy is newer than the version being installed, kept unless installing with `allow_downgrade`
+    #[serde(rename_all = "camelCase")]
+    Downgrade {
+        mod_id: String,
+        local_time_updated: i64,
+        remote_time_updated: i64,
+    },
+    /// Another instance of the app is changing the mod folder
+    #[serde(rename_all = "camelCase")]
+    Busy { mod_path: String },
+    /// The user cancelled the update
+    Cancelled,
+    /// Any other failure
+    Failed { message: String },
+}
+
+impl From<String> for InstallError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+impl InstallError {
+    /// Identifier of the variant, the `kind` it is serialized with
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::CorruptedModConflict { .. } => "corruptedModConflict",
+            Self::Downgrade { .. } => "downgrade",
+            Self::Busy { .. } => "busy",
+            Self::Cancelled => "cancelled",
+            Self::Failed { .. } => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for InstallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CorruptedModConflict { folder_name, mod_id, .. } => {
+                write!(f, "Existing folder \"{}\" of mod {} is corrupted", folder_name, mod_id)
+            }
+            Self::Downgrade { mod_id, local_time_updated, remote_time_updated } => {
+                write!(f, "Installed copy of mod {} (updated {}) is newer than the version being installed (updated {})",
+                    mod_id, local_time_updated, remote_time_updated)
+            }
+            Self::Busy { mod_path } => write!(f, "Mod folder {} is being changed by another instance of the app", mod_path),
+            Self::Cancelled => write!(f, "{}", CANCELLED_ERROR),
+            Self::Failed { message } => write!(f, "{}", message),
+        }
+    }
+}
 
 /// Mod updater for copying mods from download folder to mods folder
+/// Folder names claimed by the mods of one batch install, shared by the per-mod tasks
+/// Resolving names one at a time keeps two mods from both picking the same free name before either is copied
+#[derive(Debug, Clone, Default)]
+pub struct FolderReservations {
+    /// Lowercase folder name -> mod ID, as Windows folder names are case-insensitive
+    names: Arc<tokio::sync::Mutex<HashMap<String, String>>>,
+}
+
+impl FolderReservations {
+    /// Reserve folders already known to belong to mods of the batch
+    pub async fn reserve<'a>(&self, folders: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        let mut names = self.names.lock().await;
+        for (folder_name, mod_id) in folders {
+            names.entry(folder_name.to_lowercase()).or_insert_with(|| mod_id.to_string());
+        }
+    }
+
+    /// Resolve and reserve the destination folder of a mod, see `ModUpdater::resolve_destination_folder`
+    /// A name reserved by another mod of the batch is treated as taken
+    pub async fn resolve(
+        &self,
+        mod_id: &str,
+        mod_path: &Path,
+        download_path: &Path,
+        mods_path: &Path,
+        existing_folder_name: Option<&str>,
+        mod_title: Option<&str>,
+    ) -> Result<String, InstallError> {
+        let mut names = self.names.lock().await;
+        let mut folder_name = ModUpdater.resolve_destination_folder(
+            mod_id,
+            mod_path,
+            download_path,
+            mods_path,
+            existing_folder_name,
+            mod_title,
+            None,
+        ).await?;
+        if names.get(&folder_name.to_lowercase()).is_some_and(|owner| owner != mod_id) {
+            let reserved_name = folder_name;
+            folder_name = ModUpdater::unique_unreserved_folder_name(mods_path, &reserved_name, mod_id, &names);
+            eprintln!("[ModUpdater] Folder \"{}\" is reserved by another mod of this update, using \"{}\" instead", reserved_name, folder_name);
+        }
+        names.insert(folder_name.to_lowercase(), mod_id.to_string());
+        Ok(folder_name)
+    }
+}
+
 pub struct ModUpdater;
 
 impl ModUpdater {
@@ -41,20 +318,66 @@ impl ModUpdater {
         sanitized
     }
 
-    /// Update/Copy mod from download folder to mods folder
-    pub async fn update_mod(
+    /// Append `_` to a folder name until it is free in the mods folder
+    /// Falls back to `<name>_<mod_id>`, then a timestamp, if too many suffixed folders exist
+    pub fn unique_folder_name(mods_path: &Path, folder_name: &str, mod_id: &str) -> String {
+        Self::unique_unreserved_folder_name(mods_path, folder_name, mod_id, &HashMap::new())
+    }
+
+    /// Like `unique_folder_name`, also skipping names reserved by other mods
+    fn unique_unreserved_folder_name(mods_path: &Path, folder_name: &str, mod_id: &str, reserved: &HashMap<String, String>) -> String {
+        let is_free = |name: &str| !mods_path.join(name).exists() && !reserved.contains_key(&name.to_lowercase());
+        let mut unique_name = folder_name.to_string();
+        loop {
+            unique_name = format!("{}_", unique_name);
+            if is_free(&unique_name) {
+                return unique_name;
+            }
+            // Safety limit
+            if unique_name.len() > folder_name.len() + 50 {
+                let fallback = format!("{}_{}", folder_name, mod_id);
+                if is_free(&fallback) {
+                    return fallback;
+                }
+                // Fallback path exists - use timestamp to guarantee uniqueness
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                return format!("{}_{}_{}", folder_name, mod_id, timestamp);
+            }
+        }
+    }
+
+    /// Sanitized path a mod folder can be renamed to without replacing another folder
+    /// Returns the current path if the name doesn't change
+    pub fn rename_target(mod_path: &Path, new_name: &str) -> Result<PathBuf, String> {
+        let mods_path = mod_path.parent()
+            .ok_or_else(|| format!("Mod folder has no parent directory: {:?}", mod_path))?;
+        let folder_name = Self::sanitize_folder_name(new_name);
+        let target = mods_path.join(&folder_name);
+        // A rename that only changes case points at the mod itself on case-insensitive filesystems
+        if !target.exists() || target == mod_path || fs::canonicalize(&target).ok() == fs::canonicalize(mod_path).ok() {
+            return Ok(target);
+        }
+        let mod_id = query_mod_id(mod_path).ok().flatten().unwrap_or_default();
+        Ok(mods_path.join(Self::unique_folder_name(mods_path, &folder_name, &mod_id)))
+    }
+
+    /// Name of the folder in the mods folder a downloaded mod is installed to
+    /// Uses `existing_folder_name` if provided, otherwise the folder with the same mod ID, otherwise the mod title,
+    /// renamed if a different mod already uses that name
+    #[allow(clippy::too_many_arguments)]
+    pub async fn resolve_destination_folder(
         &self,
         mod_id: &str,
         mod_path: &Path,
         download_path: &Path,
         mods_path: &Path,
         existing_folder_name: Option<&str>,
-        create_backup: bool,
-        backup_directory: Option<&Path>,
         mod_title: Option<&str>,
         force_overwrite_corrupted: Option<bool>,
-    ) -> Result<PathBuf, String> {
-        // Use existing folder name if provided, otherwise find existing folder with same mod ID, otherwise use mod title
+    ) -> Result<String, InstallError> {
         let folder_name = if let Some(name) = existing_folder_name {
             name.to_string()
         } else {
@@ -141,8 +464,12 @@ impl ModUpdater {
                                     eprintln!("[ModUpdater] Force renaming corrupted mod, using \"{}\" instead", folder_name);
                                 }
                                 None => {
-                                    // Mod is corrupted - return special error to ask user for decision
-                                    return Err(format!("CORRUPTED_MOD_CONFLICT:{}:{}", folder_name, mod_id));
+                                    // Mod is corrupted - ask the user for a decision
+                                    return Err(InstallError::CorruptedModConflict {
+                                        folder_name,
+                                        mod_id: mod_id.to_string(),
+                                        mod_title: mod_title.unwrap_or(mod_id).to_string(),
+                                    });
                                 }
                             }
                         }
@@ -202,59 +529,13 @@ impl ModUpdater {
                             }
                             // Source has packageId, existing doesn't - different mods, change name
                             (Some(_), None) => {
-                                let base_name = folder_name.clone();
-                                loop {
-                                    folder_name = format!("{}_", folder_name);
-                                    proposed_path = mods_path.join(&folder_name);
-                                    if !proposed_path.exists() {
-                                        break;
-                                    }
-                                    // Safety limit
-                                    if folder_name.len() > base_name.len() + 50 {
-                                        folder_name = format!("{}_{}", base_name, mod_id);
-                                        proposed_path = mods_path.join(&folder_name);
-                                        // Check if fallback path is unique
-                                        if !proposed_path.exists() {
-                                            break;
-                                        }
-                                        // Fallback path exists - use timestamp to guarantee uniqueness
-                                        let timestamp = std::time::SystemTime::now()
-                                            .duration_since(std::time::UNIX_EPOCH)
-                                            .unwrap()
-                                            .as_secs();
-                                        folder_name = format!("{}_{}_{}", base_name, mod_id, timestamp);
-                                        break;
-                                    }
-                                }
+                                folder_name = Self::unique_folder_name(mods_path, &folder_name, mod_id);
                                 eprintln!("[ModUpdater] Folder \"{}\" exists but has no packageId, using \"{}\" instead", 
                                     Self::sanitize_folder_name(mod_title_to_use), folder_name);
                             }
                             // Source doesn't have packageId, existing does - different mods, change name
                             (None, Some(_)) => {
-                                let base_name = folder_name.clone();
-                                loop {
-                                    folder_name = format!("{}_", folder_name);
-                                    proposed_path = mods_path.join(&folder_name);
-                                    if !proposed_path.exists() {
-                                        break;
-                                    }
-                                    // Safety limit
-                                    if folder_name.len() > base_name.len() + 50 {
-                                        folder_name = format!("{}_{}", base_name, mod_id);
-                                        proposed_path = mods_path.join(&folder_name);
-                                        // Check if fallback path is unique
-                                        if !proposed_path.exists() {
-                                            break;
-                                        }
-                                        // Fallback path exists - use timestamp to guarantee uniqueness
-                                        let timestamp = std::time::SystemTime::now()
-                                            .duration_since(std::time::UNIX_EPOCH)
-                                            .unwrap()
-                                            .as_secs();
-                                        folder_name = format!("{}_{}_{}", base_name, mod_id, timestamp);
-                                        break;
-                                    }
-                                }
+                                folder_name = Self::unique_folder_name(mods_path, &folder_name, mod_id);
                                 eprintln!("[ModUpdater] Folder \"{}\" exists with packageId but source doesn't, using \"{}\" instead", 
                                     Self::sanitize_folder_name(mod_title_to_use), folder_name);
                             }
@@ -278,59 +559,171 @@ impl ModUpdater {
                 }
             }
         };
+        Ok(folder_name)
+    }
+
+    /// Update/Copy mod from download folder to mods folder
+    /// `time_updated` is the Workshop update time of the version being installed
+    pub async fn update_mod(
+        &self,
+        mod_id: &str,
+        mod_path: &Path,
+        existing_folder_name: Option<&str>,
+        mod_title: Option<&str>,
+        time_updated: Option<i64>,
+        options: &InstallOptions,
+    ) -> Result<PathBuf, InstallError> {
+        // Closing the app waits for the install while this is held
+        let _in_progress = mark_update_in_progress();
+        let InstallOptions {
+            download_path,
+            mods_path,
+            backup_directory,
+            link_mode,
+            backup_mode,
+            backup_format,
+            install_filter,
+            force_overwrite_corrupted,
+            allow_downgrade,
+            app,
+        } = options;
+        let (download_path, mods_path, link_mode, backup_mode, backup_format) =
+            (download_path.as_path(), mods_path.as_path(), *link_mode, *backup_mode, *backup_format);
+        let app = app.as_ref();
+        let exclude = install_filter.exclude_set()?;
+        let preserved_paths = install_filter.preserved_paths()?;
+        
+        let folder_name = self.resolve_destination_folder(
+            mod_id,
+            mod_path,
+            download_path,
+            mods_path,
+            existing_folder_name,
+            mod_title,
+            *force_overwrite_corrupted,
+        ).await?;
         
         let mod_destination_path = mods_path.join(&folder_name);
+        // Held until the install is done, so another instance of the app can't remove or copy the folder meanwhile
+        let _folder_lock = lock_mod_folder(&mod_destination_path)?;
+
+        // An installed copy of the same mod updated later is kept, e.g. to not break saves of older game versions
+        if let Some(remote_time_updated) = time_updated.filter(|_| !allow_downgrade) {
+            if query_mod_id(&mod_destination_path).ok().flatten().as_deref() == Some(mod_id) {
+                if let Some(local_time_updated) = installed_time_updated(&mod_destination_path) {
+                    if local_time_updated > remote_time_updated {
+                        return Err(InstallError::Downgrade {
+                            mod_id: mod_id.to_string(),
+                            local_time_updated,
+                            remote_time_updated,
+                        });
+                    }
+                }
+            }
+        }
 
         // Ensure mods folder exists
         fs::create_dir_all(mods_path)
             .map_err(|e| format!("Failed to create mods directory: {}", e))?;
 
+        // The new version is installed next to the current one and swapped in once complete,
+        // so a failed or interrupted install leaves the current version untouched
+        // Both sides of the swap live in the staging folder, where neither RimWorld nor the scans pick them up as mods
+        let staging_root = mods_path.join(STAGING_MODS_FOLDER);
+        fs::create_dir_all(&staging_root)
+            .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+        let staging_path = staging_root.join(format!("{}.tmp", folder_name));
+        let replaced_path = staging_root.join(format!("{}.old", folder_name));
+
+        // An install interrupted between the two renames of the swap left the previous version aside
+        if replaced_path.symlink_metadata().is_ok() && mod_destination_path.symlink_metadata().is_err() {
+            rename_with_retry(&replaced_path, &mod_destination_path).await
+                .map_err(|e| format!("Failed to restore the previous version of the mod: {}", e))?;
+            eprintln!("[ModUpdater] Restored previous version of mod {} left aside by an interrupted install", mod_id);
+        }
+        for leftover in [&staging_path, &replaced_path] {
+            if leftover.symlink_metadata().is_ok() {
+                Self::remove_dir_with_retry(leftover, 3, 200).await
+                    .map_err(|e| format!("Failed to remove leftover folder {:?}: {}", leftover, e))?;
+            }
+        }
+
+        // A linked mod is only a link to its private copy, its folder can't be moved into the backup directory
+        let installed_is_dir = mod_destination_path.symlink_metadata().is_ok_and(|metadata| metadata.is_dir());
+
         // Create backup if requested
-        if create_backup {
+        // A full backup of an installed folder is made by moving it into the backup directory once the new version is ready
+        let mut deferred_backup = None;
+        if let Some(backup_dir) = backup_directory {
             // Check if update was cancelled before starting backup
             if is_update_cancelled() {
-                return Err("Update cancelled by user".to_string());
+                return Err(InstallError::Cancelled);
             }
             
-            if let Some(backup_dir) = backup_directory {
-                fs::create_dir_all(backup_dir)
-                    .map_err(|e| format!("Failed to create backup directory: {}", e))?;
-                let backup_path = backup_dir.join(&folder_name);
-                
-                // Remove old backup if exists
-                if backup_path.exists() {
-                    fs::remove_dir_all(&backup_path)
-                        .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+            fs::create_dir_all(backup_dir)
+                .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+            let backup_path = backup_dir.join(&folder_name);
+            
+            match backup_mode {
+                BackupMode::Full if backup_format == BackupFormat::Zip => {
+                    if mod_destination_path.exists() {
+                        let source = mod_destination_path.clone();
+                        let zip_path = zip_backup_path(&backup_path);
+                        let files = tokio::task::spawn_blocking(move || create_zip_backup(&source, &zip_path))
+                            .await
+                            .map_err(|e| format!("Task panicked: {:?}", e))?
+                            .map_err(|e| format!("Failed to create backup: {}", e))?;
+                        // A backup folder from before the format was changed would be restored instead
+                        if backup_path.exists() {
+                            fs::remove_dir_all(&backup_path)
+                                .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+                        }
+                        eprintln!("[ModUpdater] Created compressed backup for mod {} at {:?} ({} file(s))",
+                            mod_id, zip_backup_path(&backup_path), files);
+                    }
                 }
-                
-                // Copy current mod to backup directory
-                if mod_destination_path.exists() {
-                    copy_dir_all_async(&mod_destination_path, &backup_path).await
-                        .map_err(|e| format!("Failed to create backup: {}", e))?;
-                    eprintln!("[ModUpdater] Created backup for mod {} at {:?}", mod_id, backup_path);
+                BackupMode::Full if installed_is_dir => deferred_backup = Some(backup_path),
+                BackupMode::Full => {
+                    // Remove old backup if exists
+                    if backup_path.exists() {
+                        fs::remove_dir_all(&backup_path)
+                            .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+                    }
+                    remove_zip_backup(&backup_path)?;
+                    
+                    // Copy current mod to backup directory
+                    if mod_destination_path.exists() {
+                        copy_dir_all_async(&mod_destination_path, &backup_path).await
+                            .map_err(|e| format!("Failed to create backup: {}", e))?;
+                        eprintln!("[ModUpdater] Created backup for mod {} at {:?}", mod_id, backup_path);
+                    }
+                }
+                BackupMode::Incremental => {
+                    if mod_destination_path.exists() {
+                        let source = mod_destination_path.clone();
+                        let destination = backup_path.clone();
+                        let manifest = tokio::task::spawn_blocking(move || create_incremental_backup(&source, &destination))
+                            .await
+                            .map_err(|e| format!("Task panicked: {:?}", e))?
+                            .map_err(|e| format!("Failed to create backup: {}", e))?;
+                        eprintln!("[ModUpdater] Created backup version {} for mod {} at {:?} ({} of {} file(s) changed)",
+                            manifest.version, mod_id, backup_path, manifest.stored_files(), manifest.files.len());
+                    }
                 }
             }
         }
 
-        // Ignore this path in mod watcher during update operation
+        // Ignore this path in mod watcher during update operation, along with the folders of the swap
         ignore_path_in_watcher(mod_destination_path.clone()).await;
+        ignore_path_in_watcher(staging_path.clone()).await;
+        ignore_path_in_watcher(replaced_path.clone()).await;
         let _guard = WatcherIgnoreGuard::new(mod_destination_path.clone()).await;
+        let _staging_guard = WatcherIgnoreGuard::new(staging_path.clone()).await;
+        let _replaced_guard = WatcherIgnoreGuard::new(replaced_path.clone()).await;
 
         // Give mod watcher a moment to close any open file handles
         tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
 
-        // Remove existing mod folder if it exists (async with retry)
-        // Use retry logic to handle cases where mod watcher or other processes have files open
-        if mod_destination_path.exists() {
-            // Check if update was cancelled before removing existing folder
-            if is_update_cancelled() {
-                return Err("Update cancelled by user".to_string());
-            }
-            
-            Self::remove_dir_with_retry(&mod_destination_path, 3, 200).await
-                .map_err(|e| format!("Failed to remove existing mod folder: {}", e))?;
-        }
-
         // Copy mod from download folder to game mods folder
         let source_path = if mod_path.exists() && mod_path.is_dir() {
             eprintln!("[ModUpdater] Using mod_path as source: {:?}", mod_path);
@@ -342,31 +735,164 @@ impl ModUpdater {
         };
         
         if !source_path.exists() || !source_path.is_dir() {
-            return Err(format!("Source mod folder not found: {:?}", source_path));
+            return Err(format!("Source mod folder not found: {:?}", source_path).into());
         }
 
         // Verify source mod is complete before copying
         if !Self::verify_mod_complete(&source_path) {
-            return Err(format!("Source mod at {:?} appears incomplete or invalid. Refusing to copy.", source_path));
+            return Err(format!("Source mod at {:?} appears incomplete or invalid. Refusing to copy.", source_path).into());
         }
 
         // Check if update was cancelled before starting copy operation
         if is_update_cancelled() {
-            return Err("Update cancelled by user".to_string());
+            return Err(InstallError::Cancelled);
         }
 
-        eprintln!("[ModUpdater] Copying mod from {:?} to {:?}", source_path, mod_destination_path);
-        copy_dir_all_async(&source_path, &mod_destination_path).await
-            .map_err(|e| format!("Failed to copy mod: {}", e))?;
+        // A linked install points at a private copy of the download, so the next download or a cleanup of the
+        // SteamCMD content folder can't change or remove the installed files before they are backed up or replaced
+        let (source_path, moved_download) = if link_mode == LinkMode::Symlink {
+            let linked_path = linked_download_path(download_path, mod_id);
+            let (source, destination) = (source_path.clone(), linked_path.clone());
+            let moved = source_path.starts_with(download_path);
+            tokio::task::spawn_blocking(move || {
+                if moved {
+                    move_dir(&source, &destination)
+                } else {
+                    copy_dir_all_sync(&source, &destination, CopyOptions::default())
+                }
+            }).await
+            .map_err(|e| format!("Task panicked: {:?}", e))?
+            .map_err(|e| format!("Failed to store linked copy of mod {}: {}", mod_id, e))?;
+            (linked_path, moved.then_some(source_path))
+        } else {
+            (source_path, None)
+        };
+
+        let installed: Result<Option<PathBuf>, InstallError> = async {
+            eprintln!("[ModUpdater] Installing mod from {:?} to {:?} ({:?})", source_path, staging_path, link_mode);
+            // Copying a large mod takes a while, report progress so the UI doesn't look stuck
+            let files_copied = Arc::new(AtomicUsize::new(0));
+            let progress_task = match app {
+                Some(app) if link_mode == LinkMode::Copy => {
+                    emit_mod_lifecycle(app, mod_id, ModPhase::Installing, serde_json::Value::Null);
+                    Some(tokio::spawn(report_install_progress(app.clone(), mod_id.to_string(), source_path.clone(), files_copied.clone())))
+                }
+                _ => None,
+            };
+            let installed = install_dir_async(&source_path, &staging_path, link_mode, Some(files_copied.clone()), exclude).await;
+            if let Some(progress_task) = progress_task {
+                progress_task.abort();
+                if let (Some(app), Ok(())) = (app, &installed) {
+                    let copied = files_copied.load(Ordering::Relaxed);
+                    emit_mod_lifecycle(app, mod_id, ModPhase::InstallProgress, serde_json::json!({
+                        "filesCopied": copied,
+                        "totalFiles": copied,
+                    }));
+                }
+            }
+            installed.map_err(|e| format!("Failed to copy mod: {}", e))?;
+
+            // Verify copied mod is complete
+            if !Self::verify_mod_complete(&staging_path) {
+                return Err(format!("Copied mod at {:?} appears incomplete. Copy may have failed.", staging_path).into());
+            }
+
+            // Carry the preserved files over from the installed version
+            if !preserved_paths.is_empty() && mod_destination_path.is_dir() {
+                let (installed, staged, paths) = (mod_destination_path.clone(), staging_path.clone(), preserved_paths.clone());
+                tokio::task::spawn_blocking(move || copy_preserved_paths(&installed, &staged, &paths))
+                    .await
+                    .map_err(|e| format!("Task panicked: {:?}", e))?
+                    .map_err(|e| format!("Failed to keep preserved files: {}", e))?;
+            }
+
+            // Ensure PublishedFileId.txt exists after copying
+            Self::ensure_published_file_id(&staging_path, mod_id).await
+                .map_err(|e| format!("Failed to create PublishedFileId.txt: {}", e))?;
+
+            // Check if update was cancelled before replacing the installed version
+            if is_update_cancelled() {
+                return Err(InstallError::Cancelled);
+            }
+
+            // Move the installed version out of the way, into the backup directory for a full backup
+            let set_aside = match &deferred_backup {
+                Some(backup_path) => {
+                    if backup_path.symlink_metadata().is_ok() {
+                        Self::remove_dir_with_retry(backup_path, 3, 200).await
+                            .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+                    }
+                    remove_zip_backup(backup_path)?;
+                    match rename_with_retry(&mod_destination_path, backup_path).await {
+                        Ok(()) => {
+                            eprintln!("[ModUpdater] Moved previous version of mod {} to backup {:?}", mod_id, backup_path);
+                            Some(backup_path.clone())
+                        }
+                        // e.g. a backup directory on another drive, the previous version is copied there instead
+                        Err(e) => {
+                            eprintln!("[ModUpdater] {}, copying the backup instead", e);
+                            copy_dir_all_async(&mod_destination_path, backup_path).await
+                                .map_err(|e| format!("Failed to create backup: {}", e))?;
+                            eprintln!("[ModUpdater] Created backup for mod {} at {:?}", mod_id, backup_path);
+                            rename_with_retry(&mod_destination_path, &replaced_path).await?;
+                            Some(replaced_path.clone())
+                        }
+                    }
+                }
+                None if mod_destination_path.symlink_metadata().is_ok() => {
+                    rename_with_retry(&mod_destination_path, &replaced_path).await?;
+                    Some(replaced_path.clone())
+                }
+                None => None,
+            };
+
+            // Put the new version in place, or the previous one back if that fails
+            if let Err(e) = rename_with_retry(&staging_path, &mod_destination_path).await {
+                if let Some(set_aside) = &set_aside {
+                    if let Err(restore_error) = rename_with_retry(set_aside, &mod_destination_path).await {
+                        eprintln!("[ModUpdater] Failed to put previous version of mod {} back: {}", mod_id, restore_error);
+                    }
+                }
+                return Err(e.into());
+            }
+            Ok(set_aside)
+        }.await;
+
+        let set_aside = match installed {
+            Ok(set_aside) => set_aside,
+            Err(e) => {
+                if staging_path.symlink_metadata().is_ok() {
+                    if let Err(cleanup_error) = Self::remove_dir_with_retry(&staging_path, 3, 200).await {
+                        eprintln!("[ModUpdater] Failed to remove staging folder {:?}: {}", staging_path, cleanup_error);
+                    }
+                }
+                // Only removed once no other install uses it
+                let _ = fs::remove_dir(&staging_root);
+                if link_mode == LinkMode::Symlink {
+                    Self::discard_linked_download(&source_path, moved_download).await;
+                }
+                return Err(e);
+            }
+        };
 
-        // Verify copied mod is complete
-        if !Self::verify_mod_complete(&mod_destination_path) {
-            return Err(format!("Copied mod at {:?} appears incomplete. Copy may have failed.", mod_destination_path));
+        // The replaced version is no longer needed, a leftover is removed by the next install or orphan cleanup
+        if set_aside.as_ref() == Some(&replaced_path) {
+            let previous_link = fs::read_link(&replaced_path).ok();
+            if let Err(e) = Self::remove_dir_with_retry(&replaced_path, 3, 200).await {
+                eprintln!("[ModUpdater] Failed to remove previous version of mod {} at {:?}: {}", mod_id, replaced_path, e);
+            } else if let Some(previous_target) = previous_link.filter(|target| is_linked_download(target)) {
+                Self::discard_linked_download(&previous_target, None).await;
+            }
         }
+        let _ = fs::remove_dir(&staging_root);
 
-        // Ensure PublishedFileId.txt exists after copying
-        Self::ensure_published_file_id(&mod_destination_path, mod_id).await
-            .map_err(|e| format!("Failed to create PublishedFileId.txt: {}", e))?;
+        // Record the installed files for later integrity checks
+        let manifest_path = mod_destination_path.clone();
+        match tokio::task::spawn_blocking(move || write_checksum_manifest(&manifest_path)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("[ModUpdater] Failed to write checksum manifest for mod {}: {}", mod_id, e),
+            Err(e) => eprintln!("[ModUpdater] Task panicked writing checksum manifest for mod {}: {:?}", mod_id, e),
+        }
 
         // Manually unignore the path (this consumes the guard and prevents Drop from running)
         // If we reach here, the operation was successful
@@ -377,6 +903,24 @@ impl ModUpdater {
         Ok(mod_destination_path)
     }
 
+    /// Remove a private copy made for a linked install, or move it back to `download` if it was moved from there
+    async fn discard_linked_download(linked_path: &Path, download: Option<PathBuf>) {
+        let linked_path = linked_path.to_path_buf();
+        let result = tokio::task::spawn_blocking({
+            let linked_path = linked_path.clone();
+            move || match download {
+                Some(download) if !download.exists() => move_dir(&linked_path, &download),
+                _ => fs::remove_dir_all(extended_length_path(&linked_path))
+                    .map_err(|e| format!("Failed to remove {}: {}", linked_path.display(), e)),
+            }
+        }).await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("[ModUpdater] Failed to discard linked copy {:?}: {}", linked_path, e),
+            Err(e) => eprintln!("[ModUpdater] Task panicked discarding linked copy {:?}: {:?}", linked_path, e),
+        }
+    }
+
     /// Find existing mod folder with the given mod ID
     async fn find_existing_mod_folder(&self, mods_path: &Path, mod_id: &str) -> Result<Option<PathBuf>, String> {
         let entries = fs::read_dir(mods_path)
@@ -386,7 +930,7 @@ impl ModUpdater {
             let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
             let path = entry.path();
             
-            if path.is_dir() {
+            if path.is_dir() && !is_staging_mods_folder(&path) {
                 if let Ok(Some(found_mod_id)) = query_mod_id(&path) {
                     if found_mod_id == mod_id {
                         return Ok(Some(path));
@@ -398,10 +942,73 @@ impl ModUpdater {
         Ok(None)
     }
 
+    /// Remove folders that hold the same mod ID as the freshly installed one
+    /// This cleans up the old folder left behind when an update changes the folder name
+    /// With a `backup_directory` the folders are moved into it, otherwise they go to the recycle bin;
+    /// a folder that can't be put in either place is kept
+    /// Returns the list of removed folders
+    pub async fn remove_orphaned_mod_folders(&self, mods_path: &Path, mod_id: &str, installed_path: &Path, backup_directory: Option<&Path>) -> Result<Vec<PathBuf>, String> {
+        let (mods_path_owned, mod_id_owned, installed_owned) = (mods_path.to_path_buf(), mod_id.to_string(), installed_path.to_path_buf());
+        let orphans = tokio::task::spawn_blocking(move || find_orphaned_mod_folders(&mods_path_owned, &mod_id_owned, &installed_owned))
+            .await
+            .map_err(|e| format!("Task panicked: {:?}", e))??;
+
+        let mut removed = Vec::new();
+        for orphan in orphans {
+            ignore_path_in_watcher(orphan.clone()).await;
+            let _guard = WatcherIgnoreGuard::new(orphan.clone()).await;
+            let result = match backup_directory {
+                Some(backup_dir) => Self::move_orphan_to_backup(&orphan, backup_dir).await,
+                None => {
+                    let orphan = orphan.clone();
+                    tokio::task::spawn_blocking(move || trash::delete(&orphan).map_err(|e| format!("Failed to move to recycle bin: {}", e)))
+                        .await
+                        .map_err(|e| format!("Task panicked: {:?}", e))
+                        .and_then(|result| result)
+                }
+            };
+            match result {
+                Ok(()) => {
+                    eprintln!("[ModUpdater] Removed orphaned folder {:?} for mod {} ({})", orphan, mod_id,
+                        if backup_directory.is_some() { "moved to backups" } else { "moved to recycle bin" });
+                    removed.push(orphan);
+                }
+                Err(e) => {
+                    eprintln!("[ModUpdater] Failed to remove orphaned folder {:?} for mod {}: {}", orphan, mod_id, e);
+                }
+            }
+            _guard.unignore().await;
+        }
+
+        Ok(removed)
+    }
+
+    /// Move an orphaned mod folder into the backup directory, replacing an older backup of the same folder
+    async fn move_orphan_to_backup(orphan: &Path, backup_dir: &Path) -> Result<(), String> {
+        let folder_name = orphan.file_name()
+            .ok_or_else(|| format!("Invalid mod folder: {:?}", orphan))?;
+        fs::create_dir_all(backup_dir)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+        let backup_path = backup_dir.join(folder_name);
+        if backup_path.symlink_metadata().is_ok() {
+            Self::remove_dir_with_retry(&backup_path, 3, 200).await
+                .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+        }
+        remove_zip_backup(&backup_path)?;
+        if let Err(e) = rename_with_retry(orphan, &backup_path).await {
+            // e.g. a backup directory on another drive
+            eprintln!("[ModUpdater] {}, copying the backup instead", e);
+            copy_dir_all_async(orphan, &backup_path).await
+                .map_err(|e| format!("Failed to create backup: {}", e))?;
+            Self::remove_dir_with_retry(orphan, 3, 200).await?;
+        }
+        Ok(())
+    }
+
     /// Ensure PublishedFileId.txt exists in the mod's About folder
     /// Creates the file if it doesn't exist
     async fn ensure_published_file_id(mod_path: &Path, mod_id: &str) -> Result<(), String> {
-        let about_path = mod_path.join("About");
+        let about_path = find_about_dir(mod_path);
         
         // Check if About folder exists, if not, create it
         if !about_path.exists() {
@@ -417,7 +1024,7 @@ impl ModUpdater {
             // Verify it contains the correct mod ID
             match fs::read_to_string(&file_id_path) {
                 Ok(content) => {
-                    let existing_id = content.trim();
+                    let existing_id = clean_published_file_id(&content);
                     if existing_id == mod_id {
                         // File exists and has correct ID, nothing to do
                         return Ok(());
@@ -436,7 +1043,7 @@ impl ModUpdater {
             let file_id_path = file_id_path.clone();
             let mod_id = mod_id.to_string();
             move || {
-                fs::write(&file_id_path, mod_id)
+                write_mod_file(&file_id_path, mod_id)
                     .map_err(|e| format!("Failed to write PublishedFileId.txt: {}", e))
             }
         }).await
@@ -449,15 +1056,15 @@ impl ModUpdater {
 
     /// Extract packageId from About.xml
     /// Returns None if About.xml doesn't exist or packageId cannot be found
-    fn get_package_id(mod_path: &Path) -> Option<String> {
-        let about_path = mod_path.join("About");
+    pub fn get_package_id(mod_path: &Path) -> Option<String> {
+        let about_path = find_about_dir(mod_path);
         let about_xml_path = about_path.join("About.xml");
         
         if !about_xml_path.exists() {
             return None;
         }
         
-        let content = match fs::read_to_string(&about_xml_path) {
+        let content = match read_mod_xml(&about_xml_path) {
             Ok(c) => c,
             Err(_) => return None,
         };
@@ -527,13 +1134,15 @@ impl ModUpdater {
     /// Remove directory with retry logic and delay to handle file locks
     /// This is useful when mod watcher or other processes might have files open
     async fn remove_dir_with_retry(path: &Path, max_retries: u32, delay_ms: u64) -> Result<(), String> {
-        let path = path.to_path_buf();
+        let path = extended_length_path(path);
         
         for attempt in 1..=max_retries {
             let result = tokio::task::spawn_blocking({
                 let path = path.clone();
                 move || {
-                    if path.exists() {
+                    if path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()) {
+                        remove_dir_symlink(&path)
+                    } else if path.exists() {
                         fs::remove_dir_all(&path)
                     } else {
                         Ok(())
@@ -585,7 +1194,7 @@ impl ModUpdater {
         }
         
         // Check for About folder (essential for RimWorld mods)
-        let about_path = mod_path.join("About");
+        let about_path = find_about_dir(mod_path);
         if !about_path.exists() || !about_path.is_dir() {
             eprintln!("[ModUpdater] Mod missing About folder: {:?}", mod_path);
             return false;
@@ -603,7 +1212,7 @@ impl ModUpdater {
         }
         
         // Check for About folder
-        let about_path = mod_path.join("About");
+        let about_path = find_about_dir(mod_path);
         if !about_path.exists() || !about_path.is_dir() {
             return true; // Missing About folder - corrupted
         }
@@ -618,19 +1227,246 @@ impl ModUpdater {
     }
 }
 
+/// A mod folder missing its About folder or About.xml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptedMod {
+    pub mod_path: String,
+    pub folder: String,
+    /// Workshop ID from PublishedFileId.txt or a folder named after it
+    pub mod_id: Option<String>,
+    /// The Workshop ID could not be recovered, the folder can only be deleted manually
+    pub unrecoverable: bool,
+}
+
+/// Find corrupted mod folders (see `ModUpdater::is_mod_corrupted`), sorted by folder name
+/// Mods with a recovered ID can be re-downloaded with `repair_mods`
+pub fn scan_corrupted_mods(mods_path: &Path) -> Result<Vec<CorruptedMod>, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+
+    let mut corrupted: Vec<CorruptedMod> = entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| ModUpdater::is_mod_corrupted(path))
+        .map(|path| {
+            let mod_id = recover_mod_id(&path);
+            CorruptedMod {
+                mod_path: path.to_string_lossy().to_string(),
+                folder: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                unrecoverable: mod_id.is_none(),
+                mod_id,
+            }
+        })
+        .collect();
+    corrupted.sort_by(|a, b| a.folder.cmp(&b.folder));
+    Ok(corrupted)
+}
+
+/// Workshop ID of a corrupted mod folder
+/// PublishedFileId.txt may be left over in the About folder or next to it, otherwise SteamCMD's
+/// folders are named after the ID
+fn recover_mod_id(mod_path: &Path) -> Option<String> {
+    [find_about_dir(mod_path).join("PublishedFileId.txt"), mod_path.join("PublishedFileId.txt")].iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|content| parse_workshop_id(clean_published_file_id(&content)))
+        .or_else(|| mod_path.file_name().and_then(|name| parse_workshop_id(&name.to_string_lossy())))
+}
+
+/// Modification times may be rounded (2 seconds on FAT), so newer files only count beyond this tolerance
+const LOCAL_CHANGE_TOLERANCE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Files of an installed mod changed after it was installed, relative to the mod folder
+/// The baseline is the time `.lastupdated` was written; mods without it and linked mods report no changes
+pub fn detect_local_changes(mod_path: &Path) -> Vec<PathBuf> {
+    // A linked mod shares its files with the download, so an update doesn't touch its own folder
+    if mod_path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()) {
+        return Vec::new();
+    }
+    let Ok(baseline) = fs::metadata(find_about_dir(mod_path).join(".lastupdated")).and_then(|m| m.modified()) else {
+        return Vec::new();
+    };
+    let baseline = baseline + LOCAL_CHANGE_TOLERANCE;
+
+    let mut files = Vec::new();
+    if let Err(e) = collect_files(mod_path, mod_path, &mut files) {
+        eprintln!("[ModUpdater] Failed to check {:?} for local changes: {}", mod_path, e);
+        return Vec::new();
+    }
+    let mut changed: Vec<PathBuf> = files.into_iter()
+        .filter(|(relative, _)| !is_app_managed_file(relative))
+        .filter(|(_, path)| fs::metadata(path).and_then(|m| m.modified()).is_ok_and(|modified| modified > baseline))
+        .map(|(relative, _)| PathBuf::from(relative))
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Path with the `\\?\` prefix on Windows, so files deeper than MAX_PATH (260 characters) can be accessed
+/// Relative paths are made absolute first, other platforms get the path unchanged
+pub fn extended_length_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        let path_str = path.to_string_lossy();
+        if path_str.starts_with(r"\\?\") {
+            return path.to_path_buf();
+        }
+        // The prefix turns off path normalization, so `..` and `/` are resolved beforehand
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let absolute_str = absolute.to_string_lossy();
+        if let Some(unc) = absolute_str.strip_prefix(r"\\") {
+            PathBuf::from(format!(r"\\?\UNC\{}", unc))
+        } else {
+            PathBuf::from(format!(r"\\?\{}", absolute_str))
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        path.to_path_buf()
+    }
+}
+
 /// Recursively copy directory (async version using spawn_blocking)
 pub async fn copy_dir_all_async(src: &Path, dst: &Path) -> Result<(), String> {
+    let src = extended_length_path(src);
+    let dst = extended_length_path(dst);
+    
+    tokio::task::spawn_blocking(move || {
+        copy_dir_all_sync(&src, &dst, CopyOptions::default())
+    }).await
+    .map_err(|e| format!("Task panicked: {:?}", e))?
+}
+
+/// Interval between `install-progress` events of a mod
+const INSTALL_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Emit InstallProgress for a mod being copied from `source_path` until aborted
+/// Events are only emitted when the number of copied files changed since the last one
+async fn report_install_progress(app: AppHandle, mod_id: String, source_path: PathBuf, files_copied: Arc<AtomicUsize>) {
+    let total_files = tokio::task::spawn_blocking(move || count_files(&source_path)).await.unwrap_or(0);
+    let mut reported = None;
+    loop {
+        let copied = files_copied.load(Ordering::Relaxed);
+        if reported != Some(copied) {
+            reported = Some(copied);
+            emit_mod_lifecycle(&app, &mod_id, ModPhase::InstallProgress, serde_json::json!({
+                "filesCopied": copied,
+                "totalFiles": total_files.max(copied),
+            }));
+        }
+        tokio::time::sleep(INSTALL_PROGRESS_INTERVAL).await;
+    }
+}
+
+/// Number of regular files in a directory tree, symlinks are not followed
+fn count_files(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else { return 0 };
+    entries.flatten().map(|entry| {
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => count_files(&entry.path()),
+            Ok(file_type) if file_type.is_file() => 1,
+            _ => 0,
+        }
+    }).sum()
+}
+
+/// Folder in the download directory holding the private copies linked installs point at
+pub const LINKED_DOWNLOADS_FOLDER: &str = ".linked";
+
+/// A new folder for a private copy of a download, one per installed version
+fn linked_download_path(download_path: &Path, mod_id: &str) -> PathBuf {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    download_path.join(LINKED_DOWNLOADS_FOLDER).join(format!("{}-{}", mod_id, stamp))
+}
+
+/// Whether a link target is a private copy made for a linked install
+fn is_linked_download(target: &Path) -> bool {
+    target.parent()
+        .and_then(|parent| parent.file_name())
+        .is_some_and(|name| name == LINKED_DOWNLOADS_FOLDER)
+}
+
+/// Place a downloaded mod at `dst` using the given link mode
+/// `files_copied` counts the files copied so far in Copy mode
+/// Files matched by `exclude` are left out, except in Symlink mode where the whole folder is linked
+pub async fn install_dir_async(
+    src: &Path,
+    dst: &Path,
+    link_mode: LinkMode,
+    files_copied: Option<Arc<AtomicUsize>>,
+    exclude: Option<GlobSet>,
+) -> Result<(), String> {
     let src = src.to_path_buf();
     let dst = dst.to_path_buf();
     
     tokio::task::spawn_blocking(move || {
-        copy_dir_all_sync(&src, &dst)
+        let exclude = exclude.as_ref();
+        match link_mode {
+            LinkMode::Copy => {
+                let count_file = || {
+                    if let Some(files_copied) = &files_copied {
+                        files_copied.fetch_add(1, Ordering::Relaxed);
+                    }
+                };
+                copy_dir_all_sync(&src, &dst, CopyOptions { on_file: Some(&count_file), exclude })
+            }
+            LinkMode::Symlink => {
+                // Link to an absolute path so the link survives a moved working directory
+                let target = src.canonicalize()
+                    .map_err(|e| format!("Failed to resolve {}: {}", src.display(), e))?;
+                symlink_dir(&target, &dst)
+                    .map_err(|e| format!("Failed to link {} to {}: {}", dst.display(), target.display(), e))
+            }
+            LinkMode::Hardlink => {
+                if let Err(e) = hard_link_dir_all_sync(&src, &dst, exclude) {
+                    eprintln!("[ModUpdater] Hard linking failed ({}), copying instead", e);
+                    if dst.exists() {
+                        fs::remove_dir_all(&dst)
+                            .map_err(|e| format!("Failed to remove partial link of {}: {}", dst.display(), e))?;
+                    }
+                    copy_dir_all_sync(&src, &dst, CopyOptions { on_file: None, exclude })
+                } else {
+                    Ok(())
+                }
+            }
+        }
     }).await
     .map_err(|e| format!("Task panicked: {:?}", e))?
 }
 
-/// Recursively copy directory (synchronous version for use in spawn_blocking)
-fn copy_dir_all_sync(src: &Path, dst: &Path) -> Result<(), String> {
+#[cfg(unix)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+/// Remove a symlink to a directory without touching its target
+fn remove_dir_symlink(path: &Path) -> std::io::Result<()> {
+    // Windows directory symlinks are removed as directories, unix ones as files
+    #[cfg(windows)]
+    {
+        fs::remove_dir(path)
+    }
+    #[cfg(not(windows))]
+    {
+        fs::remove_file(path)
+    }
+}
+
+/// Recreate the directory tree of `src` at `dst`, hard linking every file not matched by `exclude`
+/// Fails when `src` and `dst` are on different filesystems
+fn hard_link_dir_all_sync(src: &Path, dst: &Path, exclude: Option<&GlobSet>) -> Result<(), String> {
+    hard_link_dir_tree(src, dst, Path::new(""), exclude)
+}
+
+/// `relative` is the path of `src` relative to the linked folder
+fn hard_link_dir_tree(src: &Path, dst: &Path, relative: &Path, exclude: Option<&GlobSet>) -> Result<(), String> {
     fs::create_dir_all(dst)
         .map_err(|e| format!("Failed to create directory {}: {}", dst.display(), e))?;
     
@@ -638,28 +1474,532 @@ fn copy_dir_all_sync(src: &Path, dst: &Path) -> Result<(), String> {
         .map_err(|e| format!("Failed to read directory {}: {}", src.display(), e))? {
         let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
         let path = entry.path();
-        let file_name = entry.file_name();
-        let dst_path = dst.join(&file_name);
+        let dst_path = dst.join(entry.file_name());
+        let relative = relative.join(entry.file_name());
         
-        if path.is_dir() {
-            copy_dir_all_sync(&path, &dst_path)?;
+        if is_excluded(exclude, &relative) {
+            continue;
+        }
+        if is_symlink(&entry) {
+            copy_symlink(&path, &dst_path);
+        } else if path.is_dir() {
+            hard_link_dir_tree(&path, &dst_path, &relative, exclude)?;
         } else {
-            fs::copy(&path, &dst_path)
-                .map_err(|e| format!("Failed to copy {} to {}: {}", path.display(), dst_path.display(), e))?;
+            fs::hard_link(&path, &dst_path)
+                .map_err(|e| format!("Failed to hard link {} to {}: {}", path.display(), dst_path.display(), e))?;
         }
     }
     
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+/// Options of a recursive copy
+#[derive(Clone, Copy, Default)]
+struct CopyOptions<'a> {
+    /// Called after every copied file, should be cheap, e.g. increment a counter
+    on_file: Option<&'a dyn Fn()>,
+    /// Files and folders not copied, matched by name and by path relative to the copied folder
+    exclude: Option<&'a GlobSet>,
+}
 
-    #[test]
-    fn test_sanitize_folder_name() {
-        assert_eq!(ModUpdater::sanitize_folder_name("Test Mod"), "Test Mod");
+/// Recursively copy directory (synchronous version for use in spawn_blocking)
+/// Symlinks are recreated at the destination instead of followed
+fn copy_dir_all_sync(src: &Path, dst: &Path, options: CopyOptions) -> Result<(), String> {
+    let (src, dst) = (&extended_length_path(src), &extended_length_path(dst));
+    copy_dir_tree(src, dst, Path::new(""), &mut HashSet::new(), options)
+}
+
+/// `relative` is the path of `src` relative to the copied folder
+/// `visited` holds the (device, inode) of every directory copied so far, so a directory reachable
+/// twice (e.g. through a bind mount) is not copied into itself forever
+fn copy_dir_tree(src: &Path, dst: &Path, relative: &Path, visited: &mut HashSet<(u64, u64)>, options: CopyOptions) -> Result<(), String> {
+    if let Some(id) = dir_identity(src) {
+        if !visited.insert(id) {
+            eprintln!("[ModUpdater] Skipping {}, it was already copied", src.display());
+            return Ok(());
+        }
+    }
+    fs::create_dir_all(dst)
+        .map_err(|e| format!("Failed to create directory {}: {}", dst.display(), e))?;
+    
+    for entry in fs::read_dir(src)
+        .map_err(|e| format!("Failed to read directory {}: {}", src.display(), e))? {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+        let file_name = entry.file_name();
+        let dst_path = dst.join(&file_name);
+        let relative = relative.join(&file_name);
+        
+        if is_excluded(options.exclude, &relative) {
+            continue;
+        }
+        if is_symlink(&entry) {
+            copy_symlink(&path, &dst_path);
+        } else if path.is_dir() {
+            copy_dir_tree(&path, &dst_path, &relative, visited, options)?;
+        } else {
+            copy_file_with_retry(&path, &dst_path)?;
+            if let Some(on_file) = options.on_file {
+                on_file();
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Folders in `mods_path` other than `installed_path` holding the mod `mod_id`
+fn find_orphaned_mod_folders(mods_path: &Path, mod_id: &str, installed_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let installed_canonical = installed_path.canonicalize()
+        .unwrap_or_else(|_| installed_path.to_path_buf());
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+
+    let mut orphans = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = entry.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if canonical == installed_canonical {
+            continue;
+        }
+
+        if let Ok(Some(found_mod_id)) = query_mod_id(&path) {
+            if found_mod_id == mod_id {
+                orphans.push(path);
+            }
+        }
+    }
+    Ok(orphans)
+}
+
+/// Copy the preserved files and folders found in `from` to `to`, replacing what is there
+fn copy_preserved_paths(from: &Path, to: &Path, preserved_paths: &[PathBuf]) -> Result<(), String> {
+    for relative in preserved_paths {
+        let source = from.join(relative);
+        let Ok(metadata) = source.symlink_metadata() else { continue };
+        let destination = to.join(relative);
+        if let Ok(existing) = destination.symlink_metadata() {
+            if existing.is_dir() { fs::remove_dir_all(&destination) } else { fs::remove_file(&destination) }
+                .map_err(|e| format!("Failed to replace {}: {}", destination.display(), e))?;
+        }
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        if metadata.is_symlink() {
+            copy_symlink(&source, &destination);
+        } else if metadata.is_dir() {
+            copy_dir_all_sync(&source, &destination, CopyOptions::default())?;
+        } else {
+            copy_file_with_retry(&source, &destination)?;
+        }
+    }
+    Ok(())
+}
+
+/// Attempts at copying a file locked by another process before giving up
+const COPY_ATTEMPTS: u32 = 3;
+/// Wait between attempts at copying a locked file
+const COPY_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Copy a file, retrying when it is briefly locked, e.g. by an antivirus scan or the running game
+fn copy_file_with_retry(src: &Path, dst: &Path) -> Result<(), String> {
+    retry_transient(|| fs::copy(src, dst), COPY_ATTEMPTS, COPY_RETRY_DELAY, &format!("copy {}", src.display()))
+        .map(|_| ())
+        .map_err(|e| format!("Failed to copy {} to {}: {}", src.display(), dst.display(), e))
+}
+
+/// Remove the compressed backup of the backup folder `backup_path`, it would outlive the new folder backup
+fn remove_zip_backup(backup_path: &Path) -> Result<(), String> {
+    let zip_path = zip_backup_path(backup_path);
+    if zip_path.is_file() {
+        fs::remove_file(&zip_path)
+            .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Rename a file or folder, retrying when it is briefly locked
+async fn rename_with_retry(from: &Path, to: &Path) -> Result<(), String> {
+    let (from, to) = (extended_length_path(from), extended_length_path(to));
+    tokio::task::spawn_blocking(move || {
+        retry_transient(|| fs::rename(&from, &to), COPY_ATTEMPTS, COPY_RETRY_DELAY, &format!("move {}", from.display()))
+            .map_err(|e| format!("Failed to move {} to {}: {}", from.display(), to.display(), e))
+    }).await
+    .map_err(|e| format!("Task panicked: {:?}", e))?
+}
+
+/// Run a blocking file operation up to `attempts` times, retrying only errors caused by a lock held elsewhere
+fn retry_transient<T>(mut operation: impl FnMut() -> std::io::Result<T>, attempts: u32, delay: Duration, what: &str) -> std::io::Result<T> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(e) if attempt < attempts && is_transient_lock_error(&e) => {
+                eprintln!("[ModUpdater] Attempt {} to {} failed: {}. Retrying in {}ms...", attempt, what, e, delay.as_millis());
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether an IO error means another process holds the file, rather than that the copy can't succeed
+fn is_transient_lock_error(error: &std::io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION
+    #[cfg(windows)]
+    if matches!(error.raw_os_error(), Some(32) | Some(33)) {
+        return true;
+    }
+    matches!(
+        error.kind(),
+        std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::WouldBlock | std::io::ErrorKind::ResourceBusy
+    )
+}
+
+/// Check a path relative to the copied folder against the exclude patterns, by name and by path
+fn is_excluded(exclude: Option<&GlobSet>, relative: &Path) -> bool {
+    let Some(exclude) = exclude else { return false };
+    relative.file_name().is_some_and(|name| exclude.is_match(name)) || exclude.is_match(relative)
+}
+
+/// Check if a directory entry is a symlink, without following it
+fn is_symlink(entry: &fs::DirEntry) -> bool {
+    entry.file_type().map(|t| t.is_symlink()).unwrap_or(false)
+}
+
+/// Device and inode of a directory
+#[cfg(unix)]
+fn dir_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_identity(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Recreate a symlink at `dst` pointing at the same target, skipped with a warning if that fails
+/// (e.g. creating symlinks requires developer mode on Windows)
+fn copy_symlink(link: &Path, dst: &Path) {
+    let result = fs::read_link(link).and_then(|target| {
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&target, dst)
+        }
+        #[cfg(windows)]
+        {
+            if link.is_dir() {
+                std::os::windows::fs::symlink_dir(&target, dst)
+            } else {
+                std::os::windows::fs::symlink_file(&target, dst)
+            }
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("[ModUpdater] Skipping symlink {}, it could not be recreated: {}", link.display(), e);
+    }
+}
+
+/// Workshop update time recorded in a mod's `.lastupdated` when it was installed
+fn installed_time_updated(mod_path: &Path) -> Option<i64> {
+    fs::read_to_string(find_about_dir(mod_path).join(".lastupdated"))
+        .ok()
+        .and_then(|content| content.trim().parse().ok())
+}
+
+/// Check whether a download holds an error page (from SteamCMD or a proxy) instead of mod content
+/// Only About.xml and the top-level files are read, so large mods stay cheap to check
+pub fn detect_junk_download(mod_path: &Path) -> bool {
+    let about_xml_path = find_about_dir(mod_path).join("About.xml");
+    if about_xml_path.is_file() && (is_html_file(&about_xml_path) || !is_well_formed_xml(&about_xml_path)) {
+        return true;
+    }
+    fs::read_dir(mod_path)
+        .map(|entries| entries.flatten().any(|entry| entry.path().is_file() && is_html_file(&entry.path())))
+        .unwrap_or(false)
+}
+
+/// Whether a file starts like an HTML page
+fn is_html_file(path: &Path) -> bool {
+    use std::io::Read;
+    let mut head = [0u8; 256];
+    let read = match fs::File::open(path).and_then(|mut file| file.read(&mut head)) {
+        Ok(read) => read,
+        Err(_) => return false,
+    };
+    let head = String::from_utf8_lossy(&head[..read]).to_ascii_lowercase();
+    let head = head.trim_start_matches(|c: char| c.is_whitespace() || c == '\u{FEFF}');
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+/// Whether a file parses as XML
+fn is_well_formed_xml(path: &Path) -> bool {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(_) => return false,
+    };
+    let mut reader = Reader::from_reader(content.as_slice());
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => return true,
+            Ok(_) => buf.clear(),
+            Err(_) => return false,
+        }
+    }
+}
+
+/// Move a folder with everything in it (About, `.lastupdated`), copying it when it crosses drives
+/// Synchronous, use in spawn_blocking
+pub fn move_dir(src: &Path, dst: &Path) -> Result<(), String> {
+    if dst.exists() {
+        return Err(format!("Destination already exists: {:?}", dst));
+    }
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(extended_length_path(parent))
+            .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+    }
+    // A rename fails between filesystems, fall back to copy and delete
+    if fs::rename(extended_length_path(src), extended_length_path(dst)).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = copy_dir_all_sync(src, dst, CopyOptions::default()) {
+        let _ = fs::remove_dir_all(extended_length_path(dst));
+        return Err(e);
+    }
+    fs::remove_dir_all(extended_length_path(src))
+        .map_err(|e| format!("Failed to remove {} after copying it: {}", src.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_local_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Harmony");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::create_dir_all(mod_path.join("Patches")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData/>").unwrap();
+        fs::write(mod_path.join("Patches").join("Patch.xml"), "<Patch/>").unwrap();
+
+        // No baseline, nothing can be told apart from the install
+        assert!(detect_local_changes(&mod_path).is_empty());
+
+        fs::write(mod_path.join("About").join(".lastupdated"), "1700000000").unwrap();
+        assert!(detect_local_changes(&mod_path).is_empty());
+
+        let edited_at = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        for file in [mod_path.join("Patches").join("Patch.xml"), mod_path.join("About").join(".ignoredupdate")] {
+            fs::write(&file, "edited").unwrap();
+            fs::File::options().write(true).open(&file).unwrap().set_modified(edited_at).unwrap();
+        }
+        // Files written by the app itself are not local changes
+        assert_eq!(detect_local_changes(&mod_path), vec![PathBuf::from("Patches/Patch.xml")]);
+    }
+
+    #[cfg(windows)]
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_all_keeps_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        let dst = temp_dir.path().join("dst");
+        fs::create_dir_all(src.join("Textures")).unwrap();
+        fs::write(src.join("Textures").join("a.png"), "png").unwrap();
+        std::os::unix::fs::symlink("Textures/a.png", src.join("b.png")).unwrap();
+        // Following this link would copy the folder into itself forever
+        std::os::unix::fs::symlink(".", src.join("Loop")).unwrap();
+
+        copy_dir_all_sync(&src, &dst, CopyOptions::default()).unwrap();
+        assert_eq!(fs::read_to_string(dst.join("Textures").join("a.png")).unwrap(), "png");
+        assert_eq!(fs::read_link(dst.join("b.png")).unwrap(), PathBuf::from("Textures/a.png"));
+        assert_eq!(fs::read_to_string(dst.join("b.png")).unwrap(), "png");
+        assert_eq!(fs::read_link(dst.join("Loop")).unwrap(), PathBuf::from("."));
+    }
+
+    #[tokio::test]
+    async fn test_install_limiter_bounds_concurrent_installs() {
+        let limiter = InstallLimiter::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let installs = (0..8).map(|_| {
+            let (limiter, running, max_running) = (limiter.clone(), running.clone(), max_running.clone());
+            tokio::spawn(async move {
+                let _permit = limiter.acquire().await;
+                let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now_running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+        }).collect::<Vec<_>>();
+        for install in installs {
+            install.await.unwrap();
+        }
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_retry_transient_only_retries_lock_errors() {
+        use std::io::{Error, ErrorKind};
+
+        let mut calls = 0;
+        let result = retry_transient(|| {
+            calls += 1;
+            if calls < 3 { Err(Error::from(ErrorKind::PermissionDenied)) } else { Ok(calls) }
+        }, 3, Duration::ZERO, "copy");
+        assert_eq!(result.unwrap(), 3);
+
+        // Gives up after the last attempt
+        let mut calls = 0;
+        let result: std::io::Result<()> = retry_transient(|| { calls += 1; Err(Error::from(ErrorKind::WouldBlock)) }, 3, Duration::ZERO, "copy");
+        assert_eq!((result.unwrap_err().kind(), calls), (ErrorKind::WouldBlock, 3));
+
+        // Missing sources and full disks are not retried
+        for kind in [ErrorKind::NotFound, ErrorKind::StorageFull] {
+            let mut calls = 0;
+            let result: std::io::Result<()> = retry_transient(|| { calls += 1; Err(Error::from(kind)) }, 3, Duration::ZERO, "copy");
+            assert_eq!((result.unwrap_err().kind(), calls), (kind, 1));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_long_path_copy_and_remove() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Source");
+        let nested = (0..12).fold(source.clone(), |path, depth| path.join(format!("Deeply Nested Folder {:02}", depth)));
+        assert!(nested.to_string_lossy().len() > 260);
+        fs::create_dir_all(extended_length_path(&nested)).unwrap();
+        fs::write(extended_length_path(&nested.join("Patch.xml")), "<Patch/>").unwrap();
+
+        let destination = temp_dir.path().join("A Mod With A Long Sanitized Title");
+        copy_dir_all_async(&source, &destination).await.unwrap();
+        let copied = destination.join(nested.strip_prefix(&source).unwrap()).join("Patch.xml");
+        assert_eq!(fs::read_to_string(extended_length_path(&copied)).unwrap(), "<Patch/>");
+
+        ModUpdater::remove_dir_with_retry(&destination, 1, 0).await.unwrap();
+        assert!(!destination.exists());
+    }
+
+    #[test]
+    fn test_extended_length_path() {
+        let path = Path::new("Mods").join("Harmony");
+        if cfg!(windows) {
+            let extended = extended_length_path(&path);
+            assert!(extended.to_string_lossy().starts_with(r"\\?\"));
+            assert!(extended.is_absolute());
+            assert_eq!(extended_length_path(&extended), extended);
+        } else {
+            assert_eq!(extended_length_path(&path), path);
+        }
+    }
+
+    #[test]
+    fn test_detect_junk_download() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("2009463077");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData><packageId>brrainz.harmony</packageId></ModMetaData>").unwrap();
+        fs::write(mod_path.join("LoadFolders.xml"), "<loadFolders></loadFolders>").unwrap();
+        assert!(!detect_junk_download(&mod_path));
+
+        // Error page saved in place of a file
+        fs::write(mod_path.join("Harmony.dll"), "\n<!DOCTYPE HTML><html><body>502 Bad Gateway</body></html>").unwrap();
+        assert!(detect_junk_download(&mod_path));
+        fs::remove_file(mod_path.join("Harmony.dll")).unwrap();
+
+        // Truncated or garbled About.xml
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData><packageId>brrainz.harmony</name></ModMetaData>").unwrap();
+        assert!(detect_junk_download(&mod_path));
+    }
+
+    #[test]
+    fn test_scan_corrupted_mods() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        fs::create_dir_all(mods_path.join("Valid").join("About")).unwrap();
+        fs::write(mods_path.join("Valid").join("About").join("About.xml"), "<ModMetaData/>").unwrap();
+        // About.xml is missing but PublishedFileId.txt survived
+        fs::create_dir_all(mods_path.join("Harmony").join("About")).unwrap();
+        fs::write(mods_path.join("Harmony").join("About").join("PublishedFileId.txt"), "2009463077\n").unwrap();
+        fs::create_dir_all(mods_path.join("818773962").join("Textures")).unwrap();
+        fs::create_dir_all(mods_path.join("Broken").join("Defs")).unwrap();
+
+        let corrupted = scan_corrupted_mods(mods_path).unwrap();
+        let summary: Vec<(&str, Option<&str>, bool)> = corrupted.iter()
+            .map(|m| (m.folder.as_str(), m.mod_id.as_deref(), m.unrecoverable))
+            .collect();
+        assert_eq!(summary, vec![
+            ("818773962", Some("818773962"), false),
+            ("Broken", None, true),
+            ("Harmony", Some("2009463077"), false),
+        ]);
+    }
+
+    #[test]
+    fn test_move_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Mods").join("Harmony");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("PublishedFileId.txt"), "2009463077").unwrap();
+        fs::write(mod_path.join(".lastupdated"), "1700000000").unwrap();
+
+        let disabled_path = temp_dir.path().join("Mods").join(".disabled").join("Harmony");
+        move_dir(&mod_path, &disabled_path).unwrap();
+        assert!(!mod_path.exists());
+        assert_eq!(fs::read_to_string(disabled_path.join(".lastupdated")).unwrap(), "1700000000");
+        assert!(disabled_path.join("About").join("PublishedFileId.txt").exists());
+
+        // Never merges into an existing folder
+        fs::create_dir_all(&mod_path).unwrap();
+        assert!(move_dir(&disabled_path, &mod_path).is_err());
+        assert!(disabled_path.exists());
+    }
+
+    #[test]
+    fn test_rename_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Harmony");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("PublishedFileId.txt"), "2009463077").unwrap();
+        fs::create_dir_all(temp_dir.path().join("Taken")).unwrap();
+
+        assert_eq!(ModUpdater::rename_target(&mod_path, "  Harmony: Lib ").unwrap(), temp_dir.path().join("Harmony Lib"));
+        assert_eq!(ModUpdater::rename_target(&mod_path, "Harmony").unwrap(), mod_path);
+        // Existing folders are never replaced
+        assert_eq!(ModUpdater::rename_target(&mod_path, "Taken").unwrap(), temp_dir.path().join("Taken_"));
+        fs::create_dir_all(temp_dir.path().join("Taken_")).unwrap();
+        assert_eq!(ModUpdater::rename_target(&mod_path, "Taken").unwrap(), temp_dir.path().join("Taken__"));
+    }
+
+    #[test]
+    fn test_install_error_serialization() {
+        let conflict = InstallError::CorruptedModConflict {
+            folder_name: "Folder".to_string(),
+            mod_id: "1".to_string(),
+            mod_title: "Title".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&conflict).unwrap(),
+            serde_json::json!({ "kind": "corruptedModConflict", "folderName": "Folder", "modId": "1", "modTitle": "Title" })
+        );
+        assert_eq!(serde_json::to_value(InstallError::Cancelled).unwrap(), serde_json::json!({ "kind": "cancelled" }));
+    }
+
+    #[test]
+    fn test_sanitize_folder_name() {
+        assert_eq!(ModUpdater::sanitize_folder_name("Test Mod"), "Test Mod");
         assert_eq!(ModUpdater::sanitize_folder_name("Test<Mod>"), "TestMod");
         assert_eq!(ModUpdater::sanitize_folder_name(""), "Mod");
         assert_eq!(ModUpdater::sanitize_folder_name("   "), "Mod");
@@ -800,13 +2140,10 @@ mod tests {
         let result = updater.update_mod(
             "123456789",
             &source_mod,
-            &download_path,
-            &mods_path,
             Some("123456789"), // Provide folder name explicitly
-            false,
             None,
             None,
-            None, // force_overwrite_corrupted
+            &InstallOptions::new(download_path.clone(), mods_path.clone()),
         ).await.unwrap();
         
         assert!(result.exists());
@@ -814,6 +2151,85 @@ mod tests {
         assert_eq!(result.file_name().unwrap(), "123456789");
     }
 
+    #[tokio::test]
+    async fn test_update_mod_install_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+
+        let source_mod = download_path.join("123456789");
+        fs::create_dir_all(source_mod.join("About")).unwrap();
+        fs::create_dir_all(source_mod.join(".git")).unwrap();
+        fs::create_dir_all(source_mod.join("Textures")).unwrap();
+        fs::write(source_mod.join("About").join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join(".git").join("HEAD"), "ref").unwrap();
+        fs::write(source_mod.join("Textures").join("a.psd"), "psd").unwrap();
+        fs::write(source_mod.join("Textures").join("a.png"), "new png").unwrap();
+        fs::write(source_mod.join("Settings.xml"), "defaults").unwrap();
+
+        let installed = mods_path.join("123456789");
+        fs::create_dir_all(installed.join("Optional")).unwrap();
+        fs::write(installed.join("Settings.xml"), "personal").unwrap();
+        fs::write(installed.join("Optional").join("hd.png"), "hd").unwrap();
+
+        let install_filter = InstallFilter {
+            exclude: vec!["Textures/*.psd".to_string()],
+            use_default_excludes: true,
+            preserve: vec!["Settings.xml".to_string(), "Optional".to_string(), "Missing".to_string()],
+        };
+        let result = ModUpdater.update_mod(
+            "123456789",
+            &source_mod,
+            Some("123456789"),
+            None,
+            None,
+            &InstallOptions { install_filter, ..InstallOptions::new(download_path.clone(), mods_path.clone()) },
+        ).await.unwrap();
+
+        assert!(!result.join(".git").exists());
+        assert!(!result.join("Textures").join("a.psd").exists());
+        assert_eq!(fs::read_to_string(result.join("Textures").join("a.png")).unwrap(), "new png");
+        assert_eq!(fs::read_to_string(result.join("Settings.xml")).unwrap(), "personal");
+        assert_eq!(fs::read_to_string(result.join("Optional").join("hd.png")).unwrap(), "hd");
+
+        let escaping = InstallFilter { preserve: vec!["../Other".to_string()], ..InstallFilter::default() };
+        assert!(escaping.preserved_paths().is_err());
+        assert!(InstallFilter::default().exclude_set().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_mod_refuses_downgrade() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+
+        let source_mod = download_path.join("123456789");
+        fs::create_dir_all(source_mod.join("About")).unwrap();
+        fs::write(source_mod.join("About").join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join("test.txt"), "old content").unwrap();
+
+        let installed_about = mods_path.join("Installed").join("About");
+        fs::create_dir_all(&installed_about).unwrap();
+        fs::write(installed_about.join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(installed_about.join(".lastupdated"), "2000").unwrap();
+
+        let install = |allow_downgrade| {
+            let source_mod = &source_mod;
+            let options = InstallOptions { allow_downgrade, ..InstallOptions::new(download_path.clone(), mods_path.clone()) };
+            async move { ModUpdater.update_mod("123456789", source_mod, Some("Installed"), None, Some(1000), &options).await }
+        };
+
+        assert_eq!(
+            install(false).await.unwrap_err(),
+            InstallError::Downgrade { mod_id: "123456789".to_string(), local_time_updated: 2000, remote_time_updated: 1000 }
+        );
+        assert!(!mods_path.join("Installed").join("test.txt").exists());
+
+        // With allow_downgrade the older version replaces the installed one
+        let result = install(true).await.unwrap();
+        assert_eq!(fs::read_to_string(result.join("test.txt")).unwrap(), "old content");
+    }
+
     #[tokio::test]
     async fn test_update_mod_with_existing_folder_name() {
         let temp_dir = TempDir::new().unwrap();
@@ -831,13 +2247,10 @@ mod tests {
         let result = updater.update_mod(
             "123456789",
             &source_mod,
-            &download_path,
-            &mods_path,
             Some("My Custom Mod Name"),
-            false,
             None,
             None,
-            None, // force_overwrite_corrupted
+            &InstallOptions::new(download_path.clone(), mods_path.clone()),
         ).await.unwrap();
         
         assert!(result.exists());
@@ -869,13 +2282,13 @@ mod tests {
         let result = updater.update_mod(
             "123456789",
             &source_mod,
-            &download_path,
-            &mods_path,
             Some("123456789"),
-            true,
-            Some(&backup_dir),
             None,
-            None, // force_overwrite_corrupted
+            None,
+            &InstallOptions {
+                backup_directory: Some(backup_dir.clone()),
+                ..InstallOptions::new(download_path.clone(), mods_path.clone())
+            },
         ).await.unwrap();
         
         assert!(result.exists());
@@ -887,5 +2300,241 @@ mod tests {
         assert!(backup_path.exists());
         assert!(backup_path.join("old.txt").exists());
     }
-}
 
+    #[tokio::test]
+    async fn test_update_mod_with_zip_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+        let backup_dir = temp_dir.path().join("backup");
+        let backup_path = backup_dir.join("123456789");
+
+        let existing_mod = mods_path.join("123456789");
+        fs::create_dir_all(existing_mod.join("About")).unwrap();
+        fs::write(existing_mod.join("About").join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(existing_mod.join("old.txt"), "old content").unwrap();
+        // Backup folder made before switching to compressed backups
+        fs::create_dir_all(&backup_path).unwrap();
+
+        let source_mod = download_path.join("123456789");
+        fs::create_dir_all(source_mod.join("About")).unwrap();
+        fs::write(source_mod.join("About").join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join("new.txt"), "new content").unwrap();
+
+        for backup_format in [BackupFormat::Zip, BackupFormat::Folder] {
+            ModUpdater.update_mod(
+                "123456789",
+                &source_mod,
+                Some("123456789"),
+                None,
+                None,
+                &InstallOptions {
+                    backup_directory: Some(backup_dir.clone()),
+                    backup_format,
+                    ..InstallOptions::new(download_path.clone(), mods_path.clone())
+                },
+            ).await.unwrap();
+
+            if backup_format == BackupFormat::Zip {
+                assert!(!backup_path.exists());
+                let restored = temp_dir.path().join("restored");
+                crate::core::backup_archive::extract_zip_backup(&zip_backup_path(&backup_path), &restored).unwrap();
+                assert_eq!(fs::read_to_string(restored.join("old.txt")).unwrap(), "old content");
+            }
+        }
+
+        // The folder backup replaces the compressed one
+        assert!(backup_path.join("new.txt").exists());
+        assert!(!zip_backup_path(&backup_path).exists());
+        assert!(existing_mod.join("new.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_update_mod_failure_keeps_installed_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+        
+        let installed_mod = mods_path.join("123456789");
+        fs::create_dir_all(installed_mod.join("About")).unwrap();
+        fs::write(installed_mod.join("About").join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(installed_mod.join("old.txt"), "old content").unwrap();
+        
+        let source_mod = download_path.join("123456789");
+        fs::create_dir_all(source_mod.join("About")).unwrap();
+        fs::write(source_mod.join("new.txt"), "new content").unwrap();
+        
+        let install = |install_filter: InstallFilter| {
+            let (source_mod, download_path, mods_path) = (source_mod.clone(), download_path.clone(), mods_path.clone());
+            async move {
+                ModUpdater.update_mod(
+                    "123456789",
+                    &source_mod,
+                    Some("123456789"),
+                    None,
+                    None,
+                    &InstallOptions { install_filter, ..InstallOptions::new(download_path, mods_path) },
+                ).await
+            }
+        };
+        let folders = || {
+            let mut folders: Vec<String> = fs::read_dir(&mods_path).unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            folders.sort();
+            folders
+        };
+        
+        // Without its About folder the new version is rejected before the installed one is touched
+        let broken = InstallFilter { exclude: vec!["About".to_string()], ..Default::default() };
+        assert!(install(broken).await.unwrap_err().to_string().contains("incomplete"));
+        assert_eq!(fs::read_to_string(installed_mod.join("old.txt")).unwrap(), "old content");
+        assert_eq!(folders(), vec!["123456789"]);
+        
+        // A crash between the renames of the swap leaves the installed version aside, it is put back first
+        fs::create_dir_all(mods_path.join(STAGING_MODS_FOLDER).join("123456789.tmp")).unwrap();
+        fs::rename(&installed_mod, mods_path.join(STAGING_MODS_FOLDER).join("123456789.old")).unwrap();
+        let result = install(InstallFilter::default()).await.unwrap();
+        assert!(result.join("new.txt").exists());
+        assert!(!result.join("old.txt").exists());
+        assert_eq!(folders(), vec!["123456789"]);
+    }
+
+    #[tokio::test]
+    async fn test_update_mod_renamed_folder_leaves_no_orphan() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+
+        // Create existing mod under its old folder name
+        let old_mod = mods_path.join("Old Mod Name");
+        let old_about = old_mod.join("About");
+        fs::create_dir_all(&old_about).unwrap();
+        fs::write(old_about.join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(old_mod.join("old.txt"), "old content").unwrap();
+
+        // Create an unrelated mod that must be left alone
+        let other_mod = mods_path.join("Other Mod");
+        let other_about = other_mod.join("About");
+        fs::create_dir_all(&other_about).unwrap();
+        fs::write(other_about.join("PublishedFileId.txt"), "999999999").unwrap();
+
+        // Create source mod
+        let source_mod = download_path.join("123456789");
+        let source_about = source_mod.join("About");
+        fs::create_dir_all(&source_about).unwrap();
+        fs::write(source_about.join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join("new.txt"), "new content").unwrap();
+
+        let updater = ModUpdater;
+        let result = updater.update_mod(
+            "123456789",
+            &source_mod,
+            Some("New Mod Name"),
+            None,
+            None,
+            &InstallOptions::new(download_path.clone(), mods_path.clone()),
+        ).await.unwrap();
+
+        // Old folder is still there right after the update
+        assert!(old_mod.exists());
+
+        let backup_dir = temp_dir.path().join("backups");
+        let removed = updater.remove_orphaned_mod_folders(&mods_path, "123456789", &result, Some(&backup_dir)).await.unwrap();
+
+        assert_eq!(removed, vec![old_mod.clone()]);
+        assert!(!old_mod.exists());
+        // The old folder is kept as a backup rather than deleted
+        assert!(backup_dir.join(old_mod.file_name().unwrap()).join("old.txt").exists());
+        assert!(other_mod.exists());
+        assert!(result.join("new.txt").exists());
+
+        let remaining = updater.find_existing_mod_folder(&mods_path, "123456789").await.unwrap();
+        assert_eq!(remaining, Some(result));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_folder_reservations() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+        fs::create_dir_all(mods_path.join("Installed")).unwrap();
+
+        let reservations = FolderReservations::default();
+        reservations.reserve([("Installed", "111")]).await;
+
+        // Two new mods with the same title don't both pick the free name
+        let resolve = |mod_id: &'static str, existing: Option<&'static str>| {
+            let reservations = reservations.clone();
+            let download_path = download_path.clone();
+            let mods_path = mods_path.clone();
+            async move {
+                reservations.resolve(mod_id, &download_path.join(mod_id), &download_path, &mods_path, existing, Some("Foo")).await.unwrap()
+            }
+        };
+        assert_eq!(resolve("222", None).await, "Foo");
+        assert_eq!(resolve("333", None).await, "Foo_");
+        // Resolving again for the same mod keeps its name
+        assert_eq!(resolve("222", None).await, "Foo");
+        assert_eq!(resolve("111", Some("Installed")).await, "Installed");
+    }
+
+    #[tokio::test]
+    async fn test_update_mod_link_modes() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path().join("mods");
+        let download_path = temp_dir.path().join("download");
+
+        let source_mod = download_path.join("123456789");
+        let source_about = source_mod.join("About");
+        fs::create_dir_all(&source_about).unwrap();
+        fs::write(source_about.join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join("test.txt"), "test content").unwrap();
+
+        let install = |link_mode| {
+            let source_mod = &source_mod;
+            let options = InstallOptions { link_mode, ..InstallOptions::new(download_path.clone(), mods_path.clone()) };
+            async move { ModUpdater.update_mod("123456789", source_mod, Some("123456789"), None, None, &options).await }
+        };
+
+        let result = install(LinkMode::Symlink).await.unwrap();
+        assert!(fs::symlink_metadata(&result).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(result.join("test.txt")).unwrap(), "test content");
+
+        // The link points at a private copy, the next download can't change the installed files
+        let linked_copy = fs::read_link(&result).unwrap();
+        assert!(linked_copy.starts_with(download_path.join(LINKED_DOWNLOADS_FOLDER).canonicalize().unwrap()));
+        assert!(!source_mod.exists());
+        fs::create_dir_all(&source_about).unwrap();
+        fs::write(source_about.join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join("test.txt"), "new content").unwrap();
+        assert_eq!(fs::read_to_string(result.join("test.txt")).unwrap(), "test content");
+
+        // Relinking replaces the previous private copy
+        let result = install(LinkMode::Symlink).await.unwrap();
+        assert_eq!(fs::read_to_string(result.join("test.txt")).unwrap(), "new content");
+        assert!(!linked_copy.exists());
+        fs::create_dir_all(&source_about).unwrap();
+        fs::write(source_about.join("PublishedFileId.txt"), "123456789").unwrap();
+        fs::write(source_mod.join("test.txt"), "test content").unwrap();
+
+        // Replacing the symlink must not delete the downloaded files
+        let linked_copy = fs::read_link(&result).unwrap();
+        let result = install(LinkMode::Hardlink).await.unwrap();
+        assert!(!linked_copy.exists());
+        assert!(!fs::symlink_metadata(&result).unwrap().file_type().is_symlink());
+        assert!(source_mod.join("test.txt").exists());
+        assert_eq!(
+            fs::metadata(result.join("test.txt")).unwrap().ino(),
+            fs::metadata(source_mod.join("test.txt")).unwrap().ino(),
+        );
+
+        // Files written by the app into a hardlinked install leave the download alone
+        write_mod_file(&result.join("About").join("PublishedFileId.txt"), "987654321").unwrap();
+        assert_eq!(fs::read_to_string(result.join("About").join("PublishedFileId.txt")).unwrap(), "987654321");
+        assert_eq!(fs::read_to_string(source_about.join("PublishedFileId.txt")).unwrap(), "123456789");
+    }
+}
@@ -1,8 +1,65 @@
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use crate::core::about_xml::{read_about_metadata, read_mod_xml, AboutMetadata};
+use crate::core::backup_retention::dir_size;
+use crate::core::steamcmd_client::DEFAULT_APP_ID;
+use crate::core::workshop_client::{api_base_url, http_client, network_config};
 use crate::core::workshop_deserializers::{bool_from_int, u64_from_str_or_int, i64_from_str_or_int, i32_from_str_or_int};
 
+/// Folder inside the mods directory that disabled mods are moved to by default
+pub const DISABLED_MODS_FOLDER: &str = ".disabled";
+
+/// Whether a folder is the default folder of disabled mods, which is never a mod itself
+pub fn is_disabled_mods_folder(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == DISABLED_MODS_FOLDER)
+}
+
+/// Folder inside the mods directory that installs stage new versions in and set replaced versions aside
+/// RimWorld only loads the direct children of the mods directory, so nothing in it is picked up as a mod
+pub const STAGING_MODS_FOLDER: &str = ".staging";
+
+/// Whether a folder holds an install in progress rather than a mod: the staging folder,
+/// or a `.<name>.tmp` / `.<name>.old` folder left in the mods directory by earlier versions
+pub fn is_staging_mods_folder(path: &Path) -> bool {
+    path.file_name().and_then(|name| name.to_str()).is_some_and(|name| {
+        name == STAGING_MODS_FOLDER
+            || (name.starts_with('.') && (name.ends_with(".tmp") || name.ends_with(".old")))
+    })
+}
+
+// Number of folders list_installed_mods_fast reads in parallel, 0 uses one per CPU
+static SCAN_CONCURRENCY: AtomicUsize = AtomicUsize::new(0);
+
+/// Set how many mod folders are read in parallel when listing installed mods (None resets to one per CPU)
+pub fn set_scan_concurrency(concurrency: Option<usize>) {
+    SCAN_CONCURRENCY.store(concurrency.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Number of mod folders read in parallel when listing installed mods
+pub fn scan_concurrency() -> usize {
+    match SCAN_CONCURRENCY.load(Ordering::Relaxed) {
+        0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4),
+        concurrency => concurrency,
+    }
+}
+
+// Game whose Workshop items are reported as updatable, the same one the downloader fetches
+static WORKSHOP_APP_ID: AtomicU32 = AtomicU32::new(DEFAULT_APP_ID);
+
+/// Set the Steam app id installed mods are checked against for updates
+pub fn set_workshop_app_id(app_id: u32) {
+    WORKSHOP_APP_ID.store(app_id, Ordering::Relaxed);
+}
+
+/// Steam app id installed mods are checked against for updates
+pub fn workshop_app_id() -> u32 {
+    WORKSHOP_APP_ID.load(Ordering::Relaxed)
+}
+
 // Default value helpers for optional fields
 fn default_i32() -> i32 {
     0
@@ -27,6 +84,83 @@ pub struct BaseMod {
     #[serde(default)]
     pub non_steam_mod: bool,
     pub preview_image_path: Option<String>,
+    /// Set when the mod can no longer be downloaded from the Workshop
+    #[serde(default)]
+    pub status: Option<WorkshopStatus>,
+    /// Reason given by Steam when the status is banned
+    #[serde(default)]
+    pub ban_reason: Option<String>,
+    /// Game versions listed in About.xml `<supportedVersions>`
+    #[serde(default)]
+    pub supported_versions: Vec<String>,
+    /// packageIds from About.xml `<loadAfter>`
+    #[serde(default)]
+    pub load_after: Vec<String>,
+    /// packageIds from About.xml `<loadBefore>`
+    #[serde(default)]
+    pub load_before: Vec<String>,
+    /// packageIds from About.xml `<incompatibleWith>`
+    #[serde(default)]
+    pub incompatible_with: Vec<String>,
+    /// Files changed since the mod was installed, set when an update was held back to keep them
+    #[serde(default)]
+    pub local_modifications: Vec<String>,
+    /// Workshop tags (e.g. "1.5", "Mod", "Scenario"), set with the details so the UI can filter by them
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Author from About.xml `<author>`, the Workshop details only carry the creator's Steam ID
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Download left in SteamCMD's workshop content folder, set by a staging-only update instead of installing it
+    #[serde(default)]
+    pub staged_path: Option<String>,
+}
+
+/// Workshop `visibility` values of items hidden from other users
+const VISIBILITY_FRIENDS_ONLY: i32 = 1;
+const VISIBILITY_PRIVATE: i32 = 2;
+
+/// Why an installed mod can no longer be updated or downloaded again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkshopStatus {
+    /// Removed from the Workshop, Steam returns no details for it
+    Unavailable,
+    /// Made private or friends-only by its author, still on the Workshop
+    Private,
+    /// Banned by Steam
+    Banned,
+}
+
+impl BaseMod {
+    /// Set the Workshop status from the details of a successful API query
+    /// A mod missing from the response was removed: Steam only returns a result code for it
+    /// Unlisted mods can still be downloaded and are not flagged
+    pub fn apply_workshop_status(&mut self, details: Option<&WorkshopFileDetails>) {
+        let (status, ban_reason) = match details {
+            Some(details) if details.banned => {
+                let reason = Some(details.ban_reason.clone()).filter(|r| !r.is_empty());
+                (Some(WorkshopStatus::Banned), reason)
+            }
+            Some(details) if details.result == 1 && matches!(details.visibility, VISIBILITY_FRIENDS_ONLY | VISIBILITY_PRIVATE) => {
+                (Some(WorkshopStatus::Private), None)
+            }
+            Some(details) if details.result == 1 => (None, None),
+            _ => (Some(WorkshopStatus::Unavailable), None),
+        };
+        self.status = status;
+        self.ban_reason = ban_reason;
+    }
+
+    /// Attach Workshop details and the tags they list, trimmed and without duplicates
+    pub fn set_details(&mut self, details: &WorkshopFileDetails) {
+        let mut seen = std::collections::HashSet::new();
+        self.tags = details.tags.iter()
+            .map(|t| t.tag.trim().to_string())
+            .filter(|tag| !tag.is_empty() && seen.insert(tag.to_lowercase()))
+            .collect();
+        self.details = Some(details.clone());
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,11 +254,166 @@ pub struct ModInfo {
     pub is_non_steam: bool,
 }
 
+/// Get the About folder of a mod, matching its name case-insensitively
+/// On case-sensitive filesystems a mod may ship `about` or `ABOUT` instead of `About`
+/// Returns the canonical `About` path if no matching folder exists
+pub fn find_about_dir(mod_path: &Path) -> PathBuf {
+    let about_path = mod_path.join("About");
+    if about_path.is_dir() {
+        return about_path;
+    }
+    
+    fs::read_dir(mod_path)
+        .ok()
+        .and_then(|entries| {
+            entries.flatten()
+                .map(|entry| entry.path())
+                .find(|path| {
+                    path.is_dir() && path.file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| n.eq_ignore_ascii_case("About"))
+                        .unwrap_or(false)
+                })
+        })
+        .unwrap_or(about_path)
+}
+
+/// Write a file the app manages inside a mod folder (PublishedFileId.txt, `.lastupdated`, ...)
+/// The old file is removed first: in a hardlinked install it shares its inode with SteamCMD's download,
+/// writing to it in place would change the download and every other install linked to it
+pub fn write_mod_file(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    fs::write(path, contents)
+}
+
+/// Rename About folders with mismatched case (e.g. `about`) to the canonical `About`
+/// RimWorld only detects mods with an exactly named About folder on case-sensitive filesystems
+/// Returns the mod folders that were fixed
+pub fn normalize_about_folder_case(mods_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    
+    let mut fixed = Vec::new();
+    for entry in entries.flatten() {
+        let mod_path = entry.path();
+        if !mod_path.is_dir() {
+            continue;
+        }
+        
+        let about_path = find_about_dir(&mod_path);
+        let is_canonical = about_path.file_name().and_then(|n| n.to_str()) == Some("About");
+        if is_canonical || !about_path.is_dir() {
+            continue;
+        }
+        
+        fs::rename(&about_path, mod_path.join("About"))
+            .map_err(|e| format!("Failed to rename {:?} to About: {}", about_path, e))?;
+        eprintln!("[ModScanner] Renamed {:?} to About", about_path);
+        fixed.push(mod_path);
+    }
+    
+    Ok(fixed)
+}
+
+/// Clean the content of a PublishedFileId.txt
+/// Strips a BOM and surrounding whitespace or newlines some tools leave behind
+pub fn clean_published_file_id(content: &str) -> &str {
+    content.trim_matches(|c: char| c.is_whitespace() || c == '\u{FEFF}' || c == '\0')
+}
+
+/// Rewrite PublishedFileId.txt files containing extraneous characters to a clean `<id>\n`
+/// Returns the mod folders whose file was rewritten
+pub fn normalize_published_file_ids(mods_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    
+    let mut fixed = Vec::new();
+    for entry in entries.flatten() {
+        let mod_path = entry.path();
+        let file_id_path = find_about_dir(&mod_path).join("PublishedFileId.txt");
+        if !file_id_path.is_file() {
+            continue;
+        }
+        
+        let content = match fs::read_to_string(&file_id_path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("[ModScanner] Failed to read {:?}: {}", file_id_path, e);
+                continue;
+            }
+        };
+        
+        let file_id = clean_published_file_id(&content);
+        if file_id.is_empty() || content == file_id || content == format!("{}\n", file_id) {
+            continue;
+        }
+        
+        write_mod_file(&file_id_path, format!("{}\n", file_id))
+            .map_err(|e| format!("Failed to write {:?}: {}", file_id_path, e))?;
+        eprintln!("[ModScanner] Normalized {:?} to ID {}", file_id_path, file_id);
+        fixed.push(mod_path);
+    }
+    
+    Ok(fixed)
+}
+
+/// Outcome of `backfill_published_file_ids`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedFileIdBackfill {
+    /// Mod folders that got a PublishedFileId.txt
+    pub fixed: Vec<String>,
+    /// Mod folders without PublishedFileId.txt whose Workshop ID could not be found, usually local mods
+    pub unresolved: Vec<String>,
+}
+
+/// Write PublishedFileId.txt for mods installed without one (by other tools or by hand), so they get update checks
+/// The ID comes from About.xml `<publishedFileId>` or a folder named after it
+pub fn backfill_published_file_ids(mods_path: &Path) -> Result<PublishedFileIdBackfill, String> {
+    let entries = fs::read_dir(mods_path)
+        .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+    
+    let mut backfill = PublishedFileIdBackfill::default();
+    for entry in entries.flatten() {
+        let mod_path = entry.path();
+        let about_path = find_about_dir(&mod_path);
+        let file_id_path = about_path.join("PublishedFileId.txt");
+        if !about_path.is_dir() || file_id_path.exists() {
+            continue;
+        }
+        
+        let from_about = read_about_metadata(&mod_path).ok().flatten()
+            .and_then(|metadata| metadata.published_file_id)
+            .and_then(|id| parse_workshop_id(&id));
+        let from_folder = || mod_path.file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_digit()))
+            .map(|name| name.to_string());
+        let Some(file_id) = from_about.or_else(from_folder) else {
+            backfill.unresolved.push(mod_path.to_string_lossy().to_string());
+            continue;
+        };
+        
+        fs::write(&file_id_path, format!("{}\n", file_id))
+            .map_err(|e| format!("Failed to write {:?}: {}", file_id_path, e))?;
+        eprintln!("[ModScanner] Wrote {:?} with ID {}", file_id_path, file_id);
+        backfill.fixed.push(mod_path.to_string_lossy().to_string());
+    }
+    
+    backfill.fixed.sort();
+    backfill.unresolved.sort();
+    Ok(backfill)
+}
+
 /// Query mod information from mod folder
 /// Returns ModInfo if it's a valid mod folder (with or without PublishedFileId.txt)
 /// Returns None if it's not a mod folder at all
 pub fn query_mod_info(mod_path: &Path) -> Result<Option<ModInfo>, Box<dyn std::error::Error>> {
-    let about_path = mod_path.join("About");
+    let about_path = find_about_dir(mod_path);
     
     // Check if About folder exists and is a directory
     let about_metadata = match fs::metadata(&about_path) {
@@ -149,7 +438,7 @@ pub fn query_mod_info(mod_path: &Path) -> Result<Option<ModInfo>, Box<dyn std::e
             // Has PublishedFileId.txt - Steam Workshop mod
             match fs::read_to_string(&file_id_path) {
                 Ok(content) => {
-                    let file_id = content.trim();
+                    let file_id = clean_published_file_id(&content);
                     if file_id.is_empty() {
                         // Empty file - treat as non-Steam mod
                         let mod_id = mod_path
@@ -207,8 +496,8 @@ pub fn create_workshop_file_details(mod_id: &str, title: String, time_updated: i
         publishedfileid: mod_id.to_string(),
         result: 1,
         creator: String::new(),
-        creator_app_id: 294100,
-        consumer_app_id: 294100,
+        creator_app_id: workshop_app_id() as i32,
+        consumer_app_id: workshop_app_id() as i32,
         filename: String::new(),
         file_size: 0,
         file_url: String::new(),
@@ -262,22 +551,45 @@ pub fn create_base_mod_from_path(
         .map(|s| s.to_string());
     
     let preview_image_path = find_preview_image(folder_path);
+    let metadata = read_base_mod_metadata(folder_path);
     
-    BaseMod {
+    let mut base_mod = BaseMod {
         mod_id,
         mod_path: folder_path.to_string_lossy().to_string(),
         folder: folder_name,
-        details,
+        details: None,
         updated: None,
         non_steam_mod: is_non_steam,
         preview_image_path,
+        status: None,
+        ban_reason: None,
+        supported_versions: metadata.supported_versions,
+        load_after: metadata.load_after,
+        load_before: metadata.load_before,
+        incompatible_with: metadata.incompatible_with,
+        local_modifications: Vec::new(),
+        tags: Vec::new(),
+        author: metadata.author,
+        staged_path: None,
+    };
+    if let Some(details) = &details {
+        base_mod.set_details(details);
     }
+    base_mod
+}
+
+/// Read the About.xml metadata a BaseMod carries, empty if the mod has no readable About.xml
+fn read_base_mod_metadata(folder_path: &Path) -> AboutMetadata {
+    read_about_metadata(folder_path)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
 }
 
 /// Find preview image (preview.png) in About folder (case insensitive)
 /// Returns the path to the preview image if found, None otherwise
 pub fn find_preview_image(mod_path: &Path) -> Option<String> {
-    let about_path = mod_path.join("About");
+    let about_path = find_about_dir(mod_path);
     
     // Check if About folder exists
     if !about_path.exists() || !about_path.is_dir() {
@@ -316,10 +628,202 @@ pub fn query_mod_id(mod_path: &Path) -> Result<Option<String>, Box<dyn std::erro
     }
 }
 
+/// Mod another mod depends on, as listed in `modDependencies` of its About.xml
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dependency {
+    pub package_id: String,
+    pub workshop_id: String,
+}
+
+/// Extract a Workshop ID from what users paste: a bare ID, a `...filedetails/?id=<id>` URL
+/// or a `steam://url/CommunityFilePage/<id>` link
+pub fn parse_workshop_id(input: &str) -> Option<String> {
+    let input = input.trim();
+    let candidate = input.split(['?', '&', '#'])
+        .find_map(|part| part.strip_prefix("id="))
+        .or_else(|| input.strip_prefix("steam://url/CommunityFilePage/"))
+        .unwrap_or(input);
+    let id = candidate.trim_end_matches('/');
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then(|| id.to_string())
+}
+
+/// Read the Workshop mods a mod depends on from the `modDependencies` of its About.xml
+/// Dependencies without a valid Workshop URL (DLCs, malformed links) are skipped
+pub fn parse_mod_dependencies(mod_path: &Path) -> Vec<Dependency> {
+    let mut dependencies: Vec<Dependency> = Vec::new();
+    for declared in parse_declared_dependencies(mod_path) {
+        if let Some(workshop_id) = declared.workshop_id {
+            if !dependencies.iter().any(|d| d.workshop_id == workshop_id) {
+                dependencies.push(Dependency { package_id: declared.package_id, workshop_id });
+            }
+        }
+    }
+    dependencies
+}
+
+/// Dependency as declared in `modDependencies`, the Workshop ID is None without a valid Workshop URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeclaredDependency {
+    package_id: String,
+    workshop_id: Option<String>,
+}
+
+/// Read every `modDependencies` entry with a packageId from a mod's About.xml
+fn parse_declared_dependencies(mod_path: &Path) -> Vec<DeclaredDependency> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let Ok(content) = read_mod_xml(&find_about_dir(mod_path).join("About.xml")) else {
+        return vec![];
+    };
+    let mut reader = Reader::from_str(&content);
+    reader.trim_text(true);
+
+    let mut dependencies: Vec<DeclaredDependency> = Vec::new();
+    // Element names from ModMetaData down to the current element
+    let mut stack: Vec<String> = Vec::new();
+    let mut in_mod_metadata = false;
+    let mut package_id = None;
+    let mut workshop_url = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if !in_mod_metadata && name == "ModMetaData" {
+                    in_mod_metadata = true;
+                    stack.clear();
+                } else if in_mod_metadata {
+                    stack.push(name);
+                }
+            }
+            Ok(Event::Text(e)) if in_mod_metadata => {
+                let text = e.unescape().unwrap_or_default().trim().to_string();
+                match stack.iter().map(|s| s.as_str()).collect::<Vec<&str>>().as_slice() {
+                    ["modDependencies", "li", "packageId"] => package_id = Some(text),
+                    ["modDependencies", "li", "steamWorkshopUrl"] => workshop_url = Some(text),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(e)) if in_mod_metadata => {
+                if stack.is_empty() && e.name().as_ref() == b"ModMetaData" {
+                    in_mod_metadata = false;
+                    continue;
+                }
+                if stack.len() == 2 && stack[0] == "modDependencies" {
+                    let workshop_id = workshop_url.take().and_then(|url| parse_workshop_id(&url));
+                    if let Some(package_id) = package_id.take() {
+                        dependencies.push(DeclaredDependency { package_id, workshop_id });
+                    }
+                }
+                stack.pop();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                eprintln!("[ModScanner] Error parsing dependencies in {:?}: {:?}", mod_path, e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    dependencies
+}
+
+/// Dependencies of a mod that are not installed in the mods folder
+pub async fn find_missing_dependencies(mod_path: &Path, mod_id: &str, mods_path: &Path) -> Vec<Dependency> {
+    let dependencies = parse_mod_dependencies(mod_path);
+    if dependencies.is_empty() {
+        return dependencies;
+    }
+
+    let installed: std::collections::HashSet<String> = list_installed_mods_fast(mods_path).await
+        .map(|mods| mods.into_iter().map(|m| m.mod_id).collect())
+        .unwrap_or_default();
+    dependencies.into_iter()
+        .filter(|d| d.workshop_id != mod_id && !installed.contains(&d.workshop_id))
+        .collect()
+}
+
+/// Whether a declared dependency of a mod is installed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DependencyStatus {
+    /// Satisfied by the installed mod at `mod_path`, whatever its folder is named
+    Installed { mod_path: String },
+    /// Not installed, can be downloaded from the Workshop
+    MissingWorkshop { workshop_id: String },
+    /// Not installed and About.xml gives no Workshop link to get it from
+    MissingNoLink,
+}
+
+/// Declared dependency of a mod with its resolved status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReportEntry {
+    pub package_id: String,
+    #[serde(flatten)]
+    pub status: DependencyStatus,
+}
+
+/// Dependencies of a mod against the mods installed next to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReport {
+    pub mod_path: String,
+    pub dependencies: Vec<DependencyReportEntry>,
+}
+
+impl DependencyReport {
+    /// Whether every dependency is installed
+    pub fn is_satisfied(&self) -> bool {
+        self.dependencies.iter().all(|d| matches!(d.status, DependencyStatus::Installed { .. }))
+    }
+}
+
+/// Resolve each dependency declared in a mod's About.xml against the installed mods
+/// Dependencies are matched by packageId (case-insensitive), so a local copy in a differently named folder counts,
+/// then by Workshop ID. The game and its DLCs (`ludeon.rimworld*`) are not mods and are left out
+pub async fn dependency_report(mod_path: &Path, mods_path: &Path) -> Result<DependencyReport, String> {
+    let declared = parse_declared_dependencies(mod_path);
+    let mut dependencies = Vec::new();
+    if declared.iter().any(|d| !is_official_content(&d.package_id)) {
+        let installed = list_installed_mods_fast(mods_path).await
+            .map_err(|e| format!("Failed to list installed mods: {}", e))?;
+        let installed: Vec<(Option<String>, BaseMod)> = installed.into_iter()
+            .map(|m| (crate::core::mod_manager::ModUpdater::get_package_id(Path::new(&m.mod_path)).map(|id| id.to_lowercase()), m))
+            .collect();
+
+        let mut seen = std::collections::HashSet::new();
+        for dependency in declared {
+            let package_id = dependency.package_id.to_lowercase();
+            if is_official_content(&package_id) || !seen.insert(package_id.clone()) {
+                continue;
+            }
+            let installed_mod = installed.iter()
+                .find(|(installed_package_id, _)| installed_package_id.as_deref() == Some(package_id.as_str()))
+                .or_else(|| installed.iter().find(|(_, m)| !m.non_steam_mod && dependency.workshop_id.as_deref() == Some(m.mod_id.as_str())));
+            let status = match (installed_mod, dependency.workshop_id) {
+                (Some((_, m)), _) => DependencyStatus::Installed { mod_path: m.mod_path.clone() },
+                (None, Some(workshop_id)) => DependencyStatus::MissingWorkshop { workshop_id },
+                (None, None) => DependencyStatus::MissingNoLink,
+            };
+            dependencies.push(DependencyReportEntry { package_id: dependency.package_id, status });
+        }
+    }
+    Ok(DependencyReport { mod_path: mod_path.to_string_lossy().to_string(), dependencies })
+}
+
+/// The base game and DLCs, declared as dependencies but never installed in the mods folder
+fn is_official_content(package_id: &str) -> bool {
+    package_id.to_lowercase().starts_with("ludeon.rimworld")
+}
+
 /// Check if mod has ignored update (has .ignoredupdate file)
 /// Returns the timestamp from .ignoredupdate file if it exists
 pub fn get_ignored_update_timestamp(mod_path: &Path) -> Result<Option<i64>, Box<dyn std::error::Error>> {
-    let about_path = mod_path.join("About");
+    let about_path = find_about_dir(mod_path);
     let ignore_update_path = about_path.join(".ignoredupdate");
     
     match fs::read_to_string(&ignore_update_path) {
@@ -348,10 +852,20 @@ pub fn get_ignored_update_timestamp(mod_path: &Path) -> Result<Option<i64>, Box<
     }
 }
 
+/// Remove a mod's .lastupdated file, so the mod is checked for updates again
+pub fn clear_last_updated(mod_path: &Path) -> Result<(), String> {
+    let last_updated_path = find_about_dir(mod_path).join(".lastupdated");
+    match fs::remove_file(&last_updated_path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove {:?}: {}", last_updated_path, e)),
+    }
+}
+
 /// Get mod's last updated time
 /// Checks for .lastupdated file first, then falls back to PublishedFileId.txt creation time
 pub fn get_mod_last_updated_time(mod_path: &Path) -> Result<std::time::SystemTime, Box<dyn std::error::Error>> {
-    let about_path = mod_path.join("About");
+    let about_path = find_about_dir(mod_path);
     let last_updated_path = about_path.join(".lastupdated");
     
     // Check for .lastupdated timestamp file
@@ -393,17 +907,67 @@ pub fn get_mod_last_updated_time(mod_path: &Path) -> Result<std::time::SystemTim
     }
 }
 
+/// Whether an installed mod is behind its Workshop version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdateStatus {
+    pub has_update: bool,
+    /// Last update of the installed copy, in seconds since the Unix epoch
+    pub local_time: i64,
+    /// `time_updated` of the Workshop item
+    pub remote_time: i64,
+}
+
+/// Compare an installed mod with the Workshop update time, the same way `query_mods_for_updates` does
+/// An ignored update only counts once a version newer than the ignored one is published
+pub fn mod_update_status(mod_path: &Path, remote_time: i64) -> Result<ModUpdateStatus, String> {
+    let local_time = get_mod_last_updated_time(mod_path)
+        .map_err(|e| format!("Failed to read last update time: {}", e))?
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let compared_to = get_ignored_update_timestamp(mod_path).ok().flatten().unwrap_or(local_time);
+    Ok(ModUpdateStatus {
+        has_update: remote_time - compared_to > 1,
+        local_time,
+        remote_time,
+    })
+}
+
 /// Query batch of mods from Steam Workshop API
+/// Details still fresh in the shared SteamApi cache are served from it, only the others are fetched and then cached
 pub async fn query_mod_batch(
     mod_ids: &[String],
     retries: u32,
 ) -> Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error + Send + Sync>> {
-    const STEAM_API_BASE: &str = "http://api.steampowered.com";
+    let steam_api = crate::services::get_steam_api();
+    // The lock is not held during the request, so other Steam queries aren't blocked by it
+    let (mut details, missing, language) = {
+        let mut api = steam_api.lock().await;
+        let (cached, missing) = api.split_cached_file_details(mod_ids);
+        (cached, missing, api.language().to_string())
+    };
+    if missing.is_empty() {
+        return Ok(details);
+    }
+    
+    let fetched = fetch_mod_batch(&missing, &language, retries).await?;
+    steam_api.lock().await.cache_file_details(&language, &fetched);
+    details.extend(fetched);
+    Ok(details)
+}
+
+/// Fetch a batch of mods from Steam Workshop API in `language`, bypassing the cache
+pub(crate) async fn fetch_mod_batch(
+    mod_ids: &[String],
+    language: &str,
+    retries: u32,
+) -> Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error + Send + Sync>> {
     const MAX_RETRIES: u32 = 3;
     const USER_AGENT: &str = "RimworldWorkshopDownloader/1.0";
 
-    let url = format!("{}/ISteamRemoteStorage/GetPublishedFileDetails/v0001/", STEAM_API_BASE);
-    let client = reqwest::Client::new();
+    let url = format!("{}/ISteamRemoteStorage/GetPublishedFileDetails/v0001/", api_base_url());
+    let client = http_client();
     
     // Remove duplicates
     let unique_ids: Vec<String> = mod_ids.iter()
@@ -416,10 +980,14 @@ pub async fn query_mod_batch(
     let mut params = std::collections::HashMap::new();
     params.insert("itemcount".to_string(), unique_ids.len().to_string());
     params.insert("format".to_string(), "json".to_string());
+    params.insert("l".to_string(), language.to_string());
     
     for (index, id) in unique_ids.iter().enumerate() {
         params.insert(format!("publishedfileids[{}]", index), id.clone());
     }
+    if let Some(key) = network_config().api_key {
+        params.insert("key".to_string(), key);
+    }
 
     match client
         .post(&url)
@@ -434,7 +1002,7 @@ pub async fn query_mod_batch(
             if !response.status().is_success() {
             if retries < MAX_RETRIES {
                 tokio::time::sleep(tokio::time::Duration::from_secs(1 * (retries + 1) as u64)).await;
-                return Box::pin(query_mod_batch(mod_ids, retries + 1)).await;
+                return Box::pin(fetch_mod_batch(mod_ids, language, retries + 1)).await;
                 } else {
                     return Err(format!("Steam API error: {}", response.status()).into());
                 }
@@ -463,7 +1031,7 @@ pub async fn query_mod_batch(
         Err(e) => {
             if retries < MAX_RETRIES {
                 tokio::time::sleep(tokio::time::Duration::from_secs(1 * (retries + 1) as u64)).await;
-                Box::pin(query_mod_batch(mod_ids, retries + 1)).await
+                Box::pin(fetch_mod_batch(mod_ids, language, retries + 1)).await
             } else {
                 Err(e.into())
             }
@@ -475,6 +1043,114 @@ pub async fn query_mod_batch(
 pub async fn query_mods_for_updates(
     mods_path: &Path,
     ignored_mods: &[String],
+) -> Result<Vec<BaseMod>, Box<dyn std::error::Error>> {
+    query_mods_for_updates_inner(mods_path, ignored_mods, None).await
+}
+
+/// Time spent scanning a single mod folder
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModScanTiming {
+    pub mod_id: Option<String>,
+    pub mod_path: String,
+    pub folder: Option<String>,
+    pub duration_ms: f64,
+    pub about_xml_bytes: Option<u64>,
+}
+
+/// Result of a timing-instrumented scan
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimedScanResult {
+    pub mods: Vec<BaseMod>,
+    pub total_ms: f64,
+    pub folders_scanned: usize,
+    pub slowest: Vec<ModScanTiming>,
+}
+
+/// Same as query_mods_for_updates, but also records how long each mod folder took to scan
+/// About.xml is parsed as part of the timed scan so mods with huge metadata files surface
+/// Returns the `limit` slowest mods, slowest first
+pub async fn query_mods_for_updates_with_timing(
+    mods_path: &Path,
+    ignored_mods: &[String],
+    limit: usize,
+) -> Result<TimedScanResult, Box<dyn std::error::Error>> {
+    let timings = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let start = std::time::Instant::now();
+    let mods = query_mods_for_updates_inner(mods_path, ignored_mods, Some(timings.clone())).await?;
+    let total_ms = start.elapsed().as_secs_f64() * 1000.0;
+    
+    let mut slowest = std::mem::take(&mut *timings.lock().unwrap());
+    let folders_scanned = slowest.len();
+    slowest.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+    slowest.truncate(limit);
+    
+    Ok(TimedScanResult {
+        mods,
+        total_ms,
+        folders_scanned,
+        slowest,
+    })
+}
+
+/// Scan a mod folder and time it, parsing About.xml as RimWorld would
+fn scan_mod_folder_timed(folder_path: &Path) -> (Option<BaseMod>, ModScanTiming) {
+    let start = std::time::Instant::now();
+    let mod_item = scan_mod_folder(folder_path);
+    let about_xml_path = find_about_dir(folder_path).join("About.xml");
+    let about_xml_bytes = fs::metadata(&about_xml_path).ok().map(|m| m.len());
+    if mod_item.is_some() {
+        let _ = read_about_metadata(folder_path);
+    }
+    
+    let timing = ModScanTiming {
+        mod_id: mod_item.as_ref().map(|m| m.mod_id.clone()),
+        mod_path: folder_path.to_string_lossy().to_string(),
+        folder: folder_path.file_name().and_then(|n| n.to_str()).map(|s| s.to_string()),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        about_xml_bytes,
+    };
+    (mod_item, timing)
+}
+
+/// Build a BaseMod with local data from a mod folder
+/// Returns None if the folder is not a mod
+fn scan_mod_folder(folder_path: &Path) -> Option<BaseMod> {
+    query_mod_info(folder_path).ok().flatten().map(|info| {
+        let folder_name = folder_path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|s| s.to_string());
+        
+        let preview_image_path = find_preview_image(folder_path);
+        let metadata = read_base_mod_metadata(folder_path);
+        
+        BaseMod {
+            mod_id: info.mod_id.clone(),
+            mod_path: folder_path.to_string_lossy().to_string(),
+            folder: folder_name,
+            details: None,
+            updated: None,
+            non_steam_mod: info.is_non_steam,
+            preview_image_path,
+            status: None,
+            ban_reason: None,
+            supported_versions: metadata.supported_versions,
+            load_after: metadata.load_after,
+            load_before: metadata.load_before,
+            incompatible_with: metadata.incompatible_with,
+            local_modifications: Vec::new(),
+            tags: Vec::new(),
+            author: metadata.author,
+        staged_path: None,
+        }
+    })
+}
+
+async fn query_mods_for_updates_inner(
+    mods_path: &Path,
+    ignored_mods: &[String],
+    timings: Option<Arc<std::sync::Mutex<Vec<ModScanTiming>>>>,
 ) -> Result<Vec<BaseMod>, Box<dyn std::error::Error>> {
     // Convert ignored_mods to HashSet for O(1) lookup
     let ignored_set: std::collections::HashSet<String> = ignored_mods.iter().cloned().collect();
@@ -490,7 +1166,7 @@ pub async fn query_mods_for_updates(
         .filter_map(|entry| {
             entry.ok().and_then(|e| {
                 let path = e.path();
-                if path.is_dir() {
+                if path.is_dir() && !is_staging_mods_folder(&path) {
                     Some(path)
                 } else {
                     None
@@ -507,24 +1183,16 @@ pub async fn query_mods_for_updates(
     // Query mod information from each folder in parallel
     let mod_info_futures: Vec<_> = folders.into_iter().map(|folder| {
         let folder_path = folder.clone();
+        let timings = timings.clone();
         tokio::task::spawn_blocking(move || {
-            query_mod_info(&folder_path).ok().flatten().map(|info| {
-                let folder_name = folder_path.file_name()
-                    .and_then(|n| n.to_str())
-                    .map(|s| s.to_string());
-                
-                let preview_image_path = find_preview_image(&folder_path);
-                
-                BaseMod {
-                    mod_id: info.mod_id.clone(),
-                    mod_path: folder_path.to_string_lossy().to_string(),
-                    folder: folder_name,
-                    details: None,
-                    updated: None,
-                    non_steam_mod: info.is_non_steam,
-                    preview_image_path,
+            match timings {
+                Some(timings) => {
+                    let (mod_item, timing) = scan_mod_folder_timed(&folder_path);
+                    timings.lock().unwrap().push(timing);
+                    mod_item
                 }
-            })
+                None => scan_mod_folder(&folder_path),
+            }
         })
     }).collect();
     
@@ -545,11 +1213,6 @@ pub async fn query_mods_for_updates(
         .filter_map(|(idx, m)| if !m.non_steam_mod { Some(idx) } else { None })
         .collect();
     
-    if steam_mods_indices.is_empty() {
-        // No Steam mods to check for updates
-        return Ok(vec![]);
-    }
-
     // Query mods in batches of 50
     const BATCH_COUNT: usize = 50;
 
@@ -591,8 +1254,10 @@ pub async fn query_mods_for_updates(
                 
                 // Update mods with details
                 for idx in mod_indices {
-                    if let Some(detail) = details_map.get(&mods[idx].mod_id) {
-                        mods[idx].details = Some(detail.clone());
+                    let detail = details_map.get(&mods[idx].mod_id);
+                    mods[idx].apply_workshop_status(detail);
+                    if let Some(detail) = detail {
+                        mods[idx].set_details(detail);
                     }
                 }
             }
@@ -627,8 +1292,8 @@ pub async fn query_mods_for_updates(
             if details.banned {
                 return None; // Banned file
             }
-            if details.creator_app_id != 294100 {
-                return None; // Not a Rimworld mod
+            if details.creator_app_id != workshop_app_id() as i32 {
+                return None; // Not a mod of the configured game
             }
             if ignored_set.contains(&mod_ref.mod_id) {
                 return None; // Ignored mod
@@ -700,7 +1365,20 @@ pub async fn query_mods_for_updates(
         }
     }
     
-    let mods_with_updates: Vec<BaseMod> = mods_with_updates_map.into_values().collect();
+    let mut mods_with_updates: Vec<BaseMod> = mods_with_updates_map.into_values().collect();
+    
+    // Mods that are gone from the Workshop are reported too, so the UI can warn about them
+    let mut reported: std::collections::HashSet<String> = mods_with_updates.iter()
+        .map(|m| m.mod_id.clone())
+        .collect();
+    for mod_ref in &mods {
+        if mod_ref.status.is_some() && reported.insert(mod_ref.mod_id.clone()) {
+            mods_with_updates.push(mod_ref.clone());
+        }
+    }
+
+    // Non-Steam mods are listed without ever being queried, so the UI can still show them
+    mods_with_updates.extend(mods.into_iter().filter(|m| m.non_steam_mod && !ignored_set.contains(&m.mod_id)));
     Ok(mods_with_updates)
 }
 
@@ -721,7 +1399,8 @@ pub async fn list_installed_mods_fast(
         .filter_map(|entry| {
             entry.ok().and_then(|e| {
                 let path = e.path();
-                if path.is_dir() {
+                // Disabled mods and installs in progress are kept out of the list
+                if path.is_dir() && !is_disabled_mods_folder(&path) && !is_staging_mods_folder(&path) {
                     Some(path)
                 } else {
                     None
@@ -734,36 +1413,16 @@ pub async fn list_installed_mods_fast(
         return Ok(vec![]);
     }
 
-    // Query mod information from each folder in parallel
-    let mod_info_futures: Vec<_> = folders.into_iter().map(|folder| {
-        let folder_path = folder.clone();
-        tokio::task::spawn_blocking(move || {
-            query_mod_info(&folder_path).ok().flatten().map(|info| {
-                let folder_name = folder_path.file_name()
-                    .and_then(|n| n.to_str())
-                    .map(|s| s.to_string());
-                
-                let preview_image_path = find_preview_image(&folder_path);
-                
-                BaseMod {
-                    mod_id: info.mod_id.clone(),
-                    mod_path: folder_path.to_string_lossy().to_string(),
-                    folder: folder_name,
-                    details: None, // Will be populated by update_mod_details later (only for Steam mods)
-                    updated: None,
-                    non_steam_mod: info.is_non_steam,
-                    preview_image_path,
-                }
-            })
-        })
-    }).collect();
-    
-    let mut mods: Vec<BaseMod> = Vec::new();
-    for future in mod_info_futures {
-        if let Ok(Some(mod_item)) = future.await {
-            mods.push(mod_item);
-        }
-    }
+    // Query mod information from each folder in parallel, bounded so large folders don't exhaust file handles
+    let mut mods: Vec<BaseMod> = futures::stream::iter(folders)
+        .map(|folder_path| tokio::task::spawn_blocking(move || scan_mod_folder(&folder_path)))
+        .buffer_unordered(scan_concurrency())
+        .filter_map(|result| async move { result.ok().flatten() })
+        .collect()
+        .await;
+
+    // Folders finish in any order, sort so the list is the same on every launch
+    mods.sort_by(|a, b| a.folder.cmp(&b.folder).then_with(|| a.mod_path.cmp(&b.mod_path)));
 
     Ok(mods)
 }
@@ -832,8 +1491,10 @@ pub async fn update_mod_details(
                 
                 // Update mods with details
                 for idx in mod_indices {
-                    if let Some(detail) = details_map.get(&updated_mods[idx].mod_id) {
-                        updated_mods[idx].details = Some(detail.clone());
+                    let detail = details_map.get(&updated_mods[idx].mod_id);
+                    updated_mods[idx].apply_workshop_status(detail);
+                    if let Some(detail) = detail {
+                        updated_mods[idx].set_details(detail);
                     }
                 }
             }
@@ -846,6 +1507,29 @@ pub async fn update_mod_details(
     Ok(updated_mods)
 }
 
+/// On-disk footprint of a mod folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModSize {
+    /// Total size of the mod's files in bytes, those of the target for a linked mod
+    pub size: u64,
+    /// The mod folder is a symlink to a downloaded mod, so deleting it frees no space
+    pub is_link: bool,
+}
+
+/// Size of a mod folder, following it if it's a symlink
+pub fn mod_size(mod_path: &Path) -> Result<ModSize, String> {
+    let link_metadata = fs::symlink_metadata(mod_path)
+        .map_err(|e| format!("Failed to read mod folder {}: {}", mod_path.display(), e))?;
+    if !mod_path.is_dir() {
+        return Err(format!("Mod folder {} is not a directory", mod_path.display()));
+    }
+    Ok(ModSize {
+        size: dir_size(mod_path),
+        is_link: link_metadata.file_type().is_symlink(),
+    })
+}
+
 /// List all installed mods in mods folder without checking for updates
 /// This function now uses the fast version and returns immediately
 pub async fn list_installed_mods(
@@ -861,6 +1545,42 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[tokio::test]
+    async fn test_list_installed_mods_fast_is_sorted_by_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        for (folder, mod_id) in [("Zeta", "3"), ("Alpha", "1"), ("Mid", "2")] {
+            let about_path = temp_dir.path().join(folder).join("About");
+            fs::create_dir_all(&about_path).unwrap();
+            fs::write(about_path.join("PublishedFileId.txt"), mod_id).unwrap();
+        }
+        fs::create_dir_all(temp_dir.path().join("Not a mod")).unwrap();
+
+        let mods = list_installed_mods_fast(temp_dir.path()).await.unwrap();
+        let folders: Vec<&str> = mods.iter().filter_map(|m| m.folder.as_deref()).collect();
+        assert_eq!(folders, vec!["Alpha", "Mid", "Zeta"]);
+        assert_eq!(mods[0].mod_id, "1");
+    }
+
+    #[test]
+    fn test_mod_size_follows_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Harmony");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData/>").unwrap();
+        fs::write(mod_path.join("preview.png"), [0u8; 100]).unwrap();
+
+        assert_eq!(mod_size(&mod_path).unwrap(), ModSize { size: 114, is_link: false });
+        assert!(mod_size(&temp_dir.path().join("missing")).is_err());
+        assert!(mod_size(&mod_path.join("preview.png")).is_err());
+
+        #[cfg(unix)]
+        {
+            let link_path = temp_dir.path().join("HarmonyLink");
+            std::os::unix::fs::symlink(&mod_path, &link_path).unwrap();
+            assert_eq!(mod_size(&link_path).unwrap(), ModSize { size: 114, is_link: true });
+        }
+    }
+
     #[test]
     fn test_query_mod_id_valid() {
         let temp_dir = TempDir::new().unwrap();
@@ -913,6 +1633,45 @@ mod tests {
         assert!(diff.as_secs() < 2);
     }
 
+    #[test]
+    fn test_clear_last_updated() {
+        let temp_dir = TempDir::new().unwrap();
+        for (folder, about) in [("Installed", "About"), ("Lowercase", "about")] {
+            let about_path = temp_dir.path().join(folder).join(about);
+            fs::create_dir_all(&about_path).unwrap();
+            fs::write(about_path.join("About.xml"), "<ModMetaData></ModMetaData>").unwrap();
+            fs::write(about_path.join("PublishedFileId.txt"), "123").unwrap();
+            fs::write(about_path.join(".lastupdated"), "1000").unwrap();
+
+            clear_last_updated(&temp_dir.path().join(folder)).unwrap();
+            assert!(!about_path.join(".lastupdated").exists());
+            assert!(about_path.join("PublishedFileId.txt").exists());
+        }
+
+        // Nothing to clear is fine
+        clear_last_updated(&temp_dir.path().join("Installed")).unwrap();
+    }
+
+    #[test]
+    fn test_mod_update_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let about_path = temp_dir.path().join("About");
+        fs::create_dir_all(&about_path).unwrap();
+        fs::write(about_path.join(".lastupdated"), "1000").unwrap();
+
+        assert_eq!(mod_update_status(temp_dir.path(), 1000).unwrap(), ModUpdateStatus {
+            has_update: false,
+            local_time: 1000,
+            remote_time: 1000,
+        });
+        assert!(mod_update_status(temp_dir.path(), 2000).unwrap().has_update);
+
+        // Ignoring the update hides it until a newer version is published
+        fs::write(about_path.join(".ignoredupdate"), "2000").unwrap();
+        assert!(!mod_update_status(temp_dir.path(), 2000).unwrap().has_update);
+        assert!(mod_update_status(temp_dir.path(), 3000).unwrap().has_update);
+    }
+
     #[test]
     fn test_get_mod_last_updated_time_fallback_to_file_time() {
         let temp_dir = TempDir::new().unwrap();
@@ -1050,5 +1809,291 @@ mod tests {
         let diff = now.duration_since(result).unwrap_or_default();
         assert!(diff.as_secs() < 60);
     }
-}
 
+    #[test]
+    fn test_find_about_dir_case_insensitive() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("lowercase_mod");
+        fs::create_dir_all(mod_path.join("about")).unwrap();
+        fs::write(mod_path.join("about").join("PublishedFileId.txt"), "123456789").unwrap();
+        
+        let about_dir = find_about_dir(&mod_path);
+        assert!(about_dir.is_dir());
+        assert_eq!(query_mod_id(&mod_path).unwrap(), Some("123456789".to_string()));
+        
+        // Missing folder resolves to the canonical name
+        let empty_mod = temp_dir.path().join("empty_mod");
+        fs::create_dir_all(&empty_mod).unwrap();
+        assert_eq!(find_about_dir(&empty_mod), empty_mod.join("About"));
+    }
+
+    #[test]
+    fn test_normalize_about_folder_case() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        fs::create_dir_all(mods_path.join("lower").join("about")).unwrap();
+        fs::create_dir_all(mods_path.join("canonical").join("About")).unwrap();
+        
+        let fixed = normalize_about_folder_case(mods_path).unwrap();
+        
+        let names: Vec<String> = fs::read_dir(mods_path.join("lower")).unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["About"]);
+        assert!(!fixed.contains(&mods_path.join("canonical")));
+    }
+
+    #[tokio::test]
+    async fn test_query_mods_with_timing_records_local_mods() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        for name in ["LocalA", "LocalB", "LocalC"] {
+            let about = mods_path.join(name).join("About");
+            fs::create_dir_all(&about).unwrap();
+            fs::write(about.join("About.xml"), "<ModMetaData><name>x</name></ModMetaData>").unwrap();
+        }
+        fs::create_dir_all(mods_path.join("NotAMod")).unwrap();
+        
+        // Only non-Steam mods, so no Workshop queries are made, but they are still listed
+        let result = query_mods_for_updates_with_timing(mods_path, &[], 2).await.unwrap();
+        assert_eq!(result.mods.len(), 3);
+        assert!(result.mods.iter().all(|m| m.non_steam_mod && m.details.is_none() && m.updated.is_none()));
+        assert_eq!(result.folders_scanned, 4);
+        assert_eq!(result.slowest.len(), 2);
+        assert!(result.slowest[0].duration_ms >= result.slowest[1].duration_ms);
+    }
+
+    #[test]
+    fn test_query_mod_id_with_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("bom_mod");
+        let about_path = mod_path.join("About");
+        
+        fs::create_dir_all(&about_path).unwrap();
+        fs::write(about_path.join("PublishedFileId.txt"), "\u{FEFF}123456789\r\n").unwrap();
+        
+        let result = query_mod_id(&mod_path).unwrap();
+        assert_eq!(result, Some("123456789".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_published_file_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        let files = [
+            ("bom", "\u{FEFF}111"),
+            ("padded", "  222 \r\n\r\n"),
+            ("clean", "333"),
+            ("clean_newline", "444\n"),
+        ];
+        for (folder, content) in files {
+            let about_path = mods_path.join(folder).join("About");
+            fs::create_dir_all(&about_path).unwrap();
+            fs::write(about_path.join("PublishedFileId.txt"), content).unwrap();
+        }
+        
+        let mut fixed = normalize_published_file_ids(mods_path).unwrap();
+        fixed.sort();
+        assert_eq!(fixed, vec![mods_path.join("bom"), mods_path.join("padded")]);
+        
+        let read = |folder: &str| fs::read_to_string(mods_path.join(folder).join("About").join("PublishedFileId.txt")).unwrap();
+        assert_eq!(read("bom"), "111\n");
+        assert_eq!(read("padded"), "222\n");
+        assert_eq!(read("clean"), "333");
+    }
+
+    #[test]
+    fn test_backfill_published_file_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        let mods = [
+            ("FromAbout", "<ModMetaData><publishedFileId>2009463077</publishedFileId></ModMetaData>"),
+            ("818773962", "<ModMetaData><name>HugsLib</name></ModMetaData>"),
+            ("Local", "<ModMetaData><name>Local</name></ModMetaData>"),
+            ("Installed", "<ModMetaData><publishedFileId>111</publishedFileId></ModMetaData>"),
+        ];
+        for (folder, about) in mods {
+            let about_path = mods_path.join(folder).join("About");
+            fs::create_dir_all(&about_path).unwrap();
+            fs::write(about_path.join("About.xml"), about).unwrap();
+        }
+        fs::write(mods_path.join("Installed").join("About").join("PublishedFileId.txt"), "222").unwrap();
+        fs::create_dir_all(mods_path.join("NotAMod")).unwrap();
+        
+        let backfill = backfill_published_file_ids(mods_path).unwrap();
+        let folder_path = |folder: &str| mods_path.join(folder).to_string_lossy().to_string();
+        assert_eq!(backfill.fixed, vec![folder_path("818773962"), folder_path("FromAbout")]);
+        assert_eq!(backfill.unresolved, vec![folder_path("Local")]);
+        
+        assert_eq!(query_mod_id(&mods_path.join("FromAbout")).unwrap().as_deref(), Some("2009463077"));
+        assert_eq!(query_mod_id(&mods_path.join("818773962")).unwrap().as_deref(), Some("818773962"));
+        assert_eq!(query_mod_id(&mods_path.join("Installed")).unwrap().as_deref(), Some("222"));
+    }
+
+    #[test]
+    fn test_parse_workshop_id() {
+        assert_eq!(parse_workshop_id(" 2345678901 ").as_deref(), Some("2345678901"));
+        assert_eq!(
+            parse_workshop_id("https://steamcommunity.com/sharedfiles/filedetails/?id=2345678901&searchtext=").as_deref(),
+            Some("2345678901")
+        );
+        assert_eq!(
+            parse_workshop_id("https://steamcommunity.com/workshop/filedetails/?searchtext=x&id=2345678901#comments").as_deref(),
+            Some("2345678901")
+        );
+        assert_eq!(parse_workshop_id("steam://url/CommunityFilePage/2345678901/").as_deref(), Some("2345678901"));
+        assert_eq!(parse_workshop_id("https://steamcommunity.com/sharedfiles/filedetails/?id=abc"), None);
+        assert_eq!(parse_workshop_id("Harmony"), None);
+        assert_eq!(parse_workshop_id(""), None);
+    }
+
+    #[test]
+    fn test_parse_mod_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("mod");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), r#"<?xml version="1.0" encoding="utf-8"?>
+<ModMetaData>
+  <packageId>author.mod</packageId>
+  <modDependencies>
+    <li>
+      <packageId>brrainz.harmony</packageId>
+      <displayName>Harmony</displayName>
+      <steamWorkshopUrl>steam://url/CommunityFilePage/2009463077</steamWorkshopUrl>
+    </li>
+    <li>
+      <packageId>unlimitedhugs.hugslib</packageId>
+      <steamWorkshopUrl>https://steamcommunity.com/sharedfiles/filedetails/?id=818773962</steamWorkshopUrl>
+    </li>
+    <li>
+      <packageId>broken.url</packageId>
+      <steamWorkshopUrl>https://example.com/mod</steamWorkshopUrl>
+    </li>
+    <li>
+      <packageId>ludeon.rimworld.royalty</packageId>
+    </li>
+  </modDependencies>
+</ModMetaData>"#).unwrap();
+
+        let dependencies = parse_mod_dependencies(&mod_path);
+        assert_eq!(dependencies, vec![
+            Dependency { package_id: "brrainz.harmony".to_string(), workshop_id: "2009463077".to_string() },
+            Dependency { package_id: "unlimitedhugs.hugslib".to_string(), workshop_id: "818773962".to_string() },
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_find_missing_dependencies_skips_installed() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        let mod_path = mods_path.join("mod");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData><modDependencies>\
+            <li><packageId>a.installed</packageId><steamWorkshopUrl>steam://url/CommunityFilePage/111</steamWorkshopUrl></li>\
+            <li><packageId>b.missing</packageId><steamWorkshopUrl>steam://url/CommunityFilePage/222</steamWorkshopUrl></li>\
+            </modDependencies></ModMetaData>").unwrap();
+        let installed = mods_path.join("installed");
+        fs::create_dir_all(installed.join("About")).unwrap();
+        fs::write(installed.join("About").join("PublishedFileId.txt"), "111").unwrap();
+
+        let missing = find_missing_dependencies(&mod_path, "999", mods_path).await;
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].workshop_id, "222");
+    }
+
+    #[tokio::test]
+    async fn test_dependency_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let mods_path = temp_dir.path();
+        let mod_path = mods_path.join("mod");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), "<ModMetaData><modDependencies>\
+            <li><packageId>brrainz.harmony</packageId><steamWorkshopUrl>steam://url/CommunityFilePage/2009463077</steamWorkshopUrl></li>\
+            <li><packageId>b.missing</packageId><steamWorkshopUrl>steam://url/CommunityFilePage/222</steamWorkshopUrl></li>\
+            <li><packageId>c.local</packageId></li>\
+            <li><packageId>Ludeon.RimWorld.Royalty</packageId></li>\
+            </modDependencies></ModMetaData>").unwrap();
+        // A local copy of Harmony in a folder named differently, without a Workshop ID
+        let local_harmony = mods_path.join("Harmony (local)");
+        fs::create_dir_all(local_harmony.join("About")).unwrap();
+        fs::write(local_harmony.join("About").join("About.xml"), "<ModMetaData><packageId>Brrainz.Harmony</packageId></ModMetaData>").unwrap();
+
+        let report = dependency_report(&mod_path, mods_path).await.unwrap();
+        assert!(!report.is_satisfied());
+        assert_eq!(report.dependencies, vec![
+            DependencyReportEntry {
+                package_id: "brrainz.harmony".to_string(),
+                status: DependencyStatus::Installed { mod_path: local_harmony.to_string_lossy().to_string() },
+            },
+            DependencyReportEntry {
+                package_id: "b.missing".to_string(),
+                status: DependencyStatus::MissingWorkshop { workshop_id: "222".to_string() },
+            },
+            DependencyReportEntry { package_id: "c.local".to_string(), status: DependencyStatus::MissingNoLink },
+        ]);
+        assert_eq!(serde_json::to_value(&report.dependencies[1]).unwrap(), serde_json::json!({
+            "packageId": "b.missing",
+            "status": "missingWorkshop",
+            "workshopId": "222",
+        }));
+    }
+
+    #[test]
+    fn test_apply_workshop_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut base_mod = create_base_mod_from_path("123".to_string(), temp_dir.path(), None, false);
+        let mut details = create_workshop_file_details("123", "Mod".to_string(), 0);
+        
+        base_mod.apply_workshop_status(Some(&details));
+        assert_eq!(base_mod.status, None);
+        
+        base_mod.apply_workshop_status(None);
+        assert_eq!(base_mod.status, Some(WorkshopStatus::Unavailable));
+        
+        // Unlisted
+        details.visibility = 3;
+        base_mod.apply_workshop_status(Some(&details));
+        assert_eq!(base_mod.status, None);
+        
+        details.visibility = VISIBILITY_PRIVATE;
+        base_mod.apply_workshop_status(Some(&details));
+        assert_eq!(base_mod.status, Some(WorkshopStatus::Private));
+        assert_eq!(serde_json::to_value(&base_mod).unwrap()["status"], "private");
+        
+        details.visibility = 0;
+        details.result = 9;
+        base_mod.apply_workshop_status(Some(&details));
+        assert_eq!(base_mod.status, Some(WorkshopStatus::Unavailable));
+        
+        details.banned = true;
+        details.ban_reason = "Copyright".to_string();
+        base_mod.apply_workshop_status(Some(&details));
+        assert_eq!(base_mod.status, Some(WorkshopStatus::Banned));
+        assert_eq!(base_mod.ban_reason.as_deref(), Some("Copyright"));
+        
+        let json = serde_json::to_value(&base_mod).unwrap();
+        assert_eq!(json["status"], "banned");
+        assert_eq!(json["banReason"], "Copyright");
+    }
+
+    #[test]
+    fn test_set_details_tags_and_author() {
+        let temp_dir = TempDir::new().unwrap();
+        let about = temp_dir.path().join("About");
+        fs::create_dir_all(&about).unwrap();
+        fs::write(about.join("About.xml"), "<ModMetaData><author>Brrainz</author></ModMetaData>").unwrap();
+        let mut details = create_workshop_file_details("123", "Mod".to_string(), 0);
+        details.tags = ["Mod", " 1.5 ", "", "mod", "Scenario"].iter()
+            .map(|tag| Tag { tag: tag.to_string() })
+            .collect();
+        
+        let base_mod = create_base_mod_from_path("123".to_string(), temp_dir.path(), Some(details), false);
+        assert_eq!(base_mod.tags, vec!["Mod", "1.5", "Scenario"]);
+        assert_eq!(base_mod.author.as_deref(), Some("Brrainz"));
+        
+        let json = serde_json::to_value(&base_mod).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["Mod", "1.5", "Scenario"]));
+        assert_eq!(json["author"], "Brrainz");
+    }
+}
@@ -3,13 +3,120 @@
 
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use std::collections::{HashSet, HashMap};
 use tokio::sync::Mutex;
 use notify::{Watcher, RecommendedWatcher, RecursiveMode, Event, EventKind};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use tauri::{AppHandle, Emitter};
-use crate::core::mod_scanner::{list_installed_mods_fast, query_mod_info, get_mod_last_updated_time, create_workshop_file_details, create_base_mod_from_path};
+use serde::Serialize;
+use crate::core::mod_scanner::{BaseMod, is_disabled_mods_folder, is_staging_mods_folder, list_installed_mods_fast, query_mod_info, get_mod_last_updated_time, create_workshop_file_details, create_base_mod_from_path};
 use crate::services::canonicalize_path_or_fallback;
 
+/// Net changes found when reconciling the mods folder with the watcher's known mods
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileResult {
+    pub added: Vec<BaseMod>,
+    pub removed: Vec<String>,
+}
+
+/// Compile user-supplied ignore patterns (e.g. `.git`, `*.tmp`, `__MACOSX`) into a single matcher
+pub fn compile_ignore_patterns(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let glob = Glob::new(pattern)
+            .map_err(|e| format!("Invalid ignore pattern {:?}: {}", pattern, e))?;
+        builder.add(glob);
+    }
+    builder.build()
+        .map_err(|e| format!("Failed to compile ignore patterns: {}", e))
+}
+
+/// Check a path against the ignore patterns, by folder name and by path relative to the mods folder
+fn matches_ignore_pattern(ignore_patterns: &GlobSet, path: &Path, mods_path: &Path) -> bool {
+    if ignore_patterns.is_empty() {
+        return false;
+    }
+    if path.file_name().is_some_and(|name| ignore_patterns.is_match(name)) {
+        return true;
+    }
+    path.strip_prefix(mods_path)
+        .map(|relative| ignore_patterns.is_match(relative))
+        .unwrap_or(false)
+}
+
+/// Default time a mod folder must go without events before its final state is processed
+pub const DEFAULT_WATCHER_DEBOUNCE: Duration = Duration::from_millis(300);
+
+static WATCHER_DEBOUNCE_MS: AtomicU64 = AtomicU64::new(DEFAULT_WATCHER_DEBOUNCE.as_millis() as u64);
+
+/// Set how long a mod folder must go without events before it is processed (None resets to the default)
+pub fn set_watcher_debounce(debounce: Option<Duration>) {
+    let debounce = debounce.unwrap_or(DEFAULT_WATCHER_DEBOUNCE);
+    WATCHER_DEBOUNCE_MS.store(debounce.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Time a mod folder must go without events before it is processed
+pub fn watcher_debounce() -> Duration {
+    Duration::from_millis(WATCHER_DEBOUNCE_MS.load(Ordering::Relaxed))
+}
+
+/// Outcome of polling a folder with pending events
+#[derive(Debug, PartialEq, Eq)]
+enum FolderSettle {
+    /// The folder has no pending events (anymore)
+    Untracked,
+    /// More events may follow, check again after this long
+    Wait(Duration),
+    /// No events during the debounce window, the folder is no longer tracked
+    Ready { restored: bool },
+}
+
+/// Mod folders with events that are still settling, coalesced per folder
+#[derive(Default)]
+struct FolderChanges {
+    /// Folder -> (time of its last event, whether any event renamed it into the mods folder)
+    changes: std::sync::Mutex<HashMap<PathBuf, (Instant, bool)>>,
+}
+
+impl FolderChanges {
+    /// Record an event for a folder, returns true if the folder had no pending events
+    fn record(&self, folder_path: PathBuf, restored: bool, now: Instant) -> bool {
+        let mut changes = self.changes.lock().unwrap();
+        match changes.get_mut(&folder_path) {
+            Some((last_event, was_restored)) => {
+                *last_event = now;
+                *was_restored |= restored;
+                false
+            }
+            None => {
+                changes.insert(folder_path, (now, restored));
+                true
+            }
+        }
+    }
+
+    /// Check whether a folder went `debounce` without events, and stop tracking it if so
+    fn poll(&self, folder_path: &Path, debounce: Duration, now: Instant) -> FolderSettle {
+        let mut changes = self.changes.lock().unwrap();
+        let Some(&(last_event, restored)) = changes.get(folder_path) else {
+            return FolderSettle::Untracked;
+        };
+        let remaining = (last_event + debounce).saturating_duration_since(now);
+        if !remaining.is_zero() {
+            return FolderSettle::Wait(remaining);
+        }
+        changes.remove(folder_path);
+        FolderSettle::Ready { restored }
+    }
+
+    fn clear(&self) {
+        self.changes.lock().unwrap().clear();
+    }
+}
+
 pub struct ModWatcher {
     watcher: Option<RecommendedWatcher>,
     mods_path: Option<PathBuf>,
@@ -18,6 +125,8 @@ pub struct ModWatcher {
     pending_folders: Arc<Mutex<HashSet<PathBuf>>>, // Track folders that might become mods (don't have About/ yet)
     ignored_paths: Arc<RwLock<HashSet<PathBuf>>>, // Track paths to ignore during app operations (updates, restores, etc.)
     periodic_check_handle: Option<tokio::task::JoinHandle<()>>, // Handle for periodic check task to allow cancellation
+    ignore_patterns: Arc<GlobSet>, // User-supplied patterns for folders that are never reported as mods
+    folder_changes: Arc<FolderChanges>, // Folders with events still settling, processed once they stop changing
 }
 
 impl ModWatcher {
@@ -30,6 +139,8 @@ impl ModWatcher {
             pending_folders: Arc::new(Mutex::new(HashSet::new())),
             ignored_paths: Arc::new(RwLock::new(HashSet::new())),
             periodic_check_handle: None,
+            ignore_patterns: Arc::new(GlobSet::empty()),
+            folder_changes: Arc::new(FolderChanges::default()),
         }
     }
 
@@ -55,9 +166,14 @@ impl ModWatcher {
     }
 
     /// Start watching the mods folder for changes
-    pub async fn start_watching(&mut self, mods_path: PathBuf, app: AppHandle) -> Result<(), String> {
+    /// Folders matching `ignore_patterns` never produce mod-added/mod-removed events
+    pub async fn start_watching(&mut self, mods_path: PathBuf, app: AppHandle, ignore_patterns: Option<Vec<String>>) -> Result<(), String> {
+        // Compile patterns before touching the running watcher, so an invalid pattern keeps it as is
+        let ignore_patterns = Arc::new(compile_ignore_patterns(&ignore_patterns.unwrap_or_default())?);
+
         // Stop existing watcher if any
         self.stop_watching().await;
+        self.ignore_patterns = ignore_patterns.clone();
 
         self.mods_path = Some(mods_path.clone());
         self.app_handle = Some(app.clone());
@@ -99,11 +215,13 @@ impl ModWatcher {
         let known_mods_clone = self.known_mods.clone();
         let pending_folders_clone = self.pending_folders.clone();
         let ignored_paths_clone = self.ignored_paths.clone();
+        let ignore_patterns_clone = ignore_patterns.clone();
+        let folder_changes_clone = self.folder_changes.clone();
 
         // Spawn task to process file system events
         tokio::spawn(async move {
             while let Some(event) = rx.recv().await {
-                Self::process_fs_event(event, &app_clone, &canonical_mods_path_clone, &known_mods_clone, &pending_folders_clone, &ignored_paths_clone).await;
+                Self::process_fs_event(event, &app_clone, &canonical_mods_path_clone, &known_mods_clone, &pending_folders_clone, &ignored_paths_clone, &ignore_patterns_clone, &folder_changes_clone).await;
             }
         });
         
@@ -113,11 +231,12 @@ impl ModWatcher {
         let known_mods_clone_retry = self.known_mods.clone();
         let pending_folders_clone_retry = self.pending_folders.clone();
         let ignored_paths_clone_retry = self.ignored_paths.clone();
+        let ignore_patterns_clone_retry = ignore_patterns;
         let periodic_check_handle = tokio::spawn(async move {
             let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
             loop {
                 interval.tick().await;
-                Self::check_pending_folders(&app_clone_retry, &canonical_mods_path_clone_retry, &known_mods_clone_retry, &pending_folders_clone_retry, &ignored_paths_clone_retry).await;
+                Self::check_pending_folders(&app_clone_retry, &canonical_mods_path_clone_retry, &known_mods_clone_retry, &pending_folders_clone_retry, &ignored_paths_clone_retry, &ignore_patterns_clone_retry).await;
             }
         });
         self.periodic_check_handle = Some(periodic_check_handle);
@@ -157,6 +276,8 @@ impl ModWatcher {
         }
         self.mods_path = None;
         self.app_handle = None;
+        // Folders still settling are dropped, their tasks stop without processing them
+        self.folder_changes.clear();
         {
             let mut known = self.known_mods.lock().await;
             known.clear();
@@ -171,7 +292,88 @@ impl ModWatcher {
         }
     }
 
-    /// Process file system event and emit mod-added/mod-removed events
+    /// Move a known mod to the folder it was renamed to, so the rename isn't reported as a removal
+    /// `old_path` must be canonical, it no longer exists once the folder is renamed
+    pub async fn rename_known_mod(&self, old_path: &Path, new_path: &Path, mod_id: String) {
+        let mut known = self.known_mods.lock().await;
+        known.remove(old_path);
+        known.insert(canonicalize_path_or_fallback(new_path), mod_id);
+    }
+
+    /// Stop tracking a mod the app moved out of the mods folder, so the move isn't reported again
+    /// `path` must be canonical, it no longer exists once the folder is moved
+    pub async fn forget_known_mod(&self, path: &Path) {
+        self.known_mods.lock().await.remove(path);
+    }
+
+    /// Diff the mods folder against the known mods and apply only the net changes
+    /// Emits mod-added/mod-removed for each change, like regular watcher events
+    /// Cheaper than a full re-query: only folders that are new to the watcher are inspected
+    pub async fn reconcile(&self, mods_path: &Path) -> Result<ReconcileResult, String> {
+        let (watched_path, app) = match (&self.mods_path, &self.app_handle) {
+            (Some(watched_path), Some(app)) => (watched_path.clone(), app.clone()),
+            _ => return Err("Mod watcher is not running".to_string()),
+        };
+        
+        let canonical_mods_path = canonicalize_path_or_fallback(mods_path);
+        if canonical_mods_path != canonicalize_path_or_fallback(&watched_path) {
+            return Err(format!("Mod watcher is watching a different folder: {:?}", watched_path));
+        }
+        
+        let entries = std::fs::read_dir(&canonical_mods_path)
+            .map_err(|e| format!("Failed to read mods directory: {}", e))?;
+        let current_folders: HashSet<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .map(|path| canonicalize_path_or_fallback(&path))
+            .collect();
+        
+        let is_ignored = |path: &PathBuf| {
+            let ignored = self.ignored_paths.read().unwrap();
+            ignored.contains(path) || is_disabled_mods_folder(path) || is_staging_mods_folder(path) || matches_ignore_pattern(&self.ignore_patterns, path, &canonical_mods_path)
+        };
+        
+        let (new_folders, removed_mods): (Vec<PathBuf>, Vec<(PathBuf, String)>) = {
+            let known = self.known_mods.lock().await;
+            let new_folders = current_folders.iter()
+                .filter(|path| !known.contains_key(*path) && !is_ignored(path))
+                .cloned()
+                .collect();
+            let removed_mods = known.iter()
+                .filter(|(path, _)| !current_folders.contains(*path) && !is_ignored(path))
+                .map(|(path, mod_id)| (path.clone(), mod_id.clone()))
+                .collect();
+            (new_folders, removed_mods)
+        };
+        
+        let mut result = ReconcileResult::default();
+        
+        for folder_path in new_folders {
+            if let Some(base_mod) = Self::check_single_folder(&folder_path, &app, &canonical_mods_path, &self.known_mods, &self.pending_folders, false).await {
+                result.added.push(base_mod);
+            }
+        }
+        
+        {
+            let mut known = self.known_mods.lock().await;
+            for (folder_path, mod_id) in removed_mods {
+                known.remove(&folder_path);
+                eprintln!("[ModWatcher] Mod removed (reconcile): {} (folder: {:?})", mod_id, folder_path);
+                let _ = app.emit("mod-removed", serde_json::json!({
+                    "modId": mod_id,
+                }));
+                result.removed.push(mod_id);
+            }
+        }
+        
+        eprintln!("[ModWatcher] Reconciled mods folder: {} added, {} removed", result.added.len(), result.removed.len());
+        
+        Ok(result)
+    }
+
+    /// Process file system event, mod folders it touches are checked once their events settle
+    /// and mod-added/mod-removed is emitted for their final state
+    #[allow(clippy::too_many_arguments)]
     async fn process_fs_event(
         event: Event,
         app: &AppHandle,
@@ -179,6 +381,8 @@ impl ModWatcher {
         known_mods: &Arc<Mutex<HashMap<PathBuf, String>>>,
         pending_folders: &Arc<Mutex<HashSet<PathBuf>>>,
         ignored_paths: &Arc<RwLock<HashSet<PathBuf>>>,
+        ignore_patterns: &GlobSet,
+        folder_changes: &Arc<FolderChanges>,
     ) {
         // Filter out events for temporary access test files
         let is_access_test_file = event.paths.iter().any(|p| {
@@ -296,6 +500,7 @@ impl ModWatcher {
             ignored.iter().cloned().collect()
         };
         let filtered_paths: Vec<PathBuf> = paths.into_iter()
+            .filter(|p| !matches_ignore_pattern(ignore_patterns, p, mods_path) && !is_disabled_mods_folder(p) && !is_staging_mods_folder(p))
             .filter(|p| {
                 // Check if path or any parent is ignored
                 let mut current = p.clone();
@@ -321,199 +526,112 @@ impl ModWatcher {
             return;
         }
 
-        eprintln!("[ModWatcher] Processing {} path(s) for event {:?}", filtered_paths.len(), event.kind);
-
-        // Small delay to allow file system operations to complete
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-        match event.kind {
-            EventKind::Create(_) => {
-                // New folder created - check if it's a mod
-                for folder_path in &filtered_paths {
-                    Self::check_single_folder(folder_path, app, mods_path, known_mods, pending_folders, false).await;
-                }
+        // Renamed-in folders are restored mods, they are sorted as if just updated
+        let restored = matches!(event.kind, EventKind::Modify(notify::event::ModifyKind::Name(notify::event::RenameMode::To)));
+        let now = Instant::now();
+        for folder_path in filtered_paths {
+            // One task per folder waits for its events to stop, later events only push the deadline back
+            if !folder_changes.record(folder_path.clone(), restored, now) {
+                continue;
             }
-            EventKind::Remove(_) => {
-                // Folder removed - check if it was a known mod
-                eprintln!("[ModWatcher] Remove event detected for paths: {:?}", filtered_paths);
-                let mut known = known_mods.lock().await;
-                let mut pending = pending_folders.lock().await;
-                
-                eprintln!("[ModWatcher] Known mods count: {}", known.len());
-                
-                // Remove from pending folders
-                for folder_path in &filtered_paths {
-                    pending.remove(folder_path);
-                    
-                    // Try to find matching path in known_mods
-                    // We need to handle both symlink paths and canonical paths
-                    let mut mod_id_to_remove: Option<String> = None;
-                    let mut path_to_remove: Option<PathBuf> = None;
-                    
-                    // Try multiple matching strategies
-                    // 1. Try exact match (as-is)
-                    if let Some(mod_id) = known.get(folder_path) {
-                        mod_id_to_remove = Some(mod_id.clone());
-                        path_to_remove = Some(folder_path.clone());
-                    }
-                    
-                    // 2. Try canonical path if available (before removal)
-                    if mod_id_to_remove.is_none() {
-                        // Try to canonicalize parent and reconstruct path
-                        if let Some(parent) = folder_path.parent() {
-                            if let Ok(canon_parent) = parent.canonicalize() {
-                                if let Some(folder_name) = folder_path.file_name() {
-                                    let canon_path = canon_parent.join(folder_name);
-                                    if let Some(mod_id) = known.get(&canon_path) {
-                                        mod_id_to_remove = Some(mod_id.clone());
-                                        path_to_remove = Some(canon_path);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    
-                    // 3. Try comparing string paths (normalize separators and case)
-                    if mod_id_to_remove.is_none() {
-                        let folder_path_str = folder_path.to_string_lossy().to_string();
-                        let folder_name = folder_path.file_name()
-                            .and_then(|n| n.to_str())
-                            .map(|s| s.to_string());
-                        
-                        for (stored_path, stored_mod_id) in known.iter() {
-                            let stored_path_str = stored_path.to_string_lossy().to_string();
-                            let stored_name = stored_path.file_name()
-                                .and_then(|n| n.to_str())
-                                .map(|s| s.to_string());
-                            
-                            // Compare by folder name (most reliable for symlinks)
-                            if let (Some(fname), Some(sname)) = (&folder_name, &stored_name) {
-                                if fname == sname {
-                                    mod_id_to_remove = Some(stored_mod_id.clone());
-                                    path_to_remove = Some(stored_path.clone());
-                                    break;
-                                }
-                            }
-                            
-                            // Also try full path comparison (normalize separators)
-                            let normalized_folder = folder_path_str.replace('\\', "/");
-                            let normalized_stored = stored_path_str.replace('\\', "/");
-                            if normalized_folder == normalized_stored {
-                                mod_id_to_remove = Some(stored_mod_id.clone());
-                                path_to_remove = Some(stored_path.clone());
-                                break;
-                            }
-                        }
-                    }
-                    
-                    // Remove from known_mods and emit event if found
-                    if let (Some(mod_id), Some(path)) = (mod_id_to_remove, path_to_remove) {
-                        known.remove(&path);
-                        eprintln!("[ModWatcher] Mod removed: {} (folder: {:?}, stored path: {:?})", mod_id, folder_path, path);
-                        let _ = app.emit("mod-removed", serde_json::json!({
-                            "modId": mod_id,
-                        }));
-                    } else {
-                        eprintln!("[ModWatcher] Folder removed but not found in known_mods: {:?}", folder_path);
+            let app = app.clone();
+            let mods_path = mods_path.to_path_buf();
+            let known_mods = known_mods.clone();
+            let pending_folders = pending_folders.clone();
+            let ignored_paths = ignored_paths.clone();
+            let folder_changes = folder_changes.clone();
+            tokio::spawn(async move {
+                let restored = loop {
+                    match folder_changes.poll(&folder_path, watcher_debounce(), Instant::now()) {
+                        FolderSettle::Ready { restored } => break restored,
+                        FolderSettle::Wait(delay) => tokio::time::sleep(delay).await,
+                        // Watcher was stopped
+                        FolderSettle::Untracked => return,
                     }
-                }
+                };
+                Self::process_folder_change(&folder_path, restored, &app, &mods_path, &known_mods, &pending_folders, &ignored_paths).await;
+            });
+        }
+    }
+
+    /// Bring a folder's mod in line with its final state once its events settled
+    /// Folders that are gone are reported removed, new folders are checked and become pending if not a mod yet
+    async fn process_folder_change(
+        folder_path: &Path,
+        restored: bool,
+        app: &AppHandle,
+        mods_path: &Path,
+        known_mods: &Arc<Mutex<HashMap<PathBuf, String>>>,
+        pending_folders: &Arc<Mutex<HashSet<PathBuf>>>,
+        ignored_paths: &Arc<RwLock<HashSet<PathBuf>>>,
+    ) {
+        // An app operation may have started on the folder while its events settled
+        if ignored_paths.read().unwrap().contains(folder_path) {
+            return;
+        }
+        
+        if folder_path.exists() {
+            let is_known = known_mods.lock().await.contains_key(folder_path);
+            if is_known {
+                // Replaced in place (e.g. an update), the mod is already in the list
+                return;
             }
-            EventKind::Modify(modify_kind) => {
-                // Folder modified - could be:
-                // 1. Name(From) - folder moved/deleted (folder doesn't exist)
-                // 2. Name(To) - folder restored/created (folder exists)
-                // 3. Other modifications (folder exists)
-                
-                match modify_kind {
-                    notify::event::ModifyKind::Name(notify::event::RenameMode::From) => {
-                        // Folder was moved/deleted - treat as removal
-                        eprintln!("[ModWatcher] Modify(Name(From)) event - treating as removal");
-                        let mut known = known_mods.lock().await;
-                        let mut pending = pending_folders.lock().await;
-                        
-                        for folder_path in &filtered_paths {
-                            pending.remove(folder_path);
-                            
-                            // Find and remove from known_mods
-                            let mut mod_id_to_remove: Option<String> = None;
-                            let mut path_to_remove: Option<PathBuf> = None;
-                            
-                            // Try direct lookup first (most reliable)
-                            if let Some(mod_id) = known.get(folder_path) {
-                                mod_id_to_remove = Some(mod_id.clone());
-                                path_to_remove = Some(folder_path.clone());
-                            } else {
-                                // Fallback: match by folder name
-                                let folder_name = folder_path.file_name()
-                                    .and_then(|n| n.to_str())
-                                    .map(|s| s.to_string());
-                                
-                                if let Some(fname) = folder_name {
-                                    for (stored_path, stored_mod_id) in known.iter() {
-                                        if let Some(sname) = stored_path.file_name().and_then(|n| n.to_str()) {
-                                            if sname == fname {
-                                                mod_id_to_remove = Some(stored_mod_id.clone());
-                                                path_to_remove = Some(stored_path.clone());
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            
-                            if let (Some(mod_id), Some(path)) = (mod_id_to_remove, path_to_remove) {
-                                known.remove(&path);
-                                eprintln!("[ModWatcher] Mod removed (via Modify(Name(From))): {} (folder: {:?})", mod_id, folder_path);
-                                let _ = app.emit("mod-removed", serde_json::json!({
-                                    "modId": mod_id,
-                                }));
-                            } else {
-                                eprintln!("[ModWatcher] Modify(Name(From)) event but not found in known_mods: {:?}", folder_path);
-                            }
-                        }
-                    }
-                    notify::event::ModifyKind::Name(notify::event::RenameMode::To) => {
-                        // Folder was restored/created - check if it's a mod
-                        eprintln!("[ModWatcher] Modify(Name(To)) event - folder restored/created");
-                        for folder_path in &filtered_paths {
-                            // Check if folder exists and is a mod
-                            if folder_path.exists() {
-                                // Check if it's already in known_mods (might have been restored)
-                                let known = known_mods.lock().await;
-                                let is_known = known.contains_key(folder_path);
-                                drop(known);
-                                
-                                if !is_known {
-                                    // New or restored folder - check if it's a mod
-                                    // Use restored version which sets current time for proper sorting
-                                    Self::check_single_folder_restored(folder_path, app, mods_path, known_mods, pending_folders).await;
-                                }
-                                // If already known, the mod is already in the list - no need to update
-                            }
-                        }
-                    }
-                    _ => {
-                        // Other modifications - might be adding About/ folder
-                        for folder_path in &filtered_paths {
-                            if folder_path.exists() {
-                                let pending = pending_folders.lock().await;
-                                if pending.contains(folder_path) {
-                                    // This folder is pending - check if it's now a mod
-                                    drop(pending);
-                                    Self::check_single_folder(folder_path, app, mods_path, known_mods, pending_folders, false).await;
-                                }
-                            }
-                        }
-                    }
+            if restored {
+                // Use restored version which sets current time for proper sorting
+                Self::check_single_folder_restored(folder_path, app, mods_path, known_mods, pending_folders).await;
+            } else {
+                Self::check_single_folder(folder_path, app, mods_path, known_mods, pending_folders, false).await;
+            }
+            return;
+        }
+        
+        let mut known = known_mods.lock().await;
+        pending_folders.lock().await.remove(folder_path);
+        match Self::find_known_mod(&known, folder_path) {
+            Some((path, mod_id)) => {
+                known.remove(&path);
+                eprintln!("[ModWatcher] Mod removed: {} (folder: {:?}, stored path: {:?})", mod_id, folder_path, path);
+                let _ = app.emit("mod-removed", serde_json::json!({
+                    "modId": mod_id,
+                }));
+            }
+            None => eprintln!("[ModWatcher] Folder removed but not found in known_mods: {:?}", folder_path),
+        }
+    }
+
+    /// Find the known mod of a removed folder, returns its stored path and mod ID
+    /// Handles both symlink paths and canonical paths
+    fn find_known_mod(known: &HashMap<PathBuf, String>, folder_path: &Path) -> Option<(PathBuf, String)> {
+        // 1. Try exact match (as-is)
+        if let Some(mod_id) = known.get(folder_path) {
+            return Some((folder_path.to_path_buf(), mod_id.clone()));
+        }
+        
+        // 2. Try canonical parent joined with the folder name (the folder itself is gone)
+        if let (Some(parent), Some(folder_name)) = (folder_path.parent(), folder_path.file_name()) {
+            if let Ok(canon_parent) = parent.canonicalize() {
+                let canon_path = canon_parent.join(folder_name);
+                if let Some(mod_id) = known.get(&canon_path) {
+                    return Some((canon_path, mod_id.clone()));
                 }
             }
-            _ => {}
         }
+        
+        // 3. Try comparing folder names (most reliable for symlinks) and normalized separators
+        let folder_name = folder_path.file_name();
+        let normalized_folder = folder_path.to_string_lossy().replace('\\', "/");
+        known.iter()
+            .find(|(stored_path, _)| {
+                (folder_name.is_some() && stored_path.file_name() == folder_name)
+                    || stored_path.to_string_lossy().replace('\\', "/") == normalized_folder
+            })
+            .map(|(stored_path, mod_id)| (stored_path.clone(), mod_id.clone()))
     }
     
     /// Check a single folder to see if it's a mod
     /// `use_current_time` - if true, use current time for time_updated (for restored mods)
     ///                      if false, use time from folder modification time or .lastupdated file
+    /// Returns the mod if it was newly added
     async fn check_single_folder(
         folder_path: &Path,
         app: &AppHandle,
@@ -521,7 +639,7 @@ impl ModWatcher {
         known_mods: &Arc<Mutex<HashMap<PathBuf, String>>>,
         pending_folders: &Arc<Mutex<HashSet<PathBuf>>>,
         use_current_time: bool,
-    ) {
+    ) -> Option<BaseMod> {
         // Query mod info for this specific folder (use spawn_blocking to avoid Send issues)
         let folder_path_clone = folder_path.to_path_buf();
         let mod_info_result = tokio::task::spawn_blocking(move || {
@@ -535,15 +653,15 @@ impl ModWatcher {
                 let mut pending = pending_folders.lock().await;
                 pending.insert(folder_path.to_path_buf());
                 eprintln!("[ModWatcher] Folder {:?} is not a mod yet, adding to pending", folder_path);
-                return;
+                return None;
             }
             Ok(Err(e)) => {
                 eprintln!("[ModWatcher] Error querying mod info for {:?}: {}", folder_path, e);
-                return;
+                return None;
             }
             Err(e) => {
                 eprintln!("[ModWatcher] Task error for {:?}: {}", folder_path, e);
-                return;
+                return None;
             }
         };
         
@@ -561,7 +679,7 @@ impl ModWatcher {
         if let Some(existing_mod_id) = known.get(&canonical_path) {
             if existing_mod_id == &mod_info.mod_id {
                 // Already known with same mod_id, skip
-                return;
+                return None;
             }
             // Different mod_id for same path - update it
         }
@@ -630,6 +748,8 @@ impl ModWatcher {
             "modId": mod_info.mod_id,
             "mod": base_mod,
         }));
+        
+        Some(base_mod)
     }
     
     /// Check a single folder that was restored (use current time for sorting)
@@ -640,7 +760,7 @@ impl ModWatcher {
         known_mods: &Arc<Mutex<HashMap<PathBuf, String>>>,
         pending_folders: &Arc<Mutex<HashSet<PathBuf>>>,
     ) {
-        Self::check_single_folder(folder_path, app, mods_path, known_mods, pending_folders, true).await;
+        let _ = Self::check_single_folder(folder_path, app, mods_path, known_mods, pending_folders, true).await;
     }
     
     
@@ -652,6 +772,7 @@ impl ModWatcher {
         known_mods: &Arc<Mutex<HashMap<PathBuf, String>>>,
         pending_folders: &Arc<Mutex<HashSet<PathBuf>>>,
         ignored_paths: &Arc<RwLock<HashSet<PathBuf>>>,
+        ignore_patterns: &GlobSet,
     ) {
         // Check pending folders
         let folders_to_check: Vec<PathBuf> = {
@@ -660,8 +781,8 @@ impl ModWatcher {
         };
         
         for folder_path in folders_to_check {
-            // Check if folder still exists
-            if !folder_path.exists() {
+            // Drop folders that no longer exist or match an ignore pattern, they'll never be mods
+            if !folder_path.exists() || matches_ignore_pattern(ignore_patterns, &folder_path, mods_path) {
                 let mut pending = pending_folders.lock().await;
                 pending.remove(&folder_path);
                 continue;
@@ -745,3 +866,46 @@ impl Drop for ModWatcher {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_patterns_match_name_and_relative_path() {
+        let patterns = compile_ignore_patterns(&[
+            ".git".to_string(),
+            "*.tmp".to_string(),
+            "Backup/Old*".to_string(),
+            " ".to_string(),
+        ]).unwrap();
+        let mods_path = Path::new("/mods");
+
+        assert!(matches_ignore_pattern(&patterns, &mods_path.join(".git"), mods_path));
+        assert!(matches_ignore_pattern(&patterns, &mods_path.join("download.tmp"), mods_path));
+        assert!(matches_ignore_pattern(&patterns, &mods_path.join("Backup").join("Old Harmony"), mods_path));
+        assert!(!matches_ignore_pattern(&patterns, &mods_path.join("Harmony"), mods_path));
+
+        assert!(!matches_ignore_pattern(&GlobSet::empty(), &mods_path.join(".git"), mods_path));
+        assert!(compile_ignore_patterns(&["[".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_folder_changes_coalesce_until_settled() {
+        let changes = FolderChanges::default();
+        let folder = PathBuf::from("/mods/Harmony");
+        let debounce = Duration::from_millis(300);
+        let start = Instant::now();
+
+        assert_eq!(changes.poll(&folder, debounce, start), FolderSettle::Untracked);
+        assert!(changes.record(folder.clone(), false, start));
+        // Later events of the same folder push the deadline back instead of being processed separately
+        assert!(!changes.record(folder.clone(), true, start + Duration::from_millis(200)));
+        assert_eq!(changes.poll(&folder, debounce, start + Duration::from_millis(300)), FolderSettle::Wait(Duration::from_millis(200)));
+        assert_eq!(changes.poll(&folder, debounce, start + Duration::from_millis(500)), FolderSettle::Ready { restored: true });
+        assert_eq!(changes.poll(&folder, debounce, start + Duration::from_millis(500)), FolderSettle::Untracked);
+
+        assert!(changes.record(folder.clone(), false, start));
+        changes.clear();
+        assert_eq!(changes.poll(&folder, debounce, start), FolderSettle::Untracked);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use quick_xml::escape::escape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// packageId of the base game, active in every new ModsConfig.xml
+const CORE_PACKAGE_ID: &str = "ludeon.rimworld";
+
+/// RimWorld's active mod list, read from Config/ModsConfig.xml
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModsConfig {
+    pub version: Option<String>,
+    /// packageIds of the active mods, in load order
+    pub active_mods: Vec<String>,
+    pub known_expansions: Vec<String>,
+}
+
+/// Parse the content of a ModsConfig.xml file
+pub fn parse_mods_config(content: &str) -> Result<ModsConfig, String> {
+    let mut reader = Reader::from_str(content.trim_start_matches('\u{FEFF}'));
+    reader.trim_text(true);
+
+    let mut config = ModsConfig::default();
+    // Element names below ModsConfigData down to the current element
+    let mut stack: Vec<String> = Vec::new();
+    let mut in_config = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                if !in_config && name == "ModsConfigData" {
+                    in_config = true;
+                    stack.clear();
+                } else if in_config {
+                    stack.push(name);
+                }
+            }
+            Ok(Event::Text(e)) => {
+                if !in_config {
+                    continue;
+                }
+                let text = e.unescape().unwrap_or_default().trim().to_string();
+                if text.is_empty() {
+                    continue;
+                }
+                match stack.iter().map(|s| s.as_str()).collect::<Vec<&str>>().as_slice() {
+                    ["version"] => config.version = Some(text),
+                    ["activeMods", "li"] => config.active_mods.push(text),
+                    ["knownExpansions", "li"] => config.known_expansions.push(text),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(e)) if in_config => {
+                if stack.is_empty() && e.name().as_ref() == b"ModsConfigData" {
+                    in_config = false;
+                } else {
+                    stack.pop();
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Failed to parse ModsConfig.xml: {:?}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(config)
+}
+
+/// Minimal valid ModsConfig.xml with only the base game active
+fn minimal_mods_config() -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<ModsConfigData>\n  <activeMods>\n    <li>{}</li>\n  </activeMods>\n  <knownExpansions />\n</ModsConfigData>\n",
+        CORE_PACKAGE_ID
+    )
+}
+
+/// Read ModsConfig.xml, a missing file reads as a config with only the base game active
+pub fn read_mods_config(config_path: &Path) -> Result<ModsConfig, String> {
+    if !config_path.exists() {
+        return parse_mods_config(&minimal_mods_config());
+    }
+    let content = fs::read_to_string(config_path)
+        .map_err(|e| format!("Failed to read ModsConfig.xml: {}", e))?;
+    parse_mods_config(&content)
+}
+
+/// Replace the `<activeMods>` element of a ModsConfig.xml with the given packageIds
+/// Everything else (XML declaration, version, knownExpansions...) is kept byte for byte
+pub fn replace_active_mods(content: &str, package_ids: &[String]) -> Result<String, String> {
+    let bom = if content.starts_with('\u{FEFF}') { "\u{FEFF}" } else { "" };
+    let content = &content[bom.len()..];
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+
+    // Byte range of <activeMods>...</activeMods> directly under ModsConfigData
+    let mut reader = Reader::from_str(content);
+    let mut depth = 0;
+    let mut span_start = None;
+    let mut span = None;
+    let mut config_end = None;
+    loop {
+        let position = reader.buffer_position();
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                depth += 1;
+                if depth == 2 && e.name().as_ref() == b"activeMods" {
+                    span_start = Some(position);
+                }
+            }
+            Ok(Event::Empty(e)) if depth == 1 && e.name().as_ref() == b"activeMods" => {
+                span = Some((position, reader.buffer_position()));
+            }
+            Ok(Event::End(e)) => {
+                if depth == 2 && e.name().as_ref() == b"activeMods" {
+                    if let Some(start) = span_start.take() {
+                        span = Some((start, reader.buffer_position()));
+                    }
+                } else if depth == 1 && e.name().as_ref() == b"ModsConfigData" {
+                    config_end = Some(position);
+                }
+                depth -= 1;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Failed to parse ModsConfig.xml: {:?}", e)),
+            _ => {}
+        }
+    }
+
+    // Without an activeMods element, insert one at the end of ModsConfigData
+    let (start, end) = match (span, config_end) {
+        (Some(span), _) => span,
+        (None, Some(end)) => (end, end),
+        (None, None) => return Err("ModsConfig.xml has no ModsConfigData element".to_string()),
+    };
+
+    // Indent like the line the element starts on
+    let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let base_indent: String = content[line_start..start].chars().take_while(|c| c.is_whitespace()).collect();
+    let inserting = span.is_none();
+    let indent = if inserting { format!("{}  ", base_indent) } else { base_indent.clone() };
+
+    let mut element = String::new();
+    if inserting {
+        element.push_str("  ");
+    }
+    element.push_str("<activeMods>");
+    for package_id in package_ids {
+        element.push_str(&format!("{}{}  <li>{}</li>", newline, indent, escape(package_id.as_str())));
+    }
+    element.push_str(&format!("{}{}</activeMods>", newline, indent));
+    if inserting {
+        element.push_str(newline);
+        element.push_str(&base_indent);
+    }
+
+    Ok(format!("{}{}{}{}", bom, &content[..start], element, &content[end..]))
+}
+
+/// Write the active mod list to ModsConfig.xml, creating a minimal file if it doesn't exist yet
+pub fn write_active_mods(config_path: &Path, package_ids: &[String]) -> Result<ModsConfig, String> {
+    let content = if config_path.exists() {
+        fs::read_to_string(config_path)
+            .map_err(|e| format!("Failed to read ModsConfig.xml: {}", e))?
+    } else {
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+        minimal_mods_config()
+    };
+
+    let updated = replace_active_mods(&content, package_ids)?;
+    fs::write(config_path, &updated)
+        .map_err(|e| format!("Failed to write ModsConfig.xml: {}", e))?;
+
+    parse_mods_config(&updated)
+}
+
+/// Append a packageId to the active mod list unless it is already active
+/// Returns true if the list changed
+pub fn activate_mod(config_path: &Path, package_id: &str) -> Result<bool, String> {
+    let config = read_mods_config(config_path)?;
+    // RimWorld compares packageIds case-insensitively
+    if config.active_mods.iter().any(|id| id.eq_ignore_ascii_case(package_id)) {
+        return Ok(false);
+    }
+
+    let mut active_mods = config.active_mods;
+    active_mods.push(package_id.to_lowercase());
+    write_active_mods(config_path, &active_mods)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<ModsConfigData>\n  <version>1.5.4104 rev435</version>\n  <activeMods>\n    <li>brrainz.harmony</li>\n    <li>ludeon.rimworld</li>\n  </activeMods>\n  <knownExpansions>\n    <li>ludeon.rimworld.royalty</li>\n  </knownExpansions>\n</ModsConfigData>";
+
+    #[test]
+    fn test_parse_mods_config() {
+        let config = parse_mods_config(SAMPLE).unwrap();
+        assert_eq!(config.version.as_deref(), Some("1.5.4104 rev435"));
+        assert_eq!(config.active_mods, vec!["brrainz.harmony", "ludeon.rimworld"]);
+        assert_eq!(config.known_expansions, vec!["ludeon.rimworld.royalty"]);
+    }
+
+    #[test]
+    fn test_replace_active_mods_keeps_rest() {
+        let ids = vec!["ludeon.rimworld".to_string(), "a&b.mod".to_string()];
+        let updated = replace_active_mods(SAMPLE, &ids).unwrap();
+
+        assert!(updated.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<ModsConfigData>\n  <version>"));
+        assert!(updated.contains("  <activeMods>\n    <li>ludeon.rimworld</li>\n    <li>a&amp;b.mod</li>\n  </activeMods>\n  <knownExpansions>\n    <li>ludeon.rimworld.royalty</li>\n  </knownExpansions>"));
+        assert_eq!(parse_mods_config(&updated).unwrap().active_mods, vec!["ludeon.rimworld", "a&b.mod"]);
+    }
+
+    #[test]
+    fn test_replace_active_mods_without_element() {
+        let content = "<ModsConfigData>\n  <version>1.5</version>\n</ModsConfigData>\n";
+        let updated = replace_active_mods(content, &["ludeon.rimworld".to_string()]).unwrap();
+        let config = parse_mods_config(&updated).unwrap();
+        assert_eq!(config.version.as_deref(), Some("1.5"));
+        assert_eq!(config.active_mods, vec!["ludeon.rimworld"]);
+    }
+
+    #[test]
+    fn test_activate_mod_creates_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("Config").join("ModsConfig.xml");
+
+        assert!(activate_mod(&config_path, "Author.Mod").unwrap());
+        assert!(!activate_mod(&config_path, "author.mod").unwrap());
+
+        let config = read_mods_config(&config_path).unwrap();
+        assert_eq!(config.active_mods, vec!["ludeon.rimworld", "author.mod"]);
+    }
+}
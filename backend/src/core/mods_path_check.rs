@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use crate::core::mod_scanner::find_about_dir;
+
+/// Folders below a wrongly picked folder that are likely the mods folder, most likely first
+/// Covers picking the RimWorld folder, the Steam library or SteamCMD's workshop folder
+const SUGGESTED_SUBFOLDERS: &[&str] = &[
+    "Mods",
+    "RimWorld/Mods",
+    "common/RimWorld/Mods",
+    "steamapps/common/RimWorld/Mods",
+    "content/294100",
+    "workshop/content/294100",
+    "steamapps/workshop/content/294100",
+];
+
+/// Whether a folder looks like a RimWorld mods folder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ModsPathStatus {
+    /// Holds mod folders
+    Valid,
+    /// Holds no mods yet, but is named like a mods folder
+    EmptyButValid,
+    /// Doesn't look like a mods folder, `suggestion` is the likely right one if it was found
+    LikelyWrong { suggestion: Option<String> },
+    /// Doesn't exist or is a file
+    NotADirectory,
+}
+
+/// Check a folder picked as the mods folder for the usual mistakes, like picking the game or Steam folder
+pub fn check_mods_path(path: &Path) -> ModsPathStatus {
+    if !path.is_dir() {
+        return ModsPathStatus::NotADirectory;
+    }
+    // A single mod was picked instead of the folder holding it
+    if find_about_dir(path).is_dir() {
+        return ModsPathStatus::LikelyWrong {
+            suggestion: path.parent().map(|parent| parent.to_string_lossy().to_string()),
+        };
+    }
+    if contains_mods(path) {
+        return ModsPathStatus::Valid;
+    }
+
+    let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+    if name == "mods" || name == "294100" {
+        return ModsPathStatus::EmptyButValid;
+    }
+    let suggestion = SUGGESTED_SUBFOLDERS.iter()
+        .map(|subfolder| path.join(subfolder))
+        .find(|candidate| candidate.is_dir())
+        .map(|candidate| candidate.to_string_lossy().to_string());
+    ModsPathStatus::LikelyWrong { suggestion }
+}
+
+/// Whether any subfolder has an About folder
+fn contains_mods(path: &Path) -> bool {
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().any(|entry| entry.path().is_dir() && find_about_dir(&entry.path()).is_dir()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_mods_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let rimworld = temp_dir.path().join("RimWorld");
+        let mods = rimworld.join("Mods");
+        fs::create_dir_all(&mods).unwrap();
+        fs::write(rimworld.join("Version.txt"), "1.5").unwrap();
+
+        assert_eq!(check_mods_path(&temp_dir.path().join("Missing")), ModsPathStatus::NotADirectory);
+        assert_eq!(check_mods_path(&rimworld.join("Version.txt")), ModsPathStatus::NotADirectory);
+        assert_eq!(check_mods_path(&mods), ModsPathStatus::EmptyButValid);
+        assert_eq!(check_mods_path(&rimworld), ModsPathStatus::LikelyWrong {
+            suggestion: Some(mods.to_string_lossy().to_string()),
+        });
+        assert_eq!(check_mods_path(temp_dir.path()), ModsPathStatus::LikelyWrong {
+            suggestion: Some(mods.to_string_lossy().to_string()),
+        });
+
+        let harmony = mods.join("Harmony");
+        fs::create_dir_all(harmony.join("About")).unwrap();
+        assert_eq!(check_mods_path(&mods), ModsPathStatus::Valid);
+        assert_eq!(check_mods_path(&harmony), ModsPathStatus::LikelyWrong {
+            suggestion: Some(mods.to_string_lossy().to_string()),
+        });
+
+        let other = temp_dir.path().join("Documents");
+        fs::create_dir_all(&other).unwrap();
+        assert_eq!(check_mods_path(&other), ModsPathStatus::LikelyWrong { suggestion: None });
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+/// State of a scheduled bulk download job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Scheduled,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Scheduled bulk download driven by a mod list manifest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJob {
+    pub id: u64,
+    pub manifest_path: String,
+    pub mods_path: String,
+    pub start_time: i64,
+    pub rate_limit_kbps: Option<u32>,
+    pub status: JobStatus,
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub error: Option<String>,
+    /// Mods the running job downloads, cancelling the job cancels only these
+    #[serde(skip)]
+    pub mod_ids: Vec<String>,
+}
+
+/// Keeps track of scheduled jobs and their background tasks
+#[derive(Default)]
+pub struct JobScheduler {
+    jobs: HashMap<u64, ScheduledJob>,
+    handles: HashMap<u64, JoinHandle<()>>,
+    next_id: u64,
+}
+
+impl JobScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new job and return its ID
+    pub fn add_job(&mut self, manifest_path: String, mods_path: String, start_time: i64, rate_limit_kbps: Option<u32>) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.jobs.insert(id, ScheduledJob {
+            id,
+            manifest_path,
+            mods_path,
+            start_time,
+            rate_limit_kbps,
+            status: JobStatus::Scheduled,
+            total: 0,
+            completed: 0,
+            failed: 0,
+            error: None,
+            mod_ids: Vec::new(),
+        });
+        id
+    }
+
+    /// Attach the background task running a job so it can be cancelled
+    pub fn set_handle(&mut self, id: u64, handle: JoinHandle<()>) {
+        self.handles.insert(id, handle);
+    }
+
+    /// Get a job by ID
+    pub fn get(&self, id: u64) -> Option<&ScheduledJob> {
+        self.jobs.get(&id)
+    }
+
+    /// Update a job in place, ignoring jobs that were cancelled meanwhile
+    pub fn update<F: FnOnce(&mut ScheduledJob)>(&mut self, id: u64, f: F) {
+        if let Some(job) = self.jobs.get_mut(&id) {
+            if job.status != JobStatus::Cancelled {
+                f(job);
+            }
+        }
+        if matches!(self.jobs.get(&id).map(|j| j.status), Some(JobStatus::Completed | JobStatus::Failed)) {
+            self.handles.remove(&id);
+        }
+    }
+
+    /// List all jobs ordered by start time
+    pub fn list(&self) -> Vec<ScheduledJob> {
+        let mut jobs: Vec<ScheduledJob> = self.jobs.values().cloned().collect();
+        jobs.sort_by_key(|j| (j.start_time, j.id));
+        jobs
+    }
+
+    /// Cancel a scheduled or running job
+    /// Returns the status the job had before cancellation
+    pub fn cancel(&mut self, id: u64) -> Result<JobStatus, String> {
+        let job = self.jobs.get_mut(&id)
+            .ok_or_else(|| format!("Scheduled job not found: {}", id))?;
+
+        let previous_status = job.status;
+        match previous_status {
+            JobStatus::Scheduled | JobStatus::Running => {
+                job.status = JobStatus::Cancelled;
+                if let Some(handle) = self.handles.remove(&id) {
+                    handle.abort();
+                }
+                Ok(previous_status)
+            }
+            _ => Err(format!("Job {} has already finished", id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_list_jobs_sorted() {
+        let mut scheduler = JobScheduler::new();
+        let late = scheduler.add_job("b.txt".to_string(), "/mods".to_string(), 200, None);
+        let early = scheduler.add_job("a.txt".to_string(), "/mods".to_string(), 100, Some(512));
+
+        let ids: Vec<u64> = scheduler.list().iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![early, late]);
+        assert_eq!(scheduler.get(early).unwrap().rate_limit_kbps, Some(512));
+    }
+
+    #[test]
+    fn test_cancel_job() {
+        let mut scheduler = JobScheduler::new();
+        let id = scheduler.add_job("a.txt".to_string(), "/mods".to_string(), 100, None);
+
+        assert_eq!(scheduler.cancel(id).unwrap(), JobStatus::Scheduled);
+        assert_eq!(scheduler.get(id).unwrap().status, JobStatus::Cancelled);
+        assert!(scheduler.cancel(id).is_err());
+        assert!(scheduler.cancel(999).is_err());
+    }
+
+    #[test]
+    fn test_update_ignores_cancelled_job() {
+        let mut scheduler = JobScheduler::new();
+        let id = scheduler.add_job("a.txt".to_string(), "/mods".to_string(), 100, None);
+        scheduler.cancel(id).unwrap();
+
+        scheduler.update(id, |job| job.status = JobStatus::Completed);
+        assert_eq!(scheduler.get(id).unwrap().status, JobStatus::Cancelled);
+    }
+}
@@ -0,0 +1,242 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Name of the tauri store file holding the frontend settings
+pub const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Key under which the frontend keeps its settings object
+pub const SETTINGS_KEY: &str = "app-settings";
+
+/// Expected type of a settings value
+#[derive(Debug, Clone, Copy)]
+enum SettingType {
+    String,
+    Bool,
+    Number,
+    Array,
+    OneOf(&'static [&'static str]),
+}
+
+/// Known settings keys: (key, type, required)
+const SETTINGS_SCHEMA: &[(&str, SettingType, bool)] = &[
+    ("modsPath", SettingType::String, true),
+    ("backupMods", SettingType::Bool, true),
+    ("backupDirectory", SettingType::String, true),
+    ("theme", SettingType::OneOf(&["light", "dark", "system"]), true),
+    ("isFirstRun", SettingType::Bool, true),
+    ("ignoredMods", SettingType::Array, true),
+    ("installedModsSortBy", SettingType::OneOf(&["date", "name"]), false),
+    ("installedModsSortOrder", SettingType::OneOf(&["desc", "asc"]), false),
+    ("maxSteamcmdInstances", SettingType::Number, false),
+];
+
+/// Problem found in the settings store
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreIssue {
+    pub key: String,
+    pub problem: String,
+}
+
+/// Result of validating the settings store file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreValidation {
+    pub valid: bool,
+    pub exists: bool,
+    pub parse_error: Option<String>,
+    pub issues: Vec<StoreIssue>,
+}
+
+/// Default settings, matching the frontend defaults in settingsStorage.ts
+pub fn default_settings() -> Value {
+    serde_json::json!({
+        "modsPath": "",
+        "backupMods": false,
+        "backupDirectory": "",
+        "theme": "system",
+        "isFirstRun": true,
+        "ignoredMods": [],
+        "installedModsSortBy": "date",
+        "installedModsSortOrder": "desc",
+        "maxSteamcmdInstances": 1,
+    })
+}
+
+/// Full store file content with default settings
+pub fn default_store() -> Value {
+    serde_json::json!({ SETTINGS_KEY: default_settings() })
+}
+
+/// Validate the raw content of the settings store file
+/// A missing file is valid (the frontend falls back to defaults)
+pub fn validate_store_content(content: Option<&str>) -> StoreValidation {
+    let content = match content {
+        Some(c) => c,
+        None => {
+            return StoreValidation { valid: true, exists: false, parse_error: None, issues: Vec::new() };
+        }
+    };
+
+    let root: Value = match serde_json::from_str(content) {
+        Ok(v) => v,
+        Err(e) => {
+            return StoreValidation { valid: false, exists: true, parse_error: Some(e.to_string()), issues: Vec::new() };
+        }
+    };
+
+    let mut issues = Vec::new();
+    let settings = match root.as_object() {
+        Some(obj) => obj.get(SETTINGS_KEY),
+        None => {
+            issues.push(StoreIssue { key: String::new(), problem: "Store root is not an object".to_string() });
+            return StoreValidation { valid: false, exists: true, parse_error: None, issues };
+        }
+    };
+
+    match settings {
+        // Settings not saved yet - frontend uses defaults
+        None => {}
+        Some(Value::Object(settings)) => {
+            for (key, setting_type, required) in SETTINGS_SCHEMA {
+                match settings.get(*key) {
+                    None if *required => issues.push(StoreIssue {
+                        key: key.to_string(),
+                        problem: "Missing required setting".to_string(),
+                    }),
+                    None => {}
+                    Some(value) => {
+                        if let Some(problem) = check_type(value, *setting_type) {
+                            issues.push(StoreIssue { key: key.to_string(), problem });
+                        }
+                    }
+                }
+            }
+
+            if let Some(Value::Array(ignored)) = settings.get("ignoredMods") {
+                let all_valid = ignored.iter().all(|m| m.get("modId").map(|id| id.is_string()).unwrap_or(false));
+                if !all_valid {
+                    issues.push(StoreIssue {
+                        key: "ignoredMods".to_string(),
+                        problem: "Every ignored mod must have a string modId".to_string(),
+                    });
+                }
+            }
+        }
+        Some(_) => issues.push(StoreIssue {
+            key: SETTINGS_KEY.to_string(),
+            problem: "Settings are not an object".to_string(),
+        }),
+    }
+
+    StoreValidation { valid: issues.is_empty(), exists: true, parse_error: None, issues }
+}
+
+/// Mod ID of an `ignoredMods` entry, either `{ modId, title }` or a bare ID from older settings
+fn ignored_mod_id(entry: &Value) -> Option<&str> {
+    entry.as_str().or_else(|| entry.get("modId").and_then(|id| id.as_str()))
+}
+
+/// IDs of the mods in the settings `ignoredMods` list
+pub fn ignored_mod_ids(settings: &Value) -> BTreeSet<String> {
+    settings.get("ignoredMods")
+        .and_then(|v| v.as_array())
+        .map(|entries| entries.iter().filter_map(ignored_mod_id).map(|id| id.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Add a mod to or remove it from the settings `ignoredMods` list
+/// New entries are titled with the mod ID unless a title is given, returns whether the list changed
+pub fn set_mod_ignored(settings: &mut Value, mod_id: &str, title: Option<&str>, ignored: bool) -> bool {
+    if !settings.is_object() {
+        *settings = default_settings();
+    }
+    if !settings["ignoredMods"].is_array() {
+        settings["ignoredMods"] = Value::Array(Vec::new());
+    }
+    let entries = settings["ignoredMods"].as_array_mut().unwrap();
+
+    let present = entries.iter().any(|entry| ignored_mod_id(entry) == Some(mod_id));
+    if ignored && !present {
+        entries.push(serde_json::json!({ "modId": mod_id, "title": title.unwrap_or(mod_id) }));
+    } else if !ignored && present {
+        entries.retain(|entry| ignored_mod_id(entry) != Some(mod_id));
+    } else {
+        return false;
+    }
+    true
+}
+
+/// Check a value against its expected type, returning a description of the problem if any
+fn check_type(value: &Value, setting_type: SettingType) -> Option<String> {
+    match setting_type {
+        SettingType::String if !value.is_string() => Some("Expected a string".to_string()),
+        SettingType::Bool if !value.is_boolean() => Some("Expected a boolean".to_string()),
+        SettingType::Number if !value.is_u64() => Some("Expected a positive integer".to_string()),
+        SettingType::Array if !value.is_array() => Some("Expected an array".to_string()),
+        SettingType::OneOf(allowed) => match value.as_str() {
+            Some(s) if allowed.contains(&s) => None,
+            _ => Some(format!("Expected one of: {}", allowed.join(", "))),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_store_is_valid() {
+        let content = serde_json::to_string(&default_store()).unwrap();
+        let result = validate_store_content(Some(&content));
+        assert!(result.valid);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn test_missing_store_is_valid() {
+        let result = validate_store_content(None);
+        assert!(result.valid);
+        assert!(!result.exists);
+    }
+
+    #[test]
+    fn test_truncated_store_reports_parse_error() {
+        let result = validate_store_content(Some("{\"app-settings\": {\"modsPath\": \"C:/Mo"));
+        assert!(!result.valid);
+        assert!(result.parse_error.is_some());
+    }
+
+    #[test]
+    fn test_wrong_types_are_reported() {
+        let mut settings = default_settings();
+        settings["backupMods"] = serde_json::json!("yes");
+        settings["theme"] = serde_json::json!("blue");
+        settings.as_object_mut().unwrap().remove("modsPath");
+        let content = serde_json::json!({ SETTINGS_KEY: settings }).to_string();
+
+        let result = validate_store_content(Some(&content));
+        assert!(!result.valid);
+        let keys: Vec<&str> = result.issues.iter().map(|i| i.key.as_str()).collect();
+        assert!(keys.contains(&"backupMods"));
+        assert!(keys.contains(&"theme"));
+        assert!(keys.contains(&"modsPath"));
+    }
+
+    #[test]
+    fn test_set_mod_ignored_keeps_existing_entries() {
+        let mut settings = default_settings();
+        settings["ignoredMods"] = serde_json::json!(["111", { "modId": "222", "title": "Some Mod" }]);
+
+        assert!(set_mod_ignored(&mut settings, "333", None, true));
+        assert!(!set_mod_ignored(&mut settings, "222", Some("Other"), true));
+        assert_eq!(ignored_mod_ids(&settings), BTreeSet::from(["111".to_string(), "222".to_string(), "333".to_string()]));
+        assert_eq!(settings["ignoredMods"][1]["title"], "Some Mod");
+        assert_eq!(settings["ignoredMods"][2], serde_json::json!({ "modId": "333", "title": "333" }));
+
+        assert!(set_mod_ignored(&mut settings, "111", None, false));
+        assert!(!set_mod_ignored(&mut settings, "111", None, false));
+        assert_eq!(ignored_mod_ids(&settings), BTreeSet::from(["222".to_string(), "333".to_string()]));
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Window, WindowEvent};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use crate::services::{cancel_update, get_downloader, is_update_in_progress};
+
+/// How long closing the app waits for running updates to finish the files they are copying
+pub const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// Set once the user chose how to close, so the app isn't asked again
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Check if closing the app now could leave a mod half-downloaded or half-copied
+/// The downloader being locked by a command counts as busy
+pub fn has_unfinished_work() -> bool {
+    is_update_in_progress() || get_downloader().try_lock()
+        .map(|dl| dl.has_active_downloads())
+        .unwrap_or(true)
+}
+
+/// Ask whether to wait or force quit when the window is closed during a download or update
+/// Closing the dialog without choosing waits, since that can't corrupt a mod
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+    if SHUTTING_DOWN.load(Ordering::Relaxed) || !has_unfinished_work() {
+        return;
+    }
+    api.prevent_close();
+    
+    let app = window.app_handle().clone();
+    app.dialog()
+        .message("Mods are still being downloaded or installed. Closing now can leave a mod half-copied.\n\nWait for the files being copied to finish, or quit right away?")
+        .title("Update in progress")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom("Force quit".to_string(), "Wait".to_string()))
+        .show(move |force_quit| {
+            SHUTTING_DOWN.store(true, Ordering::Relaxed);
+            tauri::async_runtime::spawn(shut_down(app, force_quit));
+        });
+}
+
+/// Stop SteamCMD and exit, after the grace period unless forced
+async fn shut_down(app: AppHandle, force_quit: bool) {
+    if !force_quit {
+        eprintln!("[Shutdown] Waiting up to {} s for running updates", SHUTDOWN_GRACE_PERIOD.as_secs());
+        // Updates stop before their next mod, copies already running finish
+        cancel_update();
+        let start = Instant::now();
+        while has_unfinished_work() && start.elapsed() < SHUTDOWN_GRACE_PERIOD {
+            tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+        if has_unfinished_work() {
+            eprintln!("[Shutdown] Grace period elapsed, closing with updates still running");
+        }
+    }
+    
+    get_downloader().lock().await.kill_our_processes().await;
+    app.exit(0);
+}
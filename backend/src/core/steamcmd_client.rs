@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -8,24 +8,822 @@ use futures;
 use tauri::{AppHandle, Emitter};
 use notify::{Watcher, RecommendedWatcher, RecursiveMode, Event};
 use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::core::download_queue::{DownloadPriority, DownloadQueue, InstanceSlot};
+use crate::core::download_summary::DownloadSummaryReporter;
+use crate::core::steamcmd_log::{SteamCmdLog, SteamCmdLogLine};
+use crate::core::mod_lifecycle::{emit_mod_lifecycle, ModPhase};
+use crate::core::mod_manager::{detect_junk_download, CANCELLED_ERROR};
+use crate::core::mod_lock::{lock_steamcmd_shared, try_lock_steamcmd_exclusive};
+use crate::core::mod_scanner::find_about_dir;
+
+/// State of a single SteamCMD instance during a (parallel) download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstanceState {
+    Starting,
+    Downloading,
+    Verifying,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Progress of a single SteamCMD instance, emitted as `instance-status` events
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceStatus {
+    /// Index of the pool slot the instance runs in
+    pub instance_index: usize,
+    pub current_mod: Option<String>,
+    pub mods_remaining: usize,
+    pub status: InstanceState,
+    /// Download the instance belongs to, a retry only clears the statuses of its own download
+    #[serde(skip)]
+    pub download_id: u64,
+}
+
+/// Numbers the downloads sharing the instance pool
+static NEXT_DOWNLOAD_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Shared per-instance status of all downloads, keyed by pool slot index
+pub type InstanceStatusTracker = Arc<Mutex<HashMap<usize, InstanceStatus>>>;
+
+/// Mods whose in-flight download was cancelled by the user
+pub type CancelledModsTracker = Arc<Mutex<std::collections::HashSet<String>>>;
+
+/// Mods of a download whose instance was killed for another mod's cancellation, retried without counting the attempt
+type InterruptedModsTracker = Arc<Mutex<std::collections::HashSet<String>>>;
+
+/// Why a mod couldn't be downloaded, sent on the download channel
+/// Lets the UI tell failures worth retrying automatically from the ones to show to the user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DownloadError {
+    /// The mod didn't finish within its download timeout
+    Timeout { mod_id: String },
+    /// SteamCMD reported the download as failed
+    SteamCmdReported { mod_id: String },
+    /// The downloaded folder is missing files or much smaller than Steam reports
+    IncompleteFiles { mod_id: String },
+    /// The downloaded folder holds an error page or an unreadable About.xml instead of the mod
+    JunkContent { mod_id: String },
+    /// SteamCMD exited with an error before the mod was downloaded
+    ProcessExited { mod_id: String, exit_code: i32 },
+    /// The mod never showed up in the download folder
+    NotDetected { mod_id: String },
+    /// SteamCMD couldn't log in to Steam, so no mod of the batch could be downloaded
+    LoginFailed { mod_id: String, reason: String },
+    /// The user cancelled the download
+    Cancelled { mod_id: String },
+}
+
+impl DownloadError {
+    pub fn mod_id(&self) -> &str {
+        match self {
+            Self::Timeout { mod_id }
+            | Self::SteamCmdReported { mod_id }
+            | Self::IncompleteFiles { mod_id }
+            | Self::JunkContent { mod_id }
+            | Self::ProcessExited { mod_id, .. }
+            | Self::NotDetected { mod_id }
+            | Self::LoginFailed { mod_id, .. }
+            | Self::Cancelled { mod_id } => mod_id,
+        }
+    }
+}
+
+impl std::fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout { mod_id } => write!(f, "Download timeout for mod {}", mod_id),
+            Self::SteamCmdReported { mod_id } => write!(f, "Download failed for mod {}", mod_id),
+            Self::IncompleteFiles { mod_id } => write!(f, "Download incomplete for mod {}", mod_id),
+            Self::JunkContent { mod_id } => write!(f, "Download of mod {} contains an error page instead of the mod", mod_id),
+            Self::ProcessExited { mod_id, exit_code } => {
+                write!(f, "SteamCMD exited with code {} while downloading mod {}", exit_code, mod_id)
+            }
+            Self::NotDetected { mod_id } => write!(f, "Download not detected for mod {}", mod_id),
+            Self::LoginFailed { mod_id, reason } => {
+                write!(f, "SteamCMD login failed while downloading mod {}: {}", mod_id, reason)
+            }
+            Self::Cancelled { mod_id } => write!(f, "Download of mod {} was cancelled", mod_id),
+        }
+    }
+}
+
+/// Latest download failure of each mod, reported once its retries are exhausted
+type DownloadErrorTracker = Arc<Mutex<HashMap<String, DownloadError>>>;
+
+/// Steam account used instead of anonymous login, for items that require owning RimWorld
+/// Kept in memory only, never persisted
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteamCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// A failed SteamCMD login, which fails every mod of the instance at once
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginFailure {
+    /// The SteamCMD output line reporting the failure
+    pub reason: String,
+    /// Steam rejected the login because of too many recent logins
+    pub rate_limited: bool,
+}
+
+/// Minimum wait before retrying after a failed login, Steam's login rate limit lasts much longer than a normal backoff
+const LOGIN_FAILURE_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Login settings shared by all SteamCMD instances of a download
+#[derive(Clone)]
+struct SteamLogin {
+    credentials: Option<SteamCredentials>,
+    /// Steam Guard codes submitted by the user, forwarded to instances waiting for one
+    steam_guard_codes: tokio::sync::broadcast::Sender<String>,
+    /// Login failure seen by any instance during the current attempt
+    login_failure: Arc<Mutex<Option<LoginFailure>>>,
+}
+
+/// Removes a SteamCMD script when dropped, so scripts containing credentials never outlive a run
+struct ScriptFileGuard(PathBuf);
+
+impl Drop for ScriptFileGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Quote a value for a SteamCMD script line
+/// SteamCMD has no escape sequences, a quote or line break would end the value and break the script
+fn quote_script_arg(value: &str) -> Result<String, String> {
+    if value.contains(['"', '\n', '\r']) {
+        return Err("Value contains characters a SteamCMD script can't hold".to_string());
+    }
+    Ok(format!("\"{}\"", value))
+}
+
+/// Write a SteamCMD script readable by the current user only
+/// The file is created with its final permissions, so a script holding the Steam password is never readable by others,
+/// a leftover of a crashed run is replaced rather than reused with its old permissions
+fn write_script_file(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(content.as_bytes())
+}
+
+/// Download progress parsed from a SteamCMD progress line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DownloadProgress {
+    percent: f64,
+    bytes_downloaded: u64,
+    bytes_total: u64,
+}
+
+impl DownloadProgress {
+    /// Parse lines like `Update state (0x61) downloading, progress: 42.13 (1234567 / 2930000)`
+    fn parse(line: &str) -> Option<Self> {
+        static PROGRESS_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let re = PROGRESS_RE.get_or_init(|| {
+            regex::Regex::new(r"progress:\s*([\d.]+)\s*\((\d+)\s*/\s*(\d+)\)").unwrap()
+        });
+        let captures = re.captures(line)?;
+        Some(Self {
+            percent: captures[1].parse().ok()?,
+            bytes_downloaded: captures[2].parse().ok()?,
+            bytes_total: captures[3].parse().ok()?,
+        })
+    }
+}
+
+/// Tracks which mod the progress lines of a SteamCMD instance refer to
+/// Progress lines don't name the mod, so it's taken from the preceding item line.
+/// When resuming, progress can appear before the item line and is held until the mod is known
+#[derive(Default)]
+struct ProgressState {
+    current_mod: Option<String>,
+    pending: Option<DownloadProgress>,
+}
+
+impl ProgressState {
+    /// Feed a line of output, returns progress to report for a mod
+    fn handle_line(&mut self, line: &str, mod_ids: &[String]) -> Option<(String, DownloadProgress)> {
+        if let Some(progress) = DownloadProgress::parse(line) {
+            return match &self.current_mod {
+                Some(mod_id) => Some((mod_id.clone(), progress)),
+                None => {
+                    self.pending = Some(progress);
+                    None
+                }
+            };
+        }
+        
+        let line_lower = line.to_lowercase();
+        let mod_id = mod_ids.iter().find(|id| line_lower.contains(id.as_str()))?;
+        if line_lower.contains("downloading item") || line_lower.contains("workshop_download_item") {
+            self.current_mod = Some(mod_id.clone());
+            return self.pending.take().map(|progress| (mod_id.clone(), progress));
+        }
+        if (line_lower.contains("downloaded item") || line_lower.contains("failed"))
+            && self.current_mod.as_ref() == Some(mod_id) {
+            self.current_mod = None;
+        }
+        None
+    }
+}
+
+/// Downloads deviating more than this from the size Steam reports are checked for corruption
+const CORRUPT_SIZE_TOLERANCE_PCT: f64 = 50.0;
+
+/// Mods downloaded per SteamCMD instance at most, so instances of bulk downloads regularly free their slot
+/// for a waiting high-priority download
+const MAX_MODS_PER_INSTANCE: usize = 10;
+
+/// Estimated peak memory of a single SteamCMD instance, including the app's own buffers for it
+const ESTIMATED_INSTANCE_MEMORY: u64 = 300 * 1024 * 1024;
+
+/// Time to wait for a mod whose size is unknown
+const DEFAULT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+/// Minimum time to wait for a mod of known size (SteamCMD startup, login, verification)
+const DOWNLOAD_TIMEOUT_BASE: Duration = Duration::from_secs(120);
+/// Slowest download speed tolerated before a mod of known size times out
+const MIN_DOWNLOAD_BYTES_PER_SEC: u64 = 128 * 1024;
+/// Time to wait for a mod's folder once SteamCMD has exited, it's written by then unless the download failed
+const EXITED_DETECTION_GRACE: Duration = Duration::from_secs(5);
+
+/// SteamCMD instances allowed to run while a download throttle is set
+/// The throttle is per instance, so a single instance caps the total rate at the configured value
+pub const DEFAULT_THROTTLED_INSTANCES: usize = 1;
+
+/// Steam app id of RimWorld, the game downloads are for unless configured otherwise
+pub const DEFAULT_APP_ID: u32 = 294100;
+
+/// Time SteamCMD gets to update itself and quit during a health check
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Outcome of a SteamCMD health check that found a working installation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteamCmdHealth {
+    pub executable: String,
+    /// SteamCMD downloaded and applied an update of itself during the check
+    pub self_updated: bool,
+}
+
+/// Interpret the output of `steamcmd +quit`, returning whether SteamCMD updated itself
+/// A broken installation is reported with what to do about it
+fn parse_steamcmd_health(output: &str, exited_successfully: bool) -> Result<bool, String> {
+    let output_lower = output.to_lowercase();
+    if output_lower.contains("error while loading shared libraries") {
+        return Err("SteamCMD is missing 32-bit system libraries. Install lib32gcc-s1 (Debian/Ubuntu) or glibc.i686 and libstdc++.i686 (Fedora)".to_string());
+    }
+    // The launcher script runs linux32/steamcmd, which a failed self-update can leave missing
+    if output_lower.contains("linux32/steamcmd") && output_lower.contains("no such file") {
+        return Err("SteamCMD's linux32 folder is missing or incomplete after its self-update. Reinstall SteamCMD".to_string());
+    }
+    if output_lower.contains("update failed") || output_lower.contains("failed to apply update") {
+        return Err("SteamCMD failed to update itself. Check the internet connection or reinstall SteamCMD".to_string());
+    }
+    if !exited_successfully {
+        let last_line = output.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("no output");
+        return Err(format!("SteamCMD exited with an error ({}). Reinstall SteamCMD if this persists", last_line.trim()));
+    }
+    Ok(output_lower.contains("update complete") || output_lower.contains("downloading update"))
+}
+
+/// Find out why SteamCMD could not be started, for the common Linux causes
+/// None when nothing specific was found and the OS error is all there is
+async fn diagnose_spawn_failure(executable: &Path) -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    // steamcmd.sh only launches the real binary in linux32
+    let binary = if executable.extension().is_some_and(|ext| ext == "sh") {
+        executable.parent()?.join("linux32").join("steamcmd")
+    } else {
+        executable.to_path_buf()
+    };
+    if !binary.is_file() {
+        return Some(format!("SteamCMD's binary {} is missing, its installation is incomplete. Reinstall SteamCMD", binary.display()));
+    }
+
+    let output = Command::new("ldd").arg(&binary).output().await.ok()?;
+    let text = format!("{}\n{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    let mut missing = parse_ldd_missing_libraries(&text);
+    // Without the 32-bit loader ldd can't even read the binary
+    if missing.is_empty() && text.contains("not a dynamic executable") && is_32_bit_elf(&binary) {
+        missing.push("ld-linux.so.2".to_string());
+    }
+    if missing.is_empty() {
+        return None;
+    }
+    Some(missing_libraries_message(&missing))
+}
+
+/// Libraries ldd reports as `libfoo.so => not found`
+fn parse_ldd_missing_libraries(output: &str) -> Vec<String> {
+    output.lines()
+        .filter_map(|line| line.trim().strip_suffix("=> not found"))
+        .map(|library| library.trim().to_string())
+        .filter(|library| !library.is_empty())
+        .collect()
+}
+
+fn is_32_bit_elf(path: &Path) -> bool {
+    use std::io::Read;
+    let mut header = [0u8; 5];
+    fs::File::open(path).and_then(|mut file| file.read_exact(&mut header)).is_ok()
+        && header[..4] == *b"\x7fELF"
+        && header[4] == 1
+}
+
+/// Tell which 32-bit libraries are missing and which Debian/Ubuntu packages provide them
+fn missing_libraries_message(libraries: &[String]) -> String {
+    let mut packages: Vec<&str> = Vec::new();
+    for library in libraries {
+        let package = if library.starts_with("libstdc++") {
+            "lib32stdc++6"
+        } else if library.starts_with("libgcc_s") {
+            "lib32gcc-s1"
+        } else {
+            "libc6-i386"
+        };
+        if !packages.contains(&package) {
+            packages.push(package);
+        }
+    }
+    let libraries: Vec<String> = libraries.iter().map(|library| format!("{} i386", library)).collect();
+    format!(
+        "SteamCMD requires 32-bit libraries ({}). Install {} (Debian/Ubuntu) or the matching .i686 packages (Fedora)",
+        libraries.join(", "),
+        packages.join(" "),
+    )
+}
+
+/// How long to wait for mod downloads, and how often to poll the download folder meanwhile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownloadTimeouts {
+    /// Fixed timeout per mod, overrides the size-based timeout
+    pub fixed: Option<Duration>,
+    /// Polling interval used when no file system event arrives
+    pub poll_interval: Duration,
+}
+
+impl Default for DownloadTimeouts {
+    fn default() -> Self {
+        Self {
+            fixed: None,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+impl DownloadTimeouts {
+    /// Timeout for a mod, measured from the start of its batch, given the bytes its SteamCMD instance
+    /// has to download up to and including it
+    /// Mods of an instance download one after another, so later mods wait for the earlier ones too
+    fn for_mod(&self, queued_bytes: u64, size_known: bool) -> Duration {
+        if let Some(fixed) = self.fixed {
+            return fixed;
+        }
+        let scaled = DOWNLOAD_TIMEOUT_BASE + Duration::from_secs(queued_bytes / MIN_DOWNLOAD_BYTES_PER_SEC);
+        if size_known {
+            scaled
+        } else {
+            scaled.max(DEFAULT_DOWNLOAD_TIMEOUT)
+        }
+    }
+}
+
+/// How often failed downloads are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownloadRetryPolicy {
+    /// Retry rounds of a download batch after the first attempt
+    pub max_retries: u32,
+    /// Retries of a single mod, so a mod that keeps failing doesn't hold up the batch (None allows `max_retries`)
+    pub max_retries_per_mod: Option<u32>,
+}
+
+impl Default for DownloadRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 6,
+            max_retries_per_mod: None,
+        }
+    }
+}
+
+impl DownloadRetryPolicy {
+    /// Retries a single mod gets, bounded by the retries of the batch
+    fn retries_per_mod(&self) -> u32 {
+        self.max_retries_per_mod.map_or(self.max_retries, |n| n.min(self.max_retries))
+    }
+
+    /// Delay before a retry round: exponential backoff (1s, 2s, 4s, ...) with ±25% jitter,
+    /// so parallel instances and clients don't all retry at the same instants
+    fn backoff(retry: u32) -> Duration {
+        use std::hash::BuildHasher;
+        let base_ms = 1000_u64.saturating_mul(2_u64.saturating_pow(retry.saturating_sub(1)));
+        // RandomState is seeded randomly, which is enough for jitter without pulling in a rand crate
+        let random = std::collections::hash_map::RandomState::new().hash_one(retry);
+        let jitter = 0.75 + 0.5 * (random as f64 / u64::MAX as f64);
+        Duration::from_millis((base_ms as f64 * jitter) as u64)
+    }
+
+    /// Delay before a retry round, at least `LOGIN_FAILURE_BACKOFF` after a failed login
+    fn retry_delay(retry: u32, login_failed: bool) -> Duration {
+        let delay = Self::backoff(retry);
+        if login_failed {
+            delay.max(LOGIN_FAILURE_BACKOFF)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Result of cleaning up stuck SteamCMD processes and stale files
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteamCmdCleanupReport {
+    /// Tracked processes that were still running and got terminated
+    pub terminated_pids: Vec<u32>,
+    /// Tracked processes that had already exited but were never removed from the tracker
+    pub stale_pids: Vec<u32>,
+    /// Crash markers, lock files and leftover scripts removed from the SteamCMD directory
+    pub removed_files: Vec<String>,
+}
 
 pub struct Downloader {
     steamcmd_path: PathBuf,
+    /// Directory SteamCMD installs Workshop items into (`force_install_dir`), the SteamCMD folder if None
+    install_dir: Option<PathBuf>,
     download_path: PathBuf,
     active_downloads: std::collections::HashSet<String>,
     active_process_pids: Arc<tokio::sync::Mutex<Vec<u32>>>, 
+    download_throttle_kbps: Option<u32>,
+    throttled_max_instances: usize,
+    instance_statuses: InstanceStatusTracker,
+    custom_executable: Option<PathBuf>,
+    cancelled_mods: CancelledModsTracker,
+    max_memory_usage: Option<u64>,
+    credentials: Option<SteamCredentials>,
+    steam_guard_codes: tokio::sync::broadcast::Sender<String>,
+    download_timeouts: DownloadTimeouts,
+    retry_policy: DownloadRetryPolicy,
+    app_id: u32,
+    download_queue: Arc<DownloadQueue>,
+    /// Emit SteamCMD output live as `steamcmd-log` events
+    verbose_log: bool,
+    /// SteamCMD output of the most recently started download
+    last_download_log: Option<Arc<SteamCmdLog>>,
 }
 
 impl Downloader {
     pub fn new(steamcmd_path: Option<PathBuf>) -> Self {
+        Self::with_app_id(steamcmd_path, DEFAULT_APP_ID)
+    }
+
+    /// Create a downloader for the Workshop items of another Steam game
+    pub fn with_app_id(steamcmd_path: Option<PathBuf>, app_id: u32) -> Self {
         let steamcmd_path = steamcmd_path.unwrap_or_else(|| PathBuf::from("steamcmd"));
-        let download_path = steamcmd_path.join("steamapps").join("workshop").join("content").join("294100");
+        let download_path = Self::workshop_content_path(&steamcmd_path, app_id);
         
         Self {
             steamcmd_path,
+            install_dir: None,
             download_path,
             active_downloads: std::collections::HashSet::new(),
             active_process_pids: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            download_throttle_kbps: None,
+            throttled_max_instances: DEFAULT_THROTTLED_INSTANCES,
+            instance_statuses: Arc::new(Mutex::new(HashMap::new())),
+            custom_executable: None,
+            cancelled_mods: Arc::new(Mutex::new(std::collections::HashSet::new())),
+            max_memory_usage: None,
+            credentials: None,
+            steam_guard_codes: tokio::sync::broadcast::channel(4).0,
+            download_timeouts: DownloadTimeouts::default(),
+            retry_policy: DownloadRetryPolicy::default(),
+            app_id,
+            download_queue: Arc::new(DownloadQueue::new(Self::resolve_max_instances(None).unwrap_or(1))),
+            verbose_log: false,
+            last_download_log: None,
+        }
+    }
+
+    /// Directory SteamCMD downloads the Workshop items of a game to
+    fn workshop_content_path(steamcmd_path: &Path, app_id: u32) -> PathBuf {
+        steamcmd_path.join("steamapps").join("workshop").join("content").join(app_id.to_string())
+    }
+
+    /// Switch the game Workshop items are downloaded for
+    /// Fails while downloads are running, as they would end up in the old game's directory
+    pub fn set_app_id(&mut self, app_id: u32) -> Result<(), String> {
+        if app_id == 0 {
+            return Err("Invalid app id: 0".to_string());
+        }
+        if app_id != self.app_id && !self.active_downloads.is_empty() {
+            return Err("Cannot change the app id while downloads are running".to_string());
+        }
+        self.app_id = app_id;
+        self.download_path = Self::workshop_content_path(self.effective_install_dir(), app_id);
+        Ok(())
+    }
+
+    /// Download Workshop items into another directory, e.g. on a faster drive (None goes back to the SteamCMD folder)
+    /// Fails while downloads are running, as they would end up in the old directory
+    pub fn set_install_dir(&mut self, install_dir: Option<PathBuf>) -> Result<(), String> {
+        if install_dir != self.install_dir && !self.active_downloads.is_empty() {
+            return Err("Cannot change the download directory while downloads are running".to_string());
+        }
+        self.install_dir = install_dir;
+        self.download_path = Self::workshop_content_path(self.effective_install_dir(), self.app_id);
+        Ok(())
+    }
+
+    /// Get the configured download directory, None if items are downloaded into the SteamCMD folder
+    pub fn install_dir(&self) -> Option<&PathBuf> {
+        self.install_dir.as_ref()
+    }
+
+    fn effective_install_dir(&self) -> &Path {
+        self.install_dir.as_deref().unwrap_or(&self.steamcmd_path)
+    }
+
+    /// Get the Steam app id Workshop items are downloaded for
+    pub fn app_id(&self) -> u32 {
+        self.app_id
+    }
+
+    /// Set the SteamCMD download throttle in kbps (None or 0 disables throttling)
+    /// The throttle applies per SteamCMD instance
+    pub fn set_download_throttle(&mut self, kbps: Option<u32>) {
+        self.download_throttle_kbps = kbps.filter(|&k| k > 0);
+    }
+
+    /// Get the SteamCMD download throttle in kbps
+    pub fn download_throttle(&self) -> Option<u32> {
+        self.download_throttle_kbps
+    }
+
+    /// Set how many SteamCMD instances may run in parallel while a throttle is set
+    /// The total bandwidth is up to the throttle times this number: more instances finish
+    /// batches of small mods faster, a single instance keeps the link usage predictable
+    pub fn set_throttled_max_instances(&mut self, instances: usize) {
+        self.throttled_max_instances = instances.max(1);
+    }
+
+    /// Get how many SteamCMD instances may run in parallel while a throttle is set
+    pub fn throttled_max_instances(&self) -> usize {
+        self.throttled_max_instances
+    }
+
+    /// Limit the number of SteamCMD instances when a download throttle is set
+    fn throttle_limited_instances(&self, requested: usize) -> usize {
+        match self.download_throttle_kbps {
+            Some(_) => requested.min(self.throttled_max_instances),
+            None => requested,
+        }
+    }
+    
+    /// Set a user-configured SteamCMD executable that takes priority over the search logic
+    /// None restores the default search
+    pub fn set_custom_executable(&mut self, path: Option<PathBuf>) {
+        self.custom_executable = path;
+    }
+
+    /// Get the user-configured SteamCMD executable
+    pub fn custom_executable(&self) -> Option<&PathBuf> {
+        self.custom_executable.as_ref()
+    }
+
+    /// Resolve a user-specified SteamCMD executable or installation directory to the executable
+    pub fn resolve_steamcmd_executable(path: &Path) -> Result<PathBuf, String> {
+        if path.is_file() {
+            return Self::check_custom_executable(path).map(|_| path.to_path_buf());
+        }
+        if !path.is_dir() {
+            return Err(format!("SteamCMD path does not exist: {:?}", path));
+        }
+
+        let candidates: &[&str] = if cfg!(target_os = "windows") {
+            &["steamcmd.exe"]
+        } else {
+            &["steamcmd.sh", "steamcmd"]
+        };
+        let executable = candidates.iter()
+            .map(|name| path.join(name))
+            .find(|candidate| candidate.is_file())
+            .ok_or_else(|| format!("No SteamCMD executable found in {:?}", path))?;
+        Self::check_custom_executable(&executable)?;
+        Ok(executable)
+    }
+
+    /// Check that a user-configured SteamCMD executable exists and may be executed
+    fn check_custom_executable(path: &Path) -> Result<(), String> {
+        if !path.is_file() {
+            return Err(format!("Configured SteamCMD executable does not exist: {:?}", path));
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(path)
+                .map_err(|e| format!("Failed to read SteamCMD executable {:?}: {}", path, e))?
+                .permissions()
+                .mode();
+            if mode & 0o111 == 0 {
+                return Err(format!("Configured SteamCMD executable is not executable: {:?} (run chmod +x on it)", path));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that a SteamCMD executable can actually be started
+    /// SteamCMD may update itself on first run, so it is stopped once it is known to run
+    pub async fn check_steamcmd_runs(executable: &Path) -> Result<(), String> {
+        let mut cmd = Command::new(executable);
+        cmd.arg("+quit")
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true);
+        if let Some(dir) = executable.parent() {
+            cmd.current_dir(dir);
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            cmd.creation_flags(0x08000000);
+        }
+
+        let mut child = cmd.spawn()
+            .map_err(|e| format!("Failed to start SteamCMD at {:?}: {}", executable, e))?;
+
+        match tokio::time::timeout(Duration::from_secs(10), child.wait()).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(format!("Failed to wait for SteamCMD: {}", e)),
+            Err(_) => {
+                // Still running (e.g. self-updating) - it started fine
+                let _ = child.kill().await;
+                Ok(())
+            }
+        }
+    }
+
+    /// Run `steamcmd +quit` to completion, giving SteamCMD the chance to finish its self-update
+    /// Fails with an actionable message when the installation is broken
+    pub async fn check_steamcmd_health(executable: &Path) -> Result<SteamCmdHealth, String> {
+        let mut cmd = Command::new(executable);
+        cmd.arg("+quit")
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+        if let Some(dir) = executable.parent() {
+            cmd.current_dir(dir);
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            cmd.creation_flags(0x08000000);
+        }
+
+        let output = match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, cmd.output()).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => return Err(format!("Failed to start SteamCMD at {:?}: {}", executable, e)),
+            Err(_) => return Err(format!("SteamCMD did not finish within {} seconds, its self-update may be stuck. Reinstall SteamCMD", HEALTH_CHECK_TIMEOUT.as_secs())),
+        };
+        let text = format!("{}\n{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        let self_updated = parse_steamcmd_health(&text, output.status.success())?;
+        eprintln!("[Downloader] SteamCMD at {:?} is healthy (self-updated: {})", executable, self_updated);
+
+        Ok(SteamCmdHealth {
+            executable: executable.to_string_lossy().to_string(),
+            self_updated,
+        })
+    }
+
+    /// Get the status of every SteamCMD instance of the current (or last) download attempt
+    pub fn instance_statuses(&self) -> Vec<InstanceStatus> {
+        let statuses = self.instance_statuses.lock().unwrap();
+        let mut list: Vec<InstanceStatus> = statuses.values().cloned().collect();
+        list.sort_by_key(|s| s.instance_index);
+        list
+    }
+    
+    /// Cancel in-flight downloads of the given mods
+    /// SteamCMD instances downloading any of them are killed, other mods of those instances are retried
+    pub fn cancel_mods(&self, mod_ids: &[String]) {
+        let mut cancelled = self.cancelled_mods.lock().unwrap();
+        cancelled.extend(mod_ids.iter().cloned());
+        eprintln!("[Downloader] Cancelling download of {} mod(s): {}", mod_ids.len(), mod_ids.join(", "));
+    }
+
+    /// Take the mods cancelled meanwhile out of a download, they are no longer tracked once it stops downloading them
+    fn take_cancelled_mods(cancelled_mods: &CancelledModsTracker, remaining_mod_ids: &mut Vec<String>) -> Vec<String> {
+        let mut cancelled = cancelled_mods.lock().unwrap();
+        let (taken, kept) = remaining_mod_ids.drain(..).partition(|id| cancelled.contains(id));
+        *remaining_mod_ids = kept;
+        for mod_id in &taken {
+            cancelled.remove(mod_id);
+        }
+        taken
+    }
+
+    /// Remember the mods of a batch killed for the cancellation of one of them, the others weren't at fault
+    fn record_interrupted_mods(cancelled_mods: &CancelledModsTracker, interrupted_mods: &InterruptedModsTracker, mod_ids: &[String]) {
+        if crate::services::is_update_cancelled() {
+            return;
+        }
+        let cancelled = cancelled_mods.lock().unwrap();
+        interrupted_mods.lock().unwrap()
+            .extend(mod_ids.iter().filter(|id| !cancelled.contains(*id)).cloned());
+    }
+
+    /// Check if any mod of a batch was cancelled
+    fn is_batch_cancelled(cancelled_mods: &CancelledModsTracker, mod_ids: &[String]) -> bool {
+        let cancelled = cancelled_mods.lock().unwrap();
+        mod_ids.iter().any(|id| cancelled.contains(id))
+    }
+
+    /// Delete SteamCMD's workshop manifest before an attempt that must not trust it
+    /// SteamCMD skips mods its manifest lists as installed, even when their content folder is gone, so the manifest
+    /// is deleted at the start of every session and whenever a mod has neither a content folder nor a staged download
+    /// Retries within a session keep it otherwise, so SteamCMD resumes partial downloads instead of pulling them again,
+    /// except for corrupt downloads, which start over without their staged files
+    /// Returns true if the manifest was deleted
+    fn prepare_workshop_manifest(
+        install_dir: &Path,
+        download_path: &Path,
+        mod_ids: &[String],
+        download_errors: &DownloadErrorTracker,
+        app_id: u32,
+        first_attempt: bool,
+    ) -> bool {
+        let corrupt: Vec<String> = {
+            let errors = download_errors.lock().unwrap();
+            mod_ids.iter()
+                .filter(|id| matches!(errors.get(*id), Some(DownloadError::IncompleteFiles { .. } | DownloadError::JunkContent { .. })))
+                .cloned()
+                .collect()
+        };
+        let workshop_path = install_dir.join("steamapps").join("workshop");
+        let staging_path = workshop_path.join("downloads").join(app_id.to_string());
+        let missing = mod_ids.iter()
+            .any(|id| !download_path.join(id).is_dir() && !staging_path.join(id).is_dir());
+        if !first_attempt && !missing && corrupt.is_empty() {
+            return false;
+        }
+        
+        if !corrupt.is_empty() {
+            eprintln!("[Downloader] Clean retry for corrupt download(s): {}", corrupt.join(", "));
+            for mod_id in &corrupt {
+                let _ = fs::remove_dir_all(staging_path.join(mod_id));
+            }
+        }
+        let _ = fs::remove_file(workshop_path.join(format!("appworkshop_{}.acf", app_id)));
+        true
+    }
+
+    /// Remove partial downloads of cancelled mods so a later retry doesn't treat them as complete
+    fn remove_cancelled_downloads(
+        install_dir: &Path,
+        download_path: &Path,
+        mod_ids: &[String],
+        cancelled_mods: &CancelledModsTracker,
+        app_id: u32,
+    ) {
+        let cancelled: Vec<String> = {
+            let cancelled = cancelled_mods.lock().unwrap();
+            mod_ids.iter().filter(|id| cancelled.contains(*id)).cloned().collect()
+        };
+        
+        // SteamCMD stages files in the downloads directory before moving them to content
+        let staging_path = install_dir.join("steamapps").join("workshop").join("downloads").join(app_id.to_string());
+        for mod_id in &cancelled {
+            for path in [download_path.join(mod_id), staging_path.join(mod_id)] {
+                if path.exists() {
+                    match fs::remove_dir_all(&path) {
+                        Ok(()) => eprintln!("[Downloader] Removed partial download of cancelled mod {}: {:?}", mod_id, path),
+                        Err(e) => eprintln!("[Downloader] Failed to remove partial download {:?}: {}", path, e),
+                    }
+                }
+            }
         }
     }
     
@@ -77,18 +875,166 @@ impl Downloader {
         eprintln!("[Downloader] All process trackers cleared");
     }
 
+    /// Terminate lingering SteamCMD processes spawned by the app and remove stale lock files
+    /// Running downloads are stopped as well, so this is meant for when downloads are stuck
+    pub async fn cleanup_stuck_processes(&mut self) -> SteamCmdCleanupReport {
+        let pids: Vec<u32> = {
+            let pid_list = self.active_process_pids.lock().await;
+            pid_list.clone()
+        };
+        
+        let (terminated_pids, stale_pids): (Vec<u32>, Vec<u32>) = pids
+            .into_iter()
+            .partition(|pid| Self::is_process_alive(*pid));
+        
+        // Kills whatever is still alive and clears the tracker, including exited processes
+        self.kill_our_processes().await;
+        
+        // Give the OS a moment to release file handles of killed processes
+        if !terminated_pids.is_empty() {
+            sleep(Duration::from_millis(500)).await;
+        }
+        
+        let removed_files = Self::remove_stale_steamcmd_files(&self.steamcmd_path, true);
+        
+        eprintln!("[Downloader] Cleanup: terminated {} process(es), dropped {} stale PID(s), removed {} file(s)",
+            terminated_pids.len(), stale_pids.len(), removed_files.len());
+        
+        SteamCmdCleanupReport {
+            terminated_pids,
+            stale_pids,
+            removed_files: removed_files.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        }
+    }
+    
+    /// Check if a process with the given PID is still running
+    fn is_process_alive(pid: u32) -> bool {
+        #[cfg(unix)]
+        {
+            std::process::Command::new("kill")
+                .arg("-0")
+                .arg(pid.to_string())
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        }
+        #[cfg(windows)]
+        {
+            std::process::Command::new("tasklist")
+                .args(["/FI", format!("PID eq {}", pid).as_str(), "/NH"])
+                .output()
+                .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+                .unwrap_or(false)
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = pid;
+            false
+        }
+    }
+    
+    /// Remove crash markers and lock files left behind by killed or crashed SteamCMD instances
+    /// With `include_scripts`, leftover batch scripts are removed as well
+    /// Returns the removed files
+    /// Nothing is removed while another instance of the app or any other program runs SteamCMD
+    fn remove_stale_steamcmd_files(steamcmd_path: &Path, include_scripts: bool) -> Vec<PathBuf> {
+        let _cleanup_lock = match try_lock_steamcmd_exclusive(steamcmd_path) {
+            Ok(Some(lock)) => lock,
+            Ok(None) => {
+                eprintln!("[Downloader] SteamCMD is in use by another instance of the app, keeping its files");
+                return Vec::new();
+            }
+            Err(e) => {
+                eprintln!("[Downloader] Failed to lock SteamCMD folder, keeping its files: {}", e);
+                return Vec::new();
+            }
+        };
+        if Self::is_steamcmd_running() {
+            eprintln!("[Downloader] A SteamCMD process is running, keeping its files");
+            return Vec::new();
+        }
+        Self::remove_steamcmd_files(steamcmd_path, |name| {
+            name == ".crash"
+                || name.ends_with(".lock")
+                || (include_scripts && Self::is_batch_script(name))
+        })
+    }
+
+    /// Whether any SteamCMD process is running, whoever started it
+    fn is_steamcmd_running() -> bool {
+        let mut system = sysinfo::System::new();
+        system.refresh_processes();
+        system.processes().values().any(|process| process.name().to_lowercase().starts_with("steamcmd"))
+    }
+
+    /// Whether a (lowercase) file name is a batch script written for a SteamCMD instance
+    fn is_batch_script(name: &str) -> bool {
+        name.starts_with("run_batch_") && name.ends_with(".txt")
+    }
+
+    /// Remove batch scripts left behind when the app crashed during a download, they may hold the Steam password
+    /// Meant for startup, scripts of running downloads would be removed as well
+    pub fn remove_leftover_scripts(&self) -> Vec<PathBuf> {
+        Self::remove_steamcmd_files(&self.steamcmd_path, Self::is_batch_script)
+    }
+
+    /// Remove the files directly in the SteamCMD folder whose lowercase name matches `is_stale`
+    fn remove_steamcmd_files(steamcmd_path: &Path, is_stale: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+        let mut removed = Vec::new();
+        let entries = match fs::read_dir(steamcmd_path) {
+            Ok(entries) => entries,
+            Err(_) => return removed,
+        };
+        
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let name = match path.file_name().and_then(|n| n.to_str()) {
+                Some(name) => name.to_lowercase(),
+                None => continue,
+            };
+            
+            if is_stale(&name) {
+                match fs::remove_file(&path) {
+                    Ok(()) => {
+                        eprintln!("[Downloader] Removed stale SteamCMD file: {:?}", path);
+                        removed.push(path);
+                    }
+                    Err(e) => eprintln!("[Downloader] Failed to remove stale SteamCMD file {:?}: {}", path, e),
+                }
+            }
+        }
+        
+        removed
+    }
+
     /// Get the download path where mods are downloaded
     pub fn download_path(&self) -> &PathBuf {
         &self.download_path
     }
 
+    /// Get the directory the Workshop items of `app_id` are downloaded to
+    pub fn download_path_for_app(&self, app_id: u32) -> PathBuf {
+        Self::workshop_content_path(self.effective_install_dir(), app_id)
+    }
+
     /// Find SteamCMD executable from application resources or PATH
     pub async fn find_steamcmd_executable(&self) -> Result<PathBuf, String> {
-        Self::find_steamcmd_executable_static(&self.steamcmd_path).await
+        Self::find_steamcmd_executable_static(&self.steamcmd_path, self.custom_executable.as_ref()).await
     }
     
     /// Static version of find_steamcmd_executable for use in spawned tasks
-    async fn find_steamcmd_executable_static(steamcmd_path: &PathBuf) -> Result<PathBuf, String> {
+    async fn find_steamcmd_executable_static(steamcmd_path: &PathBuf, custom_executable: Option<&PathBuf>) -> Result<PathBuf, String> {
+        // Priority 0: User-configured executable
+        // No fallback when it became invalid, the user set it because the bundled SteamCMD doesn't work for them
+        if let Some(custom_path) = custom_executable {
+            Self::check_custom_executable(custom_path)?;
+            eprintln!("[Downloader] Using configured SteamCMD executable: {:?}", custom_path);
+            return Ok(custom_path.clone());
+        }
+
         let steamcmd_exe = if cfg!(target_os = "windows") {
             "steamcmd.exe"
         } else {
@@ -202,6 +1148,106 @@ impl Downloader {
         batches
     }
 
+    /// Resolve the number of parallel SteamCMD instances to use
+    /// Defaults to half of the available CPUs, clamped to 1..=8; an explicit 0 is rejected
+    pub fn resolve_max_instances(max_instances: Option<usize>) -> Result<usize, String> {
+        match max_instances {
+            Some(0) => Err("Number of SteamCMD instances must be at least 1".to_string()),
+            Some(n) => Ok(n),
+            None => {
+                let cpus = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(2);
+                Ok((cpus / 2).clamp(1, 8))
+            }
+        }
+    }
+
+    /// Log in with a Steam account instead of anonymously (None goes back to anonymous login)
+    pub fn set_credentials(&mut self, credentials: Option<SteamCredentials>) {
+        self.credentials = credentials;
+    }
+
+    /// Check if downloads log in with a Steam account
+    pub fn has_credentials(&self) -> bool {
+        self.credentials.is_some()
+    }
+
+    /// Emit the output of SteamCMD as `steamcmd-log` events while downloading
+    pub fn set_verbose_log(&mut self, verbose: bool) {
+        self.verbose_log = verbose;
+    }
+
+    pub fn verbose_log(&self) -> bool {
+        self.verbose_log
+    }
+
+    /// SteamCMD output of the most recently started download, empty if nothing was downloaded yet
+    pub fn last_download_log(&self) -> Vec<SteamCmdLogLine> {
+        self.last_download_log.as_ref().map(|log| log.lines()).unwrap_or_default()
+    }
+
+    /// Forward a Steam Guard code to SteamCMD instances waiting for one
+    pub fn submit_steam_guard_code(&self, code: &str) -> Result<(), String> {
+        self.steam_guard_codes.send(code.trim().to_string())
+            .map(|_| ())
+            .map_err(|_| "No SteamCMD instance is waiting for a Steam Guard code".to_string())
+    }
+
+    /// Set a fixed per-mod download timeout (None scales the timeout with the mod's size)
+    pub fn set_download_timeout(&mut self, timeout: Option<Duration>) {
+        self.download_timeouts.fixed = timeout.filter(|t| !t.is_zero());
+    }
+
+    /// Set how often the download folder is polled when no file system event arrives
+    /// Longer intervals reduce CPU usage on low-end machines
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.download_timeouts.poll_interval = interval.max(Duration::from_millis(100));
+    }
+
+    /// Get the download timeout settings
+    pub fn download_timeouts(&self) -> DownloadTimeouts {
+        self.download_timeouts
+    }
+
+    /// Set how often failed downloads are retried
+    pub fn set_retry_policy(&mut self, retry_policy: DownloadRetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Get the retry settings
+    pub fn retry_policy(&self) -> DownloadRetryPolicy {
+        self.retry_policy
+    }
+
+    /// Set the memory budget of parallel downloads in bytes (None or 0 only respects available system memory)
+    pub fn set_max_memory_usage(&mut self, bytes: Option<u64>) {
+        self.max_memory_usage = bytes.filter(|b| *b > 0);
+    }
+
+    /// Get the configured memory budget of parallel downloads in bytes
+    pub fn max_memory_usage(&self) -> Option<u64> {
+        self.max_memory_usage
+    }
+
+    /// Number of SteamCMD instances that fit into a memory budget (always at least one)
+    fn instances_for_memory(requested: usize, budget: u64) -> usize {
+        let fitting = usize::try_from(budget / ESTIMATED_INSTANCE_MEMORY).unwrap_or(usize::MAX);
+        requested.min(fitting.max(1))
+    }
+
+    /// Limit the number of SteamCMD instances by available system memory and the configured budget
+    fn memory_limited_instances(requested: usize, max_memory_usage: Option<u64>) -> usize {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        
+        // Available memory is reported as 0 on platforms sysinfo doesn't support
+        let budget = match (system.available_memory(), max_memory_usage) {
+            (0, None) => return requested,
+            (0, Some(cap)) => cap,
+            (available, cap) => cap.map_or(available, |cap| cap.min(available)),
+        };
+        Self::instances_for_memory(requested, budget)
+    }
+
     /// Balance mods across instances by size (load balancing)
     /// Uses a greedy algorithm: assign each mod to the instance with the least current load
     fn balance_mods_by_size(
@@ -253,7 +1299,7 @@ impl Downloader {
     /// For small batches (<=4 mods), uses single instance. For larger batches, uses up to max_instances parallel instances.
     /// If mod_sizes is provided, mods are balanced by size across instances.
     /// Returns a receiver channel that yields mods as they are downloaded
-    pub async fn download_mods(&mut self, mod_ids: &[String], app: Option<&AppHandle>, max_instances: Option<usize>) -> Result<mpsc::Receiver<Result<DownloadedMod, String>>, String> {
+    pub async fn download_mods(&mut self, mod_ids: &[String], app: Option<&AppHandle>, max_instances: Option<usize>) -> Result<mpsc::Receiver<Result<DownloadedMod, DownloadError>>, String> {
         self.download_mods_with_sizes(mod_ids, None, app, max_instances).await
     }
 
@@ -266,10 +1312,106 @@ impl Downloader {
         mod_sizes: Option<&std::collections::HashMap<String, u64>>,
         app: Option<&AppHandle>,
         max_instances: Option<usize>,
-    ) -> Result<mpsc::Receiver<Result<DownloadedMod, String>>, String> {
-        const MAX_RETRIES: u32 = 6;
-        const DEFAULT_MAX_INSTANCES: usize = 1;
-        let max_instances = max_instances.unwrap_or(DEFAULT_MAX_INSTANCES);
+    ) -> Result<mpsc::Receiver<Result<DownloadedMod, DownloadError>>, String> {
+        self.start_downloads(mod_ids, mod_sizes, app, max_instances, false, DownloadPriority::Normal, self.app_id).await
+    }
+
+    /// Download the Workshop items of another game than the downloader's, leaving its app id untouched
+    /// Items end up in that game's Workshop content folder, see `download_path_for_app`
+    pub async fn download_mods_for_app(
+        &mut self,
+        mod_ids: &[String],
+        mod_sizes: Option<&std::collections::HashMap<String, u64>>,
+        app_id: u32,
+        app: Option<&AppHandle>,
+        max_instances: Option<usize>,
+    ) -> Result<mpsc::Receiver<Result<DownloadedMod, DownloadError>>, String> {
+        if app_id == 0 {
+            return Err("Invalid app id: 0".to_string());
+        }
+        self.start_downloads(mod_ids, mod_sizes, app, max_instances, false, DownloadPriority::Normal, app_id).await
+    }
+
+    /// Download mods, taking the next free SteamCMD instance before any waiting download of lower priority
+    pub async fn download_mods_with_priority(
+        &mut self,
+        mod_ids: &[String],
+        mod_sizes: Option<&std::collections::HashMap<String, u64>>,
+        app: Option<&AppHandle>,
+        max_instances: Option<usize>,
+        priority: DownloadPriority,
+    ) -> Result<mpsc::Receiver<Result<DownloadedMod, DownloadError>>, String> {
+        self.start_downloads(mod_ids, mod_sizes, app, max_instances, false, priority, self.app_id).await
+    }
+
+    /// Have SteamCMD re-verify mods already in the Workshop content folder, repairing missing or damaged files
+    /// Uses `workshop_download_item ... validate`; mods arrive on the returned channel like regular downloads
+    pub async fn revalidate_mods(
+        &mut self,
+        mod_ids: &[String],
+        app: Option<&AppHandle>,
+        max_instances: Option<usize>,
+    ) -> Result<mpsc::Receiver<Result<DownloadedMod, DownloadError>>, String> {
+        self.start_downloads(mod_ids, None, app, max_instances, true, DownloadPriority::Normal, self.app_id).await
+    }
+
+    /// Start downloading mods of `app_id` in the background, with SteamCMD validation of existing files if `validate` is set
+    #[allow(clippy::too_many_arguments)]
+    async fn start_downloads(
+        &mut self,
+        mod_ids: &[String],
+        mod_sizes: Option<&std::collections::HashMap<String, u64>>,
+        app: Option<&AppHandle>,
+        max_instances: Option<usize>,
+        validate: bool,
+        priority: DownloadPriority,
+        app_id: u32,
+    ) -> Result<mpsc::Receiver<Result<DownloadedMod, DownloadError>>, String> {
+        let requested_instances = Self::resolve_max_instances(max_instances)?;
+        let max_instances = Self::memory_limited_instances(requested_instances, self.max_memory_usage);
+        if max_instances < requested_instances {
+            eprintln!("[Downloader] Limiting SteamCMD instances from {} to {} to stay within memory limits",
+                requested_instances, max_instances);
+            if let Some(app_handle) = app {
+                let _ = app_handle.emit("download-notice", serde_json::json!({
+                    "kind": "memoryLimited",
+                    "requestedInstances": requested_instances,
+                    "instances": max_instances,
+                }));
+            }
+        }
+        let unthrottled_instances = max_instances;
+        let max_instances = self.throttle_limited_instances(max_instances);
+        if max_instances < unthrottled_instances {
+            eprintln!("[Downloader] Limiting SteamCMD instances from {} to {} because downloads are throttled to {} kbps",
+                unthrottled_instances, max_instances, self.download_throttle_kbps.unwrap_or_default());
+            if let Some(app_handle) = app {
+                let _ = app_handle.emit("download-notice", serde_json::json!({
+                    "kind": "throttled",
+                    "requestedInstances": unthrottled_instances,
+                    "instances": max_instances,
+                    "throttleKbps": self.download_throttle_kbps,
+                }));
+            }
+        }
+        // All downloads share the instance pool, its size comes from the persisted setting rather than the latest request,
+        // so a single-mod download can't shrink the pool under a running bulk update
+        // A download asking for fewer instances is held to that number by its own limit instead
+        let pool_instances = match app {
+            Some(app_handle) => {
+                let configured = Self::resolve_max_instances(crate::services::load_max_steamcmd_instances(app_handle))?;
+                self.throttle_limited_instances(Self::memory_limited_instances(configured, self.max_memory_usage))
+            }
+            None => max_instances,
+        };
+        self.download_queue.set_capacity(pool_instances);
+        let download_queue = self.download_queue.clone();
+        // The output of this download replaces the log of the previous one, the password never reaches it
+        let steamcmd_log = Arc::new(SteamCmdLog::new(
+            self.credentials.iter().map(|credentials| credentials.password.clone()).collect(),
+            app.filter(|_| self.verbose_log).cloned(),
+        ));
+        self.last_download_log = Some(steamcmd_log.clone());
         let (tx, rx) = mpsc::channel(100); // Buffer up to 100 mods
         
         // Clone Arc for tracking process PIDs in spawned tasks
@@ -280,19 +1422,58 @@ impl Downloader {
         let mod_sizes_clone = mod_sizes.cloned();
         let app_clone = app.cloned();
         let steamcmd_path = self.steamcmd_path.clone();
-        let download_path = self.download_path.clone();
+        let install_dir = self.effective_install_dir().to_path_buf();
+        let custom_executable = self.custom_executable.clone();
+        let download_path = self.download_path_for_app(app_id);
         let tx_clone = tx.clone();
         let max_instances_clone = max_instances;
+        let download_throttle_kbps = self.download_throttle_kbps;
         let process_pids_tracker_clone = process_pids_tracker.clone();
+        let instance_statuses = self.instance_statuses.clone();
+        let download_id = NEXT_DOWNLOAD_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let cancelled_mods = self.cancelled_mods.clone();
+        let interrupted_mods: InterruptedModsTracker = Arc::new(Mutex::new(std::collections::HashSet::new()));
+        let download_errors: DownloadErrorTracker = Arc::new(Mutex::new(HashMap::new()));
+        let download_timeouts = self.download_timeouts;
+        let retry_policy = self.retry_policy;
+        let steam_login = SteamLogin {
+            credentials: self.credentials.clone(),
+            steam_guard_codes: self.steam_guard_codes.clone(),
+            login_failure: Arc::new(Mutex::new(None)),
+        };
+        
+        // A new download request overrides an earlier cancellation of the same mods
+        {
+            let mut cancelled = self.cancelled_mods.lock().unwrap();
+            for mod_id in mod_ids {
+                cancelled.remove(mod_id);
+            }
+        }
+        
+        // No SteamCMD instance of ours is running, crash markers and lock files are stale unless another program runs SteamCMD
+        if process_pids_tracker.lock().await.is_empty() {
+            Self::remove_stale_steamcmd_files(&self.steamcmd_path, false);
+        }
         
         // Spawn background task to handle downloads
         // This allows the function to return the channel immediately
         tokio::spawn(async move {
+            // Reports overall progress until the task ends, whichever way it returns
+            let _summary = app_clone.as_ref()
+                .map(|app_handle| DownloadSummaryReporter::start(app_handle, &mod_ids_clone, mod_sizes_clone.as_ref()));
             let mut remaining_mod_ids = mod_ids_clone;
             let mut remaining_mod_sizes = mod_sizes_clone;
             let mut retry_count = 0;
+            let max_retries = retry_policy.max_retries;
+            let retries_per_mod = retry_policy.retries_per_mod();
+            // Failed attempts of each mod, checked against the per-mod retry budget
+            let mut failed_attempts: HashMap<String, u32> = HashMap::new();
+            // Delay announced in the retry-queued events, so the next round waits exactly that long
+            let mut next_retry_delay: Option<Duration> = None;
+            // Login failure of the last attempt, the next round backs off longer for it
+            let mut last_login_failure: Option<LoginFailure> = None;
             
-            while !remaining_mod_ids.is_empty() && retry_count <= MAX_RETRIES {
+            while !remaining_mod_ids.is_empty() && retry_count <= max_retries {
                 // Check if update was cancelled
                 if crate::services::is_update_cancelled() {
                     eprintln!("[Downloader] Update cancelled, stopping download retry loop");
@@ -301,31 +1482,51 @@ impl Downloader {
                     return;
                 }
                 
+                // Mods cancelled individually are not downloaded again
+                for mod_id in Self::take_cancelled_mods(&cancelled_mods, &mut remaining_mod_ids) {
+                    let _ = tx_clone.send(Err(DownloadError::Cancelled { mod_id })).await;
+                }
+                if remaining_mod_ids.is_empty() {
+                    eprintln!("[Downloader] All remaining mods were cancelled, stopping download retry loop");
+                    break;
+                }
+                
                 if retry_count > 0 {
                     eprintln!("[Downloader] Retry attempt {}: {} mod(s) remaining (attempt {}/{})", 
-                        retry_count, remaining_mod_ids.len(), retry_count, MAX_RETRIES);
+                        retry_count, remaining_mod_ids.len(), retry_count, max_retries);
                     
-                    // Emit retry-queued events for remaining mods
-                    if let Some(app_handle) = &app_clone {
-                        for mod_id in &remaining_mod_ids {
-                            let _ = app_handle.emit("mod-state", serde_json::json!({
-                                "modId": mod_id,
-                                "state": "retry-queued",
-                                "retryAttempt": retry_count,
-                                "maxRetries": MAX_RETRIES
-                            }));
+                    // Rounds queued after a successful attempt already announced their delay,
+                    // otherwise emit retry-queued events for remaining mods now
+                    let delay = match next_retry_delay.take() {
+                        Some(delay) => delay,
+                        None => {
+                            let delay = DownloadRetryPolicy::retry_delay(retry_count, last_login_failure.is_some());
+                            if let Some(app_handle) = &app_clone {
+                                if let Some(failure) = &last_login_failure {
+                                    Self::emit_login_failure(app_handle, failure, &remaining_mod_ids, delay);
+                                }
+                                for mod_id in &remaining_mod_ids {
+                                    emit_mod_lifecycle(app_handle, mod_id, ModPhase::RetryQueued, serde_json::json!({
+                                        "retryAttempt": retry_count,
+                                        "maxRetries": retries_per_mod,
+                                        "delayMs": delay.as_millis() as u64,
+                                    }));
+                                }
+                            }
+                            delay
                         }
-                    }
+                    };
                     
-                    // Exponential backoff: 1s, 2s, 4s, 8s, 16s, 32s
-                    let backoff_seconds = 2_u64.pow(retry_count - 1);
-                    eprintln!("[Downloader] Waiting {} seconds before retry...", backoff_seconds);
-                    sleep(Duration::from_secs(backoff_seconds)).await;
+                    eprintln!("[Downloader] Waiting {} ms before retry...", delay.as_millis());
+                    sleep(delay).await;
                 }
             
             // Track which mods will be retried (before attempt) to avoid showing "failed" state
-            let mods_to_retry: std::collections::HashSet<String> = if retry_count < MAX_RETRIES {
-                remaining_mod_ids.iter().cloned().collect()
+            let mods_to_retry: std::collections::HashSet<String> = if retry_count < max_retries {
+                remaining_mod_ids.iter()
+                    .filter(|id| failed_attempts.get(*id).copied().unwrap_or(0) < retries_per_mod)
+                    .cloned()
+                    .collect()
             } else {
                 std::collections::HashSet::new()
             };
@@ -339,6 +1540,7 @@ impl Downloader {
             
             let attempt_result = Self::download_mods_single_attempt_static(
                 &steamcmd_path,
+                &install_dir,
                 &download_path,
                 &remaining_mod_ids,
                 remaining_mod_sizes.as_ref(),
@@ -347,8 +1549,40 @@ impl Downloader {
                 Some(tx_clone.clone()),
                 max_instances_clone,
                 process_pids_tracker_clone.clone(),
+                download_throttle_kbps,
+                instance_statuses.clone(),
+                download_id,
+                custom_executable.as_ref(),
+                cancelled_mods.clone(),
+                interrupted_mods.clone(),
+                download_errors.clone(),
+                steam_login.clone(),
+                download_timeouts,
+                app_id,
+                validate,
+                download_queue.clone(),
+                priority,
+                steamcmd_log.clone(),
             ).await;
             
+            // Mods cancelled during the attempt are done, the rest of their batches is retried at no cost
+            for mod_id in Self::take_cancelled_mods(&cancelled_mods, &mut remaining_mod_ids) {
+                let _ = tx_clone.send(Err(DownloadError::Cancelled { mod_id })).await;
+            }
+            let interrupted: std::collections::HashSet<String> = std::mem::take(&mut *interrupted_mods.lock().unwrap());
+            
+            // A failed login fails the whole batch, the mods themselves are not to blame
+            last_login_failure = steam_login.login_failure.lock().unwrap().take();
+            if let Some(failure) = &last_login_failure {
+                let mut errors = download_errors.lock().unwrap();
+                for mod_id in &remaining_mod_ids {
+                    errors.insert(mod_id.clone(), DownloadError::LoginFailed {
+                        mod_id: mod_id.clone(),
+                        reason: failure.reason.clone(),
+                    });
+                }
+            }
+            
             match attempt_result {
                 Ok((downloaded_mods, _failed_mod_ids)) => {
                     // Mods were already sent to channel in wait_for_mod_download_static
@@ -379,63 +1613,85 @@ impl Downloader {
                         break;
                     }
                     
+                    // Mods that used up their own retry budget fail now instead of holding up the batch
+                    // Attempts that failed to log in or were killed for a cancelled mod don't count against a mod's budget
+                    let mut exhausted_mod_ids = Vec::new();
+                    for mod_id in &remaining_mod_ids {
+                        let attempts = failed_attempts.entry(mod_id.clone()).or_insert(0);
+                        if last_login_failure.is_none() && !interrupted.contains(mod_id) {
+                            *attempts += 1;
+                        }
+                        if retry_count >= max_retries || *attempts > retries_per_mod {
+                            exhausted_mod_ids.push(mod_id.clone());
+                        }
+                    }
+                    remaining_mod_ids.retain(|id| !exhausted_mod_ids.contains(id));
+                    
                     // Emit retry-queued IMMEDIATELY for mods that will be retried
                     // This must happen BEFORE any error handling to avoid showing "failed" state
-                    if retry_count < MAX_RETRIES {
+                    if !remaining_mod_ids.is_empty() {
+                        let delay = DownloadRetryPolicy::retry_delay(retry_count + 1, last_login_failure.is_some());
                         if let Some(app_handle) = &app_clone {
+                            if let Some(failure) = &last_login_failure {
+                                Self::emit_login_failure(app_handle, failure, &remaining_mod_ids, delay);
+                            }
                             for mod_id in &remaining_mod_ids {
-                                let _ = app_handle.emit("mod-state", serde_json::json!({
-                                    "modId": mod_id,
-                                    "state": "retry-queued",
+                                emit_mod_lifecycle(app_handle, mod_id, ModPhase::RetryQueued, serde_json::json!({
                                     "retryAttempt": retry_count + 1,
-                                    "maxRetries": MAX_RETRIES
+                                    "maxRetries": retries_per_mod,
+                                    "delayMs": delay.as_millis() as u64,
                                 }));
                             }
                         }
-                    } else {
-                        // All retries exhausted - emit failed state for remaining mods
+                        next_retry_delay = Some(delay);
+                    }
+                    
+                    // Retries exhausted - emit failed state and send the last failure of these mods
+                    for mod_id in &exhausted_mod_ids {
+                        let attempts = failed_attempts.get(mod_id).copied().unwrap_or(1);
+                        let error = Self::last_download_error(&download_errors, mod_id);
                         if let Some(app_handle) = &app_clone {
-                            for mod_id in &remaining_mod_ids {
-                                let _ = app_handle.emit("mod-state", serde_json::json!({
-                                    "modId": mod_id,
-                                    "state": "failed",
-                                    "error": format!("Download failed after {} attempts", MAX_RETRIES)
-                                }));
-                            }
-                        }
-                        // Send errors to channel for final failures
-                        for _failed_mod_id in &remaining_mod_ids {
-                            let _ = tx_clone.send(Err(format!("Download failed after {} attempts", MAX_RETRIES))).await;
+                            emit_mod_lifecycle(app_handle, mod_id, ModPhase::Failed, serde_json::json!({
+                                "error": format!("Download failed after {} attempts: {}", attempts, error),
+                                "downloadError": error,
+                            }));
                         }
+                        let _ = tx_clone.send(Err(error)).await;
                     }
                     
                     retry_count += 1;
                 }
                 Err(e) => {
                     // Check if error is due to cancellation - if so, don't retry
-                    if e.contains("cancelled") || e.contains("Update cancelled by user") {
+                    if e == CANCELLED_ERROR {
                         eprintln!("[Downloader] Download cancelled by user, stopping retry loop");
-                        // Close channel to signal cancellation
+                        // Report the cancellation, then close the channel
+                        for mod_id in &remaining_mod_ids {
+                            let _ = tx_clone.send(Err(DownloadError::Cancelled { mod_id: mod_id.clone() })).await;
+                        }
                         drop(tx_clone);
                         return;
                     }
                     
                     eprintln!("[Downloader] Download attempt {} failed: {}", retry_count + 1, e);
-                    retry_count += 1;
+                    // An attempt that only failed because of a cancelled mod isn't counted
+                    if remaining_mod_ids.iter().any(|id| !interrupted.contains(id)) {
+                        retry_count += 1;
+                    }
                     
                     // If we've exceeded max retries, send remaining mods as errors and close channel
-                    if retry_count > MAX_RETRIES {
+                    if retry_count > max_retries {
                         // Send remaining mods as errors
-                        for _mod_id in &remaining_mod_ids {
-                            let _ = tx_clone.send(Err(format!("Download failed after {} attempts", MAX_RETRIES))).await;
+                        for mod_id in &remaining_mod_ids {
+                            let _ = tx_clone.send(Err(Self::last_download_error(&download_errors, mod_id))).await;
                         }
                         
                         if remaining_mod_ids.is_empty() {
                             eprintln!("[Downloader] All mod downloads failed after {} attempts. Last error: {}", 
-                                MAX_RETRIES, e);
+                                max_retries, e);
                         } else {
                             eprintln!("[Downloader] Some mod downloads failed after {} attempts. Failed mods: {}. Last error: {}", 
-                                MAX_RETRIES, remaining_mod_ids.join(", "), e);
+                                max_retries, remaining_mod_ids.join(", "), e);
                         }
                         // Close channel and exit task
                         drop(tx_clone);
@@ -449,7 +1705,7 @@ impl Downloader {
             // in the match block above, so we just log here
             if !remaining_mod_ids.is_empty() {
                 eprintln!("[Downloader] Max retries ({}) exceeded for {} mod(s): {}", 
-                    MAX_RETRIES, remaining_mod_ids.len(), remaining_mod_ids.join(", "));
+                    max_retries, remaining_mod_ids.len(), remaining_mod_ids.join(", "));
             }
             
             // Close channel to signal completion
@@ -460,18 +1716,53 @@ impl Downloader {
         Ok(rx)
     }
 
+    /// Tell the UI the batch waits for Steam to accept logins again, instead of failing each mod
+    fn emit_login_failure(app: &AppHandle, failure: &LoginFailure, mod_ids: &[String], delay: Duration) {
+        eprintln!("[Downloader] SteamCMD login failed ({}), retrying {} mod(s) in {} ms",
+            failure.reason, mod_ids.len(), delay.as_millis());
+        let _ = app.emit("steam-rate-limited", serde_json::json!({
+            "reason": failure.reason,
+            "rateLimited": failure.rate_limited,
+            "modIds": mod_ids,
+            "delayMs": delay.as_millis() as u64,
+        }));
+    }
+
+    /// Latest recorded failure of a mod, or NotDetected if none of its attempts got far enough to fail
+    fn last_download_error(download_errors: &DownloadErrorTracker, mod_id: &str) -> DownloadError {
+        download_errors.lock().unwrap()
+            .get(mod_id)
+            .cloned()
+            .unwrap_or_else(|| DownloadError::NotDetected { mod_id: mod_id.to_string() })
+    }
+
     /// Single download attempt without retry logic (static version for use in spawned tasks)
     /// Returns tuple of (downloaded_mods, failed_mod_ids)
     async fn download_mods_single_attempt_static(
         steamcmd_path: &PathBuf,
+        install_dir: &Path,
         download_path: &PathBuf,
         mod_ids: &[String],
         mod_sizes: Option<&std::collections::HashMap<String, u64>>,
         app: Option<&AppHandle>,
         mods_to_retry: Option<&std::collections::HashSet<String>>,
-        _tx: Option<mpsc::Sender<Result<DownloadedMod, String>>>,
+        _tx: Option<mpsc::Sender<Result<DownloadedMod, DownloadError>>>,
         max_instances: usize,
         process_pids_tracker: Arc<tokio::sync::Mutex<Vec<u32>>>,
+        download_throttle_kbps: Option<u32>,
+        instance_statuses: InstanceStatusTracker,
+        download_id: u64,
+        custom_executable: Option<&PathBuf>,
+        cancelled_mods: CancelledModsTracker,
+        interrupted_mods: InterruptedModsTracker,
+        download_errors: DownloadErrorTracker,
+        steam_login: SteamLogin,
+        download_timeouts: DownloadTimeouts,
+        app_id: u32,
+        validate: bool,
+        download_queue: Arc<DownloadQueue>,
+        priority: DownloadPriority,
+        steamcmd_log: Arc<SteamCmdLog>,
     ) -> Result<(Vec<DownloadedMod>, Vec<String>), String> {
         // Convert mods_to_retry to owned Option for passing to download_mods_batch
         let mods_to_retry_owned = mods_to_retry.map(|set| set.clone());
@@ -479,12 +1770,8 @@ impl Downloader {
             return Ok((vec![], vec![]));
         }
 
-        // Delete appworkshop file if it exists
-        let appworkshop_path = steamcmd_path
-            .join("steamapps")
-            .join("workshop")
-            .join("appworkshop_294100.acf");
-        let _ = fs::remove_file(&appworkshop_path);
+        // Resume state is only kept between the retries of this session
+        Self::prepare_workshop_manifest(install_dir, download_path, mod_ids, &download_errors, app_id, mods_to_retry.is_none());
 
         // Ensure download directory exists
         fs::create_dir_all(download_path)
@@ -492,7 +1779,9 @@ impl Downloader {
 
         // Calculate number of instances: use as many as possible (up to max_instances)
         // For 3+ mods, always use parallel instances to maximize throughput
-        let num_instances = std::cmp::min(mod_ids.len(), max_instances);
+        // Large downloads get more batches than instances, each batch waits for a slot in the shared pool
+        let num_instances = std::cmp::min(mod_ids.len(), max_instances)
+            .max(mod_ids.len().div_ceil(MAX_MODS_PER_INSTANCE));
         
         // Balance mods across instances by size if sizes are available
         let batches = if let Some(sizes) = mod_sizes {
@@ -511,8 +1800,13 @@ impl Downloader {
             }
         }
         
+        // Instances of a previous attempt are no longer relevant, the ones of other downloads still are
+        instance_statuses.lock().unwrap().retain(|_, status| status.download_id != download_id);
+        // Batches beyond the instances this download asked for wait for one of its own batches to finish
+        let instance_limit = Arc::new(tokio::sync::Semaphore::new(max_instances.max(1)));
+        
         let mut batch_futures = Vec::new();
-        let steamcmd_executable = Self::find_steamcmd_executable_static(steamcmd_path).await?;
+        let steamcmd_executable = Self::find_steamcmd_executable_static(steamcmd_path, custom_executable).await?;
         
         for (batch_idx, batch) in batches.into_iter().enumerate() {
             if batch.is_empty() {
@@ -520,22 +1814,85 @@ impl Downloader {
             }
             
             let steamcmd_path_clone = steamcmd_path.clone();
+            let install_dir_clone = install_dir.to_path_buf();
             let download_path_clone = download_path.clone();
             
             let mods_to_retry_for_batch = mods_to_retry_owned.clone();
             let tx_for_batch = _tx.clone();
             let process_pids_tracker_for_batch = process_pids_tracker.clone();
-            let future = Self::download_mods_batch(
-                steamcmd_executable.clone(),
-                steamcmd_path_clone,
-                download_path_clone,
-                batch,
-                batch_idx,
-                app.cloned(),
-                mods_to_retry_for_batch,
-                tx_for_batch,
-                process_pids_tracker_for_batch,
-            );
+            let app_for_batch = app.cloned();
+            let instance_statuses_for_batch = instance_statuses.clone();
+            let cancelled_mods_for_batch = cancelled_mods.clone();
+            let interrupted_mods_for_batch = interrupted_mods.clone();
+            let download_errors_for_batch = download_errors.clone();
+            let steam_login_for_batch = steam_login.clone();
+            let expected_sizes: HashMap<String, u64> = mod_sizes
+                .map(|sizes| batch.iter()
+                    .filter_map(|id| sizes.get(id).map(|size| (id.clone(), *size)))
+                    .collect())
+                .unwrap_or_default();
+            
+            let steamcmd_executable_for_batch = steamcmd_executable.clone();
+            let download_queue_for_batch = download_queue.clone();
+            let steamcmd_log_for_batch = steamcmd_log.clone();
+            let instance_limit_for_batch = instance_limit.clone();
+            let future = async move {
+                let _instance_permit = instance_limit_for_batch.acquire_owned().await
+                    .map_err(|e| format!("Instance limit closed: {}", e))?;
+                // Held until SteamCMD exits, then handed to the next waiting batch of any download
+                let slot = download_queue_for_batch.acquire(priority).await;
+                // Statuses are keyed by slot, a slot runs one instance at a time across all downloads
+                let instance = slot.index();
+                instance_statuses_for_batch.lock().unwrap().insert(instance, InstanceStatus {
+                    instance_index: instance,
+                    current_mod: None,
+                    mods_remaining: batch.len(),
+                    status: InstanceState::Starting,
+                    download_id,
+                });
+                Self::emit_instance_status(app_for_batch.as_ref(), &instance_statuses_for_batch, instance);
+                
+                let result = Self::download_mods_batch(
+                    steamcmd_executable_for_batch,
+                    steamcmd_path_clone,
+                    install_dir_clone,
+                    download_path_clone,
+                    batch,
+                    batch_idx,
+                    app_for_batch.clone(),
+                    mods_to_retry_for_batch,
+                    tx_for_batch,
+                    process_pids_tracker_for_batch,
+                    download_throttle_kbps,
+                    instance_statuses_for_batch.clone(),
+                    cancelled_mods_for_batch,
+                    interrupted_mods_for_batch,
+                    download_errors_for_batch,
+                    steam_login_for_batch,
+                    expected_sizes,
+                    download_timeouts,
+                    app_id,
+                    validate,
+                    slot,
+                    steamcmd_log_for_batch,
+                ).await;
+                
+                // Record the final state of this instance
+                Self::update_instance_status(&instance_statuses_for_batch, instance, |status| {
+                    status.current_mod = None;
+                    status.status = match &result {
+                        Ok((_, failed)) => {
+                            status.mods_remaining = failed.len();
+                            InstanceState::Completed
+                        }
+                        Err(e) if e == CANCELLED_ERROR => InstanceState::Cancelled,
+                        Err(_) => InstanceState::Failed,
+                    };
+                });
+                Self::emit_instance_status(app_for_batch.as_ref(), &instance_statuses_for_batch, instance);
+                
+                result
+            };
             batch_futures.push(future);
         }
         
@@ -601,13 +1958,26 @@ impl Downloader {
     async fn download_mods_batch(
         steamcmd_executable: PathBuf,
         steamcmd_path: PathBuf,
+        install_dir: PathBuf,
         download_path: PathBuf,
         mod_ids: Vec<String>,
         batch_idx: usize,
         app: Option<AppHandle>,
         mods_to_retry: Option<std::collections::HashSet<String>>,
-        tx: Option<mpsc::Sender<Result<DownloadedMod, String>>>,
+        tx: Option<mpsc::Sender<Result<DownloadedMod, DownloadError>>>,
         process_pids_tracker: Arc<tokio::sync::Mutex<Vec<u32>>>,
+        download_throttle_kbps: Option<u32>,
+        instance_statuses: InstanceStatusTracker,
+        cancelled_mods: CancelledModsTracker,
+        interrupted_mods: InterruptedModsTracker,
+        download_errors: DownloadErrorTracker,
+        steam_login: SteamLogin,
+        expected_sizes: HashMap<String, u64>,
+        download_timeouts: DownloadTimeouts,
+        app_id: u32,
+        validate: bool,
+        slot: InstanceSlot,
+        steamcmd_log: Arc<SteamCmdLog>,
     ) -> Result<(Vec<DownloadedMod>, Vec<String>), String> {
         eprintln!("[Downloader] Instance {}: starting download", batch_idx);
 
@@ -620,6 +1990,14 @@ impl Downloader {
             current_dir.join(&steamcmd_path)
         };
         
+        let install_dir_absolute = if install_dir.is_absolute() {
+            install_dir.clone()
+        } else {
+            let current_dir = std::env::current_dir()
+                .map_err(|e| format!("Failed to get current directory: {}", e))?;
+            current_dir.join(&install_dir)
+        };
+        
         let download_path_absolute = if download_path.is_absolute() {
             download_path.clone()
         } else {
@@ -628,32 +2006,49 @@ impl Downloader {
             current_dir.join(&download_path)
         };
 
-        // Create unique script file for this batch
-        let script_path = steamcmd_path.join(format!("run_batch_{}.txt", batch_idx));
+        // Create unique script file for this batch, batches of concurrent downloads never share a pool slot
+        let instance = slot.index();
+        let script_path = steamcmd_path.join(format!("run_batch_{}.txt", instance));
+        let login_line = match &steam_login.credentials {
+            Some(credentials) => format!("login {} {}",
+                quote_script_arg(&credentials.username).map_err(|e| format!("Invalid Steam username: {}", e))?,
+                quote_script_arg(&credentials.password).map_err(|e| format!("Invalid Steam password: {}", e))?),
+            None => "login anonymous".to_string(),
+        };
         let mut script_lines = vec![
-            format!("force_install_dir \"{}\"", steamcmd_path_absolute.to_string_lossy()),
-            "login anonymous".to_string(),
+            format!("force_install_dir {}", quote_script_arg(&install_dir_absolute.to_string_lossy())
+                .map_err(|e| format!("Invalid download directory: {}", e))?),
+            login_line,
         ];
         
+        // Limit download bandwidth of this instance if a throttle is configured
+        if let Some(kbps) = download_throttle_kbps {
+            script_lines.insert(1, format!("set_download_throttle {}", kbps));
+        }
+        
         // Emit queued events for all mods as they are added to the script
         if let Some(app_handle) = &app {
             for mod_id in &mod_ids {
-                let _ = app_handle.emit("mod-state", serde_json::json!({
-                    "modId": mod_id,
-                    "state": "queued"
-                }));
+                emit_mod_lifecycle(app_handle, mod_id, ModPhase::Queued, serde_json::Value::Null);
             }
         }
         
         for mod_id in &mod_ids {
-            script_lines.push(format!("workshop_download_item 294100 {}", mod_id));
+            if validate {
+                // SteamCMD re-checks the files already on disk and repairs the ones that don't match
+                script_lines.push(format!("workshop_download_item {} {} validate", app_id, mod_id));
+            } else {
+                script_lines.push(format!("workshop_download_item {} {}", app_id, mod_id));
+            }
         }
         
         script_lines.push("quit".to_string());
         let script_content = script_lines.join("\n") + "\n";
         
-        fs::write(&script_path, script_content)
+        write_script_file(&script_path, &script_content)
             .map_err(|e| format!("Failed to write SteamCMD script: {}", e))?;
+        // Deletes the script on every exit path, including errors
+        let script_guard = ScriptFileGuard(script_path.clone());
 
         let script_path_absolute = if script_path.is_absolute() {
             script_path.clone()
@@ -666,25 +2061,25 @@ impl Downloader {
         // Track failed mods detected from SteamCMD output
         let failed_mods_tracker: Arc<Mutex<std::collections::HashSet<String>>> = Arc::new(Mutex::new(std::collections::HashSet::new()));
         
-        // Start watching folders before starting download
-        // Each promise will detect mods when downloaded, but won't send to channel yet
-        // We'll check failed_mods_tracker before sending to channel
-        let mut download_promises = Vec::new();
+        // Timeouts are measured from the start of the batch, a mod's clock doesn't restart once SteamCMD exits
+        let batch_started = std::time::Instant::now();
+        let mut mod_timeouts = HashMap::new();
+        let mut queued_bytes = 0;
         for mod_id in &mod_ids {
-            let mod_download_path = download_path_absolute.join(mod_id.clone());
-            let mod_id_clone = mod_id.clone();
-            let app_clone = app.clone();
-            let tx_clone = tx.clone();
-            let failed_mods_tracker_clone = failed_mods_tracker.clone();
-            download_promises.push(Self::wait_for_mod_download_static(
-                mod_download_path, 
-                mod_id_clone, 
-                app_clone,
-                tx_clone,
-                Some(failed_mods_tracker_clone), // Pass failed_mods_tracker
-            ));
+            let size = expected_sizes.get(mod_id).copied().filter(|size| *size > 0);
+            queued_bytes += size.unwrap_or(0);
+            mod_timeouts.insert(mod_id.clone(), download_timeouts.for_mod(queued_bytes, size.is_some()));
         }
 
+        // Keeps other instances of the app from removing this instance's lock and crash files meanwhile
+        let _steamcmd_lock = match lock_steamcmd_shared(&steamcmd_path_absolute) {
+            Ok(lock) => Some(lock),
+            Err(e) => {
+                eprintln!("[Downloader] Instance {}: {}", batch_idx, e);
+                None
+            }
+        };
+
         // Start SteamCMD process
         let mut cmd = Command::new(&steamcmd_executable);
         cmd.arg("+runscript")
@@ -693,6 +2088,11 @@ impl Downloader {
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped());
         
+        // Steam Guard codes are typed into SteamCMD's stdin
+        if steam_login.credentials.is_some() {
+            cmd.stdin(std::process::Stdio::piped());
+        }
+        
         // Hide console window on Windows to prevent terminal window from appearing
         #[cfg(windows)]
         {
@@ -702,9 +2102,15 @@ impl Downloader {
             cmd.creation_flags(0x08000000);
         }
         
-        let mut steamcmd_process = cmd
-            .spawn()
-            .map_err(|e| format!("Failed to spawn SteamCMD: {}", e))?;
+        let mut steamcmd_process = match cmd.spawn() {
+            Ok(process) => process,
+            Err(e) => {
+                return Err(match diagnose_spawn_failure(&steamcmd_executable).await {
+                    Some(diagnosis) => format!("Failed to spawn SteamCMD: {} ({})", diagnosis, e),
+                    None => format!("Failed to spawn SteamCMD: {}", e),
+                });
+            }
+        };
         
         // Save process ID before we move it
         let process_id = steamcmd_process.id();
@@ -728,6 +2134,30 @@ impl Downloader {
         // Parse SteamCMD output to detect mod states
         let stdout = steamcmd_process.stdout.take();
         let stderr = steamcmd_process.stderr.take();
+        let stdin = steamcmd_process.stdin.take();
+        
+        // Set when SteamCMD asks for a Steam Guard code, cleared once one is sent
+        let steam_guard_waiting = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let steam_guard_waiting_stdout = steam_guard_waiting.clone();
+        let login_failure_stdout = steam_login.login_failure.clone();
+        let login_failure_stderr = steam_login.login_failure.clone();
+        let stdin_task_handle = if let Some(mut stdin) = stdin {
+            let mut codes = steam_login.steam_guard_codes.subscribe();
+            tokio::spawn(async move {
+                use tokio::io::AsyncWriteExt;
+                while let Ok(code) = codes.recv().await {
+                    if !steam_guard_waiting.swap(false, std::sync::atomic::Ordering::Relaxed) {
+                        continue;
+                    }
+                    eprintln!("[Downloader] Instance {}: Sending Steam Guard code to SteamCMD", batch_idx);
+                    if stdin.write_all(format!("{}\n", code).as_bytes()).await.is_err() || stdin.flush().await.is_err() {
+                        break;
+                    }
+                }
+            })
+        } else {
+            tokio::spawn(async {})
+        };
         
         // Clone for each task
         let mod_ids_stdout = mod_ids.clone();
@@ -739,21 +2169,72 @@ impl Downloader {
         let cancellation_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
         let cancellation_flag_stdout = cancellation_flag.clone();
         let cancellation_flag_stderr = cancellation_flag.clone();
+        let instance_statuses_stdout = instance_statuses.clone();
+        let instance_statuses_stderr = instance_statuses.clone();
+        let steamcmd_log_stdout = steamcmd_log.clone();
+        let steamcmd_log_stderr = steamcmd_log;
+        
+        // Periodically report what this instance is doing
+        let status_task_handle = {
+            let app_status = app.clone();
+            let instance_statuses_ticker = instance_statuses.clone();
+            tokio::spawn(async move {
+                loop {
+                    Self::emit_instance_status(app_status.as_ref(), &instance_statuses_ticker, instance);
+                    sleep(Duration::from_secs(1)).await;
+                }
+            })
+        };
         
         let stdout_task_handle = if let Some(stdout) = stdout {
             let batch_idx_clone = batch_idx;
             tokio::spawn(async move {
-                use tokio::io::{AsyncBufReadExt, BufReader};
-                let reader = BufReader::new(stdout);
-                let mut lines = reader.lines();
-                while let Ok(Some(line)) = lines.next_line().await {
+                use tokio::io::AsyncReadExt;
+                let mut stdout = stdout;
+                let mut buffer = [0u8; 4096];
+                let mut pending: Vec<u8> = Vec::new();
+                let mut progress_state = ProgressState::default();
+                // Read raw chunks: the Steam Guard prompt is not terminated by a newline
+                while let Ok(read) = stdout.read(&mut buffer).await {
+                    if read == 0 {
+                        break;
+                    }
                     // Stop parsing if cancelled (check both local flag and global flag)
                     if cancellation_flag_stdout.load(std::sync::atomic::Ordering::Relaxed) || crate::services::is_update_cancelled() {
                         eprintln!("[Downloader] Instance {}: Stopping stdout parser due to cancellation", batch_idx_clone);
                         break;
                     }
-                    // Parse SteamCMD output to detect mod states
-                    Self::parse_steamcmd_output(&line, &mod_ids_stdout, app_stdout.as_ref(), Some(&failed_mods_stdout), mods_to_retry_stdout.as_ref());
+                    pending.extend_from_slice(&buffer[..read]);
+                    
+                    let mut lines: Vec<String> = Vec::new();
+                    while let Some(position) = pending.iter().position(|b| *b == b'\n') {
+                        let line: Vec<u8> = pending.drain(..=position).collect();
+                        lines.push(String::from_utf8_lossy(&line).trim_end().to_string());
+                    }
+                    // An unterminated prompt waits for input, handle it as a line of its own
+                    if Self::is_steam_guard_prompt(&String::from_utf8_lossy(&pending)) {
+                        lines.push(String::from_utf8_lossy(&pending).to_string());
+                        pending.clear();
+                    }
+                    
+                    for line in lines {
+                        steamcmd_log_stdout.push(batch_idx_clone, &line);
+                        if Self::is_steam_guard_prompt(&line) {
+                            steam_guard_waiting_stdout.store(true, std::sync::atomic::Ordering::Relaxed);
+                        }
+                        // Parse SteamCMD output to detect mod states
+                        Self::parse_steamcmd_output(&line, &mod_ids_stdout, app_stdout.as_ref(), Some(&failed_mods_stdout), mods_to_retry_stdout.as_ref());
+                        Self::record_login_failure(&line, &login_failure_stdout, batch_idx_clone);
+                        Self::track_instance_progress(&line, &mod_ids_stdout, &instance_statuses_stdout, instance);
+                        
+                        if let (Some((mod_id, progress)), Some(app_handle)) = (progress_state.handle_line(&line, &mod_ids_stdout), app_stdout.as_ref()) {
+                            emit_mod_lifecycle(app_handle, &mod_id, ModPhase::DownloadProgress, serde_json::json!({
+                                "percent": progress.percent,
+                                "bytesDownloaded": progress.bytes_downloaded,
+                                "bytesTotal": progress.bytes_total,
+                            }));
+                        }
+                    }
                 }
             })
         } else {
@@ -772,8 +2253,11 @@ impl Downloader {
                         eprintln!("[Downloader] Instance {}: Stopping stderr parser due to cancellation", batch_idx_clone);
                         break;
                     }
+                    steamcmd_log_stderr.push(batch_idx_clone, &line);
                     // Parse SteamCMD output to detect mod states
                     Self::parse_steamcmd_output(&line, &mod_ids_stderr, app_stderr.as_ref(), Some(&failed_mods_stderr), mods_to_retry_stderr.as_ref());
+                    Self::record_login_failure(&line, &login_failure_stderr, batch_idx_clone);
+                    Self::track_instance_progress(&line, &mod_ids_stderr, &instance_statuses_stderr, instance);
                 }
             })
         } else {
@@ -784,7 +2268,7 @@ impl Downloader {
         sleep(Duration::from_secs(2)).await;
         
         // Check if cancelled before waiting
-        if crate::services::is_update_cancelled() {
+        if crate::services::is_update_cancelled() || Self::is_batch_cancelled(&cancelled_mods, &mod_ids) {
             eprintln!("[Downloader] Instance {}: Update was cancelled before SteamCMD started, killing process aggressively", batch_idx);
             // Try graceful kill first
             let _ = steamcmd_process.kill().await;
@@ -817,6 +2301,8 @@ impl Downloader {
             cancellation_flag.store(true, std::sync::atomic::Ordering::Relaxed);
             stdout_task_handle.abort();
             stderr_task_handle.abort();
+            status_task_handle.abort();
+            stdin_task_handle.abort();
             
             // Remove PID from tracker
             if let Some(pid) = process_id {
@@ -825,7 +2311,9 @@ impl Downloader {
             }
             
             let _ = fs::remove_file(&script_path);
-            return Err("Update cancelled by user".to_string());
+            Self::remove_cancelled_downloads(&install_dir_absolute, &download_path_absolute, &mod_ids, &cancelled_mods, app_id);
+            Self::record_interrupted_mods(&cancelled_mods, &interrupted_mods, &mod_ids);
+            return Err(CANCELLED_ERROR.to_string());
         }
 
         // Wait for SteamCMD to exit and check exit status
@@ -836,6 +2324,8 @@ impl Downloader {
                 cancellation_flag.store(true, std::sync::atomic::Ordering::Relaxed);
                 stdout_task_handle.abort();
                 stderr_task_handle.abort();
+                status_task_handle.abort();
+                stdin_task_handle.abort();
                 
                 // Remove PID from tracker when process completes
                 if let Some(pid) = process_id {
@@ -849,7 +2339,7 @@ impl Downloader {
                 use crate::services::is_update_cancelled;
                 loop {
                     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                    if is_update_cancelled() {
+                    if is_update_cancelled() || Self::is_batch_cancelled(&cancelled_mods, &mod_ids) {
                         eprintln!("[Downloader] Instance {}: Cancellation detected while waiting for SteamCMD", batch_idx);
                         break;
                     }
@@ -862,6 +2352,8 @@ impl Downloader {
                 cancellation_flag.store(true, std::sync::atomic::Ordering::Relaxed);
                 stdout_task_handle.abort();
                 stderr_task_handle.abort();
+                status_task_handle.abort();
+                stdin_task_handle.abort();
                 
                 // Try graceful kill first
                 let _ = steamcmd_process.kill().await;
@@ -903,16 +2395,23 @@ impl Downloader {
                 }
                 
                 let _ = fs::remove_file(&script_path);
-                return Err("Update cancelled by user".to_string());
+                Self::remove_cancelled_downloads(&install_dir_absolute, &download_path_absolute, &mod_ids, &cancelled_mods, app_id);
+                Self::record_interrupted_mods(&cancelled_mods, &interrupted_mods, &mod_ids);
+                return Err(CANCELLED_ERROR.to_string());
             }
         };
         
+        // SteamCMD is done, hand the slot to the next waiting download instead of holding it while the mods are checked
+        // The script goes first, the next batch in this slot writes its own under the same name
+        drop(script_guard);
+        drop(slot);
+        
         // Check if update was cancelled after process exited
-        if crate::services::is_update_cancelled() {
+        if crate::services::is_update_cancelled() || Self::is_batch_cancelled(&cancelled_mods, &mod_ids) {
             eprintln!("[Downloader] Instance {}: Update was cancelled, cleaning up", batch_idx);
-            // Clean up script file
-            let _ = fs::remove_file(&script_path);
-            return Err("Update cancelled by user".to_string());
+            Self::remove_cancelled_downloads(&install_dir_absolute, &download_path_absolute, &mod_ids, &cancelled_mods, app_id);
+            Self::record_interrupted_mods(&cancelled_mods, &interrupted_mods, &mod_ids);
+            return Err(CANCELLED_ERROR.to_string());
         }
         
         // Check if SteamCMD exited successfully
@@ -920,9 +2419,6 @@ impl Downloader {
             let exit_code = status.code().unwrap_or(-1);
             eprintln!("[Downloader] Instance {}: SteamCMD exited with error code: {}", batch_idx, exit_code);
             
-            // Clean up script file
-            let _ = fs::remove_file(&script_path);
-            
             // Check if any mods were partially downloaded
             let mut partial_mods = Vec::new();
             for mod_id in &mod_ids {
@@ -932,6 +2428,14 @@ impl Downloader {
                 }
             }
             
+            // Remembered as the failure reason in case the retries don't download these mods either
+            {
+                let mut errors = download_errors.lock().unwrap();
+                for mod_id in mod_ids.iter().filter(|id| !Self::verify_mod_download_complete(&download_path_absolute.join(id))) {
+                    errors.insert(mod_id.clone(), DownloadError::ProcessExited { mod_id: mod_id.clone(), exit_code });
+                }
+            }
+            
             if !partial_mods.is_empty() {
                 return Err(format!(
                     "SteamCMD failed (exit code: {}) but detected partial downloads for mod(s): {}. These may be incomplete.",
@@ -943,13 +2447,69 @@ impl Downloader {
             }
         }
 
+        Self::update_instance_status(&instance_statuses, instance, |status| {
+            status.current_mod = None;
+            status.status = InstanceState::Verifying;
+        });
+        Self::emit_instance_status(app.as_ref(), &instance_statuses, instance);
+
         // Wait a bit for file system operations
         sleep(Duration::from_secs(1)).await;
 
-        // Wait for all mod downloads to be detected in parallel
+        // Mods much smaller than the size Steam reports are corrupt downloads
+        // Mark them failed before the promises run, so they are retried instead of installed
+        // Their folders are removed once the promises are done, the promises resolve them right away
+        let mut undersized_mods = std::collections::HashSet::new();
+        for (mod_id, expected) in &expected_sizes {
+            let mod_download_path = download_path_absolute.join(mod_id);
+            if !mod_download_path.is_dir() || Self::verify_mod_size(&mod_download_path, *expected, CORRUPT_SIZE_TOLERANCE_PCT) {
+                continue;
+            }
+            let actual = Self::mod_folder_size(&mod_download_path);
+            if actual >= *expected {
+                eprintln!("[Downloader] Instance {}: Mod {} is larger than expected ({} vs {} bytes)", batch_idx, mod_id, actual, expected);
+                continue;
+            }
+            eprintln!("[Downloader] Instance {}: Mod {} is incomplete ({} of {} bytes), treating as corrupt", batch_idx, mod_id, actual, expected);
+            failed_mods_tracker.lock().unwrap().insert(mod_id.clone());
+            undersized_mods.insert(mod_id.clone());
+        }
+
+        // Error pages saved in place of mod files pass the checks above, retry them instead of installing them
+        // Removed after the promises like the undersized mods
+        let mut junk_mods = std::collections::HashSet::new();
+        for mod_id in &mod_ids {
+            let mod_download_path = download_path_absolute.join(mod_id);
+            if undersized_mods.contains(mod_id) || !mod_download_path.is_dir() || !detect_junk_download(&mod_download_path) {
+                continue;
+            }
+            eprintln!("[Downloader] Instance {}: Mod {} downloaded an error page instead of mod content, treating as corrupt", batch_idx, mod_id);
+            failed_mods_tracker.lock().unwrap().insert(mod_id.clone());
+            junk_mods.insert(mod_id.clone());
+        }
+
+        // Detect the downloaded mods in parallel, checking failed_mods_tracker before sending to channel
+        // SteamCMD has exited, so a missing folder only gets a short grace and what's left of the mod's timeout
+        let mut download_promises = Vec::new();
+        for mod_id in &mod_ids {
+            let timeout = mod_timeouts.get(mod_id).copied().unwrap_or(DEFAULT_DOWNLOAD_TIMEOUT);
+            download_promises.push(Self::wait_for_mod_download_static(
+                download_path_absolute.join(mod_id),
+                mod_id.clone(),
+                app.clone(),
+                tx.clone(),
+                Some(failed_mods_tracker.clone()),
+                timeout.saturating_sub(batch_started.elapsed()).min(EXITED_DETECTION_GRACE),
+                download_timeouts.poll_interval,
+            ));
+        }
+        
         // Note: Each promise sends mods to channel immediately when downloaded,
         // so we're just waiting here to collect results for tracking/failure reporting
         let download_results = futures::future::join_all(download_promises).await;
+        for mod_id in undersized_mods.iter().chain(&junk_mods) {
+            let _ = fs::remove_dir_all(download_path_absolute.join(mod_id));
+        }
         let mut downloaded_mods = Vec::new();
         let mut failed_mods = Vec::new();
         
@@ -966,10 +2526,14 @@ impl Downloader {
             if steamcmd_failed_mods.contains(mod_id) {
                 eprintln!("[Downloader] Instance {}: Mod {} failed according to SteamCMD output", batch_idx, mod_id);
                 failed_mods.push(mod_id.clone());
-                // Send error to channel if tx is available
-                if let Some(ref tx_ref) = tx {
-                    let _ = tx_ref.send(Err(format!("Download failed for mod {}", mod_id))).await;
-                }
+                let error = if undersized_mods.contains(mod_id) {
+                    DownloadError::IncompleteFiles { mod_id: mod_id.clone() }
+                } else if junk_mods.contains(mod_id) {
+                    DownloadError::JunkContent { mod_id: mod_id.clone() }
+                } else {
+                    DownloadError::SteamCmdReported { mod_id: mod_id.clone() }
+                };
+                Self::report_download_failure(app.as_ref(), tx.as_ref(), &download_errors, error).await;
                 continue;
             }
             
@@ -977,40 +2541,47 @@ impl Downloader {
                 Ok(Some(mod_info)) => {
                     // Verify download completeness before adding
                     if Self::verify_mod_download_complete(&mod_info.mod_path) {
+                        if let Some(app_handle) = &app {
+                            crate::services::clear_download_failures(app_handle, mod_id);
+                        }
                         downloaded_mods.push(mod_info.clone());
                         // Mod was already sent to channel in wait_for_mod_download_static
                         // We just keep it here for tracking
                     } else {
                         eprintln!("[Downloader] Instance {}: Mod {} detected but download appears incomplete", batch_idx, mod_id);
                         failed_mods.push(mod_id.clone());
-                        // Send error to channel if tx is available
-                        if let Some(ref tx_ref) = tx {
-                            let _ = tx_ref.send(Err(format!("Download incomplete for mod {}", mod_id))).await;
-                        }
+                        let error = DownloadError::IncompleteFiles { mod_id: mod_id.clone() };
+                        Self::report_download_failure(app.as_ref(), tx.as_ref(), &download_errors, error).await;
                     }
                 }
                 Ok(None) => {
                     eprintln!("[Downloader] Instance {}: Mod {} download timeout or not detected", batch_idx, mod_id);
-                    failed_mods.push(mod_id.clone());
-                    // Send error to channel if tx is available
-                    if let Some(ref tx_ref) = tx {
-                        let _ = tx_ref.send(Err(format!("Download timeout for mod {}", mod_id))).await;
+                    let timeout = mod_timeouts.get(mod_id).copied().unwrap_or(DEFAULT_DOWNLOAD_TIMEOUT);
+                    let timed_out = batch_started.elapsed() >= timeout;
+                    if timed_out {
+                        if let Some(app_handle) = &app {
+                            emit_mod_lifecycle(app_handle, mod_id, ModPhase::TimedOut, serde_json::json!({
+                                "timeoutSecs": timeout.as_secs(),
+                            }));
+                        }
                     }
+                    failed_mods.push(mod_id.clone());
+                    let error = if timed_out {
+                        DownloadError::Timeout { mod_id: mod_id.clone() }
+                    } else {
+                        DownloadError::NotDetected { mod_id: mod_id.clone() }
+                    };
+                    Self::report_download_failure(app.as_ref(), tx.as_ref(), &download_errors, error).await;
                 }
                 Err(e) => {
                     eprintln!("[Downloader] Instance {}: Mod {} download error: {}", batch_idx, mod_id, e);
                     failed_mods.push(mod_id.clone());
-                    // Send error to channel if tx is available
-                    if let Some(ref tx_ref) = tx {
-                        let _ = tx_ref.send(Err(format!("Download error for mod {}: {}", mod_id, e))).await;
-                    }
+                    let error = DownloadError::NotDetected { mod_id: mod_id.clone() };
+                    Self::report_download_failure(app.as_ref(), tx.as_ref(), &download_errors, error).await;
                 }
             }
         }
 
-        // Clean up script file
-        let _ = fs::remove_file(&script_path);
-
         // If some mods failed, return error with details
         if !failed_mods.is_empty() {
             if downloaded_mods.is_empty() {
@@ -1026,18 +2597,59 @@ impl Downloader {
         Ok((downloaded_mods, failed_mods))
     }
 
+    /// Record a mod's download failure and send it to the download channel
+    async fn report_download_failure(
+        app: Option<&AppHandle>,
+        tx: Option<&mpsc::Sender<Result<DownloadedMod, DownloadError>>>,
+        download_errors: &DownloadErrorTracker,
+        error: DownloadError,
+    ) {
+        if let Some(app_handle) = app {
+            crate::services::record_download_failure(app_handle, error.mod_id(), &error.to_string());
+        }
+        download_errors.lock().unwrap().insert(error.mod_id().to_string(), error.clone());
+        if let Some(tx_ref) = tx {
+            let _ = tx_ref.send(Err(error)).await;
+        }
+    }
+
+    /// Update the status of a single SteamCMD instance
+    fn update_instance_status<F: FnOnce(&mut InstanceStatus)>(instance_statuses: &InstanceStatusTracker, instance: usize, f: F) {
+        let mut statuses = instance_statuses.lock().unwrap();
+        if let Some(status) = statuses.get_mut(&instance) {
+            f(status);
+        }
+    }
+
+    /// Emit the current status of a single SteamCMD instance
+    fn emit_instance_status(app: Option<&AppHandle>, instance_statuses: &InstanceStatusTracker, instance: usize) {
+        if let Some(app_handle) = app {
+            let status = instance_statuses.lock().unwrap().get(&instance).cloned();
+            if let Some(status) = status {
+                let _ = app_handle.emit("instance-status", &status);
+            }
+        }
+    }
+
     /// Wait for a mod to be downloaded by watching the download folder (static version for Send)
     /// Sends mod to channel immediately when downloaded (if tx is provided), but only if not in failed_mods_tracker
     async fn wait_for_mod_download_static(
         mod_download_path: PathBuf,
         mod_id: String,
         app: Option<AppHandle>,
-        tx: Option<mpsc::Sender<Result<DownloadedMod, String>>>,
+        tx: Option<mpsc::Sender<Result<DownloadedMod, DownloadError>>>,
         failed_mods_tracker: Option<Arc<Mutex<std::collections::HashSet<String>>>>,
+        timeout: Duration,
+        poll_interval: Duration,
     ) -> Result<Option<DownloadedMod>, String> {
-        let timeout = Duration::from_secs(600); // 10 minutes timeout
         let start_time = std::time::Instant::now();
         
+        // A mod already marked failed is never installed, don't wait for its folder
+        if Self::is_marked_failed(failed_mods_tracker.as_ref(), &mod_id) {
+            eprintln!("[Downloader] Mod {} was marked failed, not waiting for it", mod_id);
+            return Ok(None);
+        }
+        
         // First, check if mod is already downloaded (race condition protection)
         if let Ok(metadata) = fs::metadata(&mod_download_path) {
             if metadata.is_dir() {
@@ -1102,12 +2714,15 @@ impl Downloader {
                 if start_time.elapsed() > timeout {
                     return Ok(None);
                 }
+                if Self::is_marked_failed(failed_mods_tracker_clone.as_ref(), &mod_id_clone) {
+                    return Ok(None);
+                }
                 
                 // Receive file system event with timeout using spawn_blocking
                 let rx_clone = rx_for_task.clone();
                 let event_result = tokio::task::spawn_blocking(move || {
                     let rx_guard = rx_clone.lock().unwrap();
-                    rx_guard.recv_timeout(Duration::from_secs(2))
+                    rx_guard.recv_timeout(poll_interval)
                 }).await;
                 
                 match event_result {
@@ -1269,6 +2884,11 @@ impl Downloader {
         }
     }
     
+    /// Whether the batch marked a mod failed, e.g. reported by SteamCMD or rejected after download
+    fn is_marked_failed(failed_mods_tracker: Option<&Arc<Mutex<std::collections::HashSet<String>>>>, mod_id: &str) -> bool {
+        failed_mods_tracker.is_some_and(|tracker| tracker.lock().unwrap().contains(mod_id))
+    }
+
     /// Check if a mod appears to be partially downloaded (folder exists but may be incomplete)
     /// TODO: figure out a way to check integrity of the download compared to Steam Workshop
     fn is_mod_partially_downloaded(mod_path: &PathBuf) -> bool {
@@ -1305,7 +2925,7 @@ impl Downloader {
         }
         
         // Check for About folder (essential for RimWorld mods)
-        let about_path = mod_path.join("About");
+        let about_path = find_about_dir(mod_path);
         if !about_path.exists() || !about_path.is_dir() {
             eprintln!("[Downloader] Mod missing About folder: {:?}", mod_path);
             return false;
@@ -1333,6 +2953,31 @@ impl Downloader {
         true
     }
 
+    /// Total size in bytes of all files in a mod folder
+    fn mod_folder_size(mod_path: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(mod_path) else {
+            return 0;
+        };
+        entries.flatten()
+            .map(|entry| match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => Self::mod_folder_size(&entry.path()),
+                Ok(file_type) if file_type.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Check that the size of a downloaded mod is within `tolerance_pct` percent of the size Steam reports
+    /// Steam's size can differ slightly from the size on disk, so an exact match isn't required
+    pub fn verify_mod_size(mod_path: &Path, expected: u64, tolerance_pct: f64) -> bool {
+        if expected == 0 {
+            return true; // Unknown size
+        }
+        let actual = Self::mod_folder_size(mod_path);
+        let deviation_pct = (actual as f64 - expected as f64).abs() / expected as f64 * 100.0;
+        deviation_pct <= tolerance_pct
+    }
+
     /// Helper function to create DownloadedMod result and emit event
     fn create_downloaded_mod_result(
         mod_download_path: PathBuf,
@@ -1344,9 +2989,7 @@ impl Downloader {
             .map(|s| s.to_string());
         
         if let Some(app_handle) = &app {
-            let _ = app_handle.emit("mod-downloaded", serde_json::json!({
-                "modId": mod_id,
-            }));
+            emit_mod_lifecycle(app_handle, &mod_id, ModPhase::Downloaded, serde_json::Value::Null);
         }
         
         Ok(Some(DownloadedMod {
@@ -1372,6 +3015,17 @@ impl Downloader {
             eprintln!("[SteamCMD Output] {}", line_trimmed);
         }
         
+        // SteamCMD waits for a Steam Guard code, the frontend prompts for it
+        if Self::is_steam_guard_prompt(line_trimmed) {
+            if let Some(app_handle) = app {
+                let _ = app_handle.emit("steam-guard-required", serde_json::json!({
+                    "modIds": mod_ids,
+                    "twoFactor": line_lower.contains("two-factor"),
+                }));
+            }
+            return;
+        }
+        
         // Check each mod ID in the batch
         for mod_id in mod_ids {
             // Check if this line mentions the mod ID
@@ -1398,10 +3052,8 @@ impl Downloader {
                 if let Some(app_handle) = app {
                     if !will_retry {
                         eprintln!("[SteamCMD Parser] Mod {} detected as failed (no retry)", mod_id);
-                        let _ = app_handle.emit("mod-state", serde_json::json!({
-                            "modId": mod_id,
-                            "state": "failed",
-                            "error": "SteamCMD reported download failure"
+                        emit_mod_lifecycle(app_handle, mod_id, ModPhase::Failed, serde_json::json!({
+                            "error": "SteamCMD reported download failure",
                         }));
                     } else {
                         eprintln!("[SteamCMD Parser] Mod {} detected as failed (will retry, not emitting failed state)", mod_id);
@@ -1412,27 +3064,83 @@ impl Downloader {
             }
             
             // Detect downloading state - when SteamCMD starts downloading
-            // SteamCMD echoes the command from our script: "workshop_download_item 294100 <mod_id>"
+            // SteamCMD echoes the command from our script: "workshop_download_item <app_id> <mod_id>"
             // This indicates that SteamCMD is about to start downloading this mod
             if line_lower.contains("workshop_download_item") && 
-               line_lower.contains("294100") &&
                line_lower.contains(mod_id) {
                 if let Some(app_handle) = app {
                     eprintln!("[SteamCMD Parser] Mod {} detected as downloading (workshop_download_item command)", mod_id);
-                    let _ = app_handle.emit("mod-state", serde_json::json!({
-                        "modId": mod_id,
-                        "state": "downloading"
-                    }));
+                    emit_mod_lifecycle(app_handle, mod_id, ModPhase::Downloading, serde_json::Value::Null);
                 }
             }
         }
     }
 
+    /// Check if a line of SteamCMD output reports a failed login
+    /// e.g. "FAILED login with result code Rate Limit Exceeded" or "Logging in user 'anonymous' to Steam Public...FAILED (No Connection)"
+    fn parse_login_failure(line: &str) -> Option<LoginFailure> {
+        let line_trimmed = line.trim();
+        let line_lower = line_trimmed.to_lowercase();
+        let rate_limited = line_lower.contains("rate limit exceeded");
+        let login_failed = line_lower.contains("failed") && (line_lower.contains("login") || line_lower.contains("logging in"));
+        (rate_limited || login_failed).then(|| LoginFailure {
+            reason: line_trimmed.to_string(),
+            rate_limited,
+        })
+    }
+
+    /// Remember a login failure reported by an instance, the retry loop backs off the whole batch for it
+    fn record_login_failure(line: &str, login_failure: &Arc<Mutex<Option<LoginFailure>>>, batch_idx: usize) {
+        if let Some(failure) = Self::parse_login_failure(line) {
+            eprintln!("[Downloader] Instance {}: SteamCMD login failed: {}", batch_idx, failure.reason);
+            *login_failure.lock().unwrap() = Some(failure);
+        }
+    }
+
+    /// Check if a line of SteamCMD output asks for a Steam Guard code
+    /// Email codes prompt "Steam Guard code:", mobile authenticator codes "Two-factor code:"
+    fn is_steam_guard_prompt(line: &str) -> bool {
+        let line_lower = line.trim().to_lowercase();
+        line_lower.ends_with("steam guard code:") || line_lower.ends_with("two-factor code:")
+    }
+
+    /// Track which mod a SteamCMD instance is working on from its output
+    /// SteamCMD downloads the items of a script one after another, in script order
+    fn track_instance_progress(
+        line: &str,
+        mod_ids: &[String],
+        instance_statuses: &InstanceStatusTracker,
+        instance: usize,
+    ) {
+        let line_lower = line.trim().to_lowercase();
+        let Some(position) = mod_ids.iter().position(|id| line_lower.contains(id.as_str())) else {
+            return;
+        };
+
+        if line_lower.contains("workshop_download_item") {
+            Self::update_instance_status(instance_statuses, instance, |status| {
+                status.current_mod = Some(mod_ids[position].clone());
+                status.mods_remaining = mod_ids.len() - position;
+                status.status = InstanceState::Downloading;
+            });
+        } else if line_lower.contains("success") && line_lower.contains("downloaded item") {
+            // Pattern: "Success. Downloaded item <mod_id> to ..."
+            Self::update_instance_status(instance_statuses, instance, |status| {
+                status.mods_remaining = mod_ids.len() - position - 1;
+            });
+        }
+    }
+
     /// Check if a mod is currently being downloaded
     pub fn is_downloading(&self, mod_id: &str) -> bool {
         self.active_downloads.contains(mod_id)
     }
 
+    /// Check if any mod is currently being downloaded
+    pub fn has_active_downloads(&self) -> bool {
+        !self.active_downloads.is_empty()
+    }
+
     /// Mark a mod as downloading
     pub fn mark_downloading(&mut self, mod_id: String) {
         self.active_downloads.insert(mod_id);
@@ -1456,6 +3164,32 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_parse_steamcmd_health() {
+        let updated = "Redirecting stderr to '/home/user/Steam/logs/stderr.txt'\n[  0%] Checking for available updates...\n[----] Downloading update (0 of 44,593 KB)...\n[----] Update complete, launching Steamcmd...\nLoading Steam API...OK\n";
+        assert_eq!(parse_steamcmd_health(updated, true), Ok(true));
+        assert_eq!(parse_steamcmd_health("[----] Verifying installation...\nLoading Steam API...OK\n", true), Ok(false));
+
+        let missing_libs = "linux32/steamcmd: error while loading shared libraries: libstdc++.so.6: cannot open shared object file";
+        assert!(parse_steamcmd_health(missing_libs, false).unwrap_err().contains("32-bit"));
+        let missing_binary = "steamcmd.sh: line 39: /opt/steamcmd/linux32/steamcmd: No such file or directory";
+        assert!(parse_steamcmd_health(missing_binary, false).unwrap_err().contains("Reinstall SteamCMD"));
+        assert!(parse_steamcmd_health("Loading Steam API...\nSegmentation fault\n", false).unwrap_err().contains("Segmentation fault"));
+    }
+
+    #[test]
+    fn test_missing_libraries_message() {
+        let ldd = "\tlinux-gate.so.1 (0xf7f0e000)\n\tlibstdc++.so.6 => not found\n\tlibgcc_s.so.1 => not found\n\tlibc.so.6 => /lib32/libc.so.6 (0xf7c00000)\n";
+        let missing = parse_ldd_missing_libraries(ldd);
+        assert_eq!(missing, vec!["libstdc++.so.6", "libgcc_s.so.1"]);
+        assert_eq!(
+            missing_libraries_message(&missing[..1]),
+            "SteamCMD requires 32-bit libraries (libstdc++.so.6 i386). Install lib32stdc++6 (Debian/Ubuntu) or the matching .i686 packages (Fedora)",
+        );
+        assert!(missing_libraries_message(&missing).contains("Install lib32stdc++6 lib32gcc-s1 "));
+        assert!(parse_ldd_missing_libraries("\tnot a dynamic executable\n").is_empty());
+    }
+
     #[test]
     fn test_is_downloading() {
         let mut downloader = Downloader::new(None);
@@ -1486,6 +3220,75 @@ mod tests {
         assert!(downloader.is_downloading("333333333"));
     }
 
+    #[test]
+    fn test_track_instance_progress() {
+        let downloader = Downloader::new(None);
+        let mod_ids = vec!["111111111".to_string(), "222222222".to_string()];
+        downloader.instance_statuses.lock().unwrap().insert(0, InstanceStatus {
+            instance_index: 0,
+            current_mod: None,
+            mods_remaining: mod_ids.len(),
+            status: InstanceState::Starting,
+            download_id: 0,
+        });
+        
+        Downloader::track_instance_progress("workshop_download_item 294100 222222222", &mod_ids, &downloader.instance_statuses, 0);
+        let status = &downloader.instance_statuses()[0];
+        assert_eq!(status.current_mod.as_deref(), Some("222222222"));
+        assert_eq!(status.mods_remaining, 1);
+        assert_eq!(status.status, InstanceState::Downloading);
+        
+        Downloader::track_instance_progress("Success. Downloaded item 222222222 to \"/tmp\"", &mod_ids, &downloader.instance_statuses, 0);
+        assert_eq!(downloader.instance_statuses()[0].mods_remaining, 0);
+    }
+
+    #[test]
+    fn test_remove_stale_steamcmd_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let steamcmd_path = temp_dir.path().to_path_buf();
+        fs::write(steamcmd_path.join(".crash"), "").unwrap();
+        fs::write(steamcmd_path.join("steamcmd.lock"), "").unwrap();
+        fs::write(steamcmd_path.join("run_batch_0.txt"), "").unwrap();
+        fs::write(steamcmd_path.join("steamcmd.sh"), "").unwrap();
+        
+        // Another instance of the app is running SteamCMD in this folder
+        let in_use = lock_steamcmd_shared(&steamcmd_path).unwrap();
+        assert!(Downloader::remove_stale_steamcmd_files(&steamcmd_path, true).is_empty());
+        assert!(steamcmd_path.join("steamcmd.lock").exists());
+        drop(in_use);
+        
+        let removed = Downloader::remove_stale_steamcmd_files(&steamcmd_path, false);
+        assert_eq!(removed.len(), 2);
+        assert!(steamcmd_path.join("run_batch_0.txt").exists());
+        
+        let removed = Downloader::remove_stale_steamcmd_files(&steamcmd_path, true);
+        assert_eq!(removed.len(), 1);
+        assert!(!steamcmd_path.join("run_batch_0.txt").exists());
+        assert!(steamcmd_path.join("steamcmd.sh").exists());
+    }
+
+    #[test]
+    fn test_resolve_steamcmd_executable() {
+        let temp_dir = TempDir::new().unwrap();
+        let exe_name = if cfg!(target_os = "windows") { "steamcmd.exe" } else { "steamcmd.sh" };
+        let exe_path = temp_dir.path().join(exe_name);
+        
+        assert!(Downloader::resolve_steamcmd_executable(temp_dir.path()).is_err());
+        assert!(Downloader::resolve_steamcmd_executable(&temp_dir.path().join("missing")).is_err());
+        
+        fs::write(&exe_path, "").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            // Files that can't be executed are rejected instead of failing when a download starts
+            fs::set_permissions(&exe_path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(Downloader::resolve_steamcmd_executable(&exe_path).unwrap_err().contains("not executable"));
+            fs::set_permissions(&exe_path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        assert_eq!(Downloader::resolve_steamcmd_executable(temp_dir.path()).unwrap(), exe_path);
+        assert_eq!(Downloader::resolve_steamcmd_executable(&exe_path).unwrap(), exe_path);
+    }
+
     #[test]
     fn test_downloader_paths() {
         let temp_dir = TempDir::new().unwrap();
@@ -1498,5 +3301,295 @@ mod tests {
             downloader.download_path,
             steamcmd_path.join("steamapps").join("workshop").join("content").join("294100")
         );
+        
+        let mut downloader = Downloader::with_app_id(Some(steamcmd_path.clone()), 108600);
+        assert_eq!(downloader.app_id(), 108600);
+        assert!(downloader.download_path.ends_with("108600"));
+        
+        downloader.set_app_id(DEFAULT_APP_ID).unwrap();
+        assert!(downloader.download_path.ends_with("294100"));
+        assert!(downloader.set_app_id(0).is_err());
+        
+        // A configured download directory replaces the SteamCMD folder, for any app id
+        let install_dir = temp_dir.path().join("ssd");
+        downloader.set_install_dir(Some(install_dir.clone())).unwrap();
+        assert_eq!(downloader.download_path, install_dir.join("steamapps").join("workshop").join("content").join("294100"));
+        downloader.set_app_id(108600).unwrap();
+        assert!(downloader.download_path.starts_with(&install_dir));
+        downloader.set_install_dir(None).unwrap();
+        assert!(downloader.download_path.starts_with(&steamcmd_path));
+        
+        downloader.mark_downloading("123".to_string());
+        assert!(downloader.set_app_id(DEFAULT_APP_ID).is_err());
+        assert!(downloader.set_install_dir(Some(install_dir)).is_err());
+    }
+
+    #[test]
+    fn test_throttle_limits_instances() {
+        let mut downloader = Downloader::new(None);
+        assert_eq!(downloader.throttle_limited_instances(4), 4);
+        
+        downloader.set_download_throttle(Some(2048));
+        assert_eq!(downloader.throttle_limited_instances(4), DEFAULT_THROTTLED_INSTANCES);
+        
+        downloader.set_throttled_max_instances(2);
+        assert_eq!(downloader.throttle_limited_instances(4), 2);
+        assert_eq!(downloader.throttle_limited_instances(1), 1);
+        
+        downloader.set_throttled_max_instances(0);
+        assert_eq!(downloader.throttled_max_instances(), 1);
+        
+        downloader.set_download_throttle(Some(0));
+        assert_eq!(downloader.throttle_limited_instances(4), 4);
+    }
+
+    #[test]
+    fn test_resolve_max_instances() {
+        assert!(Downloader::resolve_max_instances(Some(0)).is_err());
+        assert_eq!(Downloader::resolve_max_instances(Some(12)).unwrap(), 12);
+        
+        let default = Downloader::resolve_max_instances(None).unwrap();
+        assert!((1..=8).contains(&default));
+    }
+
+    #[test]
+    fn test_download_timeout_scales_with_size() {
+        let timeouts = DownloadTimeouts::default();
+        let gigabyte = 1024 * 1024 * 1024;
+        
+        assert_eq!(timeouts.for_mod(0, true), DOWNLOAD_TIMEOUT_BASE);
+        assert!(timeouts.for_mod(gigabyte, true) > Duration::from_secs(3600));
+        // Unknown sizes never wait less than the old fixed timeout
+        assert_eq!(timeouts.for_mod(0, false), DEFAULT_DOWNLOAD_TIMEOUT);
+        
+        let fixed = DownloadTimeouts { fixed: Some(Duration::from_secs(30)), ..timeouts };
+        assert_eq!(fixed.for_mod(gigabyte, true), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_retry_backoff_jitter_and_budget() {
+        for retry in 1..=6 {
+            let base_ms = 1000 * 2_u64.pow(retry - 1);
+            let delay = DownloadRetryPolicy::backoff(retry).as_millis() as u64;
+            assert!(delay >= base_ms * 3 / 4 && delay <= base_ms * 5 / 4, "retry {} waited {} ms", retry, delay);
+        }
+        
+        let policy = DownloadRetryPolicy::default();
+        assert_eq!(policy.retries_per_mod(), policy.max_retries);
+        let budgeted = DownloadRetryPolicy { max_retries_per_mod: Some(2), ..policy };
+        assert_eq!(budgeted.retries_per_mod(), 2);
+        // A per-mod budget can't exceed the retries of the batch
+        let capped = DownloadRetryPolicy { max_retries: 1, max_retries_per_mod: Some(5) };
+        assert_eq!(capped.retries_per_mod(), 1);
+    }
+
+    #[test]
+    fn test_remove_cancelled_downloads() {
+        let temp_dir = TempDir::new().unwrap();
+        let downloader = Downloader::new(Some(temp_dir.path().to_path_buf()));
+        let staging_path = temp_dir.path().join("steamapps").join("workshop").join("downloads").join("294100");
+        for mod_id in ["1", "2"] {
+            fs::create_dir_all(downloader.download_path.join(mod_id)).unwrap();
+            fs::create_dir_all(staging_path.join(mod_id)).unwrap();
+        }
+        let mod_ids = vec!["1".to_string(), "2".to_string()];
+        
+        assert!(!Downloader::is_batch_cancelled(&downloader.cancelled_mods, &mod_ids));
+        downloader.cancel_mods(&["2".to_string()]);
+        assert!(Downloader::is_batch_cancelled(&downloader.cancelled_mods, &mod_ids));
+        
+        Downloader::remove_cancelled_downloads(temp_dir.path(), &downloader.download_path, &mod_ids, &downloader.cancelled_mods, downloader.app_id);
+        assert!(downloader.download_path.join("1").exists());
+        assert!(staging_path.join("1").exists());
+        assert!(!downloader.download_path.join("2").exists());
+        assert!(!staging_path.join("2").exists());
+
+        // The other mod of the killed batch is retried, the cancelled one leaves the download and the tracker
+        let interrupted_mods: InterruptedModsTracker = Arc::new(Mutex::new(std::collections::HashSet::new()));
+        Downloader::record_interrupted_mods(&downloader.cancelled_mods, &interrupted_mods, &mod_ids);
+        assert_eq!(*interrupted_mods.lock().unwrap(), std::collections::HashSet::from(["1".to_string()]));
+        let mut remaining = mod_ids.clone();
+        assert_eq!(Downloader::take_cancelled_mods(&downloader.cancelled_mods, &mut remaining), vec!["2".to_string()]);
+        assert_eq!(remaining, vec!["1".to_string()]);
+        assert!(downloader.cancelled_mods.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_retry_keeps_partial_downloads() {
+        let temp_dir = TempDir::new().unwrap();
+        let download_errors: DownloadErrorTracker = Arc::new(Mutex::new(HashMap::new()));
+        let workshop_path = temp_dir.path().join("steamapps").join("workshop");
+        let manifest_path = workshop_path.join("appworkshop_294100.acf");
+        let staged_file = workshop_path.join("downloads").join("294100").join("1").join("Textures.dds");
+        fs::create_dir_all(staged_file.parent().unwrap()).unwrap();
+        fs::write(&staged_file, "partial").unwrap();
+        fs::write(&manifest_path, "\"AppWorkshop\" {}").unwrap();
+        let download_path = workshop_path.join("content").join("294100");
+        let mod_ids = vec!["1".to_string()];
+        
+        // A new session never trusts the manifest left by the previous one
+        assert!(Downloader::prepare_workshop_manifest(temp_dir.path(), &download_path, &mod_ids, &download_errors, DEFAULT_APP_ID, true));
+        assert!(!manifest_path.exists());
+        assert!(staged_file.exists());
+        fs::write(&manifest_path, "\"AppWorkshop\" {}").unwrap();
+        
+        // A regular retry resumes from what SteamCMD already has
+        download_errors.lock().unwrap().insert("1".to_string(), DownloadError::Timeout { mod_id: "1".to_string() });
+        assert!(!Downloader::prepare_workshop_manifest(temp_dir.path(), &download_path, &mod_ids, &download_errors, DEFAULT_APP_ID, false));
+        assert!(manifest_path.exists());
+        assert!(staged_file.exists());
+        
+        // A corrupt download starts over
+        download_errors.lock().unwrap().insert("1".to_string(), DownloadError::IncompleteFiles { mod_id: "1".to_string() });
+        assert!(Downloader::prepare_workshop_manifest(temp_dir.path(), &download_path, &mod_ids, &download_errors, DEFAULT_APP_ID, false));
+        assert!(!manifest_path.exists());
+        assert!(!staged_file.exists());
+        
+        // So does a mod with nothing on disk, which the manifest may still list as installed
+        fs::write(&manifest_path, "\"AppWorkshop\" {}").unwrap();
+        download_errors.lock().unwrap().clear();
+        assert!(Downloader::prepare_workshop_manifest(temp_dir.path(), &download_path, &mod_ids, &download_errors, DEFAULT_APP_ID, false));
+        assert!(!manifest_path.exists());
+    }
+
+    #[test]
+    fn test_instances_for_memory() {
+        assert_eq!(Downloader::instances_for_memory(4, 16 * 1024 * 1024 * 1024), 4);
+        assert_eq!(Downloader::instances_for_memory(8, 2 * ESTIMATED_INSTANCE_MEMORY + 1), 2);
+        // Too little memory for a single instance still downloads serially
+        assert_eq!(Downloader::instances_for_memory(4, 1024), 1);
+    }
+
+    #[test]
+    fn test_is_steam_guard_prompt() {
+        assert!(Downloader::is_steam_guard_prompt("Steam Guard code:"));
+        assert!(Downloader::is_steam_guard_prompt("Two-factor code: "));
+        assert!(!Downloader::is_steam_guard_prompt("Logging in user 'someone' to Steam Public...OK"));
+    }
+
+    #[test]
+    fn test_parse_login_failure() {
+        let failure = Downloader::parse_login_failure("FAILED login with result code Rate Limit Exceeded").unwrap();
+        assert!(failure.rate_limited);
+        assert_eq!(failure.reason, "FAILED login with result code Rate Limit Exceeded");
+
+        let failure = Downloader::parse_login_failure("Logging in user 'anonymous' to Steam Public...FAILED (No Connection)").unwrap();
+        assert!(!failure.rate_limited);
+
+        assert!(Downloader::parse_login_failure("Logging in user 'anonymous' to Steam Public...OK").is_none());
+        assert!(Downloader::parse_login_failure("ERROR! Download item 818773962 failed (Failure).").is_none());
+
+        assert!(DownloadRetryPolicy::retry_delay(1, true) >= LOGIN_FAILURE_BACKOFF);
+        assert!(DownloadRetryPolicy::retry_delay(1, false) < LOGIN_FAILURE_BACKOFF);
+    }
+
+    #[test]
+    fn test_script_file_guard_removes_script() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("run_batch_0.txt");
+        fs::write(&script_path, "login someone \"secret\"\n").unwrap();
+        
+        drop(ScriptFileGuard(script_path.clone()));
+        assert!(!script_path.exists());
+    }
+
+    #[test]
+    fn test_write_script_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let script_path = temp_dir.path().join("run_batch_0.txt");
+        // A leftover of a crashed run is replaced, not reused with its permissions
+        fs::write(&script_path, "old").unwrap();
+        
+        let login = format!("login {} {}", quote_script_arg("someone").unwrap(), quote_script_arg("pass word").unwrap());
+        assert_eq!(login, "login \"someone\" \"pass word\"");
+        write_script_file(&script_path, &login).unwrap();
+        assert_eq!(fs::read_to_string(&script_path).unwrap(), login);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&script_path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        
+        // A quote would end the value early and turn the rest into commands
+        assert!(quote_script_arg("pa\"ss").is_err());
+        assert!(quote_script_arg("pass\nquit").is_err());
+    }
+
+    #[test]
+    fn test_parse_download_progress() {
+        let progress = DownloadProgress::parse(" Update state (0x61) downloading, progress: 42.13 (1234567 / 2930000)").unwrap();
+        assert_eq!(progress, DownloadProgress { percent: 42.13, bytes_downloaded: 1234567, bytes_total: 2930000 });
+        assert!(DownloadProgress::parse("Downloading item 123 ...").is_none());
+    }
+
+    #[test]
+    fn test_progress_state_tracks_current_mod() {
+        let mod_ids = vec!["111".to_string(), "222".to_string()];
+        let progress_line = "Update state (0x61) downloading, progress: 10.00 (10 / 100)";
+        let mut state = ProgressState::default();
+        
+        // Progress before the item line (resume) is held back
+        assert!(state.handle_line(progress_line, &mod_ids).is_none());
+        let (mod_id, progress) = state.handle_line("Downloading item 111 ...", &mod_ids).unwrap();
+        assert_eq!(mod_id, "111");
+        assert_eq!(progress.bytes_downloaded, 10);
+        
+        assert_eq!(state.handle_line(progress_line, &mod_ids).unwrap().0, "111");
+        assert!(state.handle_line("Success. Downloaded item 111 to \"...\" (100 bytes)", &mod_ids).is_none());
+        assert!(state.handle_line(progress_line, &mod_ids).is_none());
+        
+        state.handle_line("Downloading item 222 ...", &mod_ids);
+        assert_eq!(state.handle_line(progress_line, &mod_ids).unwrap().0, "222");
+    }
+
+    #[test]
+    fn test_download_error_serialization() {
+        let error = DownloadError::ProcessExited { mod_id: "123".to_string(), exit_code: 8 };
+        assert_eq!(error.mod_id(), "123");
+        assert_eq!(error.to_string(), "SteamCMD exited with code 8 while downloading mod 123");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({ "kind": "processExited", "modId": "123", "exitCode": 8 })
+        );
+        assert_eq!(
+            serde_json::to_value(DownloadError::Timeout { mod_id: "456".to_string() }).unwrap(),
+            serde_json::json!({ "kind": "timeout", "modId": "456" })
+        );
+    }
+
+    #[test]
+    fn test_verify_mod_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("123");
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        fs::write(mod_path.join("About").join("About.xml"), vec![0u8; 600]).unwrap();
+        fs::write(mod_path.join("Preview.png"), vec![0u8; 400]).unwrap();
+        
+        assert!(Downloader::verify_mod_size(&mod_path, 1000, 0.0));
+        assert!(Downloader::verify_mod_size(&mod_path, 1050, 10.0));
+        assert!(!Downloader::verify_mod_size(&mod_path, 4000, 50.0));
+        assert!(Downloader::verify_mod_size(&mod_path, 0, 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_failed_mod_returns_without_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = Arc::new(Mutex::new(std::collections::HashSet::from(["123".to_string()])));
+        let started = std::time::Instant::now();
+        
+        // Marked undersized and already removed, or still on disk: neither waits for the timeout
+        let missing = Downloader::wait_for_mod_download_static(
+            temp_dir.path().join("123"), "123".to_string(), None, None, Some(tracker.clone()),
+            Duration::from_secs(60), Duration::from_millis(50),
+        ).await.unwrap();
+        fs::create_dir_all(temp_dir.path().join("123").join("About")).unwrap();
+        let present = Downloader::wait_for_mod_download_static(
+            temp_dir.path().join("123"), "123".to_string(), None, None, Some(tracker),
+            Duration::from_secs(60), Duration::from_millis(50),
+        ).await.unwrap();
+        
+        assert!(missing.is_none());
+        assert!(present.is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
     }
 }
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Lines of SteamCMD output kept per download session, older lines are dropped
+pub const MAX_LOG_LINES: usize = 2000;
+
+/// Replaces secrets in buffered and emitted lines
+const REDACTED: &str = "[redacted]";
+
+/// One line of SteamCMD output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteamCmdLogLine {
+    /// Instance of the download that printed the line
+    pub instance: usize,
+    pub line: String,
+}
+
+/// Raw SteamCMD output of one download session, shared by all its instances
+pub struct SteamCmdLog {
+    lines: Mutex<VecDeque<SteamCmdLogLine>>,
+    /// Values never stored or emitted, like the Steam password
+    secrets: Vec<String>,
+    /// Emits every line as a `steamcmd-log` event when set
+    live: Option<AppHandle>,
+}
+
+impl SteamCmdLog {
+    pub fn new(secrets: Vec<String>, live: Option<AppHandle>) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::new()),
+            secrets: secrets.into_iter().filter(|secret| !secret.is_empty()).collect(),
+            live,
+        }
+    }
+
+    pub fn push(&self, instance: usize, line: &str) {
+        let entry = SteamCmdLogLine { instance, line: redact(line, &self.secrets) };
+        if let Some(app) = &self.live {
+            let _ = app.emit("steamcmd-log", &entry);
+        }
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(entry);
+    }
+
+    /// Buffered lines, oldest first
+    pub fn lines(&self) -> Vec<SteamCmdLogLine> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Replace every occurrence of the secrets in a line
+fn redact(line: &str, secrets: &[String]) -> String {
+    secrets.iter().fold(line.to_string(), |line, secret| line.replace(secret.as_str(), REDACTED))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_redacts_and_caps_lines() {
+        let log = SteamCmdLog::new(vec!["hunter2".to_string(), String::new()], None);
+        log.push(0, "login someone \"hunter2\"");
+        assert_eq!(log.lines(), vec![SteamCmdLogLine { instance: 0, line: "login someone \"[redacted]\"".to_string() }]);
+
+        for index in 0..MAX_LOG_LINES {
+            log.push(1, &format!("line {}", index));
+        }
+        let lines = log.lines();
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert_eq!(lines[0].line, "line 0");
+        assert_eq!(lines[MAX_LOG_LINES - 1].line, format!("line {}", MAX_LOG_LINES - 1));
+    }
+}
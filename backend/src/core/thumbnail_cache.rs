@@ -0,0 +1,109 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use image::ImageFormat;
+use sha2::{Digest, Sha256};
+use crate::core::mod_scanner::find_preview_image;
+
+/// Size of the longest side of a thumbnail when none is requested
+pub const DEFAULT_THUMBNAIL_DIM: u32 = 256;
+/// Largest thumbnail that can be requested, larger previews are better loaded directly
+pub const MAX_THUMBNAIL_DIM: u32 = 1024;
+const MIN_THUMBNAIL_DIM: u32 = 16;
+
+/// Folder of the cached thumbnails inside the app cache dir
+pub const THUMBNAIL_CACHE_FOLDER: &str = "thumbnails";
+
+/// PNG thumbnail of a mod's preview image, at most `max_dim` pixels wide and high
+/// Thumbnails are cached in `cache_dir` by preview path, modification time and size, None if the mod has no preview
+pub fn get_thumbnail(mod_path: &Path, max_dim: u32, cache_dir: &Path) -> Result<Option<Vec<u8>>, String> {
+    let Some(preview_path) = find_preview_image(mod_path).map(PathBuf::from) else {
+        return Ok(None);
+    };
+    let max_dim = max_dim.clamp(MIN_THUMBNAIL_DIM, MAX_THUMBNAIL_DIM);
+    let modified = fs::metadata(&preview_path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("Failed to read preview image {}: {}", preview_path.display(), e))?;
+    let modified = modified.duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+
+    let cache_path = cache_dir.join(format!("{}.png", cache_key(&preview_path, modified, max_dim)));
+    if let Ok(cached) = fs::read(&cache_path) {
+        return Ok(Some(cached));
+    }
+
+    let thumbnail = create_thumbnail(&preview_path, max_dim)?;
+    // A failed cache write only costs a re-encode next time
+    if let Err(e) = write_cache_file(&cache_path, &thumbnail) {
+        eprintln!("[Thumbnails] {}", e);
+    }
+    Ok(Some(thumbnail))
+}
+
+/// Name of the cache file of a preview, a changed preview gets a new name
+fn cache_key(preview_path: &Path, modified: u128, max_dim: u32) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(preview_path.to_string_lossy().as_bytes());
+    hasher.update(modified.to_le_bytes());
+    hasher.update(max_dim.to_le_bytes());
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a preview and encode it as a PNG fitting in `max_dim`, smaller previews are not upscaled
+fn create_thumbnail(preview_path: &Path, max_dim: u32) -> Result<Vec<u8>, String> {
+    let mut image = image::open(preview_path)
+        .map_err(|e| format!("Failed to decode preview image {}: {}", preview_path.display(), e))?;
+    if image.width() > max_dim || image.height() > max_dim {
+        image = image.thumbnail(max_dim, max_dim);
+    }
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode thumbnail of {}: {}", preview_path.display(), e))?;
+    Ok(png)
+}
+
+fn write_cache_file(cache_path: &Path, thumbnail: &[u8]) -> Result<(), String> {
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+    }
+    // Written under a temporary name, so a concurrent read never sees half a file
+    let temp_path = cache_path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_path, thumbnail)
+        .map_err(|e| format!("Failed to write {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, cache_path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to replace {}: {}", cache_path.display(), e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, GenericImageView, RgbaImage};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_thumbnail_is_downscaled_and_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let mod_path = temp_dir.path().join("Harmony");
+        let cache_dir = temp_dir.path().join("cache");
+        assert_eq!(get_thumbnail(&mod_path, 64, &cache_dir).unwrap(), None);
+
+        fs::create_dir_all(mod_path.join("About")).unwrap();
+        DynamicImage::ImageRgba8(RgbaImage::new(400, 200)).save(mod_path.join("About").join("Preview.png")).unwrap();
+
+        let thumbnail = get_thumbnail(&mod_path, 64, &cache_dir).unwrap().unwrap();
+        assert_eq!(image::load_from_memory(&thumbnail).unwrap().dimensions(), (64, 32));
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+
+        // Served from the cache, even once the cached file differs from a fresh encode
+        let cache_path = fs::read_dir(&cache_dir).unwrap().next().unwrap().unwrap().path();
+        fs::write(&cache_path, b"cached").unwrap();
+        assert_eq!(get_thumbnail(&mod_path, 64, &cache_dir).unwrap().unwrap(), b"cached");
+
+        // Small previews keep their size
+        let thumbnail = get_thumbnail(&mod_path, 1000, &cache_dir).unwrap().unwrap();
+        assert_eq!(image::load_from_memory(&thumbnail).unwrap().dimensions(), (400, 200));
+    }
+}
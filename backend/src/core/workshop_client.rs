@@ -1,49 +1,414 @@
 use crate::core::mod_scanner::WorkshopFileDetails;
-use crate::core::api_cache::Cache;
-use crate::core::api_rate_limiter::RateLimiter;
-use std::time::Duration;
+use crate::core::api_cache::{Cache, write_json_atomic};
+use crate::core::api_rate_limiter::{BucketRateLimiter, RateLimitBucket, RateLimitConfig};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, UNIX_EPOCH};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
 
 const STEAM_API_BASE: &str = "http://api.steampowered.com";
 const USER_AGENT: &str = "RimworldWorkshopDownloader/1.0";
+/// Well-known Workshop item used to probe the Workshop API (Harmony)
+const STATUS_PROBE_MOD_ID: &str = "2009463077";
+const STATUS_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default age after which file details persisted on disk are ignored and re-fetched
+pub const DEFAULT_DISK_CACHE_TTL: Duration = Duration::from_secs(6 * 3600);
+
+// Location and TTL of the on-disk file details cache, configured once the app data dir is known
+static DISK_CACHE_PATH: OnceLock<PathBuf> = OnceLock::new();
+static DISK_CACHE_TTL_SECS: AtomicU64 = AtomicU64::new(DEFAULT_DISK_CACHE_TTL.as_secs());
+
+/// Set where the file details cache is persisted and how long persisted entries stay valid
+/// Must be called before the shared SteamApi is created for the cache to be loaded
+pub fn configure_disk_cache(path: PathBuf, ttl: Duration) {
+    let _ = DISK_CACHE_PATH.set(path);
+    set_disk_cache_ttl(ttl);
+}
+
+/// Set how long persisted file details stay valid
+pub fn set_disk_cache_ttl(ttl: Duration) {
+    DISK_CACHE_TTL_SECS.store(ttl.as_secs(), Ordering::Relaxed);
+}
+
+fn disk_cache_ttl() -> Duration {
+    Duration::from_secs(DISK_CACHE_TTL_SECS.load(Ordering::Relaxed))
+}
+
+/// Default number of Workshop pages scraped (or detail batches queried) in parallel by batch commands
+pub const DEFAULT_SCRAPE_CONCURRENCY: usize = 3;
+
+// Parallel page scrapes, kept low as Steam's web servers soft-ban IPs that scrape too fast
+static SCRAPE_CONCURRENCY: AtomicUsize = AtomicUsize::new(DEFAULT_SCRAPE_CONCURRENCY);
+
+/// Set how many Workshop pages batch commands scrape in parallel (None resets to the default)
+pub fn set_scrape_concurrency(concurrency: Option<usize>) {
+    SCRAPE_CONCURRENCY.store(concurrency.unwrap_or(DEFAULT_SCRAPE_CONCURRENCY).max(1), Ordering::Relaxed);
+}
+
+/// Number of Workshop pages batch commands scrape in parallel
+/// Each scrape still waits for the scraping rate limit, this only bounds how many are in flight
+pub fn scrape_concurrency() -> usize {
+    SCRAPE_CONCURRENCY.load(Ordering::Relaxed)
+}
+
+/// Network settings for restricted networks
+/// Without a configured proxy the system proxy (`HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY`) is used
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConfig {
+    /// HTTP(S) proxy for all requests to Steam, e.g. `http://proxy.local:3128`
+    pub proxy: Option<String>,
+    /// Replaces `http://api.steampowered.com`, e.g. for a mirror reachable where Steam is blocked
+    pub api_base_url: Option<String>,
+    /// Steam Web API key, not required; requests sent with a key count against its higher rate limit
+    pub api_key: Option<String>,
+}
+
+impl NetworkConfig {
+    /// Trimmed copy with empty values unset, fails if the proxy or base URL is not a valid URL
+    pub fn normalized(mut self) -> Result<Self, String> {
+        self.proxy = self.proxy.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+        self.api_key = self.api_key.map(|k| k.trim().to_string()).filter(|k| !k.is_empty());
+        self.api_base_url = self.api_base_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty());
+        if let Some(url) = &self.api_base_url {
+            reqwest::Url::parse(url).map_err(|e| format!("Invalid API base URL: {}", e))?;
+        }
+        build_http_client(&self, None)?;
+        Ok(self)
+    }
+}
+
+// Applies to every SteamApi and to batch queries made without one
+static NETWORK_CONFIG: RwLock<NetworkConfig> = RwLock::new(NetworkConfig { proxy: None, api_base_url: None, api_key: None });
+
+/// Set the proxy, API base URL and API key used for requests to Steam
+/// Fails without changing anything if the proxy or base URL is not a valid URL
+pub fn set_network_config(config: NetworkConfig) -> Result<(), String> {
+    *NETWORK_CONFIG.write().unwrap() = config.normalized()?;
+    Ok(())
+}
+
+pub fn network_config() -> NetworkConfig {
+    NETWORK_CONFIG.read().unwrap().clone()
+}
+
+fn build_http_client(config: &NetworkConfig, timeout: Option<Duration>) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder();
+    // A configured proxy replaces the system proxy
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|e| format!("Invalid proxy URL: {}", e))?);
+    }
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder.build().map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// HTTP client for requests to Steam, using the configured proxy
+pub fn http_client() -> reqwest::Client {
+    build_http_client(&network_config(), None).unwrap_or_default()
+}
+
+/// Base URL of the Steam Web API, without a trailing slash
+pub fn api_base_url() -> String {
+    network_config().api_base_url.unwrap_or_else(|| STEAM_API_BASE.to_string())
+}
+
+/// File details entry as stored in the on-disk cache
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedFileDetails {
+    language: String,
+    /// Unix time the details were fetched at
+    cached_at: u64,
+    details: WorkshopFileDetails,
+}
+
+/// Default number of nested collection levels resolved by `get_collection_details`
+pub const DEFAULT_COLLECTION_DEPTH: usize = 5;
+
+/// Mod found in a (possibly nested) collection
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionMod {
+    #[serde(flatten)]
+    pub details: WorkshopFileDetails,
+    /// Collection that directly contains the mod, may be a nested collection
+    #[serde(rename = "parentCollectionId")]
+    pub parent_collection_id: String,
+}
+
+/// A collection's own Workshop page, as opposed to the mods it contains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionInfo {
+    pub collection_id: String,
+    pub title: String,
+    pub description: String,
+    /// Steam ID of the creator, the Web API doesn't return profile names
+    pub author: String,
+    /// Items directly in the collection, nested collections count as one item
+    pub item_count: usize,
+    pub preview_url: String,
+}
+
+impl CollectionInfo {
+    fn from_details(details: &WorkshopFileDetails, item_count: usize) -> Self {
+        Self {
+            collection_id: details.publishedfileid.clone(),
+            title: details.title.clone(),
+            description: details.description.clone(),
+            author: details.creator.clone(),
+            item_count,
+            preview_url: details.preview_url.clone(),
+        }
+    }
+}
+
+/// One update listed on a Workshop item's change notes page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangelogEntry {
+    /// Unix time the update was published at
+    pub timestamp: i64,
+    pub text: String,
+}
+
+/// On-disk cache file, entries keyed by publishedfileid
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedCache {
+    entries: HashMap<String, PersistedFileDetails>,
+}
+
+/// File details taken from the in-memory cache, to be written to the disk cache
+pub struct DiskCacheSnapshot {
+    path: PathBuf,
+    cache: PersistedCache,
+}
+
+impl DiskCacheSnapshot {
+    /// Write the snapshot to the disk cache file
+    /// Synchronous, use in spawn_blocking
+    pub fn save(&self) -> Result<(), String> {
+        write_json_atomic(&self.path, &self.cache)
+    }
+}
+
+/// Reachability of Steam services, used to tell Steam outages apart from local problems
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteamStatus {
+    pub api_up: bool,
+    pub workshop_up: bool,
+    pub content_servers_reachable: bool,
+}
+
+/// Steam language names mapped to the matching Accept-Language value
+const STEAM_LANGUAGES: &[(&str, &str)] = &[
+    ("english", "en-US,en;q=0.5"),
+    ("german", "de-DE,de;q=0.5"),
+    ("french", "fr-FR,fr;q=0.5"),
+    ("spanish", "es-ES,es;q=0.5"),
+    ("latam", "es-419,es;q=0.5"),
+    ("italian", "it-IT,it;q=0.5"),
+    ("polish", "pl-PL,pl;q=0.5"),
+    ("russian", "ru-RU,ru;q=0.5"),
+    ("ukrainian", "uk-UA,uk;q=0.5"),
+    ("czech", "cs-CZ,cs;q=0.5"),
+    ("hungarian", "hu-HU,hu;q=0.5"),
+    ("dutch", "nl-NL,nl;q=0.5"),
+    ("swedish", "sv-SE,sv;q=0.5"),
+    ("danish", "da-DK,da;q=0.5"),
+    ("norwegian", "no-NO,no;q=0.5"),
+    ("finnish", "fi-FI,fi;q=0.5"),
+    ("portuguese", "pt-PT,pt;q=0.5"),
+    ("brazilian", "pt-BR,pt;q=0.5"),
+    ("turkish", "tr-TR,tr;q=0.5"),
+    ("japanese", "ja-JP,ja;q=0.5"),
+    ("koreana", "ko-KR,ko;q=0.5"),
+    ("schinese", "zh-CN,zh;q=0.5"),
+    ("tchinese", "zh-TW,zh;q=0.5"),
+];
 
 pub struct SteamApi {
     file_details_cache: Cache<WorkshopFileDetails>,
     is_collection_cache: Cache<bool>,
-    collection_details_cache: Cache<Vec<WorkshopFileDetails>>,
-    scraping_rate_limiter: RateLimiter,
+    collection_details_cache: Cache<Vec<CollectionMod>>,
+    collection_info_cache: Cache<CollectionInfo>,
+    changelog_cache: Cache<Vec<ChangelogEntry>>,
+    rate_limiter: BucketRateLimiter,
+    language: String,
 }
 
 impl SteamApi {
-    pub fn new() -> Self {
-        Self {
+    pub fn new(rate_limits: RateLimitConfig) -> Self {
+        let mut api = Self {
             file_details_cache: Cache::new(Duration::from_secs(3600)), // 1 hour
             is_collection_cache: Cache::new(Duration::from_secs(3600)), // 1 hour
             collection_details_cache: Cache::new(Duration::from_secs(3600)), // 1 hour
-            scraping_rate_limiter: RateLimiter::new(Duration::from_millis(2000)), // 2 seconds
+            collection_info_cache: Cache::new(Duration::from_secs(3600)), // 1 hour
+            changelog_cache: Cache::new(Duration::from_secs(3600)), // 1 hour
+            rate_limiter: BucketRateLimiter::new(rate_limits),
+            language: "english".to_string(),
+        };
+        if let Some(path) = DISK_CACHE_PATH.get() {
+            api.load_disk_cache(path, disk_cache_ttl());
+        }
+        api
+    }
+
+    /// Wait until a request to the given bucket is allowed
+    pub async fn wait_rate_limit(&mut self, bucket: RateLimitBucket) {
+        self.rate_limiter.wait(bucket).await;
+    }
+
+    fn file_details_key(language: &str, mod_id: &str) -> String {
+        format!("file-details-{}-{}", language, mod_id)
+    }
+
+    /// Load persisted file details, skipping entries older than `ttl`
+    fn load_disk_cache(&mut self, path: &Path, ttl: Duration) {
+        let persisted: PersistedCache = match std::fs::read_to_string(path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(persisted) => persisted,
+                Err(e) => {
+                    eprintln!("[SteamApi] Ignoring unreadable API cache {:?}: {}", path, e);
+                    return;
+                }
+            },
+            Err(_) => return, // No cache yet
+        };
+
+        let total = persisted.entries.len();
+        let mut loaded = 0;
+        for (mod_id, entry) in persisted.entries {
+            let stored_at = UNIX_EPOCH + Duration::from_secs(entry.cached_at);
+            let key = Self::file_details_key(&entry.language, &mod_id);
+            if self.file_details_cache.restore(key, entry.details, stored_at, ttl) {
+                loaded += 1;
+            }
+        }
+        eprintln!("[SteamApi] Loaded {} of {} cached file details from {:?}", loaded, total, path);
+    }
+
+    /// File details cached in memory, in their on-disk form
+    /// When a mod is cached in several languages, the most recent entry is kept
+    fn persisted_cache(&self) -> PersistedCache {
+        let mut persisted = PersistedCache::default();
+        for (key, details, stored_at) in self.file_details_cache.entries() {
+            let Some(language) = key.strip_prefix("file-details-")
+                .and_then(|rest| rest.strip_suffix(details.publishedfileid.as_str()))
+                .and_then(|rest| rest.strip_suffix('-')) else {
+                continue;
+            };
+            let cached_at = stored_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+            if persisted.entries.get(&details.publishedfileid).is_some_and(|e| e.cached_at >= cached_at) {
+                continue;
+            }
+            persisted.entries.insert(details.publishedfileid.clone(), PersistedFileDetails {
+                language: language.to_string(),
+                cached_at,
+                details: details.clone(),
+            });
+        }
+        persisted
+    }
+
+    /// Persist cached file details so they survive a restart
+    pub fn save_disk_cache(&self) -> Result<(), String> {
+        match self.disk_cache_snapshot() {
+            Some(snapshot) => snapshot.save(),
+            None => Ok(()),
+        }
+    }
+
+    /// Copy of the cached file details to persist, so the file can be written without holding the API lock
+    /// None if no disk cache is configured
+    pub fn disk_cache_snapshot(&self) -> Option<DiskCacheSnapshot> {
+        DISK_CACHE_PATH.get().map(|path| DiskCacheSnapshot {
+            path: path.clone(),
+            cache: self.persisted_cache(),
+        })
+    }
+
+    /// Drop every cached API response, in memory and on disk
+    pub fn clear_cache(&mut self) -> Result<(), String> {
+        self.file_details_cache.clear();
+        self.is_collection_cache.clear();
+        self.collection_details_cache.clear();
+        self.collection_info_cache.clear();
+        self.changelog_cache.clear();
+
+        if let Some(path) = DISK_CACHE_PATH.get() {
+            if path.exists() {
+                std::fs::remove_file(path)
+                    .map_err(|e| format!("Failed to remove API cache file: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether a Steam language name is supported
+    pub fn is_supported_language(lang: &str) -> bool {
+        STEAM_LANGUAGES.iter().any(|(name, _)| *name == lang)
+    }
+
+    /// Set the language used for Workshop titles and descriptions
+    /// Cached entries are keyed per language, so switching never serves stale data
+    pub fn set_language(&mut self, lang: &str) -> Result<(), String> {
+        let lang = lang.trim().to_lowercase();
+        if !Self::is_supported_language(&lang) {
+            return Err(format!("Unsupported language: {}", lang));
         }
+        self.language = lang;
+        Ok(())
+    }
+
+    /// Get the language currently used for Steam requests
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Accept-Language header value for the current language
+    fn accept_language(&self) -> &'static str {
+        STEAM_LANGUAGES.iter()
+            .find(|(name, _)| *name == self.language)
+            .map(|(_, header)| *header)
+            .unwrap_or("en-US,en;q=0.5")
     }
 
     /// Get file details from Steam Workshop
     pub async fn get_file_details(&mut self, mod_id: &str) -> Result<WorkshopFileDetails, Box<dyn std::error::Error>> {
         // Check cache first
-        let cache_key = format!("file-details-{}", mod_id);
+        let cache_key = Self::file_details_key(&self.language, mod_id);
         if let Some(cached) = self.file_details_cache.get(&cache_key) {
             return Ok(cached.clone());
         }
 
-        let url = format!("{}/ISteamRemoteStorage/GetPublishedFileDetails/v0001/", STEAM_API_BASE);
-        let client = reqwest::Client::new();
+        let url = format!("{}/ISteamRemoteStorage/GetPublishedFileDetails/v0001/", api_base_url());
+        let client = http_client();
+        let api_key = network_config().api_key;
         
         let mut params = std::collections::HashMap::new();
         params.insert("itemcount", "1");
         params.insert("publishedfileids[0]", mod_id);
         params.insert("format", "json");
+        params.insert("l", self.language.as_str());
+        if let Some(key) = &api_key {
+            params.insert("key", key);
+        }
 
+        self.rate_limiter.wait(RateLimitBucket::Api).await;
         let response = client
             .post(&url)
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("User-Agent", USER_AGENT)
             .header("Accept", "application/json")
+            .header("Accept-Language", self.accept_language())
             .form(&params)
             .send()
             .await?;
@@ -60,9 +425,49 @@ impl SteamApi {
 
         let details: WorkshopFileDetails = serde_json::from_value(file_details.clone())?;
 
-        // Cache the result
-        self.file_details_cache.set(cache_key, details.clone(), None);
+        // Cache the result, for as long as it is kept on disk
+        self.file_details_cache.set(cache_key, details.clone(), Some(disk_cache_ttl()));
+
+        Ok(details)
+    }
+
+    /// File details cached for the current language, without querying Steam
+    pub fn cached_file_details(&mut self, mod_id: &str) -> Option<WorkshopFileDetails> {
+        let cache_key = Self::file_details_key(&self.language, mod_id);
+        self.file_details_cache.get(&cache_key).cloned()
+    }
 
+    /// Split `mod_ids` into the file details cached for the current language and the ids that have to be fetched
+    pub fn split_cached_file_details(&mut self, mod_ids: &[String]) -> (Vec<WorkshopFileDetails>, Vec<String>) {
+        let mut cached = Vec::new();
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+        for mod_id in mod_ids.iter().filter(|mod_id| seen.insert(mod_id.as_str())) {
+            match self.cached_file_details(mod_id) {
+                Some(details) => cached.push(details),
+                None => missing.push(mod_id.clone()),
+            }
+        }
+        (cached, missing)
+    }
+
+    /// Cache file details fetched in `language`
+    pub fn cache_file_details(&mut self, language: &str, details: &[WorkshopFileDetails]) {
+        for detail in details {
+            let cache_key = Self::file_details_key(language, &detail.publishedfileid);
+            self.file_details_cache.set(cache_key, detail.clone(), Some(disk_cache_ttl()));
+        }
+    }
+
+    /// File details of several mods in one request, cached details are not fetched again
+    pub async fn query_file_details_batch(&mut self, mod_ids: &[String]) -> Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error + Send + Sync>> {
+        let (mut details, missing) = self.split_cached_file_details(mod_ids);
+        if !missing.is_empty() {
+            let fetched = crate::core::mod_scanner::fetch_mod_batch(&missing, &self.language, 0).await?;
+            let language = self.language.clone();
+            self.cache_file_details(&language, &fetched);
+            details.extend(fetched);
+        }
         Ok(details)
     }
 
@@ -93,15 +498,16 @@ impl SteamApi {
 
     /// Scrape Steam Workshop page to check if it's a collection
     pub async fn scrape_is_collection(&mut self, mod_id: &str) -> Result<bool, Box<dyn std::error::Error>> {
-        let workshop_url = format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", mod_id);
+        let workshop_url = format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}&l={}", mod_id, self.language);
+        let accept_language = self.accept_language();
         
-        let page_html = self.scraping_rate_limiter.execute(|| async {
-            let client = reqwest::Client::new();
+        let page_html = self.rate_limiter.execute(RateLimitBucket::Scraping, || async {
+            let client = http_client();
             let response = client
                 .get(&workshop_url)
                 .header("User-Agent", USER_AGENT)
                 .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
-                .header("Accept-Language", "en-US,en;q=0.5")
+                .header("Accept-Language", accept_language)
                 .send()
                 .await?;
             Ok::<String, Box<dyn std::error::Error>>(response.text().await?)
@@ -116,24 +522,68 @@ impl SteamApi {
         Ok(has_main_contents_collection || has_collection_header || has_subscribe_collection_btn || has_subscribe_all_btn)
     }
 
-    /// Get collection details (list of mods in collection)
-    pub async fn get_collection_details(&mut self, collection_id: &str) -> Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error>> {
+    /// Get all mods of a collection, including the mods of nested collections up to `max_depth` levels deep
+    /// Nested collections themselves are not returned, and every mod is listed once
+    pub async fn get_collection_details(&mut self, collection_id: &str, max_depth: usize) -> Result<Vec<CollectionMod>, Box<dyn std::error::Error>> {
         // Check cache first
-        let cache_key = format!("collection-details-{}", collection_id);
+        let cache_key = format!("collection-details-{}-{}-{}", self.language, max_depth, collection_id);
         if let Some(cached) = self.collection_details_cache.get(&cache_key) {
             return Ok(cached.clone());
         }
 
+        let mods = resolve_collection_mods(self, collection_id, max_depth, Self::fetch_collection_children).await?;
+
+        // Cache the result
+        self.collection_details_cache.set(cache_key, mods.clone(), None);
+
+        Ok(mods)
+    }
+
+    /// Get the title, description, author and size of a collection
+    pub async fn get_collection_info(&mut self, collection_id: &str) -> Result<CollectionInfo, Box<dyn std::error::Error>> {
+        // Check cache first
+        let cache_key = format!("collection-info-{}-{}", self.language, collection_id);
+        if let Some(cached) = self.collection_info_cache.get(&cache_key) {
+            return Ok(cached.clone());
+        }
+
+        // A collection is a published file itself, so it takes the same path as mod details
+        let details = match self.query_file_details_batch(&[collection_id.to_string()]).await {
+            Ok(mut details) if !details.is_empty() => details.remove(0),
+            _ => self.get_file_details(collection_id).await?,
+        };
+        if details.file_type != 2 && !self.is_collection(collection_id).await? {
+            return Err(format!("Workshop item {} is not a collection", collection_id).into());
+        }
+
+        // num_children is missing from some responses, the collection page lists the items as well
+        let item_count = match usize::try_from(details.num_children) {
+            Ok(count) if count > 0 => count,
+            _ => self.scrape_collection_mod_ids(collection_id).await?.len(),
+        };
+        let info = CollectionInfo::from_details(&details, item_count);
+
+        // Cache the result
+        self.collection_info_cache.set(cache_key, info.clone(), None);
+
+        Ok(info)
+    }
+
+    fn fetch_collection_children(&mut self, collection_id: String) -> BoxFuture<'_, Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error>>> {
+        Box::pin(async move { self.get_collection_children(&collection_id).await })
+    }
+
+    /// Get details of the items directly contained in a collection
+    async fn get_collection_children(&mut self, collection_id: &str) -> Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error>> {
         // Scrape collection page to get mod IDs
         let mod_ids = self.scrape_collection_mod_ids(collection_id).await?;
 
         if mod_ids.is_empty() {
-            self.collection_details_cache.set(cache_key, vec![], None);
             return Ok(vec![]);
         }
 
         // Fetch details for all mods in collection using batch query
-        let all_details = match crate::core::mod_scanner::query_mod_batch(&mod_ids, 0).await {
+        let all_details = match self.query_file_details_batch(&mod_ids).await {
             Ok(details) => details,
             Err(_) => {
                 // Fallback to individual queries if batch fails
@@ -147,23 +597,56 @@ impl SteamApi {
             }
         };
 
+        Ok(all_details)
+    }
+
+    /// Get the change notes of a Workshop item, newest first
+    /// The Web API doesn't return change notes, so they are scraped from the item's changelog page
+    pub async fn get_changelog(&mut self, mod_id: &str) -> Result<Vec<ChangelogEntry>, Box<dyn std::error::Error>> {
+        // Check cache first
+        let cache_key = format!("changelog-{}-{}", self.language, mod_id);
+        if let Some(cached) = self.changelog_cache.get(&cache_key) {
+            return Ok(cached.clone());
+        }
+
+        let changelog_url = format!("https://steamcommunity.com/sharedfiles/filedetails/changelog/{}?l={}", mod_id, self.language);
+        let accept_language = self.accept_language();
+        
+        let page_html = self.rate_limiter.execute(RateLimitBucket::Scraping, || async {
+            let client = http_client();
+            let response = client
+                .get(&changelog_url)
+                .header("User-Agent", USER_AGENT)
+                .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
+                .header("Accept-Language", accept_language)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(format!("Steam Workshop error: {}", response.status()).into());
+            }
+            Ok::<String, Box<dyn std::error::Error>>(response.text().await?)
+        }).await?;
+
+        let entries = parse_changelog(&page_html)?;
+
         // Cache the result
-        self.collection_details_cache.set(cache_key, all_details.clone(), None);
+        self.changelog_cache.set(cache_key, entries.clone(), None);
 
-        Ok(all_details)
+        Ok(entries)
     }
 
     /// Scrape collection page to extract mod IDs
     pub async fn scrape_collection_mod_ids(&mut self, collection_id: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        let workshop_url = format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", collection_id);
+        let workshop_url = format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}&l={}", collection_id, self.language);
+        let accept_language = self.accept_language();
         
-        let page_html = self.scraping_rate_limiter.execute(|| async {
-            let client = reqwest::Client::new();
+        let page_html = self.rate_limiter.execute(RateLimitBucket::Scraping, || async {
+            let client = http_client();
             let response = client
                 .get(&workshop_url)
                 .header("User-Agent", USER_AGENT)
                 .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
-                .header("Accept-Language", "en-US,en;q=0.5")
+                .header("Accept-Language", accept_language)
                 .send()
                 .await?;
             Ok::<String, Box<dyn std::error::Error>>(response.text().await?)
@@ -187,9 +670,358 @@ impl SteamApi {
     }
 }
 
+/// Parse the entries of a changelog page, newest first
+/// Each update is a `<p id="<unix time>">` element, line breaks are kept and other markup is dropped
+fn parse_changelog(page_html: &str) -> Result<Vec<ChangelogEntry>, Box<dyn std::error::Error>> {
+    let entry_re = regex::Regex::new(r#"(?s)<p id="(\d+)"[^>]*>(.*?)</p>"#)?;
+    let br_re = regex::Regex::new(r"(?i)<br\s*/?>")?;
+    let tag_re = regex::Regex::new(r"<[^>]+>")?;
+
+    let mut entries: Vec<ChangelogEntry> = entry_re.captures_iter(page_html)
+        .filter_map(|cap| {
+            let timestamp = cap[1].parse().ok()?;
+            let text = br_re.replace_all(&cap[2], "\n");
+            let text = tag_re.replace_all(&text, "")
+                .replace("&quot;", "\"")
+                .replace("&#39;", "'")
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&nbsp;", " ")
+                .replace("&amp;", "&");
+            let text = text.lines().map(str::trim).collect::<Vec<_>>().join("\n").trim().to_string();
+            Some(ChangelogEntry { timestamp, text })
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.timestamp));
+    Ok(entries)
+}
+
+impl SteamApi {
+    /// Check whether the Steam Web API, the Workshop API and a content server respond
+    /// Probes run in parallel, each with a short timeout
+    pub async fn get_status() -> SteamStatus {
+        let client = match build_http_client(&network_config(), Some(STATUS_PROBE_TIMEOUT)) {
+            Ok(client) => client,
+            Err(e) => {
+                eprintln!("[SteamApi] Failed to create HTTP client for status check: {}", e);
+                return SteamStatus { api_up: false, workshop_up: false, content_servers_reachable: false };
+            }
+        };
+
+        let (api_up, workshop_up, content_servers_reachable) = tokio::join!(
+            Self::probe_api(&client),
+            Self::probe_workshop(&client),
+            Self::probe_content_servers(&client),
+        );
+
+        SteamStatus { api_up, workshop_up, content_servers_reachable }
+    }
+
+    /// Steam Web API health: GetServerInfo answers without an API key
+    async fn probe_api(client: &reqwest::Client) -> bool {
+        let url = format!("{}/ISteamWebAPIUtil/GetServerInfo/v0001/", api_base_url());
+        match client.get(&url).header("User-Agent", USER_AGENT).send().await {
+            Ok(response) => response.status().is_success(),
+            Err(e) => {
+                eprintln!("[SteamApi] Steam API probe failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Workshop API health: details of a well-known mod must be returned
+    async fn probe_workshop(client: &reqwest::Client) -> bool {
+        let url = format!("{}/ISteamRemoteStorage/GetPublishedFileDetails/v0001/", api_base_url());
+        let params = [
+            ("itemcount", "1"),
+            ("publishedfileids[0]", STATUS_PROBE_MOD_ID),
+            ("format", "json"),
+        ];
+
+        let response = match client.post(&url).header("User-Agent", USER_AGENT).form(&params).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                eprintln!("[SteamApi] Workshop probe returned status {}", response.status());
+                return false;
+            }
+            Err(e) => {
+                eprintln!("[SteamApi] Workshop probe failed: {}", e);
+                return false;
+            }
+        };
+
+        match response.json::<serde_json::Value>().await {
+            Ok(data) => data["response"]["publishedfiledetails"][0]["result"].as_i64() == Some(1),
+            Err(e) => {
+                eprintln!("[SteamApi] Workshop probe returned invalid JSON: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Content server reachability: ask the directory for servers and connect to the first one
+    /// Any HTTP response counts, content servers answer unknown paths with an error status
+    async fn probe_content_servers(client: &reqwest::Client) -> bool {
+        let url = format!("{}/IContentServerDirectoryService/GetServersForSteamPipe/v1/?cell_id=0", api_base_url());
+        let data = match client.get(&url).header("User-Agent", USER_AGENT).send().await {
+            Ok(response) if response.status().is_success() => response.json::<serde_json::Value>().await.ok(),
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("[SteamApi] Content server directory probe failed: {}", e);
+                None
+            }
+        };
+
+        let host = data.as_ref()
+            .and_then(|d| d["response"]["servers"].as_array())
+            .and_then(|servers| servers.iter().find_map(|s| s["host"].as_str()))
+            .map(|h| h.to_string());
+
+        match host {
+            Some(host) => match client.head(format!("https://{}/", host)).header("User-Agent", USER_AGENT).send().await {
+                Ok(_) => true,
+                Err(e) => {
+                    eprintln!("[SteamApi] Content server {} unreachable: {}", host, e);
+                    false
+                }
+            },
+            None => false,
+        }
+    }
+}
+
 impl Default for SteamApi {
     fn default() -> Self {
-        Self::new()
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+/// Breadth-first walk of a collection and its nested collections up to `max_depth` levels deep
+/// `fetch_children` returns the items directly contained in a collection; collections referencing each other
+/// are fetched once and every mod is listed once
+async fn resolve_collection_mods<C, F>(
+    context: &mut C,
+    collection_id: &str,
+    max_depth: usize,
+    fetch_children: F,
+) -> Result<Vec<CollectionMod>, Box<dyn std::error::Error>>
+where
+    F: for<'a> Fn(&'a mut C, String) -> BoxFuture<'a, Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error>>>,
+{
+    let mut mods = Vec::new();
+    let mut seen_mods = std::collections::HashSet::new();
+    // Collections already queued, so collections referencing each other are resolved once
+    let mut visited = std::collections::HashSet::from([collection_id.to_string()]);
+    let mut queue = std::collections::VecDeque::from([(collection_id.to_string(), 0)]);
+
+    while let Some((current_id, depth)) = queue.pop_front() {
+        let children = match fetch_children(context, current_id.clone()).await {
+            Ok(children) => children,
+            // Only the requested collection itself must resolve
+            Err(e) if depth > 0 => {
+                eprintln!("[SteamApi] Skipping nested collection {}: {}", current_id, e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+
+        for details in children {
+            if details.file_type == 2 {
+                if depth + 1 > max_depth {
+                    eprintln!("[SteamApi] Not resolving collection {} nested deeper than {} levels", details.publishedfileid, max_depth);
+                } else if visited.insert(details.publishedfileid.clone()) {
+                    queue.push_back((details.publishedfileid.clone(), depth + 1));
+                }
+                continue;
+            }
+            if seen_mods.insert(details.publishedfileid.clone()) {
+                mods.push(CollectionMod {
+                    details,
+                    parent_collection_id: current_id.clone(),
+                });
+            }
+        }
     }
+
+    Ok(mods)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mod_scanner::create_workshop_file_details;
+
+    #[test]
+    fn test_disk_cache_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("api-cache.json");
+
+        let mut api = SteamApi::new(RateLimitConfig::default());
+        api.file_details_cache.set(
+            SteamApi::file_details_key("german", "123"),
+            create_workshop_file_details("123", "Mod".to_string(), 42),
+            None,
+        );
+        write_json_atomic(&path, &api.persisted_cache()).unwrap();
+
+        let mut restored = SteamApi::new(RateLimitConfig::default());
+        restored.load_disk_cache(&path, DEFAULT_DISK_CACHE_TTL);
+        let details = restored.file_details_cache.get(&SteamApi::file_details_key("german", "123")).unwrap();
+        assert_eq!(details.title, "Mod");
+        assert_eq!(details.time_updated, 42);
+
+        // Entries older than the TTL are ignored on load
+        let mut stale = SteamApi::new(RateLimitConfig::default());
+        stale.load_disk_cache(&path, Duration::ZERO);
+        assert!(stale.file_details_cache.get(&SteamApi::file_details_key("german", "123")).is_none());
+    }
+
+    /// Collection contents for `resolve_collection_mods`, with the collections fetched so far
+    struct FakeCollections {
+        children: HashMap<String, Vec<WorkshopFileDetails>>,
+        fetched: Vec<String>,
+    }
+
+    fn fake_item(id: &str, is_collection: bool) -> WorkshopFileDetails {
+        let mut details = create_workshop_file_details(id, id.to_string(), 0);
+        if is_collection {
+            details.file_type = 2;
+        }
+        details
+    }
+
+    fn fetch_fake_children(fake: &mut FakeCollections, collection_id: String) -> BoxFuture<'_, Result<Vec<WorkshopFileDetails>, Box<dyn std::error::Error>>> {
+        Box::pin(async move {
+            fake.fetched.push(collection_id.clone());
+            Ok(fake.children.get(&collection_id).cloned().unwrap_or_default())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_resolve_collection_cycle() {
+        // A contains B, which contains A again
+        let mut fake = FakeCollections {
+            children: HashMap::from([
+                ("A".to_string(), vec![fake_item("1", false), fake_item("B", true)]),
+                ("B".to_string(), vec![fake_item("A", true), fake_item("1", false), fake_item("2", false)]),
+            ]),
+            fetched: Vec::new(),
+        };
+
+        let mods = resolve_collection_mods(&mut fake, "A", 5, fetch_fake_children).await.unwrap();
+        assert_eq!(fake.fetched, vec!["A".to_string(), "B".to_string()]);
+        let ids: Vec<(&str, &str)> = mods.iter()
+            .map(|m| (m.details.publishedfileid.as_str(), m.parent_collection_id.as_str()))
+            .collect();
+        assert_eq!(ids, vec![("1", "A"), ("2", "B")]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_collection_max_depth() {
+        // A > B > C, each with a mod of its own
+        let mut fake = FakeCollections {
+            children: HashMap::from([
+                ("A".to_string(), vec![fake_item("1", false), fake_item("B", true)]),
+                ("B".to_string(), vec![fake_item("2", false), fake_item("C", true)]),
+                ("C".to_string(), vec![fake_item("3", false)]),
+            ]),
+            fetched: Vec::new(),
+        };
+
+        let mods = resolve_collection_mods(&mut fake, "A", 1, fetch_fake_children).await.unwrap();
+        assert_eq!(fake.fetched, vec!["A".to_string(), "B".to_string()]);
+        let ids: Vec<&str> = mods.iter().map(|m| m.details.publishedfileid.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+
+        // Depth 0 only lists the collection's own mods
+        fake.fetched.clear();
+        let mods = resolve_collection_mods(&mut fake, "A", 0, fetch_fake_children).await.unwrap();
+        assert_eq!(fake.fetched, vec!["A".to_string()]);
+        assert_eq!(mods.len(), 1);
+    }
+
+    #[test]
+    fn test_split_cached_file_details() {
+        let mut api = SteamApi::new(RateLimitConfig::default());
+        api.cache_file_details("english", &[create_workshop_file_details("123", "Mod".to_string(), 42)]);
+        api.cache_file_details("german", &[create_workshop_file_details("456", "Mod".to_string(), 42)]);
+
+        let ids = vec!["123".to_string(), "456".to_string(), "123".to_string()];
+        let (cached, missing) = api.split_cached_file_details(&ids);
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].publishedfileid, "123");
+        // Details cached in another language are fetched again
+        assert_eq!(missing, vec!["456".to_string()]);
+    }
+
+    #[test]
+    fn test_normalize_network_config() {
+        let invalid_proxy = NetworkConfig { proxy: Some("not a url".to_string()), ..Default::default() };
+        assert!(invalid_proxy.normalized().is_err());
+        let invalid_base = NetworkConfig { api_base_url: Some("steam mirror".to_string()), ..Default::default() };
+        assert!(invalid_base.normalized().is_err());
+
+        let config = NetworkConfig {
+            proxy: Some(" http://proxy.local:3128 ".to_string()),
+            api_base_url: Some("https://steam-mirror.local/".to_string()),
+            api_key: Some(String::new()),
+        };
+        assert_eq!(config.normalized().unwrap(), NetworkConfig {
+            proxy: Some("http://proxy.local:3128".to_string()),
+            api_base_url: Some("https://steam-mirror.local".to_string()),
+            api_key: None,
+        });
+        assert_eq!(NetworkConfig::default().normalized().unwrap(), NetworkConfig::default());
+    }
+
+    #[test]
+    fn test_parse_changelog() {
+        let page_html = r#"
+            <div class="detailBox workshopAnnouncement noFooter changeLogCtn">
+                <div class="changelog headline">Update: 2 Mar @ 9:14am</div>
+                <p id="1709370840" class="">Fixed &quot;Biotech&quot; patch<br>Added 1.5 support</p>
+            </div>
+            <div class="detailBox workshopAnnouncement noFooter changeLogCtn">
+                <div class="changelog headline">Update: 14 Jan @ 6:02pm</div>
+                <p id="1705255320" class=""><b>Initial</b> release &amp; more</p>
+            </div>
+            <div class="detailBox workshopAnnouncement noFooter changeLogCtn">
+                <div class="changelog headline">Update: 15 Jan @ 8:00am</div>
+                <p id="1705305600" class=""></p>
+            </div>"#;
+        assert_eq!(parse_changelog(page_html).unwrap(), vec![
+            ChangelogEntry { timestamp: 1709370840, text: "Fixed \"Biotech\" patch\nAdded 1.5 support".to_string() },
+            ChangelogEntry { timestamp: 1705305600, text: String::new() },
+            ChangelogEntry { timestamp: 1705255320, text: "Initial release & more".to_string() },
+        ]);
+        assert!(parse_changelog("<html></html>").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_collection_info_serialization() {
+        let mut details = create_workshop_file_details("456", "Vanilla Expanded".to_string(), 42);
+        details.creator = "76561198000000000".to_string();
+        details.preview_url = "https://example.com/preview.jpg".to_string();
+        let json = serde_json::to_value(CollectionInfo::from_details(&details, 47)).unwrap();
+        assert_eq!(json, serde_json::json!({
+            "collectionId": "456",
+            "title": "Vanilla Expanded",
+            "description": details.description,
+            "author": "76561198000000000",
+            "itemCount": 47,
+            "previewUrl": "https://example.com/preview.jpg",
+        }));
+    }
+
+    #[test]
+    fn test_collection_mod_serialization() {
+        let item = CollectionMod {
+            details: create_workshop_file_details("123", "Mod".to_string(), 42),
+            parent_collection_id: "456".to_string(),
+        };
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["publishedfileid"], "123");
+        assert_eq!(json["title"], "Mod");
+        assert_eq!(json["parentCollectionId"], "456");
+    }
+}
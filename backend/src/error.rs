@@ -0,0 +1,178 @@
+// Error type returned by Tauri commands
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use crate::core::mod_manager::{InstallError, CANCELLED_ERROR};
+use crate::core::steamcmd_client::DownloadError;
+
+/// Error returned to the UI by every command
+/// Serialized as `{ kind, message, ...context }`, so the UI can branch on `kind` instead of matching message text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppError {
+    /// A file or folder the command needs does not exist
+    PathNotFound { path: PathBuf },
+    /// A path that must be a folder is something else
+    NotADirectory { path: PathBuf },
+    /// An argument was rejected before anything was done
+    InvalidInput { message: String },
+    /// Input that is neither a Workshop ID nor a Workshop link
+    InvalidModId { input: String },
+    /// No usable SteamCMD executable was found
+    SteamCmdMissing { message: String },
+    /// No backup exists for the mod
+    BackupNotFound { path: PathBuf },
+    /// SteamCMD failed to download the mods
+    DownloadFailed { mod_ids: Vec<String>, message: String },
+    /// A mod couldn't be installed, keeps the `kind` and fields of the `InstallError`
+    Install(InstallError),
+    /// The user cancelled the operation
+    Cancelled,
+    /// Any other failure
+    Failed { message: String },
+}
+
+impl AppError {
+    pub fn path_not_found(path: impl AsRef<Path>) -> Self {
+        Self::PathNotFound { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn not_a_directory(path: impl AsRef<Path>) -> Self {
+        Self::NotADirectory { path: path.as_ref().to_path_buf() }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::InvalidInput { message: message.into() }
+    }
+
+    pub fn invalid_mod_id(input: impl Into<String>) -> Self {
+        Self::InvalidModId { input: input.into() }
+    }
+
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self::Failed { message: message.into() }
+    }
+
+    /// Identifier of the variant the UI receives as `kind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::PathNotFound { .. } => "pathNotFound",
+            Self::NotADirectory { .. } => "notADirectory",
+            Self::InvalidInput { .. } => "invalidInput",
+            Self::InvalidModId { .. } => "invalidModId",
+            Self::SteamCmdMissing { .. } => "steamCmdMissing",
+            Self::BackupNotFound { .. } => "backupNotFound",
+            Self::DownloadFailed { .. } => "downloadFailed",
+            Self::Install(error) => error.kind(),
+            Self::Cancelled => "cancelled",
+            Self::Failed { .. } => "failed",
+        }
+    }
+}
+
+impl From<DownloadError> for AppError {
+    fn from(error: DownloadError) -> Self {
+        match error {
+            DownloadError::Cancelled { .. } => Self::Cancelled,
+            error => Self::DownloadFailed { mod_ids: vec![error.mod_id().to_string()], message: error.to_string() },
+        }
+    }
+}
+
+impl From<InstallError> for AppError {
+    fn from(error: InstallError) -> Self {
+        Self::Install(error)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PathNotFound { path } => write!(f, "Path not found: {}", path.display()),
+            Self::NotADirectory { path } => write!(f, "Not a directory: {}", path.display()),
+            Self::InvalidInput { message } | Self::Failed { message } => write!(f, "{}", message),
+            Self::InvalidModId { input } => write!(f, "Not a Workshop mod ID or link: {}", input),
+            Self::SteamCmdMissing { message } => write!(f, "SteamCMD is not available: {}", message),
+            Self::BackupNotFound { path } => write!(f, "Backup not found: {}", path.display()),
+            Self::DownloadFailed { message, .. } => write!(f, "Failed to download mods: {}", message),
+            Self::Install(error) => write!(f, "{}", error),
+            Self::Cancelled => write!(f, "{}", CANCELLED_ERROR),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+// The message is always included so the UI can show any error without knowing its kind
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            Self::PathNotFound { path } | Self::NotADirectory { path } | Self::BackupNotFound { path } => {
+                map.serialize_entry("path", path)?;
+            }
+            Self::InvalidModId { input } => {
+                map.serialize_entry("input", input)?;
+            }
+            Self::DownloadFailed { mod_ids, .. } => {
+                map.serialize_entry("modIds", mod_ids)?;
+            }
+            Self::Install(InstallError::CorruptedModConflict { folder_name, mod_id, mod_title }) => {
+                map.serialize_entry("folderName", folder_name)?;
+                map.serialize_entry("modId", mod_id)?;
+                map.serialize_entry("modTitle", mod_title)?;
+            }
+            Self::Install(InstallError::Downgrade { mod_id, local_time_updated, remote_time_updated }) => {
+                map.serialize_entry("modId", mod_id)?;
+                map.serialize_entry("localTimeUpdated", local_time_updated)?;
+                map.serialize_entry("remoteTimeUpdated", remote_time_updated)?;
+            }
+            Self::Install(InstallError::Busy { mod_path }) => {
+                map.serialize_entry("modPath", mod_path)?;
+            }
+            Self::Install(InstallError::Cancelled | InstallError::Failed { .. })
+            | Self::InvalidInput { .. } | Self::SteamCmdMissing { .. } | Self::Cancelled | Self::Failed { .. } => {}
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_app_error() {
+        let error = AppError::BackupNotFound { path: PathBuf::from("backups/Harmony") };
+        assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({
+            "kind": "backupNotFound",
+            "message": "Backup not found: backups/Harmony",
+            "path": "backups/Harmony",
+        }));
+
+        let error = AppError::from(InstallError::CorruptedModConflict {
+            folder_name: "Harmony".to_string(),
+            mod_id: "2009463077".to_string(),
+            mod_title: "Harmony".to_string(),
+        });
+        assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({
+            "kind": "corruptedModConflict",
+            "message": "Existing folder \"Harmony\" of mod 2009463077 is corrupted",
+            "folderName": "Harmony",
+            "modId": "2009463077",
+            "modTitle": "Harmony",
+        }));
+
+        assert_eq!(AppError::from(InstallError::Cancelled).kind(), "cancelled");
+        assert_eq!(AppError::from(DownloadError::Cancelled { mod_id: "1".to_string() }), AppError::Cancelled);
+
+        // Messages of core functions reach the UI unchanged
+        let error = AppError::failed("Failed to read mods directory: denied");
+        assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({
+            "kind": "failed",
+            "message": "Failed to read mods directory: denied",
+        }));
+    }
+}
@@ -1,6 +1,8 @@
 pub mod core;
 pub mod services;
 pub mod commands;
+pub mod cli;
+pub mod error;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -21,10 +23,29 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .on_window_event(core::shutdown::handle_window_event)
         .invoke_handler(tauri::generate_handler![
             commands::query_mods,
+            commands::query_mods_with_timing,
             commands::list_installed_mods,
+            commands::set_scan_concurrency,
+            commands::set_install_concurrency,
+            commands::get_mod_size,
+            commands::get_mod_thumbnail,
+            commands::get_mod_sizes,
+            commands::verify_mod_checksums,
             commands::update_mod_details,
+            commands::validate_about_metadata,
+            commands::check_version_compatibility,
+            commands::check_mod_update,
+            commands::find_duplicate_mods,
+            commands::find_packageid_collisions,
+            commands::scan_corrupted_mods,
+            commands::get_dependency_report,
+            commands::detect_load_conflicts,
+            commands::normalize_about_folder_case,
+            commands::normalize_published_file_ids,
+            commands::backfill_published_file_ids,
             commands::update_mods,
             commands::cancel_update_mods,
             commands::check_update_cancelled,
@@ -33,24 +54,95 @@ pub fn run() {
             commands::check_backups,
             commands::restore_backup,
             commands::restore_backups,
+            commands::delete_mod,
+            commands::rename_mod_folder,
+            commands::set_mod_enabled,
+            commands::list_disabled_mods,
+            commands::set_backup_mode,
+            commands::set_backup_format,
+            commands::list_backups,
+            commands::prune_backups,
             commands::ignore_update,
             commands::undo_ignore_update,
             commands::check_ignored_updates,
+            commands::list_ignored,
+            commands::ignore,
+            commands::unignore,
             commands::get_file_details,
             commands::get_file_details_batch,
             commands::is_collection,
             commands::is_collection_batch,
+            commands::get_changelog,
             commands::get_collection_details,
+            commands::get_collection_info,
             commands::get_collection_details_batch,
+            commands::diff_collection,
+            commands::set_display_language,
+            commands::get_display_language,
+            commands::get_steam_status,
+            commands::clear_cache,
+            commands::set_api_cache_ttl,
+            commands::set_scrape_concurrency,
+            commands::set_network_config,
+            commands::get_network_config,
             commands::download_mod,
-            commands::continue_download_with_decision,
+            commands::cancel_download,
+            commands::resolve_corrupted_conflict,
+            commands::revalidate_mods,
+            commands::repair_mods,
+            commands::get_problem_mods,
+            commands::get_instance_statuses,
+            commands::set_install_filter,
+            commands::get_install_filter,
             commands::start_mod_watcher,
             commands::stop_mod_watcher,
+            commands::set_watcher_debounce,
+            commands::reconcile_mods,
+            commands::start_backup_watcher,
+            commands::stop_backup_watcher,
             commands::export_mods_to_clipboard,
+            commands::export_mod_list,
+            commands::import_mod_list,
+            commands::validate_store,
+            commands::reset_store_to_defaults,
+            commands::validate_mods_path,
+            commands::schedule_bulk_download,
+            commands::list_scheduled_jobs,
+            commands::cancel_scheduled_job,
+            commands::cleanup_stuck_steamcmd,
+            commands::set_steamcmd_path,
+            commands::get_steamcmd_path,
+            commands::check_steamcmd_health,
+            commands::reinstall_steamcmd,
+            commands::set_download_dir,
+            commands::get_download_dir,
+            commands::set_download_timeouts,
+            commands::set_download_retries,
+            commands::set_app_id,
+            commands::configure_downloader,
+            commands::set_max_memory_usage,
+            commands::set_steam_credentials,
+            commands::submit_steam_guard_code,
+            commands::get_last_download_log,
+            commands::set_steamcmd_verbose_log,
+            commands::get_active_mods,
+            commands::set_active_mods,
+            commands::open_mod_folder,
+            commands::open_workshop_page,
         ])
-        .setup(|_app| {
+        .setup(|app| {
+            services::init_api_cache(app.handle());
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                services::apply_backend_config(&app_handle).await;
+            });
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                services::save_api_cache_blocking();
+            }
+        });
 }
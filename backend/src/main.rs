@@ -2,5 +2,32 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // `--cli` runs headless, without opening a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--cli") {
+        attach_parent_console();
+        std::process::exit(rimworld_workshop_downloader_lib::cli::run(&args[1..]));
+    }
+
     rimworld_workshop_downloader_lib::run()
 }
+
+/// Write `--cli` output to the console the app was started from
+/// Release builds use the windows subsystem, so Windows doesn't give them a console of their own
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Fails when started without a console (e.g. from Explorer) or when one is attached already, both are fine
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
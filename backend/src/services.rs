@@ -1,20 +1,63 @@
 // Common services and utilities for commands
 
 use std::path::{Path, PathBuf};
-use crate::core::{SteamApi, Downloader, mod_watcher::ModWatcher};
-use std::sync::{Arc, OnceLock, atomic::{AtomicBool, Ordering}};
+use crate::core::{SteamApi, Downloader, mod_watcher::ModWatcher, backup_watcher::BackupWatcher};
+use std::sync::{Arc, OnceLock, atomic::{AtomicBool, AtomicUsize, Ordering}};
 use tokio::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use crate::core::failure_history::FailureHistory;
+use crate::core::scheduler::{JobScheduler, ScheduledJob};
+use crate::core::mod_scanner::{find_about_dir, write_mod_file};
+use crate::core::mod_manager::{BackupFormat, BackupMode, InstallFilter, InstallOptions, LinkMode};
+use crate::core::api_rate_limiter::RateLimitConfig;
+use crate::core::workshop_client::NetworkConfig;
+use crate::core::settings_store::{default_settings, ignored_mod_ids, set_mod_ignored, SETTINGS_KEY, SETTINGS_STORE_NAME};
+use crate::error::AppError;
 
 // Shared instances for stateful services
 static STEAM_API: OnceLock<Arc<Mutex<SteamApi>>> = OnceLock::new();
 static DOWNLOADER: OnceLock<Arc<Mutex<Downloader>>> = OnceLock::new();
 static MOD_WATCHER: OnceLock<Arc<Mutex<ModWatcher>>> = OnceLock::new();
+static BACKUP_WATCHER: OnceLock<Arc<Mutex<BackupWatcher>>> = OnceLock::new();
 static UPDATE_CANCEL_FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+// Number of updates and installs running, closing the app waits for them
+static UPDATES_IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+static JOB_SCHEDULER: OnceLock<Arc<Mutex<JobScheduler>>> = OnceLock::new();
+
+// Persisted download history (tauri store)
+const DOWNLOAD_HISTORY_STORE: &str = "download-history.json";
+const FAILURE_HISTORY_KEY: &str = "download-failures";
+const JOB_HISTORY_KEY: &str = "scheduled-jobs";
+const MAX_JOB_HISTORY: usize = 50;
+// Persisted backend configuration (tauri store)
+const BACKEND_CONFIG_STORE: &str = "backend-config.json";
+const STEAMCMD_PATH_KEY: &str = "steamcmd-path";
+const DOWNLOAD_DIR_KEY: &str = "download-dir";
+const MAX_MEMORY_USAGE_KEY: &str = "max-memory-usage";
+const STEAMCMD_VERBOSE_LOG_KEY: &str = "steamcmd-verbose-log";
+const API_CACHE_TTL_KEY: &str = "api-cache-ttl-secs";
+const DOWNLOAD_THROTTLE_KEY: &str = "download-throttle-kbps";
+const THROTTLED_MAX_INSTANCES_KEY: &str = "throttled-max-instances";
+const BACKUP_MODE_KEY: &str = "backup-mode";
+const BACKUP_FORMAT_KEY: &str = "backup-format";
+const INSTALL_FILTER_KEY: &str = "install-filter";
+const SCAN_CONCURRENCY_KEY: &str = "scan-concurrency";
+const INSTALL_CONCURRENCY_KEY: &str = "install-concurrency";
+const SCRAPE_CONCURRENCY_KEY: &str = "scrape-concurrency";
+const NETWORK_CONFIG_KEY: &str = "network-config";
+const WATCHER_DEBOUNCE_KEY: &str = "watcher-debounce-ms";
+// File details cache persisted between sessions (app data dir)
+const API_CACHE_FILE: &str = "api-cache.json";
+// Serializes read-modify-write of the failure history between parallel SteamCMD instances
+static FAILURE_HISTORY_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+// Serializes read-modify-write of the ignored mods list
+static IGNORED_MODS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
 
 /// Get or initialize the shared SteamApi instance
 pub fn get_steam_api() -> Arc<Mutex<SteamApi>> {
     STEAM_API.get_or_init(|| {
-        Arc::new(Mutex::new(SteamApi::new()))
+        Arc::new(Mutex::new(SteamApi::new(RateLimitConfig::default())))
     }).clone()
 }
 
@@ -32,6 +75,20 @@ pub fn get_mod_watcher() -> Arc<Mutex<ModWatcher>> {
     }).clone()
 }
 
+/// Get or initialize the shared BackupWatcher instance
+pub fn get_backup_watcher() -> Arc<Mutex<BackupWatcher>> {
+    BACKUP_WATCHER.get_or_init(|| {
+        Arc::new(Mutex::new(BackupWatcher::new()))
+    }).clone()
+}
+
+/// Get or initialize the shared JobScheduler instance
+pub fn get_job_scheduler() -> Arc<Mutex<JobScheduler>> {
+    JOB_SCHEDULER.get_or_init(|| {
+        Arc::new(Mutex::new(JobScheduler::new()))
+    }).clone()
+}
+
 /// Get or initialize the shared update cancellation flag
 pub fn get_update_cancel_flag() -> Arc<AtomicBool> {
     UPDATE_CANCEL_FLAG.get_or_init(|| {
@@ -54,21 +111,59 @@ pub fn reset_update_cancel_flag() {
     get_update_cancel_flag().store(false, Ordering::Relaxed);
 }
 
+/// RAII guard that marks an update as running until it is dropped
+pub struct UpdateInProgressGuard(());
+
+impl Drop for UpdateInProgressGuard {
+    fn drop(&mut self) {
+        UPDATES_IN_PROGRESS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Mark an update or install as running, until the returned guard is dropped
+pub fn mark_update_in_progress() -> UpdateInProgressGuard {
+    UPDATES_IN_PROGRESS.fetch_add(1, Ordering::Relaxed);
+    UpdateInProgressGuard(())
+}
+
+/// Check if any update or install is running
+pub fn is_update_in_progress() -> bool {
+    UPDATES_IN_PROGRESS.load(Ordering::Relaxed) > 0
+}
+
 /// Validate that a path exists and is a directory
-pub fn validate_mods_path(path: &str) -> Result<PathBuf, String> {
+pub fn validate_mods_path(path: &str) -> Result<PathBuf, AppError> {
     let path_buf = PathBuf::from(path);
     
     if !path_buf.exists() {
-        return Err(format!("Mods path does not exist: {}", path));
+        return Err(AppError::path_not_found(&path_buf));
     }
     
     if !path_buf.is_dir() {
-        return Err(format!("Mods path is not a directory: {}", path));
+        return Err(AppError::not_a_directory(&path_buf));
     }
     
     Ok(path_buf)
 }
 
+/// Mods folder configured in the frontend settings, None until one is picked
+pub fn load_configured_mods_path(app: &AppHandle) -> Option<PathBuf> {
+    app.store(SETTINGS_STORE_NAME).ok()
+        .and_then(|store| store.get(SETTINGS_KEY))
+        .and_then(|settings| settings.get("modsPath").and_then(|v| v.as_str()).map(|s| s.to_string()))
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// Number of SteamCMD instances configured in the frontend settings, None to use the default
+pub fn load_max_steamcmd_instances(app: &AppHandle) -> Option<usize> {
+    app.store(SETTINGS_STORE_NAME).ok()
+        .and_then(|store| store.get(SETTINGS_KEY))
+        .and_then(|settings| settings.get("maxSteamcmdInstances").and_then(|v| v.as_u64()))
+        .and_then(|instances| usize::try_from(instances).ok())
+        .filter(|instances| *instances > 0)
+}
+
 /// Extract folder name from mod path
 pub fn extract_folder_name(mod_path: &Path) -> Result<String, String> {
     mod_path
@@ -262,7 +357,7 @@ pub async fn fetch_mod_times_updated(mod_ids: &[String]) -> std::collections::Ha
 
 /// Write a timestamp file in mod's About folder (shared implementation for .ignoredupdate and .lastupdated)
 async fn write_mod_timestamp_file(folder_path: PathBuf, filename: String, timestamp: i64) {
-    let about_path = folder_path.join("About");
+    let about_path = find_about_dir(&folder_path);
     let file_path = about_path.join(&filename);
     let time_str = timestamp.to_string();
     
@@ -271,7 +366,7 @@ async fn write_mod_timestamp_file(folder_path: PathBuf, filename: String, timest
             eprintln!("Failed to create About directory: {}", e);
             return;
         }
-        if let Err(e) = std::fs::write(&file_path, time_str) {
+        if let Err(e) = write_mod_file(&file_path, time_str) {
             eprintln!("Failed to write {} file: {}", filename, e);
         }
     }).await.ok();
@@ -287,3 +382,403 @@ pub async fn write_last_updated_file(folder_path: PathBuf, time_updated: i64) {
     write_mod_timestamp_file(folder_path, ".lastupdated".to_string(), time_updated).await;
 }
 
+/// .lastupdated files written at once by `write_last_updated_files`
+const LAST_UPDATED_WRITE_CONCURRENCY: usize = 8;
+
+/// Write the .lastupdated file of several mod folders, a few at a time
+pub async fn write_last_updated_files(folder_paths: Vec<PathBuf>, time_updated: i64) {
+    use futures::StreamExt;
+    futures::stream::iter(folder_paths)
+        .map(|folder_path| write_last_updated_file(folder_path, time_updated))
+        .buffer_unordered(LAST_UPDATED_WRITE_CONCURRENCY)
+        .collect::<Vec<()>>()
+        .await;
+}
+
+
+/// Load the persisted download failure history
+pub fn load_failure_history(app: &AppHandle) -> Result<FailureHistory, String> {
+    let store = app.store(DOWNLOAD_HISTORY_STORE)
+        .map_err(|e| format!("Failed to open download history store: {}", e))?;
+    
+    match store.get(FAILURE_HISTORY_KEY) {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse download failure history: {}", e)),
+        None => Ok(FailureHistory::default()),
+    }
+}
+
+/// Apply a change to the persisted download failure history and save it
+fn modify_failure_history<F: FnOnce(&mut FailureHistory) -> bool>(app: &AppHandle, f: F) -> Result<(), String> {
+    let _lock = FAILURE_HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    
+    let mut history = load_failure_history(app)?;
+    if !f(&mut history) {
+        return Ok(());
+    }
+    
+    let store = app.store(DOWNLOAD_HISTORY_STORE)
+        .map_err(|e| format!("Failed to open download history store: {}", e))?;
+    let value = serde_json::to_value(&history)
+        .map_err(|e| format!("Failed to serialize download failure history: {}", e))?;
+    store.set(FAILURE_HISTORY_KEY, value);
+    store.save()
+        .map_err(|e| format!("Failed to save download history store: {}", e))
+}
+
+/// Record a failed download attempt for a mod in the persisted history
+pub fn record_download_failure(app: &AppHandle, mod_id: &str, error: &str) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    
+    if let Err(e) = modify_failure_history(app, |history| {
+        history.record_failure(mod_id, error, timestamp);
+        true
+    }) {
+        eprintln!("[Downloader] Failed to record download failure for mod {}: {}", mod_id, e);
+    }
+}
+
+/// Clear the persisted failure history of a mod after it downloaded successfully
+pub fn clear_download_failures(app: &AppHandle, mod_id: &str) {
+    if let Err(e) = modify_failure_history(app, |history| history.clear(mod_id)) {
+        eprintln!("[Downloader] Failed to clear download failures for mod {}: {}", mod_id, e);
+    }
+}
+
+/// Append the result of a finished scheduled job to the persisted download history
+pub fn record_job_history(app: &AppHandle, job: &ScheduledJob) {
+    let result = (|| {
+        let store = app.store(DOWNLOAD_HISTORY_STORE)
+            .map_err(|e| format!("Failed to open download history store: {}", e))?;
+        
+        let mut jobs = match store.get(JOB_HISTORY_KEY) {
+            Some(serde_json::Value::Array(jobs)) => jobs,
+            _ => Vec::new(),
+        };
+        let mut entry = serde_json::to_value(job)
+            .map_err(|e| format!("Failed to serialize job: {}", e))?;
+        entry["finishedAt"] = serde_json::json!(std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs());
+        jobs.push(entry);
+        
+        // Keep only the most recent jobs
+        if jobs.len() > MAX_JOB_HISTORY {
+            let excess = jobs.len() - MAX_JOB_HISTORY;
+            jobs.drain(..excess);
+        }
+        
+        store.set(JOB_HISTORY_KEY, serde_json::Value::Array(jobs));
+        store.save()
+            .map_err(|e| format!("Failed to save download history store: {}", e))
+    })();
+    
+    if let Err(e) = result {
+        eprintln!("[Scheduler] Failed to record job {} in download history: {}", job.id, e);
+    }
+}
+
+/// Read a backend config value, None when it is missing or no longer parses
+fn get_config<T: serde::de::DeserializeOwned>(app: &AppHandle, key: &str) -> Option<T> {
+    app.store(BACKEND_CONFIG_STORE).ok()
+        .and_then(|store| store.get(key))
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+/// Persist a backend config value, None removes the key
+fn set_config<T: serde::Serialize>(app: &AppHandle, key: &str, value: Option<T>) -> Result<(), String> {
+    let store = app.store(BACKEND_CONFIG_STORE)
+        .map_err(|e| format!("Failed to open backend config store: {}", e))?;
+    
+    match value {
+        Some(value) => store.set(key, serde_json::json!(value)),
+        None => {
+            store.delete(key);
+        }
+    }
+    store.save()
+        .map_err(|e| format!("Failed to save backend config store: {}", e))
+}
+
+/// Persist the user-configured SteamCMD executable (None clears it)
+pub fn save_steamcmd_path(app: &AppHandle, path: Option<&Path>) -> Result<(), String> {
+    set_config(app, STEAMCMD_PATH_KEY, path.map(|p| p.to_string_lossy()))
+}
+
+/// Load the user-configured SteamCMD executable
+pub fn load_steamcmd_path(app: &AppHandle) -> Option<PathBuf> {
+    get_config::<PathBuf>(app, STEAMCMD_PATH_KEY)
+        .filter(|p| !p.as_os_str().is_empty())
+}
+
+/// Persist the directory Workshop items are downloaded into (None goes back to the SteamCMD folder)
+pub fn save_download_dir(app: &AppHandle, path: Option<&Path>) -> Result<(), String> {
+    set_config(app, DOWNLOAD_DIR_KEY, path.map(|p| p.to_string_lossy()))
+}
+
+/// Load the directory Workshop items are downloaded into
+pub fn load_download_dir(app: &AppHandle) -> Option<PathBuf> {
+    get_config::<PathBuf>(app, DOWNLOAD_DIR_KEY)
+        .filter(|p| !p.as_os_str().is_empty())
+}
+
+/// Save the memory budget of parallel downloads (None removes it)
+pub fn save_max_memory_usage(app: &AppHandle, bytes: Option<u64>) -> Result<(), String> {
+    set_config(app, MAX_MEMORY_USAGE_KEY, bytes)
+}
+
+/// Load the memory budget of parallel downloads
+pub fn load_max_memory_usage(app: &AppHandle) -> Option<u64> {
+    get_config::<u64>(app, MAX_MEMORY_USAGE_KEY).filter(|b| *b > 0)
+}
+
+/// Save whether SteamCMD output is emitted live while downloading
+pub fn save_steamcmd_verbose_log(app: &AppHandle, verbose: bool) -> Result<(), String> {
+    set_config(app, STEAMCMD_VERBOSE_LOG_KEY, Some(verbose))
+}
+
+/// Load whether SteamCMD output is emitted live while downloading (off unless configured)
+pub fn load_steamcmd_verbose_log(app: &AppHandle) -> bool {
+    get_config(app, STEAMCMD_VERBOSE_LOG_KEY).unwrap_or(false)
+}
+
+/// Save how many mod folders are read in parallel when listing mods (None resets to the default)
+pub fn save_scan_concurrency(app: &AppHandle, concurrency: Option<usize>) -> Result<(), String> {
+    set_config(app, SCAN_CONCURRENCY_KEY, concurrency)
+}
+
+/// Load how many mod folders are read in parallel when listing mods
+pub fn load_scan_concurrency(app: &AppHandle) -> Option<usize> {
+    get_config::<usize>(app, SCAN_CONCURRENCY_KEY).filter(|c| *c > 0)
+}
+
+/// Save how many mods a batch update installs at once (None resets to the default)
+pub fn save_install_concurrency(app: &AppHandle, concurrency: Option<usize>) -> Result<(), String> {
+    set_config(app, INSTALL_CONCURRENCY_KEY, concurrency)
+}
+
+/// Load how many mods a batch update installs at once
+pub fn load_install_concurrency(app: &AppHandle) -> Option<usize> {
+    get_config::<usize>(app, INSTALL_CONCURRENCY_KEY).filter(|c| *c > 0)
+}
+
+/// Save how many Workshop pages are scraped in parallel (None resets to the default)
+pub fn save_scrape_concurrency(app: &AppHandle, concurrency: Option<usize>) -> Result<(), String> {
+    set_config(app, SCRAPE_CONCURRENCY_KEY, concurrency)
+}
+
+/// Load how many Workshop pages are scraped in parallel
+pub fn load_scrape_concurrency(app: &AppHandle) -> Option<usize> {
+    get_config::<usize>(app, SCRAPE_CONCURRENCY_KEY).filter(|c| *c > 0)
+}
+
+/// Save the proxy, API base URL and API key used for requests to Steam
+pub fn save_network_config(app: &AppHandle, config: &NetworkConfig) -> Result<(), String> {
+    set_config(app, NETWORK_CONFIG_KEY, Some(config))
+}
+
+/// Load the proxy, API base URL and API key used for requests to Steam
+pub fn load_network_config(app: &AppHandle) -> Option<NetworkConfig> {
+    get_config(app, NETWORK_CONFIG_KEY)
+}
+
+/// Save how long the mod watcher waits for a folder's events to settle (None resets to the default)
+pub fn save_watcher_debounce(app: &AppHandle, debounce_ms: Option<u64>) -> Result<(), String> {
+    set_config(app, WATCHER_DEBOUNCE_KEY, debounce_ms)
+}
+
+/// Load how long the mod watcher waits for a folder's events to settle, in milliseconds
+pub fn load_watcher_debounce(app: &AppHandle) -> Option<u64> {
+    get_config(app, WATCHER_DEBOUNCE_KEY)
+}
+
+/// Load the mods whose updates are always ignored (settings `ignoredMods`), unlike `.ignoredupdate` this survives reinstalling the mod
+pub fn load_ignored_mods(app: &AppHandle) -> std::collections::BTreeSet<String> {
+    app.store(SETTINGS_STORE_NAME).ok()
+        .and_then(|store| store.get(SETTINGS_KEY))
+        .map(|settings| ignored_mod_ids(&settings))
+        .unwrap_or_default()
+}
+
+/// Add a mod to or remove it from the settings `ignoredMods` list, returning the updated list
+pub fn save_mod_ignored(app: &AppHandle, mod_id: &str, title: Option<&str>, ignored: bool) -> Result<std::collections::BTreeSet<String>, String> {
+    let _lock = IGNORED_MODS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    
+    let store = app.store(SETTINGS_STORE_NAME)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+    let mut settings = store.get(SETTINGS_KEY).unwrap_or_else(default_settings);
+    if set_mod_ignored(&mut settings, mod_id, title, ignored) {
+        store.set(SETTINGS_KEY, settings.clone());
+        store.save()
+            .map_err(|e| format!("Failed to save settings store: {}", e))?;
+    }
+    Ok(ignored_mod_ids(&settings))
+}
+
+/// Save how long persisted API responses stay valid (None resets to the default)
+pub fn save_api_cache_ttl(app: &AppHandle, ttl_secs: Option<u64>) -> Result<(), String> {
+    set_config(app, API_CACHE_TTL_KEY, ttl_secs)
+}
+
+/// Load how long persisted API responses stay valid
+pub fn load_api_cache_ttl(app: &AppHandle) -> Option<u64> {
+    get_config(app, API_CACHE_TTL_KEY)
+}
+
+/// Save the download throttle and the SteamCMD instances allowed while it is set (None removes the throttle)
+pub fn save_download_throttle(app: &AppHandle, kbps: Option<u32>, throttled_max_instances: usize) -> Result<(), String> {
+    set_config(app, DOWNLOAD_THROTTLE_KEY, kbps)?;
+    set_config(app, THROTTLED_MAX_INSTANCES_KEY, Some(throttled_max_instances))
+}
+
+/// Load the download throttle in kbps and the SteamCMD instances allowed while it is set
+pub fn load_download_throttle(app: &AppHandle) -> (Option<u32>, Option<usize>) {
+    let kbps = get_config::<u32>(app, DOWNLOAD_THROTTLE_KEY).filter(|kbps| *kbps > 0);
+    (kbps, get_config(app, THROTTLED_MAX_INSTANCES_KEY))
+}
+
+/// Save how mods are backed up before an update
+pub fn save_backup_mode(app: &AppHandle, mode: BackupMode) -> Result<(), String> {
+    set_config(app, BACKUP_MODE_KEY, Some(mode))
+}
+
+/// Load how mods are backed up before an update (full copies unless configured otherwise)
+pub fn load_backup_mode(app: &AppHandle) -> BackupMode {
+    get_config(app, BACKUP_MODE_KEY).unwrap_or_default()
+}
+
+/// Save how full backups are stored
+pub fn save_backup_format(app: &AppHandle, format: BackupFormat) -> Result<(), String> {
+    set_config(app, BACKUP_FORMAT_KEY, Some(format))
+}
+
+/// Load how full backups are stored (uncompressed folders unless configured otherwise)
+pub fn load_backup_format(app: &AppHandle) -> BackupFormat {
+    get_config(app, BACKUP_FORMAT_KEY).unwrap_or_default()
+}
+
+/// Save which files are skipped when installing a mod and which are kept from the installed copy
+pub fn save_install_filter(app: &AppHandle, filter: &InstallFilter) -> Result<(), String> {
+    set_config(app, INSTALL_FILTER_KEY, Some(filter))
+}
+
+/// Load which files are skipped when installing a mod (nothing unless configured otherwise)
+pub fn load_install_filter(app: &AppHandle) -> InstallFilter {
+    get_config(app, INSTALL_FILTER_KEY).unwrap_or_default()
+}
+
+/// Install options with the saved backup and install filter settings
+/// Mods are not backed up unless the caller sets a backup directory
+pub fn load_install_options(app: &AppHandle, download_path: PathBuf, mods_path: PathBuf, link_mode: LinkMode) -> InstallOptions {
+    InstallOptions {
+        link_mode,
+        backup_mode: load_backup_mode(app),
+        backup_format: load_backup_format(app),
+        install_filter: load_install_filter(app),
+        app: Some(app.clone()),
+        ..InstallOptions::new(download_path, mods_path)
+    }
+}
+
+/// Point the API cache at its file in the app data dir
+/// Must run before the shared SteamApi is first used, so the persisted cache gets loaded
+pub fn init_api_cache(app: &AppHandle) {
+    use crate::core::workshop_client::{configure_disk_cache, DEFAULT_DISK_CACHE_TTL};
+    
+    let data_dir = match app.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("[Services] No app data dir, API cache will not be persisted: {}", e);
+            return;
+        }
+    };
+    let ttl = load_api_cache_ttl(app)
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(DEFAULT_DISK_CACHE_TTL);
+    configure_disk_cache(data_dir.join(API_CACHE_FILE), ttl);
+}
+
+/// Write the API cache to disk
+/// Only the snapshot is taken under the API lock, other requests don't wait for the file to be written
+pub async fn save_api_cache() {
+    let Some(snapshot) = get_steam_api().lock().await.disk_cache_snapshot() else {
+        return;
+    };
+    match tokio::task::spawn_blocking(move || snapshot.save()).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => eprintln!("[Services] Failed to save API cache: {}", e),
+        Err(e) => eprintln!("[Services] Task panicked saving API cache: {:?}", e),
+    }
+}
+
+/// Write the API cache to disk from outside the async runtime (app shutdown)
+pub fn save_api_cache_blocking() {
+    // Nothing to save if the API was never used
+    if let Some(steam_api) = STEAM_API.get() {
+        if let Err(e) = steam_api.blocking_lock().save_disk_cache() {
+            eprintln!("[Services] Failed to save API cache: {}", e);
+        }
+    }
+}
+
+/// Apply persisted backend configuration to the shared services
+pub async fn apply_backend_config(app: &AppHandle) {
+    if let Some(concurrency) = load_scan_concurrency(app) {
+        crate::core::mod_scanner::set_scan_concurrency(Some(concurrency));
+    }
+    if let Some(concurrency) = load_scrape_concurrency(app) {
+        crate::core::workshop_client::set_scrape_concurrency(Some(concurrency));
+    }
+    if let Some(concurrency) = load_install_concurrency(app) {
+        crate::core::mod_manager::set_install_concurrency(Some(concurrency));
+    }
+    if let Some(config) = load_network_config(app) {
+        if let Err(e) = crate::core::workshop_client::set_network_config(config) {
+            eprintln!("[Services] Failed to apply network settings: {}", e);
+        }
+    }
+    if let Some(debounce_ms) = load_watcher_debounce(app) {
+        crate::core::mod_watcher::set_watcher_debounce(Some(std::time::Duration::from_millis(debounce_ms)));
+    }
+    
+    let downloader = get_downloader();
+    let mut dl = downloader.lock().await;
+    
+    if let Some(path) = load_steamcmd_path(app) {
+        eprintln!("[Services] Using configured SteamCMD executable: {:?}", path);
+        dl.set_custom_executable(Some(path));
+    }
+    
+    // Scripts of a crashed session may hold the Steam password
+    let removed_scripts = dl.remove_leftover_scripts();
+    if !removed_scripts.is_empty() {
+        eprintln!("[Services] Removed {} SteamCMD script(s) left by a previous session", removed_scripts.len());
+    }
+    
+    if let Some(path) = load_download_dir(app) {
+        eprintln!("[Services] Downloading Workshop items into {:?}", path);
+        if let Err(e) = dl.set_install_dir(Some(path)) {
+            eprintln!("[Services] Failed to apply download directory: {}", e);
+        }
+    }
+    
+    if let Some(bytes) = load_max_memory_usage(app) {
+        eprintln!("[Services] Limiting parallel downloads to {} bytes of memory", bytes);
+        dl.set_max_memory_usage(Some(bytes));
+    }
+    
+    dl.set_verbose_log(load_steamcmd_verbose_log(app));
+    
+    let (throttle_kbps, throttled_max_instances) = load_download_throttle(app);
+    if let Some(instances) = throttled_max_instances {
+        dl.set_throttled_max_instances(instances);
+    }
+    if let Some(kbps) = throttle_kbps {
+        eprintln!("[Services] Throttling downloads to {} kbps per SteamCMD instance", kbps);
+        dl.set_download_throttle(Some(kbps));
+    }
+}
@@ -17,6 +17,7 @@ import MessageModal from "./components/MessageModal";
 import CorruptedModConflictModal from "./components/CorruptedModConflictModal";
 import ContextMenu from "./components/ContextMenu";
 import AccessErrorBanner from "./components/AccessErrorBanner";
+import SteamStatusBanner from "./components/SteamStatusBanner";
 import AppMenu from "./components/AppMenu";
 import { Theme } from "./utils/settingsStorage";
 import "./App.css";
@@ -75,6 +76,7 @@ function AppContent() {
   return (
     <div className="app-container">
       <AccessErrorBanner />
+      <SteamStatusBanner />
       <div className="app-tabs">
         <div className="app-tabs-left">
           <button
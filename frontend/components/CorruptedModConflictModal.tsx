@@ -1,6 +1,7 @@
 import { useState, useCallback } from "react";
 import { useModal } from "../contexts/ModalContext";
 import { useAccessError } from "../contexts/AccessErrorContext";
+import { getErrorMessage } from "../utils/errors";
 import "./ModList.css";
 
 interface CorruptedModConflictModalProps {
@@ -38,7 +39,7 @@ export default function CorruptedModConflictModal({
       await onResolve(true);
       closeModal();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const errorMessage = getErrorMessage(err);
       setError(errorMessage);
     } finally {
       setIsProcessing(false);
@@ -58,7 +59,7 @@ export default function CorruptedModConflictModal({
       await onResolve(false);
       closeModal();
     } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : String(err);
+      const errorMessage = getErrorMessage(err);
       setError(errorMessage);
     } finally {
       setIsProcessing(false);
@@ -8,6 +8,7 @@ import { useModal } from "../contexts/ModalContext";
 import { useFormatting } from "../hooks/useFormatting";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import { getErrorMessage, isAppError } from "../utils/errors";
 import "./DownloadTab.css";
 
 interface ModInput {
@@ -457,12 +458,10 @@ export default function DownloadTab() {
         } catch (error) {
           console.error(`Failed to download mod ${mod.modId}:`, error);
           
-          const errorMessage = error instanceof Error ? error.message : String(error);
+          const errorMessage = getErrorMessage(error);
           
           // Check if error is due to cancellation
-          const isCancelled = errorMessage.includes("cancelled") || 
-                             errorMessage.includes("Update cancelled") ||
-                             errorMessage.includes("Update cancelled by user");
+          const isCancelled = isAppError(error) && error.kind === "cancelled";
           
           // Update mod status - check current status to avoid overwriting cancelled
           setModInputs(prev => {
@@ -546,53 +545,50 @@ export default function DownloadTab() {
       
       setDownloadedMods(prev => [...prev, mod]);
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
-      
       // Check if this is a corrupted mod conflict error
-      if (errorMessage.startsWith("CORRUPTED_MOD_CONFLICT:")) {
-        // Parse the error: CORRUPTED_MOD_CONFLICT:folderName:modId:modTitle
-        const parts = errorMessage.split(":");
-        if (parts.length >= 4) {
-          const folderName = parts[1];
-          const modId = parts[2];
-          const modTitle = parts.slice(3).join(":"); // In case title contains ":"
-          
-          // Show modal to ask user for decision
-          return new Promise<void>((resolve, reject) => {
-            openModal("corrupted-mod-conflict", {
-              folderName,
-              modId,
-              modTitle: modTitle || details.title || modId,
-              onResolve: async (overwrite: boolean) => {
-                try {
-                  const result = await invoke<{ modId: string; modPath: string; folder: string }>("continue_download_with_decision", {
-                    modId: modId,
-                    modsPath: modsPath,
-                    overwrite: overwrite
-                  });
-                  
-                  // Convert result to BaseMod format
-                  const mod: BaseMod = {
-                    modId: result.modId,
-                    modPath: result.modPath,
-                    folder: result.folder, // Use actual folder name from backend (may differ if renamed)
-                    details: details,
-                    updated: undefined
-                  };
-                  
-                  setDownloadedMods(prev => [...prev, mod]);
-                  resolve();
-                } catch (err) {
-                  console.error("Failed to continue download:", err);
-                  reject(err);
-                }
-              },
-              onReject: () => {
-                reject(new Error("Download cancelled by user"));
+      if (isAppError(error) && error.kind === "corruptedModConflict") {
+        const { folderName, modId, modTitle } = error;
+        
+        // Show modal to ask user for decision
+        return new Promise<void>((resolve, reject) => {
+          openModal("corrupted-mod-conflict", {
+            folderName,
+            modId,
+            modTitle: modTitle || details.title || modId,
+            onResolve: async (overwrite: boolean) => {
+              try {
+                const result = await invoke<{ modId: string; modPath: string; folder: string }>("resolve_corrupted_conflict", {
+                  modId: modId,
+                  modsPath: modsPath,
+                  overwrite: overwrite
+                });
+                
+                // Convert result to BaseMod format
+                const mod: BaseMod = {
+                  modId: result.modId,
+                  modPath: result.modPath,
+                  folder: result.folder, // Use actual folder name from backend (may differ if renamed)
+                  details: details,
+                  updated: undefined
+                };
+                
+                setDownloadedMods(prev => [...prev, mod]);
+                resolve();
+              } catch (err) {
+                console.error("Failed to resolve corrupted mod conflict:", err);
+                reject(new Error(getErrorMessage(err)));
               }
-            });
+            },
+            onReject: () => {
+              reject(new Error("Download cancelled by user"));
+            }
           });
-        }
+        });
+      }
+      
+      if (isAppError(error)) {
+        console.error("Failed to download mod:", error.message);
+        throw new Error(error.message);
       }
       
       console.error("Failed to download mod:", error);
@@ -11,6 +11,8 @@ import { useModal } from "../contexts/ModalContext";
 import { useContextMenu, ContextMenuItem } from "../contexts/ContextMenuContext";
 import { useFormatting } from "../hooks/useFormatting";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getErrorMessage } from "../utils/errors";
 import "./ModList.css";
 
 interface ModListProps {
@@ -134,6 +136,47 @@ export default function ModList({ onUpdateSelected, modsPath, useInstalledModsCo
     checkBackups();
   }, [checkBackups]);
 
+  // Keep backup state live - the backup watcher reports backups by folder name
+  useEffect(() => {
+    let unlistenAdded: (() => void) | undefined;
+    let unlistenRemoved: (() => void) | undefined;
+
+    const setBackup = (folder: string, hasBackup: boolean, backupDate?: number) => {
+      const affected = mods.filter(mod => mod.folder === folder);
+      if (affected.length === 0) return;
+      setModBackups(prev => {
+        const next = new Map(prev);
+        affected.forEach(mod => next.set(mod.modId, hasBackup));
+        return next;
+      });
+      setBackupDates(prev => {
+        const next = new Map(prev);
+        affected.forEach(mod => {
+          if (hasBackup && backupDate) {
+            next.set(mod.modId, new Date(backupDate * 1000));
+          } else {
+            next.delete(mod.modId);
+          }
+        });
+        return next;
+      });
+    };
+
+    (async () => {
+      unlistenAdded = await listen<{ folder: string; backupDate: number }>("backup-added", (event) => {
+        setBackup(event.payload.folder, true, event.payload.backupDate);
+      });
+      unlistenRemoved = await listen<{ folder: string }>("backup-removed", (event) => {
+        setBackup(event.payload.folder, false);
+      });
+    })();
+
+    return () => {
+      unlistenAdded?.();
+      unlistenRemoved?.();
+    };
+  }, [mods]);
+
   // Check if mods have ignored updates
   const checkIgnoredUpdates = useCallback(async () => {
     if (mods.length === 0 || !modsPath) {
@@ -402,7 +445,7 @@ export default function ModList({ onUpdateSelected, modsPath, useInstalledModsCo
                   console.error("[ModList] Both revealItemInDir and openPath failed:", openError);
                   openModal("message", {
                     title: "Failed to Open Folder",
-                    message: `Failed to open folder: ${mod.modPath}\nError: ${openError instanceof Error ? openError.message : String(openError)}`,
+                    message: `Failed to open folder: ${mod.modPath}\nError: ${getErrorMessage(openError)}`,
                     type: "error"
                   });
                 });
@@ -468,7 +511,7 @@ export default function ModList({ onUpdateSelected, modsPath, useInstalledModsCo
           console.error("Failed to undo ignore update:", error);
           openModal("message", {
             title: "Failed to Undo Ignore",
-            message: `Failed to undo ignore update: ${error instanceof Error ? error.message : String(error)}`,
+            message: `Failed to undo ignore update: ${getErrorMessage(error)}`,
             type: "error"
           });
         }
@@ -5,6 +5,7 @@ import { useModal } from "../contexts/ModalContext";
 import { useSettings } from "../contexts/SettingsContext";
 import { useAccessError } from "../contexts/AccessErrorContext";
 import { useFormatting } from "../hooks/useFormatting";
+import { getErrorMessage } from "../utils/errors";
 import "./ModList.css";
 
 interface RestoreBackupModalProps {
@@ -60,7 +61,7 @@ export default function RestoreBackupModal({ mod, backupDate, onRestoreComplete,
         setRestoreSuccess(null);
       }, 2000);
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setRestoreError(errorMessage);
     } finally {
       setIsRestoring(false);
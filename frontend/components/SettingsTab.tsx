@@ -4,6 +4,7 @@ import { openUrl } from "@tauri-apps/plugin-opener";
 import { useSettings } from "../contexts/SettingsContext";
 import SettingField from "./SettingField";
 import ThemeSelect from "./ThemeSelect";
+import { getErrorMessage } from "../utils/errors";
 import "./SettingsTab.css";
 
 export default function SettingsTab() {
@@ -51,13 +52,13 @@ export default function SettingsTab() {
           setTimeout(() => setSuccess(""), 3000);
         } catch (err) {
           console.error("Failed to save mods path:", err);
-          setError(`Failed to save mods folder path: ${err instanceof Error ? err.message : String(err)}`);
+          setError(`Failed to save mods folder path: ${getErrorMessage(err)}`);
           setTimeout(() => setError(""), 5000);
         }
       }
     } catch (err) {
       console.error("Failed to select mods path:", err);
-      setError(`Failed to select mods folder: ${err instanceof Error ? err.message : String(err)}`);
+      setError(`Failed to select mods folder: ${getErrorMessage(err)}`);
       setTimeout(() => setError(""), 5000);
     }
   };
@@ -79,7 +80,7 @@ export default function SettingsTab() {
         await updateSetting("modsPath", value);
       } catch (err) {
         console.error("Failed to save mods path:", err);
-        setError(`Failed to save mods folder path: ${err instanceof Error ? err.message : String(err)}`);
+        setError(`Failed to save mods folder path: ${getErrorMessage(err)}`);
         setTimeout(() => setError(""), 5000);
       }
     }, 500);
@@ -96,7 +97,7 @@ export default function SettingsTab() {
       await updateSetting("modsPath", localModsPath);
     } catch (err) {
       console.error("Failed to save mods path:", err);
-      setError(`Failed to save mods folder path: ${err instanceof Error ? err.message : String(err)}`);
+      setError(`Failed to save mods folder path: ${getErrorMessage(err)}`);
       setTimeout(() => setError(""), 5000);
     }
   };
@@ -124,7 +125,7 @@ export default function SettingsTab() {
       }
     } catch (err) {
       console.error("Failed to select backup directory:", err);
-      setError(`Failed to select backup directory: ${err instanceof Error ? err.message : String(err)}`);
+      setError(`Failed to select backup directory: ${getErrorMessage(err)}`);
       setTimeout(() => setError(""), 5000);
     }
   };
@@ -146,7 +147,7 @@ export default function SettingsTab() {
         await updateSetting("backupDirectory", value);
       } catch (err) {
         console.error("Failed to save backup directory:", err);
-        setError(`Failed to save backup directory: ${err instanceof Error ? err.message : String(err)}`);
+        setError(`Failed to save backup directory: ${getErrorMessage(err)}`);
         setTimeout(() => setError(""), 5000);
       }
     }, 500);
@@ -163,7 +164,7 @@ export default function SettingsTab() {
       await updateSetting("backupDirectory", localBackupDirectory);
     } catch (err) {
       console.error("Failed to save backup directory:", err);
-      setError(`Failed to save backup directory: ${err instanceof Error ? err.message : String(err)}`);
+      setError(`Failed to save backup directory: ${getErrorMessage(err)}`);
       setTimeout(() => setError(""), 5000);
     }
   };
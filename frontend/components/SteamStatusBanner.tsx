@@ -0,0 +1,101 @@
+import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import "./SteamStatusBanner.css";
+
+interface SteamStatus {
+  apiUp: boolean;
+  workshopUp: boolean;
+  contentServersReachable: boolean;
+}
+
+// Don't check Steam more often than this while operations keep failing
+const CHECK_COOLDOWN_MS = 60_000;
+
+export default function SteamStatusBanner() {
+  const [status, setStatus] = useState<SteamStatus | null>(null);
+  const [isDismissed, setIsDismissed] = useState(false);
+  const lastCheckRef = useRef(0);
+
+  // Check Steam status whenever a download or update fails
+  useEffect(() => {
+    let unlistenState: (() => void) | undefined;
+    let unlistenUpdated: (() => void) | undefined;
+
+    const checkStatus = async () => {
+      const now = Date.now();
+      if (now - lastCheckRef.current < CHECK_COOLDOWN_MS) {
+        return;
+      }
+      lastCheckRef.current = now;
+
+      try {
+        const result = await invoke<SteamStatus>("get_steam_status");
+        setStatus(result);
+        setIsDismissed(false);
+      } catch (error) {
+        console.error("Failed to check Steam status:", error);
+      }
+    };
+
+    const setupListeners = async () => {
+      unlistenState = await listen<{ modId: string; state: string }>("mod-state", (event) => {
+        if (event.payload.state === "failed") {
+          checkStatus();
+        }
+      });
+      unlistenUpdated = await listen<{ modId: string; success: boolean }>("mod-updated", (event) => {
+        if (!event.payload.success) {
+          checkStatus();
+        }
+      });
+    };
+
+    setupListeners();
+
+    return () => {
+      unlistenState?.();
+      unlistenUpdated?.();
+    };
+  }, []);
+
+  if (!status || isDismissed) {
+    return null;
+  }
+
+  const problems: string[] = [];
+  if (!status.apiUp) {
+    problems.push("Steam Web API is not responding");
+  }
+  if (!status.workshopUp) {
+    problems.push("Steam Workshop API is not responding");
+  }
+  if (!status.contentServersReachable) {
+    problems.push("Steam content servers are unreachable");
+  }
+
+  if (problems.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="steam-status-banner">
+      <div className="steam-status-content">
+        <div className="steam-status-icon">⚠️</div>
+        <div className="steam-status-text">
+          <div className="steam-status-title">Steam seems to be having problems</div>
+          <div className="steam-status-message">
+            {problems.join(". ")}. Failures are likely on Steam's side - try again later.
+          </div>
+        </div>
+        <button
+          className="steam-status-close"
+          onClick={() => setIsDismissed(true)}
+          aria-label="Dismiss Steam status"
+        >
+          ×
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -5,6 +5,7 @@ import { BaseMod } from "../types";
 import { useSettings } from "./SettingsContext";
 import { ModState } from "./ModsContext";
 import { sortMods } from "../utils/modSorting";
+import { getErrorMessage } from "../utils/errors";
 
 interface InstalledModsContextType {
   mods: BaseMod[];
@@ -103,7 +104,7 @@ export function InstalledModsProvider({ children }: { children: ReactNode }) {
           });
       }
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       console.error("Failed to load installed mods:", error);
       setError(`Error loading installed mods: ${errorMessage}`);
       setMods([]);
@@ -366,6 +367,7 @@ export function InstalledModsProvider({ children }: { children: ReactNode }) {
       // Call Tauri command - events will update UI in real-time
       const updated = await invoke<BaseMod[]>("update_mods", {
         mods: modsToUpdate,
+        modsPath: settings.modsPath || undefined,
         backupMods: settings.backupMods || false,
         backupDirectory: settings.backupDirectory || undefined,
         maxSteamcmdInstances: settings.maxSteamcmdInstances || 1
@@ -383,7 +385,7 @@ export function InstalledModsProvider({ children }: { children: ReactNode }) {
       }
     } catch (error) {
       console.error("Failed to update mods:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error updating mods: ${errorMessage}`);
       
       // On error, clear states for all mods that were being updated
@@ -403,7 +405,7 @@ export function InstalledModsProvider({ children }: { children: ReactNode }) {
       console.log("[UPDATE] Cancellation requested");
     } catch (error) {
       console.error("Failed to cancel update:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error cancelling update: ${errorMessage}`);
     }
   };
@@ -428,7 +430,7 @@ export function InstalledModsProvider({ children }: { children: ReactNode }) {
       setMods(prev => prev.filter(m => !modsToIgnore.some(ignored => ignored.modId === m.modId)));
     } catch (error) {
       console.error("Failed to ignore update:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error ignoring update: ${errorMessage}`);
     }
   };
@@ -461,7 +463,7 @@ export function InstalledModsProvider({ children }: { children: ReactNode }) {
       // They will just be filtered out in Query & Update tab
     } catch (error) {
       console.error("Failed to ignore permanently:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error ignoring permanently: ${errorMessage}`);
     }
   };
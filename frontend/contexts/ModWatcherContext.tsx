@@ -43,6 +43,25 @@ export function ModWatcherProvider({ children }: { children: ReactNode }) {
     };
   }, [settings.modsPath]);
 
+  // Watch the backup directory so backup state stays live without polling check_backups
+  useEffect(() => {
+    if (settings.backupDirectory && settings.backupDirectory.trim().length > 0) {
+      invoke("start_backup_watcher", { backupDirectory: settings.backupDirectory })
+        .then(() => {
+          console.log("[BACKUP_WATCHER] Started backup watcher for path:", settings.backupDirectory);
+        })
+        .catch((error) => {
+          console.error("[BACKUP_WATCHER] Failed to start backup watcher:", error);
+        });
+    } else {
+      invoke("stop_backup_watcher").catch(console.error);
+    }
+
+    return () => {
+      invoke("stop_backup_watcher").catch(console.error);
+    };
+  }, [settings.backupDirectory]);
+
   return (
     <ModWatcherContext.Provider value={{}}>
       {children}
@@ -3,6 +3,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { BaseMod } from "../types";
 import { useSettings } from "./SettingsContext";
+import { getErrorMessage } from "../utils/errors";
 
 // Simplified state system: each mod has a single state that is managed ONLY by events
 export type ModState = "queued" | "retry-queued" | "downloading" | "installing" | "completed" | "failed" | "cancelled" | null;
@@ -235,7 +236,7 @@ export function ModsProvider({ children }: { children: ReactNode }) {
       setError(null);
       setHasQueried(true);
     } catch (error) {
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       console.error("Failed to query mods:", error);
       setError(`Error querying mods: ${errorMessage}`);
       setMods([]);
@@ -267,6 +268,7 @@ export function ModsProvider({ children }: { children: ReactNode }) {
       // Call Tauri command - events will update UI in real-time
       const updated = await invoke<BaseMod[]>("update_mods", {
         mods: modsToUpdate,
+        modsPath: settings.modsPath || undefined,
         backupMods: settings.backupMods || false,
         backupDirectory: settings.backupDirectory || undefined,
         maxSteamcmdInstances: settings.maxSteamcmdInstances || 1
@@ -289,7 +291,7 @@ export function ModsProvider({ children }: { children: ReactNode }) {
       }
     } catch (error) {
       console.error("Failed to update mods:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error updating mods: ${errorMessage}`);
       
       // On error, clear states for all mods that were being updated
@@ -319,7 +321,7 @@ export function ModsProvider({ children }: { children: ReactNode }) {
       setMods(prev => prev.filter(m => !modsToIgnore.some(ignored => ignored.modId === m.modId)));
     } catch (error) {
       console.error("Failed to ignore update:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error ignoring update: ${errorMessage}`);
     }
   };
@@ -335,7 +337,7 @@ export function ModsProvider({ children }: { children: ReactNode }) {
       setMods(prev => prev.filter(m => !modsToIgnore.some(ignored => ignored.modId === m.modId)));
     } catch (error) {
       console.error("Failed to ignore permanently:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error ignoring permanently: ${errorMessage}`);
     }
   };
@@ -346,7 +348,7 @@ export function ModsProvider({ children }: { children: ReactNode }) {
       console.log("[UPDATE] Cancellation requested");
     } catch (error) {
       console.error("Failed to cancel update:", error);
-      const errorMessage = error instanceof Error ? error.message : String(error);
+      const errorMessage = getErrorMessage(error);
       setError(`Error cancelling update: ${errorMessage}`);
     }
   };
@@ -6,6 +6,11 @@ export interface BaseMod {
   updated?: boolean;
   nonSteamMod?: boolean;
   previewImagePath?: string;
+  // Workshop tags (e.g. "1.5", "Mod"), filter the returned list by these
+  tags?: string[];
+  author?: string;
+  // Download in SteamCMD's workshop content folder, set by update_mods with stageOnly
+  stagedPath?: string;
 }
 
 export interface WorkshopFileDetails {
@@ -52,3 +57,20 @@ export interface WorkshopFileDetails {
   tags: Array<{ tag: string }>;
 }
 
+
+// Install failure reported in mod-updated events (`installError`)
+export type InstallError =
+  | { kind: "corruptedModConflict"; folderName: string; modId: string; modTitle: string }
+  | { kind: "downgrade"; modId: string; localTimeUpdated: number; remoteTimeUpdated: number }
+  | { kind: "busy"; modPath: string }
+  | { kind: "cancelled" }
+  | { kind: "failed"; message: string };
+
+// Error every backend command rejects with, `message` is always readable
+// Install failures keep the fields of their InstallError
+export type AppError =
+  | { kind: "pathNotFound" | "notADirectory" | "backupNotFound"; message: string; path: string }
+  | { kind: "invalidInput" | "steamCmdMissing" | "cancelled" | "failed"; message: string }
+  | { kind: "invalidModId"; message: string; input: string }
+  | { kind: "downloadFailed"; message: string; modIds: string[] }
+  | (Exclude<InstallError, { kind: "cancelled" | "failed" }> & { message: string });
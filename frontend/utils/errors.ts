@@ -0,0 +1,17 @@
+import { AppError } from "../types";
+
+/** Whether a rejected invoke carries an error returned by a backend command */
+export function isAppError(error: unknown): error is AppError {
+  return typeof error === "object" && error !== null && "kind" in error && "message" in error;
+}
+
+/** Readable message of anything a command or promise rejected with */
+export function getErrorMessage(error: unknown): string {
+  if (error instanceof Error) {
+    return error.message;
+  }
+  if (isAppError(error)) {
+    return error.message;
+  }
+  return String(error);
+}
@@ -0,0 +1,311 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+
+/// Error of the SteamCMD installer, `Send` so it can cross threads in the app
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+// Valve doesn't publish checksums, but every SteamCMD archive is well over this size
+// A smaller file is an error page or a truncated download
+const MIN_ARCHIVE_SIZE: u64 = 256 * 1024;
+
+// Expected SHA-256 of the archive, checked when set
+const SHA256_ENV_VAR: &str = "STEAMCMD_SHA256";
+
+// Writer that hashes everything written through it
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    written: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, hasher: Sha256::new(), written: 0 }
+    }
+
+    // Hex-encoded SHA-256 and size of the written data
+    fn finish(mut self) -> io::Result<(String, u64)> {
+        self.inner.flush()?;
+        let hash = self.hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+        Ok((hash, self.written))
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn get_steamcmd_urls() -> Result<Vec<String>, Error> {
+    let platform = std::env::consts::OS;
+    
+    let urls = match platform {
+        "linux" => {
+            vec!["https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz".to_string()]
+        }
+        "windows" => {
+            vec![
+                "https://steamcdn-a.akamaihd.net/client/installer/steamcmd.zip".to_string(),
+                "https://steamcdn-a.akamaihd.net/client/installer/steamcmd_win32.zip".to_string(),
+            ]
+        }
+        "macos" => {
+            vec!["https://steamcdn-a.akamaihd.net/client/installer/steamcmd_osx.tar.gz".to_string()]
+        }
+        _ => return Err(format!("Unsupported platform: {}", platform).into()),
+    };
+    
+    Ok(urls)
+}
+
+// Download a file, returning the SHA-256 of its content
+fn download_file(url: &str, output_path: &Path) -> Result<String, Error> {
+    println!("Downloading SteamCMD from {}...", url);
+    
+    // reqwest resolves relative redirect locations against the request URL
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("RimworldWorkshopDownloader/1.0")
+        .redirect(reqwest::redirect::Policy::limited(10))
+        .build()?;
+    
+    let mut response = client.get(url).send()?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Failed to download: {} {}", response.status(), response.status().canonical_reason().unwrap_or("")).into());
+    }
+    
+    // Stream the body to disk, hashing it on the way
+    let mut writer = HashingWriter::new(io::BufWriter::new(fs::File::create(output_path)?));
+    response.copy_to(&mut writer)?;
+    let (hash, size) = writer.finish()?;
+    
+    if size < MIN_ARCHIVE_SIZE {
+        return Err(format!("Downloaded archive is too small ({} bytes), expected at least {} bytes", size, MIN_ARCHIVE_SIZE).into());
+    }
+    
+    println!("Downloaded {} bytes, SHA-256: {}", size, hash);
+    Ok(hash)
+}
+
+// Compare the archive hash with STEAMCMD_SHA256, if set
+fn verify_checksum(hash: &str) -> Result<(), Error> {
+    let expected = match std::env::var(SHA256_ENV_VAR) {
+        Ok(expected) if !expected.trim().is_empty() => expected.trim().to_lowercase(),
+        _ => return Ok(()),
+    };
+    
+    if expected != hash {
+        return Err(format!("Checksum mismatch: {} is {}, but the downloaded archive is {}", SHA256_ENV_VAR, expected, hash).into());
+    }
+    
+    println!("Checksum matches {}", SHA256_ENV_VAR);
+    Ok(())
+}
+
+// Decode the whole archive without writing anything, so a corrupt archive is never partially extracted
+fn verify_archive(archive_path: &Path, is_zip: bool) -> Result<(), Error> {
+    println!("Verifying archive...");
+    let file = fs::File::open(archive_path)?;
+    
+    if is_zip {
+        let mut archive = zip::ZipArchive::new(file)?;
+        for i in 0..archive.len() {
+            // The zip reader checks each entry's CRC once it's read to the end
+            let mut entry = archive.by_index(i)?;
+            io::copy(&mut entry, &mut io::sink())?;
+        }
+    } else {
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        for entry in archive.entries()? {
+            io::copy(&mut entry?, &mut io::sink())?;
+        }
+        // Anything after the tar end marker must still be valid gzip
+        let mut decoder = archive.into_inner();
+        io::copy(&mut decoder, &mut io::sink())?;
+    }
+    
+    Ok(())
+}
+
+fn extract_tar_gz(tar_gz_path: &Path, output_dir: &Path) -> Result<(), Error> {
+    println!("Extracting SteamCMD...");
+    fs::create_dir_all(output_dir)?;
+    
+    let tar_gz = fs::File::open(tar_gz_path)?;
+    let tar = flate2::read::GzDecoder::new(tar_gz);
+    let mut archive = tar::Archive::new(tar);
+    archive.unpack(output_dir)?;
+    
+    Ok(())
+}
+
+fn extract_zip(zip_path: &Path, output_dir: &Path) -> Result<(), Error> {
+    println!("Extracting SteamCMD...");
+    fs::create_dir_all(output_dir)?;
+    
+    let file = fs::File::open(zip_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let outpath = output_dir.join(file.mangled_name());
+        
+        if file.name().ends_with('/') {
+            fs::create_dir_all(&outpath)?;
+        } else {
+            if let Some(p) = outpath.parent() {
+                if !p.exists() {
+                    fs::create_dir_all(p)?;
+                }
+            }
+            let mut outfile = fs::File::create(&outpath)?;
+            io::copy(&mut file, &mut outfile)?;
+        }
+    }
+    
+    Ok(())
+}
+
+/// File name of the SteamCMD executable on a platform
+pub fn steamcmd_exe_name(platform: &str) -> &'static str {
+    if platform == "windows" { "steamcmd.exe" } else { "steamcmd" }
+}
+
+fn find_steamcmd_executable(bin_dir: &Path, platform: &str) -> Option<PathBuf> {
+    let steamcmd_exe = steamcmd_exe_name(platform);
+    
+    let possible_paths = if platform == "linux" {
+        vec![
+            bin_dir.join("linux32").join(steamcmd_exe),
+            bin_dir.join("linux64").join(steamcmd_exe),
+            bin_dir.join(steamcmd_exe),
+            bin_dir.parent().unwrap().join("steamcmd").join(steamcmd_exe),
+        ]
+    } else if platform == "windows" {
+        vec![
+            bin_dir.join("steamcmd").join(steamcmd_exe),
+            bin_dir.join(steamcmd_exe),
+            bin_dir.parent().unwrap().join("steamcmd").join(steamcmd_exe),
+        ]
+    } else {
+        vec![
+            bin_dir.join(steamcmd_exe),
+            bin_dir.parent().unwrap().join("steamcmd").join(steamcmd_exe),
+        ]
+    };
+    
+    possible_paths.into_iter().find(|path| path.exists())
+}
+
+/// Download SteamCMD for the current platform and extract it into `bin_dir`
+/// Returns the path of the executable, `bin_dir/steamcmd` (`steamcmd.exe` on Windows)
+pub fn install_steamcmd(bin_dir: &Path) -> Result<PathBuf, Error> {
+    let platform = std::env::consts::OS;
+    let urls = get_steamcmd_urls()?;
+    fs::create_dir_all(bin_dir)?;
+    
+    let is_zip = urls[0].ends_with(".zip");
+    let archive_name = if is_zip { "steamcmd.zip" } else { "steamcmd.tar.gz" };
+    let archive_path = bin_dir.join(archive_name);
+    
+    // Try each URL until one works
+    let mut download_success = false;
+    let mut last_error = None;
+    
+    for url in &urls {
+        let result = download_file(url, &archive_path)
+            .and_then(|hash| {
+                verify_archive(&archive_path, is_zip)?;
+                Ok(hash)
+            });
+        match result {
+            Ok(hash) => {
+                // A mismatch means the archive isn't the expected build, trying other URLs won't fix that
+                if let Err(e) = verify_checksum(&hash) {
+                    let _ = fs::remove_file(&archive_path);
+                    return Err(e);
+                }
+                download_success = true;
+                break;
+            }
+            Err(e) => {
+                eprintln!("Failed to download from {}: {}", url, e);
+                last_error = Some(e);
+                // Clean up failed download
+                let _ = fs::remove_file(&archive_path);
+                continue;
+            }
+        }
+    }
+    
+    if !download_success {
+        return Err(format!("Failed to download SteamCMD from all URLs. Last error: {}", 
+            last_error.map(|e| e.to_string()).unwrap_or_else(|| "Unknown error".to_string())).into());
+    }
+    
+    // Extract archive
+    if is_zip {
+        extract_zip(&archive_path, bin_dir)?;
+    } else {
+        extract_tar_gz(&archive_path, bin_dir)?;
+    }
+    
+    // Find steamcmd executable
+    let source_path = find_steamcmd_executable(bin_dir, platform)
+        .ok_or_else(|| {
+            // List directory contents to help debug
+            if let Ok(entries) = fs::read_dir(bin_dir) {
+                eprintln!("Files in {:?}:", bin_dir);
+                for entry in entries.flatten() {
+                    eprintln!("  {:?}", entry.path());
+                }
+            }
+            format!("SteamCMD executable not found after extraction. Expected: {}", steamcmd_exe_name(platform))
+        })?;
+    
+    println!("Found SteamCMD at: {:?}", source_path);
+    
+    let target_path = bin_dir.join(steamcmd_exe_name(platform));
+    
+    // Move or copy to target location
+    if source_path != target_path {
+        if fs::rename(&source_path, &target_path).is_err() {
+            println!("Rename failed, trying copy...");
+            fs::copy(&source_path, &target_path)?;
+            println!("Copied SteamCMD from {:?} to {:?}", source_path, target_path);
+        } else {
+            println!("Moved SteamCMD from {:?} to {:?}", source_path, target_path);
+        }
+    }
+    
+    // Make executable on Unix
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&target_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&target_path, perms)?;
+        println!("Set executable permissions on {:?}", target_path);
+    }
+    
+    // Clean up archive
+    let _ = fs::remove_file(&archive_path);
+    
+    // Clean up extracted directory if it exists
+    let extracted_dir_path = bin_dir.join("steamcmd");
+    if extracted_dir_path.exists() && extracted_dir_path.is_dir() && extracted_dir_path != bin_dir {
+        let _ = fs::remove_dir_all(&extracted_dir_path);
+    }
+    
+    Ok(target_path)
+}